@@ -1,6 +1,6 @@
 //! Collective intelligence and swarm behavior
 
-use genius_core::{PlayerAction, GameState, Result};
+use genius_core::{PlayerAction, Observation, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -42,7 +42,7 @@ impl CollectiveIntelligence {
     
     pub async fn make_collective_decision(
         &self,
-        _game_state: &GameState,
+        _observation: &Observation,
         individual_decisions: HashMap<String, PlayerAction>,
     ) -> Result<CollectiveDecision> {
         match self.strategy {
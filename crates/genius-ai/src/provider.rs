@@ -1,7 +1,7 @@
 //! AI provider traits and abstractions
 
 use async_trait::async_trait;
-use genius_core::{PlayerAction, GameState, Result};
+use genius_core::{PlayerAction, LegalAction, Observation, Result};
 use serde::{Deserialize, Serialize};
 
 /// Decision made by an AI provider
@@ -18,12 +18,13 @@ pub trait AIProvider: Send + Sync {
     /// Get the provider name
     fn name(&self) -> &str;
     
-    /// Make a decision based on game state
+    /// Make a decision based on what the player can observe of the game,
+    /// choosing among the fully parameterized legal moves
     async fn make_decision(
         &self,
-        game_state: &GameState,
+        observation: &Observation,
         player_id: &str,
-        valid_actions: Vec<String>,
+        valid_actions: Vec<LegalAction>,
     ) -> Result<AIDecision>;
     
     /// Get provider capabilities
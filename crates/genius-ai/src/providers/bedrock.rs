@@ -2,7 +2,7 @@
 
 use crate::provider::{AIProvider, AIDecision, ProviderCapabilities};
 use async_trait::async_trait;
-use genius_core::{PlayerAction, GameState, Result};
+use genius_core::{LegalAction, Observation, Result};
 
 pub struct BedrockProvider {
     model_id: String,
@@ -26,16 +26,15 @@ impl AIProvider for BedrockProvider {
     
     async fn make_decision(
         &self,
-        _game_state: &GameState,
+        _observation: &Observation,
         player_id: &str,
-        valid_actions: Vec<String>,
+        valid_actions: Vec<LegalAction>,
     ) -> Result<AIDecision> {
         // Placeholder implementation
-        let action = PlayerAction::new(
-            player_id.to_string(),
-            valid_actions.first().unwrap_or(&"pass".to_string()).clone(),
-            serde_json::json!({}),
-        );
+        let action = valid_actions.first()
+            .cloned()
+            .unwrap_or_else(|| LegalAction::simple("pass"))
+            .to_action(player_id);
         
         Ok(AIDecision {
             action,
@@ -2,23 +2,35 @@
 
 use crate::provider::{AIProvider, AIDecision, ProviderCapabilities};
 use async_trait::async_trait;
-use genius_core::{PlayerAction, GameState, Result};
+use genius_core::{game_rng, GameRng, LegalAction, Observation, Result};
 use rand::Rng;
+use std::sync::Mutex;
 
 pub struct MockProvider {
     deterministic: bool,
+    rng: Mutex<GameRng>,
 }
 
 impl MockProvider {
     pub fn new() -> Self {
         Self {
             deterministic: false,
+            rng: Mutex::new(game_rng(None)),
         }
     }
     
     pub fn deterministic() -> Self {
         Self {
             deterministic: true,
+            rng: Mutex::new(game_rng(None)),
+        }
+    }
+    
+    /// Random choices drawn from a seeded RNG, reproducible across runs
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            deterministic: false,
+            rng: Mutex::new(game_rng(Some(seed))),
         }
     }
 }
@@ -37,23 +49,21 @@ impl AIProvider for MockProvider {
     
     async fn make_decision(
         &self,
-        _game_state: &GameState,
+        _observation: &Observation,
         player_id: &str,
-        valid_actions: Vec<String>,
+        valid_actions: Vec<LegalAction>,
     ) -> Result<AIDecision> {
-        let action_type = if self.deterministic {
-            valid_actions.first().unwrap_or(&"pass".to_string()).clone()
+        let chosen = if self.deterministic || valid_actions.is_empty() {
+            valid_actions.first().cloned()
         } else {
-            let mut rng = rand::rng();
+            let mut rng = self.rng.lock().unwrap();
             let idx = rng.random_range(0..valid_actions.len());
-            valid_actions[idx].clone()
+            Some(valid_actions[idx].clone())
         };
         
-        let action = PlayerAction::new(
-            player_id.to_string(),
-            action_type,
-            serde_json::json!({}),
-        );
+        let action = chosen
+            .unwrap_or_else(|| LegalAction::simple("pass"))
+            .to_action(player_id);
         
         Ok(AIDecision {
             action,
@@ -2,7 +2,7 @@
 
 use crate::provider::{AIProvider, AIDecision, ProviderCapabilities};
 use async_trait::async_trait;
-use genius_core::{PlayerAction, GameState, Result};
+use genius_core::{LegalAction, Observation, Result};
 use serde::{Deserialize, Serialize};
 
 pub struct OllamaProvider {
@@ -27,6 +27,28 @@ impl OllamaProvider {
             client: reqwest::Client::new(),
         }
     }
+    
+    /// Pick the legal move the model named, by its number or failing that by action type
+    fn parse_choice(response: &str, valid_actions: &[LegalAction]) -> Option<LegalAction> {
+        let answer = response.find("action:")
+            .map(|idx| &response[idx + 7..])
+            .unwrap_or(response);
+        let number: String = answer.trim_start()
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        
+        if let Some(action) = number.parse::<usize>().ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|idx| valid_actions.get(idx))
+        {
+            return Some(action.clone());
+        }
+        
+        valid_actions.iter()
+            .find(|action| response.contains(&action.action_type.to_lowercase()))
+            .cloned()
+    }
 }
 
 #[derive(Serialize)]
@@ -56,25 +78,37 @@ impl AIProvider for OllamaProvider {
     
     async fn make_decision(
         &self,
-        game_state: &GameState,
+        observation: &Observation,
         player_id: &str,
-        valid_actions: Vec<String>,
+        valid_actions: Vec<LegalAction>,
     ) -> Result<AIDecision> {
-        // Build prompt based on game state
+        let default_action = valid_actions.first()
+            .cloned()
+            .unwrap_or_else(|| LegalAction::simple("pass"));
+        
+        let legal_moves = valid_actions.iter()
+            .enumerate()
+            .map(|(idx, action)| format!("{}. {} {}", idx + 1, action.action_type, action.data))
+            .collect::<Vec<_>>()
+            .join("\n");
+        
+        // Build prompt from the player's own view of the game
         let prompt = format!(
             "You are playing {} as player {}.\n\
              Current round: {}\n\
              Your score: {:?}\n\
-             Valid actions: {:?}\n\n\
-             What is your next action? Choose from the valid actions and explain briefly.\n\
+             What you can see: {}\n\
+             Legal moves:\n{}\n\n\
+             What is your next move? Choose one of the legal moves and explain briefly.\n\
              Respond in this format:\n\
-             Action: <your chosen action>\n\
+             Action: <number of your chosen move>\n\
              Reasoning: <brief explanation>",
-            game_state.game_type.display_name(),
+            observation.game_type.display_name(),
             player_id,
-            game_state.round,
-            game_state.scores.get(player_id).unwrap_or(&0.0),
-            valid_actions
+            observation.round,
+            observation.scores.get(player_id).unwrap_or(&0),
+            observation.view,
+            legal_moves
         );
         
         // Create Ollama request
@@ -98,14 +132,9 @@ impl AIProvider for OllamaProvider {
                             // Parse response
                             let response_text = ollama_resp.response.to_lowercase();
                             
-                            // Find action in response
-                            let mut chosen_action = valid_actions[0].clone();
-                            for action in &valid_actions {
-                                if response_text.contains(&action.to_lowercase()) {
-                                    chosen_action = action.clone();
-                                    break;
-                                }
-                            }
+                            // Find the chosen move in the response
+                            let chosen_action = Self::parse_choice(&response_text, &valid_actions)
+                                .unwrap_or_else(|| default_action.clone());
                             
                             // Extract reasoning
                             let reasoning = if let Some(idx) = response_text.find("reasoning:") {
@@ -114,11 +143,7 @@ impl AIProvider for OllamaProvider {
                                 "Ollama AI decision".to_string()
                             };
                             
-                            let action = PlayerAction::new(
-                                player_id.to_string(),
-                                chosen_action,
-                                serde_json::json!({"llm_response": ollama_resp.response}),
-                            );
+                            let action = chosen_action.to_action(player_id);
                             
                             Ok(AIDecision {
                                 action,
@@ -128,29 +153,17 @@ impl AIProvider for OllamaProvider {
                         }
                         Err(e) => {
                             // Fallback on parse error
-                            let action = PlayerAction::new(
-                                player_id.to_string(),
-                                valid_actions[0].clone(),
-                                serde_json::json!({"error": e.to_string()}),
-                            );
-                            
                             Ok(AIDecision {
-                                action,
-                                reasoning: "Parse error, using default action".to_string(),
+                                action: default_action.to_action(player_id),
+                                reasoning: format!("Parse error ({}), using default action", e),
                                 confidence: 0.3,
                             })
                         }
                     }
                 } else {
                     // Fallback on HTTP error
-                    let action = PlayerAction::new(
-                        player_id.to_string(),
-                        valid_actions[0].clone(),
-                        serde_json::json!({"error": "HTTP error"}),
-                    );
-                    
                     Ok(AIDecision {
-                        action,
+                        action: default_action.to_action(player_id),
                         reasoning: format!("Ollama API error: {}", response.status()),
                         confidence: 0.1,
                     })
@@ -158,15 +171,9 @@ impl AIProvider for OllamaProvider {
             }
             Err(e) => {
                 // Fallback on connection error
-                let action = PlayerAction::new(
-                    player_id.to_string(),
-                    valid_actions[0].clone(),
-                    serde_json::json!({"error": e.to_string()}),
-                );
-                
                 Ok(AIDecision {
-                    action,
-                    reasoning: "Ollama connection failed, using default action".to_string(),
+                    action: default_action.to_action(player_id),
+                    reasoning: format!("Ollama connection failed ({}), using default action", e),
                     confidence: 0.1,
                 })
             }
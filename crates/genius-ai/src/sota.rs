@@ -1,6 +1,6 @@
 //! State-of-the-art AI reasoning and decision making
 
-use genius_core::{GameState, PlayerAction, Result};
+use genius_core::{Observation, LegalAction, PlayerAction, Result};
 use serde::{Deserialize, Serialize};
 
 /// Chain of reasoning steps
@@ -30,7 +30,7 @@ impl SOTAManager {
     
     pub async fn reason_about_state(
         &self,
-        _game_state: &GameState,
+        _observation: &Observation,
         _player_id: &str,
     ) -> Result<ReasoningChain> {
         // Placeholder implementation
@@ -54,19 +54,14 @@ impl SOTAManager {
     
     pub async fn make_strategic_decision(
         &self,
-        _game_state: &GameState,
+        _observation: &Observation,
         _reasoning: &ReasoningChain,
-        valid_actions: Vec<String>,
+        valid_actions: Vec<LegalAction>,
     ) -> Result<PlayerAction> {
         // Placeholder: pick first valid action
-        let action_type = valid_actions.first()
-            .ok_or_else(|| genius_core::GameError::InvalidAction { reason: "No valid actions".to_string() })?
-            .clone();
+        let action = valid_actions.first()
+            .ok_or_else(|| genius_core::GameError::InvalidAction { reason: "No valid actions".to_string() })?;
             
-        Ok(PlayerAction::new(
-            "sota_player".to_string(),
-            action_type,
-            serde_json::json!({}),
-        ))
+        Ok(action.to_action("sota_player"))
     }
 }
\ No newline at end of file
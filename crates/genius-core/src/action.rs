@@ -0,0 +1,167 @@
+//! Action schemas and fully parameterized legal moves
+
+use crate::player::PlayerAction;
+use serde::{Deserialize, Serialize};
+
+/// A complete move a player may submit: the action type plus its exact payload
+///
+/// A move made with `LegalAction::amount` instead stands for every integer payload in
+/// `range`; its `data` is the smallest of them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LegalAction {
+    pub action_type: String,
+    pub data: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<AmountRange>,
+}
+
+/// Inclusive bounds on the integer payload of a parameterised move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AmountRange {
+    pub min: i64,
+    pub max: i64,
+}
+
+impl LegalAction {
+    /// Create a move with the given payload
+    pub fn new(action_type: impl Into<String>, data: serde_json::Value) -> Self {
+        Self {
+            action_type: action_type.into(),
+            data,
+            range: None,
+        }
+    }
+
+    /// Create a move whose payload may be any integer from `min` to `max`
+    pub fn amount(action_type: impl Into<String>, min: i64, max: i64) -> Self {
+        Self {
+            range: Some(AmountRange { min, max }),
+            ..Self::new(action_type, serde_json::json!(min))
+        }
+    }
+
+    /// Create a move that carries no payload
+    pub fn simple(action_type: impl Into<String>) -> Self {
+        Self::new(action_type, serde_json::Value::Null)
+    }
+
+    /// Turn this move into an action submitted by the given player
+    pub fn to_action(&self, player_id: &str) -> PlayerAction {
+        PlayerAction::new(player_id.to_string(), self.action_type.clone(), self.data.clone())
+    }
+
+    /// Whether a submitted action is exactly this move
+    ///
+    /// A `null` payload and an empty object both mean "no payload" and match each other.
+    pub fn matches(&self, action: &PlayerAction) -> bool {
+        if self.action_type != action.action_type {
+            return false;
+        }
+        match self.range {
+            Some(range) => action.data.as_i64().is_some_and(|n| (range.min..=range.max).contains(&n)),
+            None => payload(&self.data) == payload(&action.data),
+        }
+    }
+
+    /// Check a submitted action against a player's legal moves, explaining any mismatch
+    pub fn check(legal: &[LegalAction], action: &PlayerAction) -> std::result::Result<(), String> {
+        if legal.iter().any(|m| m.matches(action)) {
+            return Ok(());
+        }
+        if legal.is_empty() {
+            return Err("no moves are legal for this player right now".to_string());
+        }
+        if legal.iter().any(|m| m.action_type == action.action_type) {
+            return Err(format!("'{}' is legal but not with data {}", action.action_type, action.data));
+        }
+
+        let mut types: Vec<&str> = legal.iter().map(|m| m.action_type.as_str()).collect();
+        types.sort();
+        types.dedup();
+        Err(format!("'{}' is not a legal action; expected one of: {}", action.action_type, types.join(", ")))
+    }
+}
+
+/// A payload with `null` and `{}` folded together
+fn payload(data: &serde_json::Value) -> Option<&serde_json::Value> {
+    match data {
+        serde_json::Value::Null => None,
+        serde_json::Value::Object(map) if map.is_empty() => None,
+        _ => Some(data),
+    }
+}
+
+/// Why the engine refused one player's action
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionRejection {
+    pub player_id: String,
+    pub action_type: String,
+    pub reason: String,
+}
+
+/// Builder for the JSON Schema describing every `PlayerAction` a game accepts
+///
+/// Each action type becomes one `oneOf` branch pinning `action_type` and describing `data`.
+#[derive(Debug, Clone, Default)]
+pub struct ActionSchema {
+    title: String,
+    variants: Vec<serde_json::Value>,
+}
+
+impl ActionSchema {
+    /// Start a schema for the named game
+    pub fn new(game_name: &str) -> Self {
+        Self {
+            title: format!("{} action", game_name),
+            variants: Vec::new(),
+        }
+    }
+
+    /// Add an action type with the schema of its `data` payload
+    pub fn action(mut self, action_type: &str, description: &str, data: serde_json::Value) -> Self {
+        self.variants.push(serde_json::json!({
+            "type": "object",
+            "description": description,
+            "properties": {
+                "action_type": { "const": action_type },
+                "data": data,
+            },
+            "required": ["action_type", "data"],
+        }));
+        self
+    }
+
+    /// Add an action type that carries no payload
+    pub fn simple_action(self, action_type: &str, description: &str) -> Self {
+        self.action(action_type, description, serde_json::json!({ "type": "null" }))
+    }
+
+    /// Schema for a `data` object that repeats its own `action_type` tag, for games that
+    /// deserialize `data` into an action struct with a variant field
+    pub fn tagged_data(tag: &str, fields: serde_json::Value, required: &[&str]) -> serde_json::Value {
+        let mut properties = serde_json::Map::new();
+        properties.insert("action_type".to_string(), serde_json::json!({ "const": tag }));
+        if let serde_json::Value::Object(fields) = fields {
+            properties.extend(fields);
+        }
+
+        let mut required_fields = vec!["action_type"];
+        required_fields.extend_from_slice(required);
+
+        serde_json::json!({
+            "type": "object",
+            "properties": properties,
+            "required": required_fields,
+        })
+    }
+
+    /// Finish the schema
+    pub fn build(self) -> serde_json::Value {
+        serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": self.title,
+            "type": "object",
+            "oneOf": self.variants,
+        })
+    }
+}
@@ -0,0 +1,86 @@
+//! Chess-clock time controls: a bank of thinking time per player, topped up every move
+
+use serde::{Deserialize, Serialize};
+
+/// How a player's bank is topped up after each move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IncrementStyle {
+    /// The full increment is added after every move, so quick moves bank time
+    Fischer,
+    /// Time used is refunded up to the increment, so the bank never grows
+    Bronstein,
+}
+
+/// What happens to a player who runs out of time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagPenalty {
+    /// Play a default move for them this turn and every turn after
+    #[default]
+    DefaultAction,
+    /// Take them out of the game
+    Forfeit,
+}
+
+/// Time bank and increment given to every player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeControl {
+    /// Thinking time each player starts with
+    pub initial_ms: u64,
+    /// Time credited per move, according to `style`
+    pub increment_ms: u64,
+    pub style: IncrementStyle,
+}
+
+impl TimeControl {
+    /// Bank plus a full increment after every move
+    pub fn fischer(initial_ms: u64, increment_ms: u64) -> Self {
+        Self {
+            initial_ms,
+            increment_ms,
+            style: IncrementStyle::Fischer,
+        }
+    }
+
+    /// Bank that refunds up to `increment_ms` of each move's time
+    pub fn bronstein(initial_ms: u64, increment_ms: u64) -> Self {
+        Self {
+            initial_ms,
+            increment_ms,
+            style: IncrementStyle::Bronstein,
+        }
+    }
+
+    /// A full, unflagged clock
+    pub fn start(&self) -> PlayerClock {
+        PlayerClock {
+            remaining_ms: self.initial_ms,
+            flagged: false,
+        }
+    }
+}
+
+/// One player's remaining thinking time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerClock {
+    pub remaining_ms: u64,
+    /// Set once the player has run out of time; a flagged clock stays empty
+    pub flagged: bool,
+}
+
+impl PlayerClock {
+    /// Charge a move that took `used_ms`, returning whether the player was still within their bank
+    pub fn charge(&mut self, used_ms: u64, control: &TimeControl) -> bool {
+        if self.flagged || used_ms > self.remaining_ms {
+            self.remaining_ms = 0;
+            self.flagged = true;
+            return false;
+        }
+        self.remaining_ms = match control.style {
+            IncrementStyle::Fischer => self.remaining_ms - used_ms + control.increment_ms,
+            IncrementStyle::Bronstein => self.remaining_ms - used_ms + used_ms.min(control.increment_ms),
+        };
+        true
+    }
+}
@@ -1,5 +1,6 @@
 //! Error types for the game platform
 
+use crate::action::ActionRejection;
 use thiserror::Error;
 
 /// Main error type for game operations
@@ -14,6 +15,9 @@ pub enum GameError {
     #[error("Invalid action: {reason}")]
     InvalidAction { reason: String },
     
+    #[error("Actions rejected: {}", rejections.iter().map(|r| format!("{} ({})", r.player_id, r.reason)).collect::<Vec<_>>().join("; "))]
+    ActionsRejected { rejections: Vec<ActionRejection> },
+    
     #[error("Game already started")]
     GameAlreadyStarted,
     
@@ -0,0 +1,105 @@
+//! Typed events games report while rounds are played
+
+use crate::state::{EmergenceEvent, EmergenceType};
+use serde::{Deserialize, Serialize};
+
+/// What happened, with the details that matter for that kind of event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EventKind {
+    /// A player was knocked out of the game
+    Eliminated { player_id: String, by: Option<String> },
+    /// A player took pieces off the board
+    Captured { player_id: String, count: u32 },
+    /// Players allied with one another
+    AllianceFormed { members: Vec<String> },
+    /// A player turned on their allies
+    AllianceBetrayed { betrayer: String, victims: Vec<String> },
+    /// A bid or claim was challenged; `was_bluff` tells whether the challenge was right
+    BluffCalled { caller: String, bidder: String, was_bluff: bool },
+    /// The playable area shrank to the given size
+    ZoneShrink { size: f32 },
+    /// The rules of the game changed
+    RuleChange { rule: String, proposer: Option<String> },
+    /// A player took a pot or prize
+    PotWon { player_id: String, amount: i32 },
+    /// Collective behaviour was detected
+    Emergence { emergence_type: EmergenceType, score: f32 },
+    /// Anything specific to one game
+    Custom { name: String, data: serde_json::Value },
+}
+
+impl EventKind {
+    /// Game-specific event with a free-form payload
+    pub fn custom(name: &str, data: serde_json::Value) -> Self {
+        Self::Custom {
+            name: name.to_string(),
+            data,
+        }
+    }
+
+    /// Short snake_case name, e.g. `"alliance_formed"`; custom events use their own name
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Eliminated { .. } => "eliminated",
+            Self::Captured { .. } => "captured",
+            Self::AllianceFormed { .. } => "alliance_formed",
+            Self::AllianceBetrayed { .. } => "alliance_betrayed",
+            Self::BluffCalled { .. } => "bluff_called",
+            Self::ZoneShrink { .. } => "zone_shrink",
+            Self::RuleChange { .. } => "rule_change",
+            Self::PotWon { .. } => "pot_won",
+            Self::Emergence { .. } => "emergence",
+            Self::Custom { name, .. } => name,
+        }
+    }
+
+    /// Players named in the payload
+    pub fn players(&self) -> Vec<String> {
+        match self {
+            Self::Eliminated { player_id, by } => std::iter::once(player_id).chain(by).cloned().collect(),
+            Self::Captured { player_id, .. } | Self::PotWon { player_id, .. } => vec![player_id.clone()],
+            Self::AllianceFormed { members } => members.clone(),
+            Self::AllianceBetrayed { betrayer, victims } => std::iter::once(betrayer).chain(victims).cloned().collect(),
+            Self::BluffCalled { caller, bidder, .. } => vec![caller.clone(), bidder.clone()],
+            Self::RuleChange { proposer, .. } => proposer.iter().cloned().collect(),
+            Self::ZoneShrink { .. } | Self::Emergence { .. } | Self::Custom { .. } => Vec::new(),
+        }
+    }
+}
+
+/// Something that happened during a round
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameEvent {
+    pub kind: EventKind,
+    pub description: String,
+    pub affected_players: Vec<String>,
+}
+
+impl GameEvent {
+    /// Event affecting the players named in its payload
+    pub fn new(kind: EventKind, description: impl Into<String>) -> Self {
+        Self {
+            affected_players: kind.players(),
+            kind,
+            description: description.into(),
+        }
+    }
+
+    /// Event reporting emergence the game detected this round
+    pub fn emergence(emergence: &EmergenceEvent) -> Self {
+        Self {
+            kind: EventKind::Emergence {
+                emergence_type: emergence.event_type.clone(),
+                score: emergence.emergence_score,
+            },
+            description: emergence.description.clone(),
+            affected_players: emergence.involved_players.clone(),
+        }
+    }
+
+    /// Short snake_case name of the event kind
+    pub fn event_type(&self) -> &str {
+        self.kind.name()
+    }
+}
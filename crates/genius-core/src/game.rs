@@ -1,6 +1,6 @@
 //! Core game trait and types
 
-use crate::{state::*, player::{Player, PlayerAction}, error::Result};
+use crate::{state::*, player::{Player, PlayerAction}, action::LegalAction, turn::Turn, params::ParamSchema, team::Team, observation::{Observation, Observer}, snapshot::Snapshot, error::Result};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -11,8 +11,16 @@ pub struct GameConfig {
     pub game_type: GameType,
     pub rounds: u32,
     pub time_limit_ms: u64,
-    pub special_rules: HashMap<String, String>,
+    /// Game-specific parameters, checked against `Game::parameters`; omitted ones take their defaults
+    #[serde(default, alias = "special_rules")]
+    pub params: HashMap<String, serde_json::Value>,
     pub initial_players: Vec<Player>,
+    /// Fixed sides; a player may be on at most one team
+    #[serde(default)]
+    pub teams: Vec<Team>,
+    /// Seed for the game's random number generator; `None` draws one from the OS
+    #[serde(default)]
+    pub seed: Option<u64>,
 }
 
 /// Available game types in the platform
@@ -50,11 +58,14 @@ pub enum GameType {
     TrustFall,
     LiarsDice,
     ConsciousnessPoker,
+    
+    /// A game registered at runtime under its own name
+    Custom(String),
 }
 
 impl GameType {
     /// Get human-readable name for the game type
-    pub fn display_name(&self) -> &'static str {
+    pub fn display_name(&self) -> &str {
         match self {
             Self::MinorityGame => "Minority Game",
             Self::ByzantineGenerals => "Byzantine Generals",
@@ -81,6 +92,7 @@ impl GameType {
             Self::TrustFall => "Trust Fall",
             Self::LiarsDice => "Liar's Dice",
             Self::ConsciousnessPoker => "Consciousness Poker",
+            Self::Custom(name) => name,
         }
     }
     
@@ -102,22 +114,51 @@ impl GameType {
             
             Self::PrisonersDilemma | Self::TrustFall | Self::LiarsDice |
             Self::ConsciousnessPoker => GameCategory::Trust,
+            
+            Self::Custom(_) => GameCategory::Custom,
+        }
+    }
+    
+    /// Identifier used to look the game up by name, e.g. `MiniGo` or a custom game's own name
+    pub fn key(&self) -> String {
+        match self {
+            Self::Custom(name) => name.clone(),
+            builtin => format!("{:?}", builtin),
         }
     }
 }
 
 /// Game categories for organization
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GameCategory {
     Strategic,
     Collective,
     Survival,
     Trust,
+    /// Registered at runtime without one of the built-in categories
+    Custom,
+}
+
+/// Builds game instances by type, so the engine can run games registered at runtime
+pub trait GameFactory: Send + Sync {
+    /// Create a fresh, uninitialized instance of the given game
+    fn create(&self, game_type: GameType) -> Result<Box<dyn Game>>;
+}
+
+impl<F> GameFactory for F
+where
+    F: Fn(GameType) -> Result<Box<dyn Game>> + Send + Sync,
+{
+    fn create(&self, game_type: GameType) -> Result<Box<dyn Game>> {
+        self(game_type)
+    }
 }
 
 /// Core trait that all games must implement
+///
+/// Games must also be serializable so running instances can be snapshotted and restored.
 #[async_trait]
-pub trait Game: Send + Sync {
+pub trait Game: Snapshot + Send + Sync {
     /// Initialize a new game with the given configuration
     async fn initialize(&mut self, config: GameConfig) -> Result<GameState>;
     
@@ -134,13 +175,103 @@ pub trait Game: Send + Sync {
     /// Calculate final results and analytics
     async fn calculate_final_result(&self, state: &GameState) -> GameResult;
     
-    /// Get valid actions for a player in the current state
-    async fn get_valid_actions(&self, _state: &GameState, _player_id: &str) -> Vec<String> {
+    /// Typed parameters this game reads from `GameConfig::params`
+    fn parameters(&self) -> ParamSchema {
+        // Default implementation declares no parameters
+        ParamSchema::new()
+    }
+    
+    /// JSON Schema for the `PlayerAction`s this game accepts, built with `ActionSchema`
+    fn action_schema(&self) -> serde_json::Value;
+    
+    /// Get every fully parameterized move the player may make in the current state
+    async fn get_valid_actions(&self, _state: &GameState, _player_id: &str) -> Vec<LegalAction> {
         // Default implementation returns empty vec
-        // Games should override this to provide action hints
+        // Games should override this to list their legal moves
         vec![]
     }
     
+    /// Get the players who must act next and whether the round resolves after all or one of them
+    async fn current_turn(&self, state: &GameState) -> Turn {
+        // Default implementation treats the game as simultaneous
+        // Turn-based games should override this to name the player to move
+        let mut players: Vec<String> = state.players().into_iter().cloned().collect();
+        players.sort();
+        Turn::simultaneous(players)
+    }
+    
+    /// Players this game has knocked out so far
+    ///
+    /// Games with elimination override this; everyone listed is marked eliminated in
+    /// `GameState` and is no longer asked to act.
+    async fn eliminated_players(&self, _state: &GameState) -> Vec<String> {
+        Vec::new()
+    }
+    
+    /// Alliances currently holding between players
+    ///
+    /// Games with in-game alliances override this; each group is recorded as a
+    /// `Coalition` in `GameState`, along with the rounds it formed and broke up in.
+    async fn coalitions(&self, _state: &GameState) -> Vec<Vec<String>> {
+        Vec::new()
+    }
+    
+    /// Deal a player into the game as play starts
+    ///
+    /// Games with a turn order or per-player holdings override this rather than waiting
+    /// to meet players in their first actions. The engine calls it once for every seated
+    /// player, in the order they sat down, when the game leaves its lobby.
+    async fn join(&mut self, _state: &GameState, _player_id: &str) {}
+    
+    /// Fold a player joining into the state and the game
+    async fn apply_join(&mut self, state: &mut GameState, player_id: &str) {
+        if !state.player_status.contains_key(player_id) {
+            state.add_player(player_id.to_string());
+        }
+        self.join(state, player_id).await;
+    }
+    
+    /// Take a player who left mid-match out of play
+    ///
+    /// Games with a turn order or per-player holdings override this so the rest of the
+    /// table can carry on, e.g. by passing the turn on or conceding to the opponent.
+    /// The player is marked eliminated in `GameState` afterwards either way.
+    async fn forfeit(&mut self, _state: &GameState, _player_id: &str) {}
+    
+    /// Fold a player leaving into the state and the game
+    async fn apply_forfeit(&mut self, state: &mut GameState, player_id: &str) {
+        self.forfeit(state, player_id).await;
+        let round = state.round;
+        state.eliminate(player_id, round);
+    }
+    
+    /// Fold a processed round into the state, including anyone it knocked out
+    /// and any coalitions that formed or broke up
+    async fn apply_round(&self, state: &mut GameState, result: &RoundResult) {
+        state.apply_round(result);
+        let round = state.round;
+        for player_id in self.eliminated_players(state).await {
+            state.eliminate(&player_id, round);
+        }
+        let groups = self.coalitions(state).await;
+        state.update_coalitions(groups, round);
+    }
+    
+    /// Check a submitted action before the round is processed
+    ///
+    /// Defaults to requiring an exact match with one of `get_valid_actions`.
+    async fn validate_action(&self, state: &GameState, player_id: &str, action: &PlayerAction) -> std::result::Result<(), String> {
+        let legal = self.get_valid_actions(state, player_id).await;
+        LegalAction::check(&legal, action)
+    }
+    
+    /// Get what the given observer is allowed to see of the current state
+    async fn get_observation(&self, state: &GameState, observer: &Observer) -> Observation {
+        // Default implementation exposes only the shared state
+        // Games with hidden information should override this to add each player's private view
+        Observation::public(state, observer.clone())
+    }
+    
     /// Get game-specific state for visualization
     async fn get_visualization_data(&self, _state: &GameState) -> serde_json::Value {
         // Default implementation returns empty object
@@ -6,18 +6,52 @@ pub mod game;
 pub mod player;
 pub mod state;
 pub mod error;
+pub mod rng;
+pub mod observation;
+pub mod snapshot;
+pub mod replay;
+pub mod action;
+pub mod turn;
+pub mod params;
+pub mod standings;
+pub mod team;
+pub mod event;
+pub mod lifecycle;
+pub mod clock;
 
 pub use game::*;
 pub use player::*;
 pub use state::*;
 pub use error::*;
+pub use rng::*;
+pub use observation::*;
+pub use snapshot::*;
+pub use replay::*;
+pub use action::*;
+pub use turn::*;
+pub use params::*;
+pub use standings::*;
+pub use team::*;
+pub use event::*;
+pub use lifecycle::*;
+pub use clock::*;
 
 /// Re-export commonly used types
 pub mod prelude {
     pub use crate::{
-        game::{Game, GameConfig, GameType},
+        game::{Game, GameConfig, GameType, GameCategory, GameFactory},
         player::{Player, PlayerId, PlayerAction},
-        state::{GameState, RoundResult, GameResult},
+        action::{ActionSchema, LegalAction},
+        turn::{Turn, TurnResolution},
+        params::{GameParams, ParamSchema},
+        state::{GameState, PlayerStatus, RoundResult, GameResult},
+        standings::{GameOutcome, Placement, Standings},
+        team::{Coalition, Team},
+        event::{EventKind, GameEvent},
+        lifecycle::GamePhase,
+        clock::{PlayerClock, TimeControl},
+        observation::{Observation, Observer},
+        snapshot::{GameSnapshot, Snapshot},
         error::{GameError, Result},
     };
 }
\ No newline at end of file
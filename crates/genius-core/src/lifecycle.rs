@@ -0,0 +1,91 @@
+//! Lifecycle of a game, from the lobby to its end
+
+use crate::error::{GameError, Result};
+use serde::{Deserialize, Serialize};
+
+/// Where a game is in its lifecycle
+///
+/// Games move from `Lobby` to `Running`, may be paused and resumed any number
+/// of times, and end either `Finished` or `Aborted`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GamePhase {
+    /// Waiting for players; no rounds can be played yet
+    #[default]
+    Lobby,
+    Running,
+    /// Halted until resumed; no rounds can be played
+    Paused,
+    /// Played to its end
+    Finished,
+    /// Called off before reaching an end
+    Aborted,
+}
+
+impl GamePhase {
+    /// Whether the game has ended, either way
+    pub fn is_over(self) -> bool {
+        matches!(self, Self::Finished | Self::Aborted)
+    }
+
+    /// Check that rounds may be played now
+    pub fn check_running(self) -> Result<()> {
+        match self {
+            Self::Running => Ok(()),
+            Self::Lobby => Err(GameError::GameNotStarted),
+            Self::Paused => Err(GameError::InvalidState {
+                reason: "Game is paused".to_string(),
+            }),
+            Self::Finished | Self::Aborted => Err(GameError::GameAlreadyEnded),
+        }
+    }
+
+    /// Leave the lobby and begin play
+    pub fn start(&mut self) -> Result<()> {
+        match self {
+            Self::Lobby => *self = Self::Running,
+            Self::Running | Self::Paused => return Err(GameError::GameAlreadyStarted),
+            Self::Finished | Self::Aborted => return Err(GameError::GameAlreadyEnded),
+        }
+        Ok(())
+    }
+
+    /// Halt a running game
+    pub fn pause(&mut self) -> Result<()> {
+        self.check_running()?;
+        *self = Self::Paused;
+        Ok(())
+    }
+
+    /// Continue a paused game
+    pub fn resume(&mut self) -> Result<()> {
+        match self {
+            Self::Paused => *self = Self::Running,
+            Self::Lobby => return Err(GameError::GameNotStarted),
+            Self::Running => return Err(GameError::InvalidState {
+                reason: "Game is not paused".to_string(),
+            }),
+            Self::Finished | Self::Aborted => return Err(GameError::GameAlreadyEnded),
+        }
+        Ok(())
+    }
+
+    /// Mark a started game as played to its end
+    pub fn finish(&mut self) -> Result<()> {
+        match self {
+            Self::Running | Self::Paused => *self = Self::Finished,
+            Self::Lobby => return Err(GameError::GameNotStarted),
+            Self::Finished | Self::Aborted => return Err(GameError::GameAlreadyEnded),
+        }
+        Ok(())
+    }
+
+    /// Call the game off, whether or not it has started
+    pub fn abort(&mut self) -> Result<()> {
+        if self.is_over() {
+            return Err(GameError::GameAlreadyEnded);
+        }
+        *self = Self::Aborted;
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+//! Per-player views of game state for imperfect-information games
+
+use crate::clock::PlayerClock;
+use crate::game::GameType;
+use crate::state::{GameState, PlayerStatus, RoundResult};
+use crate::team::{Coalition, Team};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Who a view of the game is being prepared for
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "player_id", rename_all = "snake_case")]
+pub enum Observer {
+    /// A seated player, who may see their own hidden information
+    Player(String),
+    /// Anyone watching the game, who only sees public information
+    Spectator,
+}
+
+impl Observer {
+    /// Player id of the observer, if they are seated in the game
+    pub fn player_id(&self) -> Option<&str> {
+        match self {
+            Self::Player(id) => Some(id),
+            Self::Spectator => None,
+        }
+    }
+
+    /// Whether this observer is the given player
+    pub fn is_player(&self, player_id: &str) -> bool {
+        self.player_id() == Some(player_id)
+    }
+}
+
+/// What an observer is allowed to see of a game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Observation {
+    pub game_id: Uuid,
+    pub game_type: GameType,
+    pub round: u32,
+    pub observer: Observer,
+    pub scores: HashMap<String, i32>,
+    pub player_status: HashMap<String, PlayerStatus>,
+    pub teams: Vec<Team>,
+    pub coalitions: Vec<Coalition>,
+    /// Thinking time left per player, empty without a time control
+    pub clocks: HashMap<String, PlayerClock>,
+    pub history: Vec<RoundResult>,
+    pub metadata: HashMap<String, serde_json::Value>,
+    /// Game-specific view, holding only the hidden information this observer may see
+    pub view: serde_json::Value,
+}
+
+impl Observation {
+    /// Observation carrying only the public parts of the shared state
+    pub fn public(state: &GameState, observer: Observer) -> Self {
+        Self {
+            game_id: state.game_id,
+            game_type: state.game_type.clone(),
+            round: state.round,
+            observer,
+            scores: state.scores.clone(),
+            player_status: state.player_status.clone(),
+            teams: state.teams.clone(),
+            coalitions: state.coalitions.clone(),
+            clocks: state.clocks.clone(),
+            history: state.history.clone(),
+            metadata: state.metadata.clone(),
+            view: serde_json::json!({}),
+        }
+    }
+
+    /// Attach the game-specific view
+    pub fn with_view(mut self, view: serde_json::Value) -> Self {
+        self.view = view;
+        self
+    }
+}
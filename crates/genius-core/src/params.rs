@@ -0,0 +1,171 @@
+//! Typed game parameters with defaults, ranges and validation
+
+use crate::error::{GameError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+/// Type, default and allowed values of one parameter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ParamKind {
+    Integer { default: i64, min: i64, max: i64 },
+    Float { default: f64, min: f64, max: f64 },
+    Boolean { default: bool },
+}
+
+/// A single parameter a game accepts in `GameConfig::params`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParamSpec {
+    pub name: String,
+    pub description: String,
+    #[serde(flatten)]
+    pub kind: ParamKind,
+}
+
+impl ParamSpec {
+    fn default_value(&self) -> serde_json::Value {
+        match self.kind {
+            ParamKind::Integer { default, .. } => serde_json::json!(default),
+            ParamKind::Float { default, .. } => serde_json::json!(default),
+            ParamKind::Boolean { default } => serde_json::json!(default),
+        }
+    }
+
+    /// Check a submitted value, accepting numbers and booleans written as strings
+    fn check(&self, value: &serde_json::Value) -> std::result::Result<serde_json::Value, String> {
+        let text = value.as_str().map(str::trim);
+        match self.kind {
+            ParamKind::Integer { min, max, .. } => {
+                let parsed = value.as_i64().or_else(|| text.and_then(|t| t.parse().ok()))
+                    .ok_or_else(|| format!("{} must be an integer, got {}", self.name, value))?;
+                if !(min..=max).contains(&parsed) {
+                    return Err(format!("{} must be between {} and {}, got {}", self.name, min, max, parsed));
+                }
+                Ok(serde_json::json!(parsed))
+            }
+            ParamKind::Float { min, max, .. } => {
+                let parsed = value.as_f64().or_else(|| text.and_then(|t| t.parse().ok()))
+                    .ok_or_else(|| format!("{} must be a number, got {}", self.name, value))?;
+                if !(min..=max).contains(&parsed) {
+                    return Err(format!("{} must be between {} and {}, got {}", self.name, min, max, parsed));
+                }
+                Ok(serde_json::json!(parsed))
+            }
+            ParamKind::Boolean { .. } => {
+                let parsed = value.as_bool().or_else(|| text.and_then(|t| t.parse().ok()))
+                    .ok_or_else(|| format!("{} must be true or false, got {}", self.name, value))?;
+                Ok(serde_json::json!(parsed))
+            }
+        }
+    }
+}
+
+/// Builder and validator for the parameters a game declares
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ParamSchema {
+    params: Vec<ParamSpec>,
+}
+
+impl ParamSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare an integer parameter
+    pub fn integer(mut self, name: &str, description: &str, default: i64, range: RangeInclusive<i64>) -> Self {
+        self.params.push(ParamSpec {
+            name: name.to_string(),
+            description: description.to_string(),
+            kind: ParamKind::Integer { default, min: *range.start(), max: *range.end() },
+        });
+        self
+    }
+
+    /// Declare a floating point parameter
+    pub fn float(mut self, name: &str, description: &str, default: f64, range: RangeInclusive<f64>) -> Self {
+        self.params.push(ParamSpec {
+            name: name.to_string(),
+            description: description.to_string(),
+            kind: ParamKind::Float { default, min: *range.start(), max: *range.end() },
+        });
+        self
+    }
+
+    /// Declare a boolean parameter
+    pub fn boolean(mut self, name: &str, description: &str, default: bool) -> Self {
+        self.params.push(ParamSpec {
+            name: name.to_string(),
+            description: description.to_string(),
+            kind: ParamKind::Boolean { default },
+        });
+        self
+    }
+
+    pub fn specs(&self) -> &[ParamSpec] {
+        &self.params
+    }
+
+    /// Check submitted values against the declared parameters and fill in defaults
+    ///
+    /// Every problem is reported at once, including names the game does not declare.
+    pub fn validate(&self, values: &HashMap<String, serde_json::Value>) -> Result<GameParams> {
+        let mut problems = Vec::new();
+        let mut resolved = HashMap::new();
+
+        for spec in &self.params {
+            match values.get(&spec.name) {
+                Some(value) => match spec.check(value) {
+                    Ok(value) => {
+                        resolved.insert(spec.name.clone(), value);
+                    }
+                    Err(problem) => problems.push(problem),
+                },
+                None => {
+                    resolved.insert(spec.name.clone(), spec.default_value());
+                }
+            }
+        }
+
+        let mut unknown: Vec<&String> = values.keys()
+            .filter(|name| !self.params.iter().any(|spec| spec.name == **name))
+            .collect();
+        unknown.sort();
+        problems.extend(unknown.into_iter().map(|name| format!("unknown parameter {}", name)));
+
+        if !problems.is_empty() {
+            return Err(GameError::ConfigError { reason: problems.join("; ") });
+        }
+        Ok(GameParams { values: resolved })
+    }
+}
+
+/// Validated parameter values, with every declared parameter present
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct GameParams {
+    values: HashMap<String, serde_json::Value>,
+}
+
+impl GameParams {
+    /// Integer value of a declared parameter
+    pub fn int(&self, name: &str) -> i64 {
+        self.values.get(name).and_then(|v| v.as_i64()).unwrap_or_default()
+    }
+
+    /// Floating point value of a declared parameter
+    pub fn float(&self, name: &str) -> f64 {
+        self.values.get(name).and_then(|v| v.as_f64()).unwrap_or_default()
+    }
+
+    /// Boolean value of a declared parameter
+    pub fn flag(&self, name: &str) -> bool {
+        self.values.get(name).and_then(|v| v.as_bool()).unwrap_or_default()
+    }
+
+    /// Resolved values, suitable for storing back into `GameConfig::params`
+    pub fn into_values(self) -> HashMap<String, serde_json::Value> {
+        self.values
+    }
+}
@@ -59,10 +59,15 @@ pub enum PlayerType {
 pub struct PlayerAction {
     pub player_id: String,
     pub action_type: String,
+    /// Move payload; a missing payload reads as `null`
+    #[serde(default)]
     pub data: serde_json::Value,
     pub reasoning: Option<String>,
     pub confidence: Option<f32>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// How long the player took to decide, when it was measured
+    #[serde(default)]
+    pub latency_ms: Option<u64>,
 }
 
 impl PlayerAction {
@@ -75,6 +80,7 @@ impl PlayerAction {
             reasoning: None,
             confidence: None,
             timestamp: chrono::Utc::now(),
+            latency_ms: None,
         }
     }
     
@@ -89,4 +95,10 @@ impl PlayerAction {
         self.confidence = Some(confidence.clamp(0.0, 1.0));
         self
     }
+    
+    /// Record how long the decision took
+    pub fn with_latency(mut self, latency_ms: u64) -> Self {
+        self.latency_ms = Some(latency_ms);
+        self
+    }
 }
\ No newline at end of file
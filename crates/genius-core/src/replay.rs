@@ -0,0 +1,128 @@
+//! Versioned replay files for recorded matches
+
+use crate::error::{GameError, Result};
+use crate::game::GameConfig;
+use crate::player::PlayerAction;
+use crate::snapshot::GameSnapshot;
+use crate::state::RoundResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Current replay format version; bump when the layout changes incompatibly
+pub const REPLAY_FORMAT_VERSION: u32 = 1;
+
+/// A complete recorded match: enough to re-execute it round by round
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    /// Configuration the game was created with, including its seed
+    pub config: GameConfig,
+    pub rounds: Vec<ReplayRound>,
+    /// Players in the order they took their seats, and so were dealt in when play started
+    #[serde(default)]
+    pub players: Vec<String>,
+    /// Players who left mid-game, in the order they left
+    #[serde(default)]
+    pub forfeits: Vec<ReplayForfeit>,
+}
+
+/// One recorded round: the actions submitted and what the game produced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayRound {
+    pub round: u32,
+    pub actions: HashMap<String, PlayerAction>,
+    pub result: RoundResult,
+}
+
+/// A player leaving between rounds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayForfeit {
+    pub player_id: String,
+    /// Rounds played before the player left
+    pub after_round: u32,
+}
+
+impl Replay {
+    /// Start an empty replay for a game created with the given config
+    pub fn new(config: GameConfig) -> Self {
+        Self {
+            version: REPLAY_FORMAT_VERSION,
+            config,
+            rounds: Vec::new(),
+            players: Vec::new(),
+            forfeits: Vec::new(),
+        }
+    }
+
+    /// Rebuild a replay from a game's history, using the actions stored in each round result
+    pub fn from_history(config: GameConfig, history: &[RoundResult]) -> Self {
+        let mut replay = Self::new(config);
+        for result in history {
+            replay.record_round(result.actions.clone(), result.clone());
+        }
+        replay
+    }
+
+    /// Rebuild a replay from a snapshot, including the table it was dealt and who left it
+    pub fn from_snapshot(snapshot: &GameSnapshot) -> Self {
+        let mut replay = Self::from_history(snapshot.config.clone(), &snapshot.state.history);
+        replay.players = snapshot.players.clone();
+        replay.forfeits = snapshot.forfeits.clone();
+        replay
+    }
+
+    /// Append a processed round
+    pub fn record_round(&mut self, actions: HashMap<String, PlayerAction>, result: RoundResult) {
+        self.rounds.push(ReplayRound {
+            round: self.rounds.len() as u32 + 1,
+            actions,
+            result,
+        });
+    }
+
+    /// Append a player leaving after the rounds recorded so far
+    pub fn record_forfeit(&mut self, player_id: &str) {
+        self.forfeits.push(ReplayForfeit {
+            player_id: player_id.to_string(),
+            after_round: self.rounds.len() as u32,
+        });
+    }
+
+    /// Players who left once `round` rounds had been played, in the order they left
+    pub fn forfeits_after(&self, round: u32) -> impl Iterator<Item = &str> {
+        self.forfeits.iter()
+            .filter(move |f| f.after_round == round)
+            .map(|f| f.player_id.as_str())
+    }
+
+    /// Parse a replay from JSON, rejecting unsupported format versions
+    pub fn from_json(json: &str) -> Result<Self> {
+        let replay: Self = serde_json::from_str(json)?;
+        if replay.version != REPLAY_FORMAT_VERSION {
+            return Err(GameError::ConfigError {
+                reason: format!(
+                    "Unsupported replay version {} (expected {})",
+                    replay.version, REPLAY_FORMAT_VERSION
+                ),
+            });
+        }
+        Ok(replay)
+    }
+
+    /// Serialize the replay to pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Write the replay to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Read a replay from a file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+}
@@ -0,0 +1,35 @@
+//! Deterministic random number generation for games
+//!
+//! Games never touch `rand::thread_rng()` directly. Each game owns a [`GameRng`]
+//! created from [`GameConfig::seed`](crate::GameConfig) in `initialize`, so the
+//! same config and the same actions always produce the same rounds.
+
+use rand::SeedableRng;
+
+/// Random number generator used by every game.
+///
+/// ChaCha8 is portable across platforms and its state is serializable, so a
+/// game's RNG can be captured alongside the rest of its internals.
+pub type GameRng = rand_chacha::ChaCha8Rng;
+
+/// Create a game RNG from an optional seed
+pub fn game_rng(seed: Option<u64>) -> GameRng {
+    match seed {
+        Some(seed) => GameRng::seed_from_u64(seed),
+        None => GameRng::from_rng(&mut rand::rng()),
+    }
+}
+
+/// Draw a fresh seed from the OS, for games that should still be replayable later
+pub fn random_seed() -> u64 {
+    rand::random()
+}
+
+/// Derive an independent seed for a sub-stream (e.g. one AI player) from a base seed
+pub fn derive_seed(seed: u64, stream: u64) -> u64 {
+    // SplitMix64 finalizer keeps neighbouring streams uncorrelated
+    let mut z = seed ^ stream.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,81 @@
+//! Snapshots of complete game internals, so a game can outlive its process
+
+use crate::error::Result;
+use crate::game::GameConfig;
+use crate::lifecycle::GamePhase;
+use crate::replay::ReplayForfeit;
+use crate::state::GameState;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Capture and rebuild a game's private internal state
+///
+/// Implemented for every serializable type, so a game only has to derive
+/// `Serialize` and `Deserialize` over all of its fields, including its RNG.
+pub trait Snapshot {
+    /// Serialize the complete internal state to JSON
+    fn snapshot(&self) -> Result<serde_json::Value>;
+
+    /// Replace the internal state with one produced by `snapshot`
+    fn restore(&mut self, snapshot: serde_json::Value) -> Result<()>;
+}
+
+impl<T: Serialize + DeserializeOwned> Snapshot for T {
+    fn snapshot(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::to_value(self)?)
+    }
+
+    fn restore(&mut self, snapshot: serde_json::Value) -> Result<()> {
+        *self = serde_json::from_value(snapshot)?;
+        Ok(())
+    }
+}
+
+/// Everything needed to rebuild a running game instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub config: GameConfig,
+    pub state: GameState,
+    /// Snapshots from before lifecycles were tracked are of running games
+    #[serde(default = "running")]
+    pub phase: GamePhase,
+    /// Game-specific internals produced by `Snapshot::snapshot`
+    pub internals: serde_json::Value,
+    /// Players in the order they took their seats
+    #[serde(default)]
+    pub players: Vec<String>,
+    /// Players who left mid-game, so rewinds after a restore keep them out
+    #[serde(default)]
+    pub forfeits: Vec<ReplayForfeit>,
+}
+
+fn running() -> GamePhase {
+    GamePhase::Running
+}
+
+/// Serde adapter for maps with tuple keys, which JSON objects cannot represent
+///
+/// Use with `#[serde(with = "genius_core::tuple_keys")]`; entries are written as a list of pairs.
+pub mod tuple_keys {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+    use std::hash::Hash;
+
+    pub fn serialize<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let entries: Vec<(K, V)> = Vec::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
+    }
+}
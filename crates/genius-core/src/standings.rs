@@ -0,0 +1,230 @@
+//! Final standings: placement order, draws, team results and eliminations
+
+use crate::team::Team;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+/// Where one player finished
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Placement {
+    pub player_id: String,
+    /// 1-based; tied players share a rank and the ranks after them are skipped
+    pub rank: u32,
+    pub score: i32,
+    pub team: Option<String>,
+    /// Round the player was knocked out in, or `None` if they lasted to the end
+    pub eliminated_round: Option<u32>,
+}
+
+/// Where one team finished
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TeamResult {
+    pub team: String,
+    pub members: Vec<String>,
+    /// Sum of the members' scores
+    pub score: i32,
+    pub rank: u32,
+}
+
+/// How a game was decided
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GameOutcome {
+    /// One player finished alone in first place
+    Winner { player_id: String },
+    /// Several players share first place
+    Draw { player_ids: Vec<String> },
+    /// One team finished alone in first place
+    TeamWin { team: String, members: Vec<String> },
+    /// Nobody won, e.g. every player was eliminated
+    NoWinner,
+}
+
+/// Ranks players into placements and derives the outcome
+///
+/// Survivors place above eliminated players and later eliminations above earlier ones;
+/// otherwise players are ordered by score. Games build one of these in
+/// `calculate_final_result` and copy its views into the `GameResult`.
+#[derive(Debug, Clone, Default)]
+pub struct Standings {
+    scores: HashMap<String, i32>,
+    eliminated: HashMap<String, u32>,
+    teams: BTreeMap<String, Vec<String>>,
+    survivors_share_first: bool,
+    no_winner: bool,
+}
+
+impl Standings {
+    /// Standings over the given final scores
+    pub fn new(scores: &HashMap<String, i32>) -> Self {
+        Self {
+            scores: scores.clone(),
+            ..Self::default()
+        }
+    }
+
+    /// Record the round a player was knocked out in; the earliest counts if recorded twice
+    pub fn eliminated(mut self, player_id: &str, round: u32) -> Self {
+        self.scores.entry(player_id.to_string()).or_insert(0);
+        let out = self.eliminated.entry(player_id.to_string()).or_insert(round);
+        *out = (*out).min(round);
+        self
+    }
+
+    /// Put players on a team; teams are ranked by the sum of their members' scores.
+    /// A team with nobody on it is left out, so it can't place ahead of real ones
+    pub fn team<S: Into<String>>(mut self, team: &str, members: impl IntoIterator<Item = S>) -> Self {
+        let members: Vec<String> = members.into_iter().map(Into::into).collect();
+        if members.is_empty() {
+            return self;
+        }
+        for member in &members {
+            self.scores.entry(member.clone()).or_insert(0);
+        }
+        let team = self.teams.entry(team.to_string()).or_default();
+        team.extend(members);
+        team.sort();
+        team.dedup();
+        self
+    }
+
+    /// Put players on the teams declared in `GameConfig`
+    pub fn declared_teams(self, teams: &[Team]) -> Self {
+        teams.iter().fold(self, |standings, team| standings.team(&team.name, team.members.iter().cloned()))
+    }
+
+    /// Rank every player still standing first, whatever their score, as in co-operative survival
+    pub fn survivors_share_first(mut self) -> Self {
+        self.survivors_share_first = true;
+        self
+    }
+
+    /// Declare that nobody won, regardless of how players placed
+    pub fn without_winner(mut self) -> Self {
+        self.no_winner = true;
+        self
+    }
+
+    fn compare(&self, a: &str, b: &str) -> Ordering {
+        let survived = |p: &str| !self.eliminated.contains_key(p);
+        let score = |p: &str| self.scores.get(p).copied().unwrap_or(0);
+
+        survived(b).cmp(&survived(a))
+            .then_with(|| self.eliminated.get(b).cmp(&self.eliminated.get(a)))
+            .then_with(|| {
+                if self.survivors_share_first && survived(a) {
+                    Ordering::Equal
+                } else {
+                    score(b).cmp(&score(a))
+                }
+            })
+    }
+
+    fn team_of(&self, player_id: &str) -> Option<String> {
+        self.teams.iter()
+            .find(|(_, members)| members.iter().any(|m| m == player_id))
+            .map(|(team, _)| team.clone())
+    }
+
+    /// Every player from first to last, ties broken by id
+    pub fn placements(&self) -> Vec<Placement> {
+        let mut players: Vec<&String> = self.scores.keys().collect();
+        players.sort_by(|a, b| self.compare(a, b).then_with(|| a.cmp(b)));
+
+        let mut placements: Vec<Placement> = Vec::with_capacity(players.len());
+        for (i, player) in players.iter().enumerate() {
+            let rank = match placements.last() {
+                Some(prev) if self.compare(&prev.player_id, player) == Ordering::Equal => prev.rank,
+                _ => i as u32 + 1,
+            };
+            placements.push(Placement {
+                player_id: player.to_string(),
+                rank,
+                score: self.scores[*player],
+                team: self.team_of(player),
+                eliminated_round: self.eliminated.get(*player).copied(),
+            });
+        }
+        placements
+    }
+
+    /// Every team from first to last, ties broken by name
+    pub fn teams(&self) -> Vec<TeamResult> {
+        let mut teams: Vec<TeamResult> = self.teams.iter()
+            .map(|(team, members)| TeamResult {
+                team: team.clone(),
+                members: members.clone(),
+                score: members.iter().filter_map(|m| self.scores.get(m)).sum(),
+                rank: 0,
+            })
+            .collect();
+        teams.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.team.cmp(&b.team)));
+
+        for i in 0..teams.len() {
+            teams[i].rank = if i > 0 && teams[i - 1].score == teams[i].score {
+                teams[i - 1].rank
+            } else {
+                i as u32 + 1
+            };
+        }
+        teams
+    }
+
+    /// Who won, if anyone
+    pub fn outcome(&self) -> GameOutcome {
+        if self.no_winner {
+            return GameOutcome::NoWinner;
+        }
+
+        if !self.teams.is_empty() {
+            let teams = self.teams();
+            let top: Vec<&TeamResult> = teams.iter().filter(|t| t.rank == 1).collect();
+            return match top.as_slice() {
+                [team] => GameOutcome::TeamWin {
+                    team: team.team.clone(),
+                    members: team.members.clone(),
+                },
+                _ => {
+                    let mut player_ids: Vec<String> = top.iter()
+                        .flat_map(|t| t.members.iter().cloned())
+                        .collect();
+                    player_ids.sort();
+                    if player_ids.is_empty() {
+                        GameOutcome::NoWinner
+                    } else {
+                        GameOutcome::Draw { player_ids }
+                    }
+                }
+            };
+        }
+
+        let mut first: Vec<String> = self.placements().into_iter()
+            .filter(|p| p.rank == 1)
+            .map(|p| p.player_id)
+            .collect();
+        match first.len() {
+            0 => GameOutcome::NoWinner,
+            1 => GameOutcome::Winner { player_id: first.remove(0) },
+            _ => GameOutcome::Draw { player_ids: first },
+        }
+    }
+
+    /// Single name for display: the winner, the best-placed member of a winning team,
+    /// the first of several tied players, or "No winner"
+    pub fn winner(&self) -> String {
+        let placements = self.placements();
+        match self.outcome() {
+            GameOutcome::Winner { player_id } => player_id,
+            GameOutcome::Draw { player_ids } => placements.iter()
+                .find(|p| player_ids.contains(&p.player_id))
+                .map(|p| p.player_id.clone())
+                .unwrap_or_else(|| "No winner".to_string()),
+            GameOutcome::TeamWin { members, .. } => placements.iter()
+                .find(|p| members.contains(&p.player_id))
+                .map(|p| p.player_id.clone())
+                .unwrap_or_else(|| "No winner".to_string()),
+            GameOutcome::NoWinner => "No winner".to_string(),
+        }
+    }
+}
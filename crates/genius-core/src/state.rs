@@ -1,7 +1,11 @@
 //! Game state and result types
 
+use crate::clock::PlayerClock;
+use crate::event::{EventKind, GameEvent};
 use crate::game::GameType;
 use crate::player::PlayerAction;
+use crate::standings::{GameOutcome, Placement, TeamResult};
+use crate::team::{Coalition, Team};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -13,6 +17,18 @@ pub struct GameState {
     pub game_type: GameType,
     pub round: u32,
     pub scores: HashMap<String, i32>,
+    /// Everyone who has taken part in or is watching the game
+    #[serde(default)]
+    pub player_status: HashMap<String, PlayerStatus>,
+    /// Fixed sides copied from `GameConfig::teams`
+    #[serde(default)]
+    pub teams: Vec<Team>,
+    /// Alliances formed during play, including ones that have since broken up
+    #[serde(default)]
+    pub coalitions: Vec<Coalition>,
+    /// Thinking time left per player when the game is played under a time control
+    #[serde(default)]
+    pub clocks: HashMap<String, PlayerClock>,
     pub history: Vec<RoundResult>,
     pub metadata: HashMap<String, serde_json::Value>,
     pub created_at: chrono::DateTime<chrono::Utc>,
@@ -28,6 +44,10 @@ impl GameState {
             game_type,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: Vec::new(),
             metadata: HashMap::new(),
             created_at: now,
@@ -35,16 +55,125 @@ impl GameState {
         }
     }
     
-    /// Get list of active players
+    /// Every player in the game, sorted
     pub fn players(&self) -> Vec<&String> {
-        self.scores.keys().collect()
+        let mut players: Vec<&String> = self.scores.keys().collect();
+        players.sort();
+        players
     }
     
     /// Add a player to the game
     pub fn add_player(&mut self, player_id: String) {
+        self.player_status.insert(player_id.clone(), PlayerStatus::Alive);
         self.scores.insert(player_id, 0);
     }
     
+    /// Status of a player; anyone not yet seen is assumed free to join
+    pub fn status(&self, player_id: &str) -> PlayerStatus {
+        self.player_status.get(player_id).copied().unwrap_or(PlayerStatus::Alive)
+    }
+    
+    /// Whether the player may still act
+    pub fn is_active(&self, player_id: &str) -> bool {
+        self.status(player_id) == PlayerStatus::Alive
+    }
+    
+    /// Players still in the game, sorted
+    pub fn active_players(&self) -> Vec<&String> {
+        let mut players: Vec<&String> = self.player_status.iter()
+            .filter(|(_, status)| **status == PlayerStatus::Alive)
+            .map(|(id, _)| id)
+            .collect();
+        players.sort();
+        players
+    }
+    
+    /// Knock a player out in the given round; a player already out keeps their original round
+    pub fn eliminate(&mut self, player_id: &str, round: u32) {
+        let status = self.player_status.entry(player_id.to_string()).or_insert(PlayerStatus::Alive);
+        if !matches!(status, PlayerStatus::Eliminated { .. }) {
+            *status = PlayerStatus::Eliminated { round };
+        }
+    }
+    
+    /// Register someone who watches the game without taking part
+    pub fn spectate(&mut self, player_id: &str) {
+        self.player_status.insert(player_id.to_string(), PlayerStatus::Spectating);
+    }
+    
+    /// Name of the team the player is on, if any
+    pub fn team_of(&self, player_id: &str) -> Option<&str> {
+        self.teams.iter()
+            .find(|team| team.includes(player_id))
+            .map(|team| team.name.as_str())
+    }
+    
+    /// Coalitions that still hold
+    pub fn active_coalitions(&self) -> Vec<&Coalition> {
+        self.coalitions.iter().filter(|c| c.is_active()).collect()
+    }
+    
+    /// Teammates and current coalition partners of a player, sorted
+    pub fn allies(&self, player_id: &str) -> Vec<&String> {
+        let teammates = self.teams.iter()
+            .filter(|team| team.includes(player_id))
+            .flat_map(|team| team.members.iter());
+        let partners = self.active_coalitions().into_iter()
+            .filter(|c| c.includes(player_id))
+            .flat_map(|c| c.members.iter());
+        
+        let mut allies: Vec<&String> = teammates.chain(partners)
+            .filter(|id| *id != player_id)
+            .collect();
+        allies.sort();
+        allies.dedup();
+        allies
+    }
+    
+    /// Reconcile coalitions with the groups holding as of the given round
+    ///
+    /// Groups not seen before are recorded as formed in `round`; active coalitions
+    /// missing from `groups` are marked dissolved in `round`. Groups of fewer than
+    /// two players are ignored.
+    pub fn update_coalitions(&mut self, groups: Vec<Vec<String>>, round: u32) {
+        let mut groups: Vec<Vec<String>> = groups.into_iter()
+            .map(|mut members| {
+                members.sort();
+                members.dedup();
+                members
+            })
+            .filter(|members| members.len() > 1)
+            .collect();
+        groups.sort();
+        groups.dedup();
+        
+        for coalition in self.coalitions.iter_mut().filter(|c| c.is_active()) {
+            if !groups.contains(&coalition.members) {
+                coalition.dissolved_round = Some(round);
+            }
+        }
+        for members in groups {
+            if !self.coalitions.iter().any(|c| c.is_active() && c.members == members) {
+                self.coalitions.push(Coalition {
+                    members,
+                    formed_round: round,
+                    dissolved_round: None,
+                });
+            }
+        }
+    }
+    
+    /// Record a processed round: advance the round counter, apply scores and append history
+    pub fn apply_round(&mut self, result: &RoundResult) {
+        self.round += 1;
+        for player_id in result.actions.keys() {
+            self.player_status.entry(player_id.clone()).or_insert(PlayerStatus::Alive);
+        }
+        self.apply_score_deltas(&result.scores_delta);
+        self.history.push(result.clone());
+        self.updated_at = chrono::Utc::now();
+    }
+    
     /// Update scores based on deltas
     pub fn apply_score_deltas(&mut self, deltas: &HashMap<String, i32>) {
         for (player, delta) in deltas {
@@ -55,6 +184,17 @@ impl GameState {
     }
 }
 
+/// Whether a player is still in the game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum PlayerStatus {
+    Alive,
+    /// Knocked out at the end of the given round
+    Eliminated { round: u32 },
+    /// Watching without taking part
+    Spectating,
+}
+
 /// Result of a single game round
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoundResult {
@@ -62,33 +202,52 @@ pub struct RoundResult {
     pub actions: HashMap<String, PlayerAction>,
     pub outcome: RoundOutcome,
     pub scores_delta: HashMap<String, i32>,
+    /// Typed record of what happened this round
     pub events: Vec<GameEvent>,
+    /// Emergence the engine's detector found in this round
+    #[serde(default)]
+    pub emergence: Vec<EmergenceEvent>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
+impl RoundResult {
+    /// Emergence seen this round, whether reported by the game or found by the engine
+    pub fn emergence_events(&self) -> Vec<EmergenceEvent> {
+        let reported = self.events.iter().filter_map(|event| match &event.kind {
+            EventKind::Emergence { emergence_type, score } => Some(EmergenceEvent {
+                round: self.round,
+                event_type: emergence_type.clone(),
+                description: event.description.clone(),
+                emergence_score: *score,
+                involved_players: event.affected_players.clone(),
+            }),
+            _ => None,
+        });
+        reported.chain(self.emergence.iter().cloned()).collect()
+    }
+}
+
 /// Outcome of a round
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoundOutcome {
     pub winners: Vec<String>,
     pub losers: Vec<String>,
+    /// Human-readable summaries for display; see `RoundResult::events` for typed events
     pub special_events: Vec<String>,
     pub emergence_detected: bool,
 }
 
-/// Events that can occur during gameplay
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GameEvent {
-    pub event_type: String,
-    pub description: String,
-    pub affected_players: Vec<String>,
-    pub data: serde_json::Value,
-}
-
 /// Final result of a completed game
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameResult {
     pub game_id: Uuid,
+    /// Best-placed player, for display; see `outcome` for draws and team wins
     pub winner: String,
+    pub outcome: GameOutcome,
+    /// Every player from first to last
+    pub placements: Vec<Placement>,
+    /// Every team from first to last; empty when the game has no teams
+    pub teams: Vec<TeamResult>,
     pub final_scores: HashMap<String, i32>,
     pub total_rounds: u32,
     pub duration_ms: u64,
@@ -96,6 +255,13 @@ pub struct GameResult {
     pub analytics: GameAnalytics,
 }
 
+impl GameResult {
+    /// Where a player finished
+    pub fn placement(&self, player_id: &str) -> Option<&Placement> {
+        self.placements.iter().find(|p| p.player_id == player_id)
+    }
+}
+
 /// Emergence event detected during gameplay
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmergenceEvent {
@@ -0,0 +1,80 @@
+//! Teams declared up front and coalitions formed during play
+
+use crate::error::{GameError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A fixed side declared in `GameConfig`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Team {
+    pub name: String,
+    pub members: Vec<String>,
+}
+
+impl Team {
+    /// Team with the given members, sorted and without duplicates
+    pub fn new<S: Into<String>>(name: &str, members: impl IntoIterator<Item = S>) -> Self {
+        let mut members: Vec<String> = members.into_iter().map(Into::into).collect();
+        members.sort();
+        members.dedup();
+        Self {
+            name: name.to_string(),
+            members,
+        }
+    }
+
+    /// Whether the player is on this team
+    pub fn includes(&self, player_id: &str) -> bool {
+        self.members.iter().any(|m| m == player_id)
+    }
+}
+
+/// Check that team names are unique and nobody is on two teams
+pub fn validate_teams(teams: &[Team]) -> Result<()> {
+    let mut seen: HashMap<&str, &str> = HashMap::new();
+    for (i, team) in teams.iter().enumerate() {
+        if team.name.is_empty() {
+            return Err(GameError::ConfigError {
+                reason: "Team names must not be empty".to_string(),
+            });
+        }
+        if teams[..i].iter().any(|t| t.name == team.name) {
+            return Err(GameError::ConfigError {
+                reason: format!("Team '{}' is declared twice", team.name),
+            });
+        }
+        for member in &team.members {
+            if let Some(other) = seen.insert(member, &team.name) {
+                if other != team.name {
+                    return Err(GameError::ConfigError {
+                        reason: format!("Player '{}' is on both '{}' and '{}'", member, other, team.name),
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// An alliance players formed during the game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Coalition {
+    /// Sorted member ids
+    pub members: Vec<String>,
+    /// Round in which the coalition was first seen
+    pub formed_round: u32,
+    /// Round in which it broke up, or `None` while it holds
+    pub dissolved_round: Option<u32>,
+}
+
+impl Coalition {
+    /// Whether the coalition still holds
+    pub fn is_active(&self) -> bool {
+        self.dissolved_round.is_none()
+    }
+
+    /// Whether the player belongs to this coalition
+    pub fn includes(&self, player_id: &str) -> bool {
+        self.members.iter().any(|m| m == player_id)
+    }
+}
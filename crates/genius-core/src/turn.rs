@@ -0,0 +1,72 @@
+//! Turn structure: who must act next and when their actions resolve the round
+
+use crate::player::PlayerAction;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// When a round resolves relative to the players asked to act
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TurnResolution {
+    /// Once every listed player has acted, as in simultaneous-move games
+    AllOf,
+    /// As soon as one listed player has acted, as in turn-based games
+    OneOf,
+}
+
+/// The players a game is waiting on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Turn {
+    /// Players who may act next; empty while the game has no roster yet and accepts anyone
+    pub players: Vec<String>,
+    pub resolution: TurnResolution,
+}
+
+impl Turn {
+    /// Every listed player moves at once
+    pub fn simultaneous(players: Vec<String>) -> Self {
+        Self {
+            players,
+            resolution: TurnResolution::AllOf,
+        }
+    }
+
+    /// Only the given player moves
+    pub fn sequential(player: String) -> Self {
+        Self::one_of(vec![player])
+    }
+
+    /// Any one of the listed players moves
+    pub fn one_of(players: Vec<String>) -> Self {
+        Self {
+            players,
+            resolution: TurnResolution::OneOf,
+        }
+    }
+
+    /// Whether the game accepts an action from this player now
+    pub fn expects(&self, player_id: &str) -> bool {
+        self.players.is_empty() || self.players.iter().any(|p| p == player_id)
+    }
+
+    /// Expected players who have not submitted an action yet
+    pub fn waiting_on(&self, actions: &HashMap<String, PlayerAction>) -> Vec<String> {
+        self.players.iter()
+            .filter(|p| !actions.contains_key(*p))
+            .cloned()
+            .collect()
+    }
+
+    /// Whether the submitted actions are enough to resolve the round
+    ///
+    /// An open turn is never ready on its own; the caller decides when everyone has joined.
+    pub fn is_ready(&self, actions: &HashMap<String, PlayerAction>) -> bool {
+        if self.players.is_empty() {
+            return false;
+        }
+        match self.resolution {
+            TurnResolution::AllOf => self.waiting_on(actions).is_empty(),
+            TurnResolution::OneOf => self.players.iter().any(|p| actions.contains_key(p)),
+        }
+    }
+}
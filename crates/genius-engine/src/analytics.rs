@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use uuid::Uuid;
 use dashmap::DashMap;
 
-use genius_core::RoundResult;
+use genius_core::{GameState, RoundResult};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameAnalyticsData {
@@ -47,6 +47,15 @@ pub struct PerformanceComparison {
     pub draw_rate: f32,
     pub avg_score_differential: f32,
     pub critical_moments: Vec<CriticalMoment>,
+    /// Teams as declared in the game's config
+    #[serde(default)]
+    pub teams: Vec<String>,
+    /// Rounds in which each team had more winners than any other team
+    #[serde(default)]
+    pub team_round_wins: HashMap<String, u32>,
+    /// Share of rounds each team won
+    #[serde(default)]
+    pub team_win_rates: HashMap<String, f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,7 +82,7 @@ impl AnalyticsEngine {
         Self::default()
     }
     
-    pub async fn process_round(&self, game_id: Uuid, round_result: &RoundResult) {
+    pub async fn process_round(&self, game_id: Uuid, state: &GameState, round_result: &RoundResult) {
         let mut analytics = self.game_analytics.entry(game_id)
             .or_insert_with(|| GameAnalyticsData {
                 game_id,
@@ -103,6 +112,9 @@ impl AnalyticsEngine {
                     draw_rate: 0.0,
                     avg_score_differential: 0.0,
                     critical_moments: vec![],
+                    teams: state.teams.iter().map(|team| team.name.clone()).collect(),
+                    team_round_wins: state.teams.iter().map(|team| (team.name.clone(), 0)).collect(),
+                    team_win_rates: HashMap::new(),
                 },
             });
         
@@ -110,7 +122,7 @@ impl AnalyticsEngine {
         analytics.rounds_played += 1;
         
         // Track emergence
-        if round_result.outcome.emergence_detected {
+        if round_result.outcome.emergence_detected || !round_result.emergence.is_empty() {
             analytics.emergence_analysis.total_emergence_events += 1;
             analytics.emergence_analysis.emergence_timeline.push((
                 round_result.round,
@@ -118,16 +130,31 @@ impl AnalyticsEngine {
             ));
         }
         
-        // Update win rates
-        let collective_winners = round_result.outcome.winners.iter()
-            .filter(|w| w.starts_with("collective_"))
-            .count();
-        let sota_winners = round_result.outcome.winners.iter()
-            .filter(|w| w.starts_with("sota_"))
-            .count();
+        // Tally emergence events by kind, whether the game reported them or the engine detected them
+        for event in round_result.emergence_events() {
+            *analytics.emergence_analysis.emergence_types
+                .entry(format!("{:?}", event.event_type))
+                .or_insert(0) += 1;
+        }
+        
+        // Credit the round to the team with the most winners, if one stands out
+        let mut team_winners: HashMap<&str, usize> = HashMap::new();
+        for winner in &round_result.outcome.winners {
+            if let Some(team) = state.team_of(winner) {
+                *team_winners.entry(team).or_insert(0) += 1;
+            }
+        }
+        let most = team_winners.values().copied().max().unwrap_or(0);
+        let leaders: Vec<&str> = team_winners.iter()
+            .filter(|(_, count)| **count == most)
+            .map(|(team, _)| *team)
+            .collect();
         
-        if collective_winners > sota_winners {
-            analytics.emergence_analysis.collective_advantage_moments.push(round_result.round);
+        if let [team] = leaders.as_slice() {
+            *analytics.performance_comparison.team_round_wins.entry(team.to_string()).or_insert(0) += 1;
+            if analytics.performance_comparison.teams.first().map(String::as_str) == Some(*team) {
+                analytics.emergence_analysis.collective_advantage_moments.push(round_result.round);
+            }
         }
         
         // Detect critical moments
@@ -153,10 +180,19 @@ impl AnalyticsEngine {
             analytics.collective_metrics.emergence_frequency = 
                 analytics.emergence_analysis.total_emergence_events as f32 / total_rounds;
             
-            // Collective advantage rate
-            let collective_advantage_rounds = analytics.emergence_analysis.collective_advantage_moments.len() as f32;
-            analytics.performance_comparison.collective_win_rate = collective_advantage_rounds / total_rounds;
-            analytics.performance_comparison.sota_win_rate = 1.0 - analytics.performance_comparison.collective_win_rate;
+            // Per-team win rates
+            let comparison = &mut analytics.performance_comparison;
+            comparison.team_win_rates = comparison.team_round_wins.iter()
+                .map(|(team, wins)| (team.clone(), *wins as f32 / total_rounds))
+                .collect();
+            // The first two declared teams are the collective and SOTA sides
+            let rate = |side: usize| comparison.teams.get(side)
+                .and_then(|team| comparison.team_win_rates.get(team))
+                .copied()
+                .unwrap_or(0.0);
+            let (collective, sota) = (rate(0), rate(1));
+            comparison.collective_win_rate = collective;
+            comparison.sota_win_rate = sota;
             
             Some(analytics.clone())
         } else {
@@ -0,0 +1,374 @@
+//! Batch simulation: thousands of headless games between AI agents, with aggregate statistics
+
+use crate::{GameEngine, MatchRunner, TurnScheduler};
+use genius_ai::AIProvider;
+use genius_core::{derive_seed, random_seed, EmergenceType, GameConfig, GameError, GameOutcome, GameResult, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+
+/// z-score for 95% confidence intervals
+const Z_95: f64 = 1.96;
+
+/// Builds an agent for one game from a seed derived for it
+pub type AgentFactory = Arc<dyn Fn(u64) -> Arc<dyn AIProvider> + Send + Sync>;
+
+/// A range a statistic lies in with 95% confidence
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Interval {
+    pub low: f64,
+    pub high: f64,
+}
+
+impl Interval {
+    /// Wilson score interval for `hits` out of `trials`
+    fn wilson(hits: u32, trials: u32) -> Self {
+        if trials == 0 {
+            return Self { low: 0.0, high: 1.0 };
+        }
+        let n = trials as f64;
+        let p = hits as f64 / n;
+        let z2 = Z_95 * Z_95;
+        let centre = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let margin = Z_95 * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / (1.0 + z2 / n);
+        Self { low: (centre - margin).max(0.0), high: (centre + margin).min(1.0) }
+    }
+}
+
+/// Summary of a set of integer samples
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Distribution {
+    pub count: usize,
+    pub mean: f64,
+    /// Sample standard deviation
+    pub std_dev: f64,
+    pub min: i64,
+    pub max: i64,
+    pub median: f64,
+    /// Where the true mean lies, by the normal approximation
+    pub mean_ci: Interval,
+    /// How many samples took each value
+    pub histogram: BTreeMap<i64, u32>,
+}
+
+impl Distribution {
+    fn of(samples: &[i64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let n = sorted.len();
+        let mean = sorted.iter().sum::<i64>() as f64 / n as f64;
+        let std_dev = if n > 1 {
+            (sorted.iter().map(|&x| (x as f64 - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+        let median = if n % 2 == 1 {
+            sorted[n / 2] as f64
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) as f64 / 2.0
+        };
+        let margin = Z_95 * std_dev / (n as f64).sqrt();
+        let mut histogram = BTreeMap::new();
+        for &x in &sorted {
+            *histogram.entry(x).or_insert(0) += 1;
+        }
+        Self {
+            count: n,
+            mean,
+            std_dev,
+            min: sorted[0],
+            max: sorted[n - 1],
+            median,
+            mean_ci: Interval { low: mean - margin, high: mean + margin },
+            histogram,
+        }
+    }
+}
+
+/// How one agent did across the batch
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AgentStats {
+    /// Games won outright, alone or with their team
+    pub wins: u32,
+    /// Games in which they shared first place
+    pub draws: u32,
+    pub losses: u32,
+    pub win_rate: f64,
+    pub win_rate_ci: Interval,
+    /// Final scores
+    pub score: Distribution,
+}
+
+/// How often a kind of emergence showed up
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct EmergenceFrequency {
+    /// Games in which it was detected at least once
+    pub games: u32,
+    pub rate: f64,
+    pub rate_ci: Interval,
+}
+
+/// Aggregate results of a batch, so far or in full
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct BatchStats {
+    /// Seed every game's seed was derived from; rerunning with it repeats the batch
+    pub base_seed: u64,
+    pub planned: u32,
+    pub completed: u32,
+    /// Games that could not be played to the end; they count towards nothing else
+    pub failed: u32,
+    /// Completed games with shared first place
+    pub draws: u32,
+    /// Completed games nobody won
+    pub no_winner: u32,
+    pub agents: BTreeMap<String, AgentStats>,
+    pub rounds: Distribution,
+    /// Keyed by emergence type, e.g. `NashEquilibrium`
+    pub emergence: BTreeMap<String, EmergenceFrequency>,
+}
+
+/// Raw samples the statistics are computed from
+struct Tally {
+    base_seed: u64,
+    planned: u32,
+    completed: u32,
+    failed: u32,
+    draws: u32,
+    no_winner: u32,
+    /// Per agent: wins, draws, losses and final scores
+    agents: BTreeMap<String, (u32, u32, u32, Vec<i64>)>,
+    rounds: Vec<i64>,
+    emergence: BTreeMap<String, u32>,
+}
+
+impl Tally {
+    fn add(&mut self, result: &GameResult) {
+        self.completed += 1;
+        match &result.outcome {
+            GameOutcome::Draw { .. } => self.draws += 1,
+            GameOutcome::NoWinner => self.no_winner += 1,
+            GameOutcome::Winner { .. } | GameOutcome::TeamWin { .. } => {}
+        }
+        for (name, (wins, draws, losses, scores)) in self.agents.iter_mut() {
+            match &result.outcome {
+                GameOutcome::Winner { player_id } if player_id == name => *wins += 1,
+                GameOutcome::TeamWin { members, .. } if members.contains(name) => *wins += 1,
+                GameOutcome::Draw { player_ids } if player_ids.contains(name) => *draws += 1,
+                _ => *losses += 1,
+            }
+            scores.push(result.final_scores.get(name).copied().unwrap_or_default() as i64);
+        }
+        self.rounds.push(result.total_rounds as i64);
+
+        let mut kinds: Vec<String> = result.emergence_events.iter().map(|e| emergence_key(&e.event_type)).collect();
+        kinds.sort();
+        kinds.dedup();
+        for kind in kinds {
+            *self.emergence.entry(kind).or_insert(0) += 1;
+        }
+    }
+
+    fn stats(&self) -> BatchStats {
+        let rate = |hits: u32| if self.completed == 0 { 0.0 } else { hits as f64 / self.completed as f64 };
+        BatchStats {
+            base_seed: self.base_seed,
+            planned: self.planned,
+            completed: self.completed,
+            failed: self.failed,
+            draws: self.draws,
+            no_winner: self.no_winner,
+            agents: self.agents.iter()
+                .map(|(name, (wins, draws, losses, scores))| {
+                    (name.clone(), AgentStats {
+                        wins: *wins,
+                        draws: *draws,
+                        losses: *losses,
+                        win_rate: rate(*wins),
+                        win_rate_ci: Interval::wilson(*wins, self.completed),
+                        score: Distribution::of(scores),
+                    })
+                })
+                .collect(),
+            rounds: Distribution::of(&self.rounds),
+            emergence: self.emergence.iter()
+                .map(|(kind, games)| {
+                    (kind.clone(), EmergenceFrequency {
+                        games: *games,
+                        rate: rate(*games),
+                        rate_ci: Interval::wilson(*games, self.completed),
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+fn emergence_key(kind: &EmergenceType) -> String {
+    match kind {
+        EmergenceType::Custom(name) => name.clone(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Plays the same game many times over between a fixed set of agents
+///
+/// Games run concurrently on the tokio runtime, so a multi-threaded runtime spreads them
+/// over every CPU core. Each game's seed is derived from the config's seed (or a fresh
+/// one) and the game's index, and each agent is built for its game from a seed derived
+/// from that, so a batch is reproducible as a whole whatever order games finish in.
+/// Seats rotate from game to game so no agent always moves first.
+///
+/// Statistics are published to `progress` subscribers as games finish.
+pub struct BatchRunner {
+    engine: Arc<GameEngine>,
+    config: GameConfig,
+    games: u32,
+    agents: Vec<(String, AgentFactory)>,
+    scheduler: TurnScheduler,
+    concurrency: usize,
+    report_every: u32,
+    progress: watch::Sender<BatchStats>,
+}
+
+impl BatchRunner {
+    /// Batch of `games` games created from `config`
+    pub fn new(engine: Arc<GameEngine>, config: GameConfig, games: u32) -> Self {
+        let concurrency = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        Self {
+            engine,
+            config,
+            games,
+            agents: Vec::new(),
+            scheduler: TurnScheduler::default(),
+            concurrency,
+            report_every: 100,
+            progress: watch::channel(BatchStats::default()).0,
+        }
+    }
+
+    /// Seat an agent under `name` in every game, built fresh for each game by `factory`
+    pub fn agent<F>(mut self, name: &str, factory: F) -> Self
+    where
+        F: Fn(u64) -> Arc<dyn AIProvider> + Send + Sync + 'static,
+    {
+        self.agents.push((name.to_string(), Arc::new(factory)));
+        self
+    }
+
+    pub fn scheduler(mut self, scheduler: TurnScheduler) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    /// Games in flight at once; defaults to the number of CPU cores
+    pub fn concurrency(mut self, games: usize) -> Self {
+        self.concurrency = games.max(1);
+        self
+    }
+
+    /// Publish statistics every `games` finished games, as well as at the end
+    pub fn report_every(mut self, games: u32) -> Self {
+        self.report_every = games.max(1);
+        self
+    }
+
+    /// Receive the latest statistics while the batch runs
+    pub fn progress(&self) -> watch::Receiver<BatchStats> {
+        self.progress.subscribe()
+    }
+
+    /// Play every game and return the final statistics
+    pub async fn run(&self) -> Result<BatchStats> {
+        if self.agents.is_empty() {
+            return Err(GameError::MinPlayersNotMet { required: 1 });
+        }
+
+        let base_seed = self.config.seed.unwrap_or_else(random_seed);
+        let mut tally = Tally {
+            base_seed,
+            planned: self.games,
+            completed: 0,
+            failed: 0,
+            draws: 0,
+            no_winner: 0,
+            agents: self.agents.iter().map(|(name, _)| (name.clone(), (0, 0, 0, Vec::new()))).collect(),
+            rounds: Vec::new(),
+            emergence: BTreeMap::new(),
+        };
+        self.progress.send_replace(tally.stats());
+
+        let agents = Arc::new(self.agents.clone());
+        let scheduler = Arc::new(self.scheduler.clone());
+        let mut in_flight = JoinSet::new();
+        let mut next = 0;
+        loop {
+            while next < self.games && in_flight.len() < self.concurrency {
+                let mut config = self.config.clone();
+                config.seed = Some(derive_seed(base_seed, next as u64));
+                in_flight.spawn(play(self.engine.clone(), config, agents.clone(), scheduler.clone(), next as usize));
+                next += 1;
+            }
+            let Some(joined) = in_flight.join_next().await else {
+                break;
+            };
+
+            match joined {
+                Ok(Ok(result)) => tally.add(&result),
+                Ok(Err(e)) => {
+                    tracing::warn!("Batch game failed: {}", e);
+                    tally.failed += 1;
+                }
+                Err(e) => {
+                    tracing::error!("Batch game panicked: {}", e);
+                    tally.failed += 1;
+                }
+            }
+            if (tally.completed + tally.failed).is_multiple_of(self.report_every) {
+                self.progress.send_replace(tally.stats());
+            }
+        }
+
+        let stats = tally.stats();
+        self.progress.send_replace(stats.clone());
+        Ok(stats)
+    }
+}
+
+/// Play game number `index` to the end
+async fn play(
+    engine: Arc<GameEngine>,
+    config: GameConfig,
+    agents: Arc<Vec<(String, AgentFactory)>>,
+    scheduler: Arc<TurnScheduler>,
+    index: usize,
+) -> Result<GameResult> {
+    let seed = config.seed.expect("batch games are always seeded");
+    let game_id = engine.open_lobby(config).await?.game_id;
+
+    let mut runner = MatchRunner::new(engine.clone()).scheduler((*scheduler).clone());
+    let played = async {
+        for seat in 0..agents.len() {
+            let agent = (index + seat) % agents.len();
+            let (name, factory) = &agents[agent];
+            engine.join_game(game_id, name).await?;
+            runner = runner.seat(name, factory(derive_seed(seed, agent as u64 + 1)));
+        }
+        engine.start_game(game_id).await?;
+        runner.run(game_id).await
+    }
+    .await;
+
+    if let Err(e) = played {
+        // Don't leave thousands of broken games behind in the engine
+        if engine.abort_game(game_id).await.is_ok() {
+            let _ = engine.finalize_game(game_id).await;
+        }
+        return Err(e);
+    }
+    engine.finalize_game(game_id).await
+}
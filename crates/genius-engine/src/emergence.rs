@@ -1,12 +1,14 @@
 //! Emergence detection and analysis
 
-use genius_core::{GameState, RoundResult, EmergenceEvent, EmergenceType};
+use genius_core::{RoundResult, EmergenceEvent, EmergenceType};
 use std::collections::HashMap;
 
 /// Detects emergence patterns in game behavior
 pub struct EmergenceDetector {
     /// History of pattern observations
     pattern_history: Vec<PatternObservation>,
+    /// Where the current phase begins in the history
+    phase_start: usize,
     /// Thresholds for different emergence types
     thresholds: HashMap<EmergenceType, f32>,
 }
@@ -30,59 +32,98 @@ impl EmergenceDetector {
         
         Self {
             pattern_history: Vec::new(),
+            phase_start: 0,
             thresholds,
         }
     }
     
-    /// Analyze a round for emergence events
-    pub fn analyze_round(&mut self, state: &GameState, round_result: &RoundResult) -> Vec<EmergenceEvent> {
+    /// Rebuild a detector from the rounds already played
+    pub fn from_history(history: &[RoundResult]) -> Self {
+        let mut detector = Self::new();
+        for round_result in history {
+            detector.analyze_round(round_result);
+        }
+        detector
+    }
+    
+    /// Analyze a round for emergence events, adding it to the detector's history
+    pub fn analyze_round(&mut self, round_result: &RoundResult) -> Vec<EmergenceEvent> {
         let mut events = Vec::new();
+        if round_result.actions.is_empty() {
+            return events;
+        }
         
         // Check for collective strategy emergence
-        if let Some(event) = self.check_collective_strategy(state, round_result) {
+        if let Some(event) = self.check_collective_strategy(round_result) {
             events.push(event);
         }
         
         // Check for spontaneous coordination
-        if let Some(event) = self.check_spontaneous_coordination(state, round_result) {
+        if let Some(event) = self.check_spontaneous_coordination(round_result) {
             events.push(event);
         }
         
-        // Check for phase transitions
-        if let Some(event) = self.check_phase_transition(state, round_result) {
+        // Check for phase transitions against what came before
+        self.record(round_result);
+        if let Some(event) = self.check_phase_transition(round_result) {
             events.push(event);
         }
         
         events
     }
     
-    fn check_collective_strategy(&mut self, state: &GameState, round_result: &RoundResult) -> Option<EmergenceEvent> {
+    /// Remember the round's dominant action and how widely it was shared
+    fn record(&mut self, round_result: &RoundResult) {
+        let Some((pattern_type, count)) = Self::dominant_action(round_result) else {
+            return;
+        };
+        self.pattern_history.push(PatternObservation {
+            round: round_result.round,
+            strength: count as f32 / round_result.actions.len() as f32,
+            players_involved: Self::players(round_result).into_iter()
+                .filter(|player_id| round_result.actions[player_id].action_type == pattern_type)
+                .collect(),
+            pattern_type,
+        });
+    }
+    
+    /// Everyone who acted this round, sorted so events compare equal across runs
+    fn players(round_result: &RoundResult) -> Vec<String> {
+        let mut players: Vec<String> = round_result.actions.keys().cloned().collect();
+        players.sort();
+        players
+    }
+    
+    /// Most common action this round, ties broken by name so the result is stable
+    fn dominant_action(round_result: &RoundResult) -> Option<(String, usize)> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for action in round_result.actions.values() {
+            *counts.entry(action.action_type.as_str()).or_insert(0) += 1;
+        }
+        counts.into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(action, count)| (action.to_string(), count))
+    }
+    
+    fn check_collective_strategy(&mut self, round_result: &RoundResult) -> Option<EmergenceEvent> {
         // Analyze if players are converging on a collective strategy
-        let action_types: HashMap<String, usize> = round_result.actions.values()
-            .map(|a| a.action_type.clone())
-            .fold(HashMap::new(), |mut acc, action| {
-                *acc.entry(action).or_insert(0) += 1;
-                acc
-            });
-            
-        let total_actions = round_result.actions.len() as f32;
-        let max_consensus = action_types.values().max().copied().unwrap_or(0) as f32;
-        let consensus_ratio = max_consensus / total_actions;
+        let (strategy, max_consensus) = Self::dominant_action(round_result)?;
+        let consensus_ratio = max_consensus as f32 / round_result.actions.len() as f32;
         
         if consensus_ratio > self.thresholds[&EmergenceType::CollectiveStrategy] {
             return Some(EmergenceEvent {
-                round: state.round,
+                round: round_result.round,
                 event_type: EmergenceType::CollectiveStrategy,
-                description: format!("Players converging on collective strategy with {:.1}% consensus", consensus_ratio * 100.0),
+                description: format!("Players converging on '{}' with {:.1}% consensus", strategy, consensus_ratio * 100.0),
                 emergence_score: consensus_ratio,
-                involved_players: round_result.actions.keys().cloned().collect(),
+                involved_players: Self::players(round_result),
             });
         }
         
         None
     }
     
-    fn check_spontaneous_coordination(&mut self, state: &GameState, round_result: &RoundResult) -> Option<EmergenceEvent> {
+    fn check_spontaneous_coordination(&mut self, round_result: &RoundResult) -> Option<EmergenceEvent> {
         // Check if players are coordinating without explicit communication
         // This is simplified - real implementation would be more sophisticated
         
@@ -90,36 +131,53 @@ impl EmergenceDetector {
         
         if coordination_score > self.thresholds[&EmergenceType::SpontaneousCoordination] {
             return Some(EmergenceEvent {
-                round: state.round,
+                round: round_result.round,
                 event_type: EmergenceType::SpontaneousCoordination,
                 description: "Players exhibiting spontaneous coordination without explicit communication".to_string(),
                 emergence_score: coordination_score,
-                involved_players: round_result.actions.keys().cloned().collect(),
+                involved_players: Self::players(round_result),
             });
         }
         
         None
     }
     
-    fn check_phase_transition(&mut self, state: &GameState, round_result: &RoundResult) -> Option<EmergenceEvent> {
-        // Detect sudden changes in collective behavior
-        if self.pattern_history.len() < 5 {
+    fn check_phase_transition(&mut self, round_result: &RoundResult) -> Option<EmergenceEvent> {
+        // Detect sudden changes in collective behavior since the last transition
+        let phase = &self.pattern_history[self.phase_start..];
+        if phase.len() < 5 {
             return None;
         }
         
-        // Compare recent patterns to historical patterns
-        let recent_avg = self.recent_pattern_strength(3);
-        let historical_avg = self.historical_pattern_strength(10);
+        // Compare recent patterns to historical patterns, measured against
+        // the behaviour that dominated before, so a switch of strategy counts
+        let (historical, recent) = phase.split_at(phase.len() - 3);
+        let baseline = Self::dominant_pattern(historical);
+        let recent_avg = Self::pattern_strength(recent, &baseline);
+        let historical_avg = Self::pattern_strength(&historical[historical.len().saturating_sub(10)..], &baseline);
         
         let change_ratio = (recent_avg - historical_avg).abs() / historical_avg.max(0.1);
         
         if change_ratio > 0.5 {
+            // The new phase starts where the old behaviour was first abandoned
+            let changed = recent.iter().position(|p| p.pattern_type != baseline).unwrap_or(0);
+            let since = recent[changed].round;
+            let mut involved_players: Vec<String> = recent.iter()
+                .flat_map(|p| p.players_involved.iter().cloned())
+                .chain(round_result.actions.keys().cloned())
+                .collect();
+            involved_players.sort();
+            involved_players.dedup();
+            self.phase_start = self.pattern_history.len() - recent.len() + changed;
             return Some(EmergenceEvent {
-                round: state.round,
+                round: round_result.round,
                 event_type: EmergenceType::PhaseTransition,
-                description: format!("System undergoing phase transition with {:.1}% change in behavior", change_ratio * 100.0),
+                description: format!(
+                    "System undergoing phase transition away from '{}' since round {} with {:.1}% change in behavior",
+                    baseline, since, change_ratio * 100.0,
+                ),
                 emergence_score: change_ratio.min(1.0),
-                involved_players: round_result.actions.keys().cloned().collect(),
+                involved_players,
             });
         }
         
@@ -152,31 +210,27 @@ impl EmergenceDetector {
         }
     }
     
-    fn recent_pattern_strength(&self, window: usize) -> f32 {
-        let recent: Vec<_> = self.pattern_history.iter()
-            .rev()
-            .take(window)
-            .collect();
-            
-        if recent.is_empty() {
-            return 0.0;
+    /// Pattern most rounds settled on, ties broken by name
+    fn dominant_pattern(observations: &[PatternObservation]) -> String {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for observation in observations {
+            *counts.entry(observation.pattern_type.as_str()).or_insert(0) += 1;
         }
-        
-        recent.iter().map(|p| p.strength).sum::<f32>() / recent.len() as f32
+        counts.into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(pattern, _)| pattern.to_string())
+            .unwrap_or_default()
     }
     
-    fn historical_pattern_strength(&self, window: usize) -> f32 {
-        let historical: Vec<_> = self.pattern_history.iter()
-            .rev()
-            .skip(3)
-            .take(window)
-            .collect();
-            
-        if historical.is_empty() {
+    /// Average strength of `pattern`, counting rounds that followed another pattern as zero
+    fn pattern_strength(observations: &[PatternObservation], pattern: &str) -> f32 {
+        if observations.is_empty() {
             return 0.0;
         }
         
-        historical.iter().map(|p| p.strength).sum::<f32>() / historical.len() as f32
+        observations.iter()
+            .map(|p| if p.pattern_type == pattern { p.strength } else { 0.0 })
+            .sum::<f32>() / observations.len() as f32
     }
 }
 
@@ -1,12 +1,17 @@
 //! Core game engine implementation
 
 use genius_core::{
-    Game, GameConfig, GameState, GameType, RoundResult, GameResult,
-    PlayerAction, GameError, Result,
+    Game, GameConfig, GameFactory, GameState, GamePhase, RoundResult, GameResult,
+    PlayerAction, PlayerClock, PlayerStatus, LegalAction, ActionRejection, Turn, Observation, Observer, GameSnapshot, Replay, GameError, Result,
+    random_seed, validate_teams,
 };
+use crate::emergence::EmergenceDetector;
+use crate::journal::{Journal, JournalEntry, JournalEvent};
+use crate::storage::{GameStore, StoredGame};
+use std::path::Path;
 use dashmap::DashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use uuid::Uuid;
 use std::collections::HashMap;
 
@@ -14,8 +19,10 @@ use std::collections::HashMap;
 pub struct GameEngine {
     /// Active game instances
     games: DashMap<Uuid, Arc<RwLock<GameInstance>>>,
-    /// Game factory function
-    game_factory: Arc<dyn Fn(GameType) -> Result<Box<dyn Game>> + Send + Sync>,
+    /// Resolves game types to fresh instances, e.g. a `GameRegistry` or a plain factory function
+    game_factory: Arc<dyn GameFactory>,
+    /// Where games are saved after every change, if anywhere
+    store: Option<Arc<dyn GameStore>>,
 }
 
 /// A single game instance with its state
@@ -23,39 +30,170 @@ struct GameInstance {
     game: Box<dyn Game>,
     state: GameState,
     config: GameConfig,
+    replay: Replay,
+    phase: GamePhase,
+    journal: Journal,
+    /// Watches the rounds played for emergent behaviour
+    emergence: EmergenceDetector,
+}
+
+impl GameInstance {
+    /// Seat a player in the lobby; players are dealt in in the order they sat down
+    fn take_seat(&mut self, player_id: &str) {
+        self.state.add_player(player_id.to_string());
+        if !self.replay.players.iter().any(|p| p == player_id) {
+            self.replay.players.push(player_id.to_string());
+        }
+    }
+    
+    /// Give up a seat in the lobby
+    fn give_up_seat(&mut self, player_id: &str) {
+        self.state.player_status.remove(player_id);
+        self.state.scores.remove(player_id);
+        self.replay.players.retain(|p| p != player_id);
+    }
 }
 
 impl GameEngine {
     /// Create a new game engine
     pub fn new<F>(game_factory: F) -> Self 
     where 
-        F: Fn(GameType) -> Result<Box<dyn Game>> + Send + Sync + 'static
+        F: GameFactory + 'static
     {
         Self {
             games: DashMap::new(),
             game_factory: Arc::new(game_factory),
+            store: None,
         }
     }
     
-    /// Create a new game instance
+    /// Save every game to the given store as it changes; call `recover` to pick up stored games
+    pub fn with_store(mut self, store: Arc<dyn GameStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+    
+    /// Load every in-flight game from the store and make it active again, returning their ids
+    ///
+    /// Meant to be called once on start-up. Finalized games stay in the store for
+    /// `get_result` and `get_replay`; games that can no longer be rebuilt, e.g. because
+    /// their type is not registered, are logged and skipped.
+    pub async fn recover(&self) -> Result<Vec<Uuid>> {
+        let Some(store) = &self.store else {
+            return Ok(Vec::new());
+        };
+        
+        let mut recovered = Vec::new();
+        for game_id in store.list().await? {
+            if self.games.contains_key(&game_id) {
+                continue;
+            }
+            let Some(stored) = store.load(game_id).await? else {
+                continue;
+            };
+            if !stored.is_in_flight() {
+                continue;
+            }
+            match self.rebuild_instance(stored.snapshot, stored.replay, stored.journal) {
+                Ok(instance) => {
+                    self.games.insert(game_id, Arc::new(RwLock::new(instance)));
+                    recovered.push(game_id);
+                }
+                Err(e) => tracing::error!("Could not recover game {}: {}", game_id, e),
+            }
+        }
+        
+        recovered.sort();
+        Ok(recovered)
+    }
+    
+    /// Save a game to the store, if there is one
+    ///
+    /// Failures are logged rather than returned: the change has already been made in
+    /// memory, and the next successful save brings the store up to date.
+    async fn persist(&self, instance: &GameInstance) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        let saved = match Self::stored(instance, None) {
+            Ok(game) => store.save(&game).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = saved {
+            tracing::error!("Failed to store game {}: {}", instance.state.game_id, e);
+        }
+    }
+    
+    fn stored(instance: &GameInstance, result: Option<GameResult>) -> Result<StoredGame> {
+        Ok(StoredGame {
+            snapshot: Self::snapshot_of(instance)?,
+            replay: instance.replay.clone(),
+            journal: instance.journal.entries().to_vec(),
+            result,
+        })
+    }
+    
+    /// A game that is no longer active, from the store
+    async fn stored_game(&self, game_id: Uuid) -> Result<StoredGame> {
+        let stored = match &self.store {
+            Some(store) => store.load(game_id).await?,
+            None => None,
+        };
+        stored.ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })
+    }
+    
+    /// Create a new game instance and start it right away
     pub async fn create_game(&self, config: GameConfig) -> Result<GameState> {
+        let game_id = self.open_lobby(config).await?.game_id;
+        self.start_game(game_id).await?;
+        self.get_game_state(game_id).await
+    }
+    
+    /// Create a new game instance that waits in the lobby until `start_game`
+    pub async fn open_lobby(&self, config: GameConfig) -> Result<GameState> {
+        let instance = self.new_instance(config).await?;
+        let state = instance.state.clone();
+        
+        self.persist(&instance).await;
+        self.games.insert(state.game_id, Arc::new(RwLock::new(instance)));
+        
+        Ok(state)
+    }
+    
+    /// Set up a game in its lobby, journaling its creation
+    async fn new_instance(&self, mut config: GameConfig) -> Result<GameInstance> {
+        // Always run seeded so the game can be replayed
+        config.seed.get_or_insert_with(random_seed);
+        
         // Create game instance
-        let mut game = (self.game_factory)(config.game_type.clone())?;
+        let mut game = self.game_factory.create(config.game_type.clone())?;
+        
+        // Reject bad parameters up front and record the effective values for replays
+        config.params = game.parameters().validate(&config.params)?.into_values();
+        validate_teams(&config.teams)?;
         
         // Initialize game
-        let state = game.initialize(config.clone()).await?;
-        let game_id = state.game_id;
+        let mut state = game.initialize(config.clone()).await?;
+        state.teams = config.teams.clone();
         
-        // Store instance
-        let instance = GameInstance {
+        let mut journal = Journal::new(state.game_id);
+        journal.append(JournalEvent::Created { config: config.clone() });
+        
+        let mut instance = GameInstance {
             game,
-            state: state.clone(),
+            state,
+            replay: Replay::new(config.clone()),
             config,
+            phase: GamePhase::Lobby,
+            journal,
+            emergence: EmergenceDetector::new(),
         };
         
-        self.games.insert(game_id, Arc::new(RwLock::new(instance)));
-        
-        Ok(state)
+        // Players named up front take the first seats in the lobby
+        for player in &instance.config.initial_players.clone() {
+            instance.take_seat(&player.id.0);
+        }
+        Ok(instance)
     }
     
     /// Process a turn for a game
@@ -71,40 +209,515 @@ impl GameEngine {
         let mut instance = game_arc.write().await;
         
         // Check if game is over
+        instance.phase.check_running()?;
         if instance.game.is_game_over(&instance.state).await {
             return Err(GameError::GameAlreadyEnded);
         }
         
+        // Refuse the whole turn if any action is illegal, so nothing malformed reaches scoring
+        let rejections = Self::validate_actions(&instance, &actions).await;
+        if !rejections.is_empty() {
+            instance.journal.append(JournalEvent::ActionsRejected { rejections: rejections.clone() });
+            self.persist(&instance).await;
+            return Err(GameError::ActionsRejected { rejections });
+        }
+        
         // Process round (clone state to avoid borrow checker issues)
         let state_clone = instance.state.clone();
-        let round_result = instance.game.process_round(&state_clone, actions).await?;
+        let mut round_result = instance.game.process_round(&state_clone, actions.clone()).await?;
+        round_result.emergence = instance.emergence.analyze_round(&round_result);
         
         // Update state
-        instance.state.round += 1;
-        instance.state.apply_score_deltas(&round_result.scores_delta);
-        instance.state.history.push(round_result.clone());
-        instance.state.updated_at = chrono::Utc::now();
+        let GameInstance { game, state, replay, journal, .. } = &mut *instance;
+        game.apply_round(state, &round_result).await;
+        journal.append(JournalEvent::RoundProcessed {
+            actions: actions.clone(),
+            result: round_result.clone(),
+        });
+        replay.record_round(actions, round_result.clone());
+        Self::finish_if_over(&mut instance).await;
+        self.persist(&instance).await;
         
         Ok(round_result)
     }
     
+    /// Move a running or paused game to `Finished` once the game reports it is over
+    async fn finish_if_over(instance: &mut GameInstance) {
+        if !instance.phase.is_over() && instance.game.is_game_over(&instance.state).await {
+            let from = std::mem::replace(&mut instance.phase, GamePhase::Finished);
+            instance.journal.append(JournalEvent::PhaseChanged { from, to: GamePhase::Finished });
+        }
+    }
+    
+    /// Where a game is in its lifecycle
+    pub async fn get_phase(&self, game_id: Uuid) -> Result<GamePhase> {
+        let game_arc = self.games.get(&game_id)
+            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
+            .clone();
+            
+        let instance = game_arc.read().await;
+        Ok(instance.phase)
+    }
+    
+    /// Seat a player while the game is still in the lobby
+    pub async fn join_game(&self, game_id: Uuid, player_id: &str) -> Result<()> {
+        let game_arc = self.games.get(&game_id)
+            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
+            .clone();
+            
+        let mut instance = game_arc.write().await;
+        match instance.phase {
+            GamePhase::Lobby => {}
+            GamePhase::Finished | GamePhase::Aborted => return Err(GameError::GameAlreadyEnded),
+            GamePhase::Running | GamePhase::Paused => return Err(GameError::GameAlreadyStarted),
+        }
+        instance.take_seat(player_id);
+        instance.journal.append(JournalEvent::PlayerJoined { player_id: player_id.to_string() });
+        self.persist(&instance).await;
+        Ok(())
+    }
+    
+    /// Take a player out of a game
+    ///
+    /// In the lobby the player simply gives up their seat. Once play has started
+    /// they forfeit: the game takes them out of play, they are marked eliminated,
+    /// and the forfeit is recorded in the replay.
+    pub async fn leave_game(&self, game_id: Uuid, player_id: &str) -> Result<()> {
+        let game_arc = self.games.get(&game_id)
+            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
+            .clone();
+            
+        let mut instance = game_arc.write().await;
+        if !instance.state.player_status.contains_key(player_id) {
+            return Err(GameError::PlayerNotFound { id: player_id.to_string() });
+        }
+        
+        match instance.phase {
+            GamePhase::Lobby => {
+                instance.give_up_seat(player_id);
+                instance.journal.append(JournalEvent::PlayerLeft { player_id: player_id.to_string() });
+            }
+            GamePhase::Running | GamePhase::Paused => {
+                // Spectators and players already out have nothing to forfeit
+                if !instance.state.is_active(player_id) {
+                    return Ok(());
+                }
+                let GameInstance { game, state, replay, journal, .. } = &mut *instance;
+                game.apply_forfeit(state, player_id).await;
+                replay.record_forfeit(player_id);
+                journal.append(JournalEvent::Forfeited { player_id: player_id.to_string() });
+                Self::finish_if_over(&mut instance).await;
+            }
+            GamePhase::Finished | GamePhase::Aborted => return Err(GameError::GameAlreadyEnded),
+        }
+        self.persist(&instance).await;
+        Ok(())
+    }
+    
+    /// Leave the lobby and begin play
+    pub async fn start_game(&self, game_id: Uuid) -> Result<()> {
+        self.change_phase(game_id, GamePhase::start).await
+    }
+    
+    /// Halt a running game; rounds are refused until it is resumed
+    pub async fn pause_game(&self, game_id: Uuid) -> Result<()> {
+        self.change_phase(game_id, GamePhase::pause).await
+    }
+    
+    /// Continue a paused game
+    pub async fn resume_game(&self, game_id: Uuid) -> Result<()> {
+        self.change_phase(game_id, GamePhase::resume).await
+    }
+    
+    /// Call a game off; it stays available for its replay and result until finalized
+    pub async fn abort_game(&self, game_id: Uuid) -> Result<()> {
+        self.change_phase(game_id, GamePhase::abort).await
+    }
+    
+    async fn change_phase(&self, game_id: Uuid, transition: fn(&mut GamePhase) -> Result<()>) -> Result<()> {
+        let game_arc = self.games.get(&game_id)
+            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
+            .clone();
+            
+        let mut instance = game_arc.write().await;
+        let from = instance.phase;
+        transition(&mut instance.phase)?;
+        let to = instance.phase;
+        if from == GamePhase::Lobby && to == GamePhase::Running {
+            Self::seat_players(&mut instance).await;
+        }
+        instance.journal.append(JournalEvent::PhaseChanged { from, to });
+        self.persist(&instance).await;
+        Ok(())
+    }
+    
+    /// Deal the players still seated into the game as it leaves the lobby, in the order they sat down
+    async fn seat_players(instance: &mut GameInstance) {
+        let GameInstance { game, state, replay, .. } = instance;
+        replay.players.retain(|p| state.is_active(p));
+        for player_id in &replay.players {
+            game.apply_join(state, player_id).await;
+        }
+    }
+    
+    /// Check every submitted action against the current turn and the game's legal moves for its player
+    async fn validate_actions(
+        instance: &GameInstance,
+        actions: &HashMap<String, PlayerAction>,
+    ) -> Vec<ActionRejection> {
+        let mut rejections = Vec::new();
+        let turn = instance.game.current_turn(&instance.state).await;
+        
+        for (player_id, action) in actions {
+            let verdict = if action.player_id != *player_id {
+                Err(format!("action is signed by {}", action.player_id))
+            } else if let Err(reason) = Self::check_status(&instance.state, player_id) {
+                Err(reason)
+            } else if !turn.expects(player_id) {
+                Err(format!("it is not {}'s turn", player_id))
+            } else {
+                instance.game.validate_action(&instance.state, player_id, action).await
+            };
+            
+            if let Err(reason) = verdict {
+                rejections.push(ActionRejection {
+                    player_id: player_id.clone(),
+                    action_type: action.action_type.clone(),
+                    reason,
+                });
+            }
+        }
+        
+        rejections.sort_by(|a, b| a.player_id.cmp(&b.player_id));
+        rejections
+    }
+    
+    /// Refuse actions from players who are out of the game or only watching
+    fn check_status(state: &GameState, player_id: &str) -> std::result::Result<(), String> {
+        match state.status(player_id) {
+            PlayerStatus::Alive => Ok(()),
+            PlayerStatus::Eliminated { round } => Err(format!("{} was eliminated in round {}", player_id, round)),
+            PlayerStatus::Spectating => Err(format!("{} is spectating", player_id)),
+        }
+    }
+    
     /// Get current game state
     pub async fn get_game_state(&self, game_id: Uuid) -> Result<GameState> {
         let game_arc = self.games.get(&game_id)
-            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?;
+            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
+            .clone();
+            
+        let instance = game_arc.read().await;
+        Ok(instance.state.clone())
+    }
+    
+    /// Get what an observer is allowed to see of a game
+    pub async fn get_observation(&self, game_id: Uuid, observer: &Observer) -> Result<Observation> {
+        let game_arc = self.games.get(&game_id)
+            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
+            .clone();
+            
+        let instance = game_arc.read().await;
+        Ok(instance.game.get_observation(&instance.state, observer).await)
+    }
+    
+    /// Get the replay recorded so far for a game, or the full replay of a finalized one from the store
+    pub async fn get_replay(&self, game_id: Uuid) -> Result<Replay> {
+        let Some(game_arc) = self.games.get(&game_id).map(|entry| entry.clone()) else {
+            return Ok(self.stored_game(game_id).await?.replay);
+        };
+            
+        let instance = game_arc.read().await;
+        Ok(instance.replay.clone())
+    }
+    
+    /// Get a game's journal, falling back to the store for finalized games
+    pub async fn get_journal(&self, game_id: Uuid) -> Result<Vec<JournalEntry>> {
+        let Some(game_arc) = self.games.get(&game_id).map(|entry| entry.clone()) else {
+            return Ok(self.stored_game(game_id).await?.journal);
+        };
+            
+        let instance = game_arc.read().await;
+        Ok(instance.journal.entries().to_vec())
+    }
+    
+    /// Follow a game's journal: the entries from `seq` on, then every new entry as it is appended
+    ///
+    /// Nothing is missed or repeated between the two.
+    pub async fn tail_journal(
+        &self,
+        game_id: Uuid,
+        seq: u64,
+    ) -> Result<(Vec<JournalEntry>, broadcast::Receiver<JournalEntry>)> {
+        let game_arc = self.games.get(&game_id)
+            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
+            .clone();
             
         let instance = game_arc.read().await;
+        Ok((instance.journal.since(seq).to_vec(), instance.journal.subscribe()))
+    }
+    
+    /// Rebuild a game from its journal as a new active game
+    ///
+    /// The journal must begin with the game's `Created` or `Restored` entry. Rounds are
+    /// re-executed rather than copied, so the new game's internals match the original's;
+    /// the new game starts a journal of its own.
+    pub async fn replay_journal(&self, entries: &[JournalEntry]) -> Result<GameState> {
+        let mut events = entries.iter().map(|entry| &entry.event);
+        let mut instance = match events.next() {
+            Some(JournalEvent::Created { config }) => self.new_instance(config.clone()).await?,
+            Some(JournalEvent::Restored { snapshot }) => {
+                let replay = Replay::from_snapshot(snapshot);
+                self.rebuild_instance((**snapshot).clone(), replay, Vec::new())?
+            }
+            _ => {
+                return Err(GameError::InvalidState {
+                    reason: "Journal does not begin with the game's creation".to_string(),
+                })
+            }
+        };
+        
+        for event in events {
+            match event {
+                JournalEvent::Created { .. } | JournalEvent::Restored { .. } => {
+                    return Err(GameError::InvalidState {
+                        reason: "Journal creates the game twice".to_string(),
+                    });
+                }
+                JournalEvent::PlayerJoined { player_id } => instance.take_seat(player_id),
+                JournalEvent::PlayerLeft { player_id } => instance.give_up_seat(player_id),
+                JournalEvent::Forfeited { player_id } => {
+                    let GameInstance { game, state, replay, .. } = &mut instance;
+                    game.apply_forfeit(state, player_id).await;
+                    replay.record_forfeit(player_id);
+                }
+                JournalEvent::SpectatorAdded { player_id } => instance.state.spectate(player_id),
+                JournalEvent::PhaseChanged { from, to } => {
+                    instance.phase = *to;
+                    if *from == GamePhase::Lobby && *to == GamePhase::Running {
+                        Self::seat_players(&mut instance).await;
+                    }
+                }
+                JournalEvent::RoundProcessed { actions, .. } => {
+                    let state_clone = instance.state.clone();
+                    let mut result = instance.game.process_round(&state_clone, actions.clone()).await?;
+                    result.emergence = instance.emergence.analyze_round(&result);
+                    let GameInstance { game, state, replay, .. } = &mut instance;
+                    game.apply_round(state, &result).await;
+                    replay.record_round(actions.clone(), result);
+                }
+                JournalEvent::ClockSet { player_id, clock } => {
+                    instance.state.clocks.insert(player_id.clone(), *clock);
+                }
+                JournalEvent::Rewound { round } => {
+                    let rebuilt = self.rebuild_at_round(&instance, *round).await?;
+                    Self::replace_rewound(&mut instance, rebuilt);
+                }
+                JournalEvent::ActionsRejected { .. } | JournalEvent::Finalized { .. } => {}
+            }
+            instance.journal.append(event.clone());
+        }
+        
+        let state = instance.state.clone();
+        self.persist(&instance).await;
+        self.games.insert(state.game_id, Arc::new(RwLock::new(instance)));
+        
+        Ok(state)
+    }
+    
+    /// Get the result of a game finalized with a store attached
+    pub async fn get_result(&self, game_id: Uuid) -> Result<GameResult> {
+        if self.games.contains_key(&game_id) {
+            return Err(GameError::InvalidState {
+                reason: format!("Game {} has not been finalized", game_id),
+            });
+        }
+        self.stored_game(game_id).await?.result.ok_or_else(|| GameError::InvalidState {
+            reason: format!("Game {} has not been finalized", game_id),
+        })
+    }
+    
+    /// Write the replay recorded so far for a game to a file
+    pub async fn save_replay(&self, game_id: Uuid, path: impl AsRef<Path>) -> Result<()> {
+        self.get_replay(game_id).await?.save(path)
+    }
+    
+    /// Capture a running game, including its private internals
+    pub async fn snapshot_game(&self, game_id: Uuid) -> Result<GameSnapshot> {
+        let game_arc = self.games.get(&game_id)
+            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
+            .clone();
+            
+        let instance = game_arc.read().await;
+        Self::snapshot_of(&instance)
+    }
+    
+    fn snapshot_of(instance: &GameInstance) -> Result<GameSnapshot> {
+        Ok(GameSnapshot {
+            config: instance.config.clone(),
+            state: instance.state.clone(),
+            phase: instance.phase,
+            internals: instance.game.snapshot()?,
+            players: instance.replay.players.clone(),
+            forfeits: instance.replay.forfeits.clone(),
+        })
+    }
+    
+    /// Rebuild a game from a snapshot and make it active again
+    pub async fn restore_game(&self, snapshot: GameSnapshot) -> Result<Uuid> {
+        let replay = Replay::from_snapshot(&snapshot);
+        let instance = self.rebuild_instance(snapshot, replay, Vec::new())?;
+        let game_id = instance.state.game_id;
+        
+        self.persist(&instance).await;
+        self.games.insert(game_id, Arc::new(RwLock::new(instance)));
+        
+        Ok(game_id)
+    }
+    
+    /// Rebuild an instance from a snapshot, continuing its journal or starting one from the snapshot
+    fn rebuild_instance(&self, snapshot: GameSnapshot, replay: Replay, journal: Vec<JournalEntry>) -> Result<GameInstance> {
+        let mut game = self.game_factory.create(snapshot.config.game_type.clone())?;
+        game.restore(snapshot.internals.clone())?;
+        
+        let mut journal = Journal::from_entries(snapshot.state.game_id, journal);
+        if journal.entries().is_empty() {
+            journal.append(JournalEvent::Restored { snapshot: Box::new(snapshot.clone()) });
+        }
+        
+        Ok(GameInstance {
+            game,
+            replay,
+            emergence: EmergenceDetector::from_history(&snapshot.state.history),
+            state: snapshot.state,
+            config: snapshot.config,
+            phase: snapshot.phase,
+            journal,
+        })
+    }
+    
+    /// Rewind a game in place to the end of `round`, discarding everything after it
+    pub async fn rewind_game(&self, game_id: Uuid, round: u32) -> Result<GameState> {
+        let game_arc = self.games.get(&game_id)
+            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
+            .clone();
+            
+        let mut instance = game_arc.write().await;
+        let rebuilt = self.rebuild_at_round(&instance, round).await?;
+        Self::replace_rewound(&mut instance, rebuilt);
+        instance.journal.append(JournalEvent::Rewound { round });
+        self.persist(&instance).await;
+        
         Ok(instance.state.clone())
     }
     
+    /// Swap a rebuilt instance in for a rewound game
+    fn replace_rewound(instance: &mut GameInstance, mut rebuilt: GameInstance) {
+        // Keep the original identity and journal so clients holding the id follow the rewind
+        rebuilt.state.game_id = instance.state.game_id;
+        rebuilt.state.created_at = instance.state.created_at;
+        std::mem::swap(&mut rebuilt.journal, &mut instance.journal);
+        *instance = rebuilt;
+    }
+    
+    /// Copy a game as it was at the end of `round` into a new game, leaving the original untouched
+    pub async fn fork_game(&self, game_id: Uuid, round: u32) -> Result<GameState> {
+        let game_arc = self.games.get(&game_id)
+            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
+            .clone();
+            
+        let instance = game_arc.read().await;
+        let mut forked = self.rebuild_at_round(&instance, round).await?;
+        drop(instance);
+        
+        // The copy's journal starts from where it branched off
+        let snapshot = Self::snapshot_of(&forked)?;
+        forked.journal.append(JournalEvent::Restored { snapshot: Box::new(snapshot) });
+        
+        let state = forked.state.clone();
+        self.persist(&forked).await;
+        self.games.insert(state.game_id, Arc::new(RwLock::new(forked)));
+        
+        Ok(state)
+    }
+    
+    /// Rebuild a fresh instance by replaying the recorded seating, rounds and forfeits up to and including `round`
+    async fn rebuild_at_round(&self, instance: &GameInstance, round: u32) -> Result<GameInstance> {
+        if round > instance.state.round {
+            return Err(GameError::InvalidState {
+                reason: format!(
+                    "Cannot rewind to round {}; game is only at round {}",
+                    round, instance.state.round
+                ),
+            });
+        }
+        
+        let mut game = self.game_factory.create(instance.config.game_type.clone())?;
+        let mut state = game.initialize(instance.config.clone()).await?;
+        state.teams = instance.config.teams.clone();
+        let mut replay = Replay::new(instance.config.clone());
+        let mut emergence = EmergenceDetector::new();
+        
+        // Players still in the lobby have a seat but have not been dealt in yet
+        for player_id in &instance.replay.players {
+            if instance.phase == GamePhase::Lobby {
+                state.add_player(player_id.clone());
+            } else {
+                game.apply_join(&mut state, player_id).await;
+            }
+        }
+        replay.players = instance.replay.players.clone();
+        
+        for played in 0..=round {
+            for player_id in instance.replay.forfeits_after(played) {
+                game.apply_forfeit(&mut state, player_id).await;
+                replay.record_forfeit(player_id);
+            }
+            if played == round {
+                break;
+            }
+            let recorded = &instance.replay.rounds[played as usize];
+            let mut result = game.process_round(&state, recorded.actions.clone()).await?;
+            result.emergence = emergence.analyze_round(&result);
+            game.apply_round(&mut state, &result).await;
+            replay.record_round(recorded.actions.clone(), result);
+        }
+        
+        // A finished game taken back to before its end is playable again
+        let phase = match instance.phase {
+            GamePhase::Finished if !game.is_game_over(&state).await => GamePhase::Running,
+            phase => phase,
+        };
+        
+        Ok(GameInstance {
+            game,
+            journal: Journal::new(state.game_id),
+            state,
+            config: instance.config.clone(),
+            replay,
+            phase,
+            emergence,
+        })
+    }
+    
     /// Finalize a game and get results
     pub async fn finalize_game(&self, game_id: Uuid) -> Result<GameResult> {
         let game_arc = self.games.get(&game_id)
             .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
             .clone();
             
-        let instance = game_arc.read().await;
-        let result = instance.game.calculate_final_result(&instance.state).await;
+        let mut instance = game_arc.write().await;
+        if instance.phase == GamePhase::Lobby {
+            return Err(GameError::GameNotStarted);
+        }
+        let mut result = instance.game.calculate_final_result(&instance.state).await;
+        Self::collect_emergence(&instance.state, &mut result);
+        instance.journal.append(JournalEvent::Finalized { result: result.clone() });
+        
+        // Keep the finished game with its result; a game that cannot be stored stays active
+        if let Some(store) = &self.store {
+            store.save(&Self::stored(&instance, Some(result.clone()))?).await?;
+        }
         
         // Remove from active games
         drop(instance);
@@ -113,21 +726,88 @@ impl GameEngine {
         Ok(result)
     }
     
+    /// Gather the emergence seen in every round, reported by the game or detected here, into the result
+    fn collect_emergence(state: &GameState, result: &mut GameResult) {
+        result.emergence_events = state.history.iter().flat_map(|round| round.emergence_events()).collect();
+        result.analytics.emergence_frequency = if state.history.is_empty() {
+            0.0
+        } else {
+            result.emergence_events.len() as f32 / state.history.len() as f32
+        };
+    }
+    
     /// Get list of active games
     pub fn active_games(&self) -> Vec<Uuid> {
         self.games.iter().map(|entry| *entry.key()).collect()
     }
     
-    /// Get valid actions for a player
+    /// Get the players a game is waiting on
+    pub async fn get_current_turn(&self, game_id: Uuid) -> Result<Turn> {
+        let game_arc = self.games.get(&game_id)
+            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
+            .clone();
+            
+        let instance = game_arc.read().await;
+        let mut turn = instance.game.current_turn(&instance.state).await;
+        turn.players.retain(|p| instance.state.is_active(p));
+        Ok(turn)
+    }
+    
+    /// Record how much thinking time a player has left
+    pub async fn set_clock(&self, game_id: Uuid, player_id: &str, clock: PlayerClock) -> Result<()> {
+        let game_arc = self.games.get(&game_id)
+            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
+            .clone();
+            
+        let mut instance = game_arc.write().await;
+        instance.state.clocks.insert(player_id.to_string(), clock);
+        instance.journal.append(JournalEvent::ClockSet { player_id: player_id.to_string(), clock });
+        self.persist(&instance).await;
+        Ok(())
+    }
+    
+    /// Let someone watch a game without taking part; they are never asked to act
+    pub async fn add_spectator(&self, game_id: Uuid, player_id: &str) -> Result<()> {
+        let game_arc = self.games.get(&game_id)
+            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
+            .clone();
+            
+        let mut instance = game_arc.write().await;
+        if matches!(instance.state.player_status.get(player_id), Some(status) if *status != PlayerStatus::Spectating) {
+            return Err(GameError::InvalidState {
+                reason: format!("{} is already taking part", player_id),
+            });
+        }
+        instance.state.spectate(player_id);
+        instance.journal.append(JournalEvent::SpectatorAdded { player_id: player_id.to_string() });
+        self.persist(&instance).await;
+        Ok(())
+    }
+    
+    /// Get the JSON Schema of the actions a game accepts
+    pub async fn get_action_schema(&self, game_id: Uuid) -> Result<serde_json::Value> {
+        let game_arc = self.games.get(&game_id)
+            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
+            .clone();
+            
+        let instance = game_arc.read().await;
+        Ok(instance.game.action_schema())
+    }
+    
+    /// Get the fully parameterized legal moves for a player
     pub async fn get_valid_actions(
         &self, 
         game_id: Uuid, 
         player_id: &str
-    ) -> Result<Vec<String>> {
+    ) -> Result<Vec<LegalAction>> {
         let game_arc = self.games.get(&game_id)
-            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?;
+            .ok_or_else(|| GameError::GameNotFound { id: game_id.to_string() })?
+            .clone();
             
         let instance = game_arc.read().await;
+        if instance.phase != GamePhase::Running || !instance.state.is_active(player_id) {
+            return Ok(Vec::new());
+        }
         Ok(instance.game.get_valid_actions(&instance.state, player_id).await)
     }
 }
\ No newline at end of file
@@ -0,0 +1,97 @@
+//! Append-only journal of everything that happens to a game
+
+use genius_core::{
+    ActionRejection, GameConfig, GamePhase, GameResult, GameSnapshot, PlayerAction, PlayerClock, RoundResult,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// Entries buffered for live subscribers that fall behind
+const LIVE_CAPACITY: usize = 256;
+
+/// A state transition of a game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JournalEvent {
+    /// The game was set up in its lobby with this effective config
+    Created { config: GameConfig },
+    /// The game was rebuilt from a snapshot rather than created, e.g. forked or restored
+    Restored { snapshot: Box<GameSnapshot> },
+    PlayerJoined { player_id: String },
+    /// A player gave up their seat in the lobby
+    PlayerLeft { player_id: String },
+    /// A player left mid-game
+    Forfeited { player_id: String },
+    SpectatorAdded { player_id: String },
+    PhaseChanged { from: GamePhase, to: GamePhase },
+    RoundProcessed {
+        actions: HashMap<String, PlayerAction>,
+        result: RoundResult,
+    },
+    /// A submitted turn was refused; nothing changed
+    ActionsRejected { rejections: Vec<ActionRejection> },
+    ClockSet { player_id: String, clock: PlayerClock },
+    /// The game was taken back to the end of `round`
+    Rewound { round: u32 },
+    Finalized { result: GameResult },
+}
+
+/// One numbered journal entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Position in the game's journal, starting at 0
+    pub seq: u64,
+    pub game_id: Uuid,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub event: JournalEvent,
+}
+
+/// A game's journal, with a live feed of new entries
+pub struct Journal {
+    game_id: Uuid,
+    entries: Vec<JournalEntry>,
+    live: broadcast::Sender<JournalEntry>,
+}
+
+impl Journal {
+    /// Empty journal for a game
+    pub fn new(game_id: Uuid) -> Self {
+        Self::from_entries(game_id, Vec::new())
+    }
+
+    /// Continue a journal loaded from storage
+    pub fn from_entries(game_id: Uuid, entries: Vec<JournalEntry>) -> Self {
+        let (live, _) = broadcast::channel(LIVE_CAPACITY);
+        Self { game_id, entries, live }
+    }
+
+    /// Append an entry and send it to live subscribers
+    pub fn append(&mut self, event: JournalEvent) {
+        let entry = JournalEntry {
+            seq: self.entries.len() as u64,
+            game_id: self.game_id,
+            timestamp: chrono::Utc::now(),
+            event,
+        };
+        // Nobody listening is fine; the entry is kept either way
+        let _ = self.live.send(entry.clone());
+        self.entries.push(entry);
+    }
+
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    /// Entries from `seq` on
+    pub fn since(&self, seq: u64) -> &[JournalEntry] {
+        let start = (seq as usize).min(self.entries.len());
+        &self.entries[start..]
+    }
+
+    /// Receive entries as they are appended
+    pub fn subscribe(&self) -> broadcast::Receiver<JournalEntry> {
+        self.live.subscribe()
+    }
+}
@@ -7,9 +7,23 @@ pub mod analytics;
 pub mod streaming;
 pub mod scheduler;
 pub mod emergence;
+pub mod runner;
+pub mod storage;
+pub mod journal;
+pub mod tournament;
+pub mod ratings;
+pub mod matchmaking;
+pub mod batch;
 
 pub use engine::GameEngine;
 pub use analytics::AnalyticsEngine;
 pub use streaming::GameEventStreamer;
 pub use scheduler::TurnScheduler;
-pub use emergence::EmergenceDetector;
\ No newline at end of file
+pub use emergence::EmergenceDetector;
+pub use runner::{Collection, Fallback, FallbackRecord, MatchReport, MatchRunner};
+pub use storage::{FileStore, GameStore, MemoryStore, StoredGame};
+pub use journal::{Journal, JournalEntry, JournalEvent};
+pub use tournament::{ByeRecord, Format, MatchOutcome, MatchRecord, Standing, Tournament, TournamentReport};
+pub use ratings::{Glicko2Rating, RatingChange, RatingService, RatingSubject, RatingSystem, Ratings, TrueSkillRating};
+pub use matchmaking::{MatchFound, Matchmaker, QueueRules, TicketStatus};
+pub use batch::{AgentFactory, AgentStats, BatchRunner, BatchStats, Distribution, EmergenceFrequency, Interval};
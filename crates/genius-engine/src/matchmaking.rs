@@ -0,0 +1,392 @@
+//! Matchmaking: players and bots queue for a game type and are seated together automatically
+
+use crate::{GameEngine, RatingService, RatingSubject, RatingSystem, Ratings};
+use genius_core::{GameConfig, GameError, GameType, Player, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, Mutex};
+use tokio::time::Instant;
+use uuid::Uuid;
+
+/// Formed matches buffered for subscribers that fall behind
+const FOUND_CAPACITY: usize = 256;
+
+/// When a queue may seat a match
+#[derive(Debug, Clone)]
+pub struct QueueRules {
+    pub min_players: usize,
+    pub max_players: usize,
+    /// Widest rating gap from the longest-waiting player allowed in a match, in the
+    /// units of the matchmaker's rating system
+    pub rating_window: f64,
+    /// How far the window widens for every second that player has waited
+    pub window_growth: f64,
+    /// Once a player has waited this long they are seated as soon as `min_players`
+    /// are queued, whatever the ratings
+    pub max_wait: Duration,
+    /// Players still waiting after this long are taken out of the queue
+    pub expire_after: Option<Duration>,
+}
+
+impl QueueRules {
+    /// Seat between `min_players` and `max_players` at a time, preferring full tables
+    pub fn new(min_players: usize, max_players: usize) -> Self {
+        Self {
+            min_players,
+            max_players,
+            rating_window: 100.0,
+            window_growth: 10.0,
+            max_wait: Duration::from_secs(30),
+            expire_after: None,
+        }
+    }
+
+    pub fn rating_window(mut self, window: f64, growth_per_second: f64) -> Self {
+        self.rating_window = window;
+        self.window_growth = growth_per_second;
+        self
+    }
+
+    pub fn max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+
+    pub fn expire_after(mut self, expire_after: Duration) -> Self {
+        self.expire_after = Some(expire_after);
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.min_players < 2 || self.max_players < self.min_players {
+            return Err(GameError::ConfigError {
+                reason: format!(
+                    "A queue needs at least 2 players and no fewer than its minimum, got {}..{}",
+                    self.min_players, self.max_players
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for QueueRules {
+    /// Head-to-head matches
+    fn default() -> Self {
+        Self::new(2, 2)
+    }
+}
+
+/// Where a player stands with the matchmaker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TicketStatus {
+    Waiting {
+        game_type: GameType,
+        waited_ms: u64,
+        /// Players in the queue, including this one
+        queued: usize,
+    },
+    /// Seated in a game that has been started
+    Matched { game_id: Uuid, players: Vec<String> },
+    /// Waited out the queue's limit without a match
+    Expired { game_type: GameType },
+}
+
+/// A match the matchmaker has seated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchFound {
+    pub game_id: Uuid,
+    pub game_type: GameType,
+    /// Seat order, longest-waiting first
+    pub players: Vec<String>,
+    /// Gap between the highest and lowest rating at the table
+    pub rating_spread: f64,
+}
+
+struct Ticket {
+    player: Player,
+    rating: f64,
+    since: Instant,
+}
+
+struct Queue {
+    config: GameConfig,
+    rules: QueueRules,
+    /// Oldest first
+    waiting: Vec<Ticket>,
+    formed: u64,
+}
+
+impl Queue {
+    /// Pick the next table to seat, taking its players out of the queue
+    ///
+    /// Each player in turn, oldest first, is seated with the queued players closest to
+    /// their rating within their window. A full table is seated straight away; a table
+    /// of at least `min_players` once that player has waited `max_wait`, at which point
+    /// the window no longer applies.
+    fn take_table(&mut self, now: Instant) -> Option<Vec<Ticket>> {
+        for (anchor, ticket) in self.waiting.iter().enumerate() {
+            let waited = now.duration_since(ticket.since);
+            let relaxed = waited >= self.rules.max_wait;
+            let window = self.rules.rating_window + self.rules.window_growth * waited.as_secs_f64();
+
+            let mut nearby: Vec<(usize, f64)> = self.waiting.iter().enumerate()
+                .filter(|(i, _)| *i != anchor)
+                .map(|(i, other)| (i, (other.rating - ticket.rating).abs()))
+                .filter(|(_, gap)| relaxed || *gap <= window)
+                .collect();
+            // Stable, so equally close players keep their queue order
+            nearby.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+            let mut table: Vec<usize> = std::iter::once(anchor)
+                .chain(nearby.into_iter().map(|(i, _)| i))
+                .take(self.rules.max_players)
+                .collect();
+            if table.len() == self.rules.max_players || (relaxed && table.len() >= self.rules.min_players) {
+                // Remove from the back so earlier indices stay valid, then restore queue order
+                table.sort_unstable_by(|a, b| b.cmp(a));
+                let mut seated: Vec<Ticket> = table.into_iter().map(|i| self.waiting.remove(i)).collect();
+                seated.reverse();
+                return Some(seated);
+            }
+        }
+        None
+    }
+}
+
+#[derive(Default)]
+struct Queues {
+    queues: HashMap<GameType, Queue>,
+    /// How each player's last ticket ended
+    outcomes: HashMap<String, TicketStatus>,
+}
+
+/// Seats queued players into games on the engine
+///
+/// Matches are formed when a player joins a queue and on every `tick`; `run` ticks in
+/// the background so waiting-time limits are applied without new arrivals. Players are
+/// balanced by their rating in the attached `RatingService`, or all rated alike without one.
+pub struct Matchmaker {
+    engine: Arc<GameEngine>,
+    ratings: Option<(Arc<RatingService>, RatingSystem)>,
+    queues: Mutex<Queues>,
+    found: broadcast::Sender<MatchFound>,
+}
+
+impl Matchmaker {
+    pub fn new(engine: Arc<GameEngine>) -> Self {
+        let (found, _) = broadcast::channel(FOUND_CAPACITY);
+        Self {
+            engine,
+            ratings: None,
+            queues: Mutex::new(Queues::default()),
+            found,
+        }
+    }
+
+    /// Balance matches by each player's rating in `system`
+    pub fn ratings(mut self, ratings: Arc<RatingService>, system: RatingSystem) -> Self {
+        self.ratings = Some((ratings, system));
+        self
+    }
+
+    /// Queue for games created from `config`; reopening a queue keeps its players waiting
+    ///
+    /// A seed in the config is varied per match so games differ but stay reproducible.
+    pub async fn open_queue(&self, config: GameConfig, rules: QueueRules) -> Result<()> {
+        rules.validate()?;
+        let mut queues = self.queues.lock().await;
+        match queues.queues.get_mut(&config.game_type) {
+            Some(queue) => {
+                queue.config = config;
+                queue.rules = rules;
+            }
+            None => {
+                queues.queues.insert(config.game_type.clone(), Queue { config, rules, waiting: Vec::new(), formed: 0 });
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn has_queue(&self, game_type: &GameType) -> bool {
+        self.queues.lock().await.queues.contains_key(game_type)
+    }
+
+    /// Wait for a game of this type; the player is seated as soon as a match can be made
+    pub async fn enqueue(&self, game_type: &GameType, player: Player) -> Result<TicketStatus> {
+        let rating = self.rating_of(&player).await;
+        let player_id = player.id.0.clone();
+
+        let mut queues = self.queues.lock().await;
+        if queues.queues.values().any(|q| q.waiting.iter().any(|t| t.player.id == player.id)) {
+            return Err(GameError::InvalidState {
+                reason: format!("Player {} is already queued", player_id),
+            });
+        }
+        let queue = queues.queues.get_mut(game_type).ok_or_else(|| GameError::ConfigError {
+            reason: format!("No matchmaking queue for {}", game_type.key()),
+        })?;
+        queue.waiting.push(Ticket { player, rating, since: Instant::now() });
+        queues.outcomes.remove(&player_id);
+
+        for (failed_type, e) in self.seat(&mut queues, Instant::now()).await.1 {
+            if failed_type != *game_type {
+                continue;
+            }
+            // The player's own match could not be started, so they were never queued
+            if let Some(queue) = queues.queues.get_mut(game_type) {
+                queue.waiting.retain(|t| t.player.id.0 != player_id);
+            }
+            return Err(e);
+        }
+        Ok(Self::status_in(&queues, &player_id, Instant::now()).expect("player was just queued"))
+    }
+
+    /// Leave the queue without being matched
+    pub async fn leave(&self, player_id: &str) -> Result<()> {
+        let mut queues = self.queues.lock().await;
+        for queue in queues.queues.values_mut() {
+            if let Some(i) = queue.waiting.iter().position(|t| t.player.id.0 == player_id) {
+                queue.waiting.remove(i);
+                return Ok(());
+            }
+        }
+        Err(GameError::PlayerNotFound { id: player_id.to_string() })
+    }
+
+    /// Whether the player is waiting, or how their last ticket ended
+    pub async fn status(&self, player_id: &str) -> Option<TicketStatus> {
+        Self::status_in(&*self.queues.lock().await, player_id, Instant::now())
+    }
+
+    /// Receive matches as they are seated
+    pub fn subscribe(&self) -> broadcast::Receiver<MatchFound> {
+        self.found.subscribe()
+    }
+
+    /// Expire overdue tickets and seat every match that can be made now
+    ///
+    /// A queue whose games cannot be started keeps its players waiting and is logged;
+    /// the other queues are seated regardless.
+    pub async fn tick(&self) -> Result<Vec<MatchFound>> {
+        let mut queues = self.queues.lock().await;
+        let now = Instant::now();
+
+        let Queues { queues: by_type, outcomes } = &mut *queues;
+        for (game_type, queue) in by_type.iter_mut() {
+            if let Some(limit) = queue.rules.expire_after {
+                queue.waiting.retain(|t| {
+                    let keep = now.duration_since(t.since) < limit;
+                    if !keep {
+                        tracing::info!("{} gave up waiting for {}", t.player.id, game_type.key());
+                        outcomes.insert(t.player.id.0.clone(), TicketStatus::Expired { game_type: game_type.clone() });
+                    }
+                    keep
+                });
+            }
+        }
+
+        let (found, failed) = self.seat(&mut queues, now).await;
+        for (game_type, e) in failed {
+            tracing::error!("Could not start a {} match: {}", game_type.key(), e);
+        }
+        Ok(found)
+    }
+
+    /// Tick every `interval` until the matchmaker is dropped elsewhere
+    pub fn run(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            loop {
+                ticks.tick().await;
+                if Arc::strong_count(&self) == 1 {
+                    break;
+                }
+                if let Err(e) = self.tick().await {
+                    tracing::error!("Matchmaking failed: {}", e);
+                }
+            }
+        })
+    }
+
+    async fn rating_of(&self, player: &Player) -> f64 {
+        match &self.ratings {
+            Some((ratings, system)) => ratings.rating(&RatingSubject::player(&player.id.0)).await
+                .unwrap_or_default()
+                .score(*system),
+            None => Ratings::default().elo,
+        }
+    }
+
+    fn status_in(queues: &Queues, player_id: &str, now: Instant) -> Option<TicketStatus> {
+        for (game_type, queue) in &queues.queues {
+            if let Some(ticket) = queue.waiting.iter().find(|t| t.player.id.0 == player_id) {
+                return Some(TicketStatus::Waiting {
+                    game_type: game_type.clone(),
+                    waited_ms: now.duration_since(ticket.since).as_millis() as u64,
+                    queued: queue.waiting.len(),
+                });
+            }
+        }
+        queues.outcomes.get(player_id).cloned()
+    }
+
+    /// Start a game for every table that can be seated
+    ///
+    /// If a game cannot be created its players go back to the queue, which is left
+    /// alone until the next attempt; the error is returned alongside the matches made.
+    async fn seat(&self, queues: &mut Queues, now: Instant) -> (Vec<MatchFound>, Vec<(GameType, GameError)>) {
+        let mut found = Vec::new();
+        let mut failed = Vec::new();
+        let Queues { queues: by_type, outcomes } = queues;
+        for (game_type, queue) in by_type.iter_mut() {
+            while let Some(table) = queue.take_table(now) {
+                let mut config = queue.config.clone();
+                config.seed = config.seed.map(|seed| seed.wrapping_add(queue.formed));
+                config.initial_players = table.iter().map(|t| t.player.clone()).collect();
+
+                let game_id = match self.start(config).await {
+                    Ok(game_id) => game_id,
+                    Err(e) => {
+                        // Back in their original places, so nobody loses their wait
+                        queue.waiting.extend(table);
+                        queue.waiting.sort_by_key(|t| t.since);
+                        failed.push((game_type.clone(), e));
+                        break;
+                    }
+                };
+                queue.formed += 1;
+
+                let players: Vec<String> = table.iter().map(|t| t.player.id.0.clone()).collect();
+                let (low, high) = table.iter().fold((f64::MAX, f64::MIN), |(low, high), t| (low.min(t.rating), high.max(t.rating)));
+                for player_id in &players {
+                    outcomes.insert(player_id.clone(), TicketStatus::Matched { game_id, players: players.clone() });
+                }
+
+                tracing::info!("Matched {} for {} in game {}", players.join(", "), game_type.key(), game_id);
+                let matched = MatchFound { game_id, game_type: game_type.clone(), players, rating_spread: high - low };
+                // Nobody listening is fine; players can poll their status instead
+                let _ = self.found.send(matched.clone());
+                found.push(matched);
+            }
+        }
+        (found, failed)
+    }
+
+    /// Open a game for the seated players and start it
+    async fn start(&self, config: GameConfig) -> Result<Uuid> {
+        // The lobby seats the config's initial players itself
+        let game_id = self.engine.open_lobby(config).await?.game_id;
+        if let Err(e) = self.engine.start_game(game_id).await {
+            // Don't leave a game nobody will play behind in the engine
+            if self.engine.abort_game(game_id).await.is_ok() {
+                let _ = self.engine.finalize_game(game_id).await;
+            }
+            return Err(e);
+        }
+        Ok(game_id)
+    }
+}
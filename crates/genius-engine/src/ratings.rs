@@ -0,0 +1,450 @@
+//! Skill ratings that carry over from game to game, for players and the AI models behind them
+//!
+//! Two-player games update Elo and Glicko-2. Every game, including free-for-alls and team
+//! games, updates a TrueSkill-style rating (the Weng-Lin Bradley-Terry approximation).
+
+use chrono::{DateTime, Utc};
+use genius_core::{GameError, GameResult, Player, PlayerId, PlayerType, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::PI;
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+const ELO_START: f64 = 1500.0;
+const ELO_K: f64 = 32.0;
+
+/// Converts between the Glicko and Glicko-2 scales
+const GLICKO_SCALE: f64 = 173.7178;
+const GLICKO_START_DEVIATION: f64 = 350.0;
+const GLICKO_START_VOLATILITY: f64 = 0.06;
+/// How much volatility may change between games
+const GLICKO_TAU: f64 = 0.5;
+const GLICKO_EPSILON: f64 = 0.000001;
+
+const TRUESKILL_MU: f64 = 25.0;
+const TRUESKILL_SIGMA: f64 = TRUESKILL_MU / 3.0;
+/// Spread of a single performance around the player's skill
+const TRUESKILL_BETA: f64 = TRUESKILL_SIGMA / 2.0;
+/// Uncertainty added before each game so ratings can keep moving
+const TRUESKILL_TAU: f64 = TRUESKILL_SIGMA / 100.0;
+/// Floor on how much one game can shrink the uncertainty
+const TRUESKILL_KAPPA: f64 = 0.0001;
+
+/// Who a rating belongs to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RatingSubject {
+    Player { player_id: PlayerId },
+    /// Every player driven by this model, pooled
+    Model { provider: String, model: String },
+}
+
+impl RatingSubject {
+    pub fn player(player_id: &str) -> Self {
+        Self::Player { player_id: PlayerId::from_string(player_id.to_string()) }
+    }
+
+    pub fn model(provider: &str, model: &str) -> Self {
+        Self::Model { provider: provider.to_string(), model: model.to_string() }
+    }
+}
+
+impl std::fmt::Display for RatingSubject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Player { player_id } => write!(f, "{}", player_id),
+            Self::Model { provider, model } => write!(f, "{}/{}", provider, model),
+        }
+    }
+}
+
+/// Which rating to rank by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RatingSystem {
+    Elo,
+    Glicko2,
+    /// Ranked by the conservative estimate, so barely-known subjects don't top the table
+    TrueSkill,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Glicko2Rating {
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+}
+
+impl Default for Glicko2Rating {
+    fn default() -> Self {
+        Self { rating: ELO_START, deviation: GLICKO_START_DEVIATION, volatility: GLICKO_START_VOLATILITY }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrueSkillRating {
+    pub mu: f64,
+    pub sigma: f64,
+}
+
+impl TrueSkillRating {
+    /// Skill the subject is very likely to have at least
+    pub fn conservative(&self) -> f64 {
+        self.mu - 3.0 * self.sigma
+    }
+}
+
+impl Default for TrueSkillRating {
+    fn default() -> Self {
+        Self { mu: TRUESKILL_MU, sigma: TRUESKILL_SIGMA }
+    }
+}
+
+/// Every rating held for one subject
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Ratings {
+    pub elo: f64,
+    pub glicko: Glicko2Rating,
+    pub trueskill: TrueSkillRating,
+    /// Rated games played
+    pub games: u32,
+}
+
+impl Ratings {
+    pub fn score(&self, system: RatingSystem) -> f64 {
+        match system {
+            RatingSystem::Elo => self.elo,
+            RatingSystem::Glicko2 => self.glicko.rating,
+            RatingSystem::TrueSkill => self.trueskill.conservative(),
+        }
+    }
+}
+
+impl Default for Ratings {
+    fn default() -> Self {
+        Self {
+            elo: ELO_START,
+            glicko: Glicko2Rating::default(),
+            trueskill: TrueSkillRating::default(),
+            games: 0,
+        }
+    }
+}
+
+/// How one game moved one subject's ratings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatingChange {
+    pub game_id: Uuid,
+    pub at: DateTime<Utc>,
+    pub subject: RatingSubject,
+    pub before: Ratings,
+    pub after: Ratings,
+}
+
+/// Current ratings, derived from the history
+#[derive(Default)]
+struct RatingBook {
+    current: HashMap<RatingSubject, Ratings>,
+    rated_games: HashSet<Uuid>,
+    history: Vec<RatingChange>,
+}
+
+impl RatingBook {
+    fn apply(&mut self, change: RatingChange) {
+        self.current.insert(change.subject.clone(), change.after);
+        self.rated_games.insert(change.game_id);
+        self.history.push(change);
+    }
+
+    fn get(&self, subject: &RatingSubject) -> Ratings {
+        self.current.get(subject).copied().unwrap_or_default()
+    }
+}
+
+/// Keeps ratings for every player and AI model across games
+///
+/// Ratings are kept in memory, or in a JSON file holding the full history when opened
+/// with `open`. Each game can only be rated once.
+pub struct RatingService {
+    book: RwLock<RatingBook>,
+    path: Option<PathBuf>,
+}
+
+impl RatingService {
+    /// Ratings that last as long as the service does
+    pub fn new() -> Self {
+        Self { book: RwLock::new(RatingBook::default()), path: None }
+    }
+
+    /// Ratings kept in a file, picking up any history already stored there
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let mut book = RatingBook::default();
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => {
+                let history: Vec<RatingChange> = serde_json::from_slice(&bytes)?;
+                history.into_iter().for_each(|change| book.apply(change));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        Ok(Self { book: RwLock::new(book), path: Some(path) })
+    }
+
+    /// Update ratings from a finished game
+    ///
+    /// `players` says who is behind each seat; AI players also move their model's pooled
+    /// rating, which is rated against the other seats' models, or the players themselves
+    /// when they are not AI. In team games, players outside every team are not rated.
+    pub async fn record(&self, result: &GameResult, players: &[Player]) -> Result<Vec<RatingChange>> {
+        let mut book = self.book.write().await;
+        if book.rated_games.contains(&result.game_id) {
+            return Err(GameError::InvalidState {
+                reason: format!("Game {} has already been rated", result.game_id),
+            });
+        }
+
+        let sides = sides(result);
+        if sides.len() < 2 {
+            return Ok(Vec::new());
+        }
+
+        let types: HashMap<&str, &PlayerType> = players.iter().map(|p| (p.id.0.as_str(), &p.player_type)).collect();
+        let model_of = |player_id: &str| match types.get(player_id) {
+            Some(PlayerType::AI { provider, model }) => RatingSubject::model(provider, model),
+            _ => RatingSubject::player(player_id),
+        };
+
+        let mut updated = rate(&book, &sides, RatingSubject::player);
+        updated.extend(
+            rate(&book, &sides, model_of)
+                .into_iter()
+                .filter(|(subject, _)| matches!(subject, RatingSubject::Model { .. })),
+        );
+
+        let at = Utc::now();
+        let changes: Vec<RatingChange> = updated.into_iter()
+            .map(|(subject, after)| RatingChange { game_id: result.game_id, at, before: book.get(&subject), subject, after })
+            .collect();
+        changes.iter().cloned().for_each(|change| book.apply(change));
+
+        if let Some(path) = &self.path {
+            let partial = path.with_extension("tmp");
+            tokio::fs::write(&partial, serde_json::to_vec(&book.history)?).await?;
+            tokio::fs::rename(&partial, path).await?;
+        }
+        Ok(changes)
+    }
+
+    /// Current ratings, if the subject has played a rated game
+    pub async fn rating(&self, subject: &RatingSubject) -> Option<Ratings> {
+        self.book.read().await.current.get(subject).copied()
+    }
+
+    /// Ratings as they stood at a moment, if the subject had played a rated game by then
+    pub async fn rating_at(&self, subject: &RatingSubject, at: DateTime<Utc>) -> Option<Ratings> {
+        self.book.read().await.history.iter()
+            .rev()
+            .find(|c| &c.subject == subject && c.at <= at)
+            .map(|c| c.after)
+    }
+
+    /// Every change to a subject's ratings, oldest first
+    pub async fn history(&self, subject: &RatingSubject) -> Vec<RatingChange> {
+        self.book.read().await.history.iter().filter(|c| &c.subject == subject).cloned().collect()
+    }
+
+    /// Every rated subject, best first
+    pub async fn leaderboard(&self, system: RatingSystem) -> Vec<(RatingSubject, Ratings)> {
+        let mut board: Vec<_> = self.book.read().await.current.iter().map(|(s, r)| (s.clone(), *r)).collect();
+        board.sort_by(|a, b| {
+            b.1.score(system).partial_cmp(&a.1.score(system)).unwrap_or(Ordering::Equal)
+                .then_with(|| a.0.to_string().cmp(&b.0.to_string()))
+        });
+        board
+    }
+}
+
+impl Default for RatingService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The competing sides of a game, each with its rank and members
+fn sides(result: &GameResult) -> Vec<(u32, Vec<String>)> {
+    if result.teams.is_empty() {
+        result.placements.iter().map(|p| (p.rank, vec![p.player_id.clone()])).collect()
+    } else {
+        result.teams.iter().map(|t| (t.rank, t.members.clone())).collect()
+    }
+}
+
+/// New ratings for everyone in a game, with seats mapped to subjects by `subject_of`
+///
+/// Every seat is rated from the ratings held before the game. A subject holding several
+/// seats, like a model playing itself, ends up with the average of its seats' results.
+fn rate(
+    book: &RatingBook,
+    sides: &[(u32, Vec<String>)],
+    subject_of: impl Fn(&str) -> RatingSubject,
+) -> Vec<(RatingSubject, Ratings)> {
+    let seats: Vec<Vec<(RatingSubject, Ratings)>> = sides.iter()
+        .map(|(_, members)| members.iter().map(|m| { let s = subject_of(m); let r = book.get(&s); (s, r) }).collect())
+        .collect();
+
+    let trueskill = trueskill(
+        &sides.iter().zip(&seats)
+            .map(|((rank, _), side)| (*rank, side.iter().map(|(_, r)| r.trueskill).collect()))
+            .collect::<Vec<_>>(),
+    );
+
+    let mut rated: Vec<(RatingSubject, Ratings, Ratings)> = Vec::new();
+    for (i, side) in seats.iter().enumerate() {
+        for (j, (subject, before)) in side.iter().enumerate() {
+            let mut after = Ratings { trueskill: trueskill[i][j], games: before.games + 1, ..*before };
+
+            // Head-to-head systems only apply to one player against one other
+            if seats.len() == 2 && seats.iter().all(|side| side.len() == 1) {
+                let opponent = seats[1 - i][0].1;
+                let score = score(sides[i].0, sides[1 - i].0);
+                after.elo = elo(before.elo, opponent.elo, score);
+                after.glicko = glicko2(before.glicko, opponent.glicko, score);
+            }
+            rated.push((subject.clone(), *before, after));
+        }
+    }
+
+    let mut merged: Vec<(RatingSubject, Ratings)> = Vec::new();
+    for (subject, before, _) in &rated {
+        if merged.iter().any(|(s, _)| s == subject) {
+            continue;
+        }
+        let seats: Vec<&Ratings> = rated.iter().filter(|(s, _, _)| s == subject).map(|(_, _, after)| after).collect();
+        let n = seats.len() as f64;
+        let mean = |f: fn(&Ratings) -> f64| seats.iter().map(|r| f(r)).sum::<f64>() / n;
+        merged.push((subject.clone(), Ratings {
+            elo: mean(|r| r.elo),
+            glicko: Glicko2Rating {
+                rating: mean(|r| r.glicko.rating),
+                deviation: mean(|r| r.glicko.deviation),
+                volatility: mean(|r| r.glicko.volatility),
+            },
+            trueskill: TrueSkillRating { mu: mean(|r| r.trueskill.mu), sigma: mean(|r| r.trueskill.sigma) },
+            games: before.games + 1,
+        }));
+    }
+    merged
+}
+
+/// 1 for finishing ahead of the other side, 0.5 for a tie and 0 for finishing behind
+fn score(rank: u32, other_rank: u32) -> f64 {
+    match rank.cmp(&other_rank) {
+        Ordering::Less => 1.0,
+        Ordering::Equal => 0.5,
+        Ordering::Greater => 0.0,
+    }
+}
+
+/// Elo after scoring `score` (1 win, 0.5 draw, 0 loss) against `opponent`
+fn elo(rating: f64, opponent: f64, score: f64) -> f64 {
+    let expected = 1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0));
+    rating + ELO_K * (score - expected)
+}
+
+/// Glicko-2 after one game, treated as its own rating period
+fn glicko2(me: Glicko2Rating, opponent: Glicko2Rating, score: f64) -> Glicko2Rating {
+    let mu = (me.rating - ELO_START) / GLICKO_SCALE;
+    let phi = me.deviation / GLICKO_SCALE;
+    let mu_j = (opponent.rating - ELO_START) / GLICKO_SCALE;
+    let phi_j = opponent.deviation / GLICKO_SCALE;
+
+    let g = 1.0 / (1.0 + 3.0 * phi_j.powi(2) / PI.powi(2)).sqrt();
+    let expected = 1.0 / (1.0 + (-g * (mu - mu_j)).exp());
+    let v = 1.0 / (g.powi(2) * expected * (1.0 - expected));
+    let delta = v * g * (score - expected);
+
+    // New volatility, found with the Illinois algorithm
+    let a = me.volatility.powi(2).ln();
+    let f = |x: f64| {
+        let ex = x.exp();
+        ex * (delta.powi(2) - phi.powi(2) - v - ex) / (2.0 * (phi.powi(2) + v + ex).powi(2)) - (x - a) / GLICKO_TAU.powi(2)
+    };
+    let mut lower = a;
+    let mut upper = if delta.powi(2) > phi.powi(2) + v {
+        (delta.powi(2) - phi.powi(2) - v).ln()
+    } else {
+        let mut k = 1.0;
+        while f(a - k * GLICKO_TAU) < 0.0 {
+            k += 1.0;
+        }
+        a - k * GLICKO_TAU
+    };
+    let (mut f_lower, mut f_upper) = (f(lower), f(upper));
+    while (upper - lower).abs() > GLICKO_EPSILON {
+        let c = lower + (lower - upper) * f_lower / (f_upper - f_lower);
+        let f_c = f(c);
+        if f_c * f_upper <= 0.0 {
+            lower = upper;
+            f_lower = f_upper;
+        } else {
+            f_lower /= 2.0;
+        }
+        upper = c;
+        f_upper = f_c;
+    }
+    let volatility = (lower / 2.0).exp();
+
+    let phi_star = (phi.powi(2) + volatility.powi(2)).sqrt();
+    let phi_new = 1.0 / (1.0 / phi_star.powi(2) + 1.0 / v).sqrt();
+    let mu_new = mu + phi_new.powi(2) * g * (score - expected);
+    Glicko2Rating {
+        rating: mu_new * GLICKO_SCALE + ELO_START,
+        deviation: phi_new * GLICKO_SCALE,
+        volatility,
+    }
+}
+
+/// TrueSkill-style ratings after a game between ranked sides, lower rank being better
+///
+/// Every side is compared with every other; members share their side's update in
+/// proportion to how uncertain their own rating is.
+fn trueskill(sides: &[(u32, Vec<TrueSkillRating>)]) -> Vec<Vec<TrueSkillRating>> {
+    let sides: Vec<(u32, Vec<TrueSkillRating>)> = sides.iter()
+        .map(|(rank, members)| {
+            let members = members.iter()
+                .map(|r| TrueSkillRating { sigma: (r.sigma.powi(2) + TRUESKILL_TAU.powi(2)).sqrt(), ..*r })
+                .collect();
+            (*rank, members)
+        })
+        .collect();
+    let totals: Vec<(f64, f64)> = sides.iter()
+        .map(|(_, members)| (members.iter().map(|r| r.mu).sum(), members.iter().map(|r| r.sigma.powi(2)).sum()))
+        .collect();
+
+    sides.iter().enumerate()
+        .map(|(i, (rank, members))| {
+            let (mu, variance) = totals[i];
+            let (mut omega, mut delta) = (0.0, 0.0);
+            for (q, (other_rank, _)) in sides.iter().enumerate().filter(|(q, _)| *q != i) {
+                let (other_mu, other_variance) = totals[q];
+                let c = (variance + other_variance + 2.0 * TRUESKILL_BETA.powi(2)).sqrt();
+                let p = 1.0 / (1.0 + ((other_mu - mu) / c).exp());
+                omega += variance / c * (score(*rank, *other_rank) - p);
+                delta += variance.sqrt() / c * variance / c.powi(2) * p * (1.0 - p);
+            }
+            members.iter()
+                .map(|r| {
+                    let share = r.sigma.powi(2) / variance;
+                    TrueSkillRating {
+                        mu: r.mu + share * omega,
+                        sigma: r.sigma * (1.0 - share * delta).max(TRUESKILL_KAPPA).sqrt(),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
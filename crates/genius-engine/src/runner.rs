@@ -0,0 +1,403 @@
+//! Autonomous matches played by AI providers
+
+use crate::{GameEngine, GameEventStreamer, TurnScheduler};
+use genius_ai::AIProvider;
+use genius_core::{
+    FlagPenalty, GameError, GamePhase, LegalAction, Observer, PlayerAction, Result, TurnResolution,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tokio::time::{timeout_at, Instant};
+use uuid::Uuid;
+
+/// What to submit for a player whose provider timed out, failed or chose an illegal move
+#[derive(Debug, Clone, Default)]
+pub enum Fallback {
+    /// The first of the player's legal moves
+    #[default]
+    FirstLegal,
+    /// A fixed move, e.g. `LegalAction::simple("pass")`
+    Action(LegalAction),
+    /// Nothing; the player sits the round out
+    Skip,
+}
+
+impl Fallback {
+    fn action_for(&self, player_id: &str, legal: &[LegalAction]) -> Option<PlayerAction> {
+        match self {
+            Self::FirstLegal => legal.first().map(|m| m.to_action(player_id)),
+            Self::Action(fixed) => Some(fixed.to_action(player_id)),
+            Self::Skip => None,
+        }
+    }
+}
+
+/// How the runner gathers a round's decisions
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Collection {
+    /// Ask the seats one after another
+    #[default]
+    Sequential,
+    /// Ask every seat at once; the round closes at the shared deadline with whatever
+    /// has arrived, and the stragglers get the fallback
+    Parallel { round_deadline: Duration },
+}
+
+/// Why a provider's decision could not be used
+enum DecisionFailure {
+    /// The player's clock ran out during this decision
+    Flagged,
+    Failed(String),
+}
+
+impl std::fmt::Display for DecisionFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Flagged => write!(f, "out of time"),
+            Self::Failed(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+/// A decision the runner had to make on a player's behalf
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FallbackRecord {
+    /// Round the decision was for
+    pub round: u32,
+    pub player_id: String,
+    pub reason: String,
+}
+
+/// How a run ended
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchReport {
+    pub game_id: Uuid,
+    pub rounds_played: u32,
+    /// `Finished` when the game was played out; otherwise the phase it was stopped in
+    pub phase: GamePhase,
+    pub fallbacks: Vec<FallbackRecord>,
+}
+
+/// Plays a game by asking each seat's `AIProvider` for its moves until the game ends
+///
+/// Every round the runner collects decisions from the seats the game is waiting on,
+/// each bounded by the scheduler's turn timeout, and submits them to the engine.
+/// Seats whose provider times out, errors or picks a move the engine rejects get
+/// the configured `Fallback` instead. Under a time control each decision is charged
+/// to the player's clock in `GameState`, and players who run out of time get the
+/// scheduler's `FlagPenalty`.
+///
+/// Each action carries the time its provider took in `latency_ms`. With a streamer
+/// attached, every played round is streamed and its latencies feed the streamer's
+/// `RealTimeMetrics`.
+pub struct MatchRunner {
+    engine: Arc<GameEngine>,
+    scheduler: Arc<TurnScheduler>,
+    seats: BTreeMap<String, Arc<dyn AIProvider>>,
+    fallback: Fallback,
+    collection: Collection,
+    streamer: Option<Arc<GameEventStreamer>>,
+}
+
+impl MatchRunner {
+    /// Runner over the given engine with no seats, the default scheduler and `Fallback::FirstLegal`
+    pub fn new(engine: Arc<GameEngine>) -> Self {
+        Self {
+            engine,
+            scheduler: Arc::new(TurnScheduler::default()),
+            seats: BTreeMap::new(),
+            fallback: Fallback::default(),
+            collection: Collection::default(),
+            streamer: None,
+        }
+    }
+
+    /// Let the provider play as the given player
+    pub fn seat(mut self, player_id: &str, provider: Arc<dyn AIProvider>) -> Self {
+        self.seats.insert(player_id.to_string(), provider);
+        self
+    }
+
+    /// Bound each decision by this scheduler's turn timeout
+    pub fn scheduler(mut self, scheduler: TurnScheduler) -> Self {
+        self.scheduler = Arc::new(scheduler);
+        self
+    }
+
+    /// What to play for a seat whose provider does not come up with a legal move
+    pub fn fallback(mut self, fallback: Fallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Gather decisions one seat at a time or all at once under a round deadline
+    pub fn collection(mut self, collection: Collection) -> Self {
+        self.collection = collection;
+        self
+    }
+
+    /// Stream played rounds and their response times through this streamer
+    pub fn streamer(mut self, streamer: Arc<GameEventStreamer>) -> Self {
+        self.streamer = Some(streamer);
+        self
+    }
+
+    /// Play a running game until it ends or is paused or aborted
+    pub async fn run(&self, game_id: Uuid) -> Result<MatchReport> {
+        self.engine.get_phase(game_id).await?.check_running()?;
+        let mut report = MatchReport {
+            game_id,
+            rounds_played: 0,
+            phase: GamePhase::Running,
+            fallbacks: Vec::new(),
+        };
+
+        while report.phase == GamePhase::Running {
+            if self.play_round(game_id, &mut report).await? {
+                report.rounds_played += 1;
+            }
+            report.phase = self.engine.get_phase(game_id).await?;
+        }
+
+        Ok(report)
+    }
+
+    /// Collect one round of decisions and submit them, returning whether a round was played
+    ///
+    /// A round in which someone forfeits on time is not submitted; the turn is collected
+    /// again without them.
+    async fn play_round(&self, game_id: Uuid, report: &mut MatchReport) -> Result<bool> {
+        let round = self.engine.get_game_state(game_id).await?.round + 1;
+        let movers = self.movers(game_id).await?;
+
+        let mut legal_moves = HashMap::new();
+        for player_id in &movers {
+            legal_moves.insert(player_id.clone(), self.engine.get_valid_actions(game_id, player_id).await?);
+        }
+
+        let decisions = match self.collection {
+            Collection::Sequential => {
+                let mut decisions = Vec::new();
+                for player_id in &movers {
+                    let provider = self.seats[player_id].as_ref();
+                    let legal = legal_moves[player_id].clone();
+                    let outcome = decide(&self.engine, &self.scheduler, provider, game_id, player_id, legal).await;
+                    decisions.push((player_id.clone(), outcome));
+                }
+                decisions
+            }
+            Collection::Parallel { round_deadline } => {
+                self.collect_parallel(game_id, &movers, &legal_moves, round_deadline).await
+            }
+        };
+
+        let mut actions = HashMap::new();
+        let mut forfeited = false;
+        for (player_id, outcome) in decisions {
+            match outcome {
+                Ok(action) => {
+                    actions.insert(player_id, action);
+                }
+                Err(DecisionFailure::Flagged) if self.scheduler.flag_penalty() == FlagPenalty::Forfeit => {
+                    tracing::warn!("{} ran out of time in round {} and forfeits", player_id, round);
+                    report.fallbacks.push(FallbackRecord {
+                        round,
+                        player_id: player_id.clone(),
+                        reason: "out of time; forfeited".to_string(),
+                    });
+                    self.engine.leave_game(game_id, &player_id).await?;
+                    forfeited = true;
+                }
+                Err(failure) => {
+                    if let Some(action) = self.fall_back(report, round, &player_id, failure.to_string(), &legal_moves[&player_id]) {
+                        actions.insert(player_id, action);
+                    }
+                }
+            }
+        }
+        if forfeited {
+            return Ok(false);
+        }
+
+        let result = match self.engine.process_turn(game_id, actions.clone()).await {
+            Ok(result) => result,
+            Err(GameError::ActionsRejected { rejections }) => {
+                // Replace the rejected moves and try once more
+                for rejection in rejections {
+                    actions.remove(&rejection.player_id);
+                    let legal = legal_moves.get(&rejection.player_id).map(Vec::as_slice).unwrap_or_default();
+                    let reason = format!("move rejected: {}", rejection.reason);
+                    if let Some(action) = self.fall_back(report, round, &rejection.player_id, reason, legal) {
+                        actions.insert(rejection.player_id, action);
+                    }
+                }
+                self.engine.process_turn(game_id, actions).await?
+            }
+            Err(e) => return Err(e),
+        };
+
+        if let Some(streamer) = &self.streamer {
+            streamer.record_round(game_id, &result).await;
+        }
+        Ok(true)
+    }
+
+    /// Ask every mover at once and take what has arrived by the round deadline, in seat order
+    async fn collect_parallel(
+        &self,
+        game_id: Uuid,
+        movers: &[String],
+        legal_moves: &HashMap<String, Vec<LegalAction>>,
+        round_deadline: Duration,
+    ) -> Vec<(String, std::result::Result<PlayerAction, DecisionFailure>)> {
+        let started = Instant::now();
+        let deadline = started + round_deadline;
+        let mut pending = JoinSet::new();
+        for player_id in movers {
+            let engine = self.engine.clone();
+            let scheduler = self.scheduler.clone();
+            let provider = self.seats[player_id].clone();
+            let legal = legal_moves[player_id].clone();
+            let player_id = player_id.clone();
+            pending.spawn(async move {
+                let outcome = decide(&engine, &scheduler, provider.as_ref(), game_id, &player_id, legal).await;
+                (player_id, outcome)
+            });
+        }
+
+        let mut arrived = HashMap::new();
+        while let Ok(Some(joined)) = timeout_at(deadline, pending.join_next()).await {
+            match joined {
+                Ok((player_id, outcome)) => {
+                    arrived.insert(player_id, outcome);
+                }
+                Err(e) => tracing::error!("Decision task failed: {}", e),
+            }
+        }
+        // Dropping the set cancels the stragglers
+        drop(pending);
+        let used_ms = started.elapsed().as_millis() as u64;
+
+        let mut decisions = Vec::new();
+        for player_id in movers {
+            let outcome = match arrived.remove(player_id) {
+                Some(outcome) => outcome,
+                None => self.charge_straggler(game_id, player_id, used_ms).await,
+            };
+            decisions.push((player_id.clone(), outcome));
+        }
+        decisions
+    }
+
+    /// Charge a player cut off by the round deadline for the whole round, as their own decision would have been
+    async fn charge_straggler(
+        &self,
+        game_id: Uuid,
+        player_id: &str,
+        used_ms: u64,
+    ) -> std::result::Result<PlayerAction, DecisionFailure> {
+        let missed = || DecisionFailure::Failed("missed the round deadline".to_string());
+        let Some(control) = self.scheduler.time_control() else {
+            return Err(missed());
+        };
+        let state = self.engine.get_game_state(game_id)
+            .await
+            .map_err(|e| DecisionFailure::Failed(e.to_string()))?;
+        let mut clock = state.clocks.get(player_id).copied().unwrap_or_else(|| control.start());
+        let was_flagged = clock.flagged;
+        clock.charge(used_ms, control);
+        self.engine.set_clock(game_id, player_id, clock)
+            .await
+            .map_err(|e| DecisionFailure::Failed(e.to_string()))?;
+        Err(if clock.flagged && !was_flagged { DecisionFailure::Flagged } else { missed() })
+    }
+
+    /// Seated players the game is waiting on, in seat order
+    async fn movers(&self, game_id: Uuid) -> Result<Vec<String>> {
+        let turn = self.engine.get_current_turn(game_id).await?;
+        let state = self.engine.get_game_state(game_id).await?;
+
+        // An open roster takes anyone still in the game
+        let mut movers: Vec<String> = if turn.players.is_empty() {
+            self.seats.keys().filter(|p| state.is_active(p)).cloned().collect()
+        } else {
+            let unseated: Vec<&String> = turn.players.iter().filter(|p| !self.seats.contains_key(*p)).collect();
+            if !unseated.is_empty() && turn.resolution == TurnResolution::AllOf {
+                return Err(GameError::InvalidState {
+                    reason: format!("Waiting on players without a provider: {:?}", unseated),
+                });
+            }
+            self.seats.keys().filter(|p| turn.players.contains(p)).cloned().collect()
+        };
+
+        // Only one move is needed when any of the listed players may act
+        if turn.resolution == TurnResolution::OneOf {
+            movers.truncate(1);
+        }
+        if movers.is_empty() {
+            return Err(GameError::InvalidState {
+                reason: "No seated player can act".to_string(),
+            });
+        }
+        Ok(movers)
+    }
+
+    /// Record a fallback and produce the move it stands for, if any
+    fn fall_back(
+        &self,
+        report: &mut MatchReport,
+        round: u32,
+        player_id: &str,
+        reason: String,
+        legal: &[LegalAction],
+    ) -> Option<PlayerAction> {
+        tracing::warn!("Falling back for {} in round {}: {}", player_id, round, reason);
+        report.fallbacks.push(FallbackRecord {
+            round,
+            player_id: player_id.to_string(),
+            reason,
+        });
+        self.fallback.action_for(player_id, legal)
+    }
+}
+
+/// Ask a provider for its move within the turn timeout and the player's clock, timing the answer
+async fn decide(
+    engine: &GameEngine,
+    scheduler: &TurnScheduler,
+    provider: &dyn AIProvider,
+    game_id: Uuid,
+    player_id: &str,
+    legal: Vec<LegalAction>,
+) -> std::result::Result<PlayerAction, DecisionFailure> {
+    let observation = engine.get_observation(game_id, &Observer::Player(player_id.to_string()))
+        .await
+        .map_err(|e| DecisionFailure::Failed(e.to_string()))?;
+    let decision = provider.make_decision(&observation, player_id, legal);
+    let started = Instant::now();
+
+    let outcome = match scheduler.time_control() {
+        Some(control) => {
+            let mut clock = observation.clocks.get(player_id).copied().unwrap_or_else(|| control.start());
+            let was_flagged = clock.flagged;
+            let outcome = scheduler.execute_on_clock(&mut clock, player_id.to_string(), decision).await;
+            engine.set_clock(game_id, player_id, clock)
+                .await
+                .map_err(|e| DecisionFailure::Failed(e.to_string()))?;
+            if clock.flagged && !was_flagged {
+                return Err(DecisionFailure::Flagged);
+            }
+            outcome
+        }
+        None => scheduler.execute_with_timeout(player_id.to_string(), decision).await,
+    };
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    outcome
+        .map_err(|e| DecisionFailure::Failed(e.to_string()))?
+        .map(|decision| decision.action.with_latency(latency_ms))
+        .map_err(|e| DecisionFailure::Failed(format!("{} failed: {}", provider.name(), e)))
+}
@@ -1,13 +1,18 @@
 //! Turn scheduling and timeout management
 
+use std::collections::HashMap;
 use std::time::Duration;
-use tokio::time::{timeout, Instant};
-use genius_core::{Result, GameError};
+use tokio::sync::mpsc;
+use tokio::time::{timeout, timeout_at, Instant};
+use genius_core::{PlayerAction, PlayerClock, TimeControl, FlagPenalty, Result, GameError, Turn};
 
 /// Manages turn timing and scheduling
+#[derive(Debug, Clone)]
 pub struct TurnScheduler {
     turn_timeout: Duration,
     grace_period: Duration,
+    time_control: Option<TimeControl>,
+    flag_penalty: FlagPenalty,
 }
 
 impl TurnScheduler {
@@ -15,9 +20,33 @@ impl TurnScheduler {
         Self {
             turn_timeout,
             grace_period: Duration::from_secs(5),
+            time_control: None,
+            flag_penalty: FlagPenalty::default(),
         }
     }
     
+    /// Play under a chess clock; each decision is also bounded by the player's remaining bank
+    pub fn with_time_control(mut self, time_control: TimeControl) -> Self {
+        self.time_control = Some(time_control);
+        self
+    }
+    
+    /// What happens to a player who runs out of time
+    pub fn with_flag_penalty(mut self, flag_penalty: FlagPenalty) -> Self {
+        self.flag_penalty = flag_penalty;
+        self
+    }
+    
+    /// Chess clock in use, if any
+    pub fn time_control(&self) -> Option<&TimeControl> {
+        self.time_control.as_ref()
+    }
+    
+    /// What happens to a player who runs out of time
+    pub fn flag_penalty(&self) -> FlagPenalty {
+        self.flag_penalty
+    }
+    
     /// Execute a turn with timeout
     pub async fn execute_with_timeout<F, T>(&self, player_id: String, f: F) -> Result<T>
     where
@@ -29,6 +58,61 @@ impl TurnScheduler {
         }
     }
     
+    /// Execute a player's decision against their clock
+    ///
+    /// The decision is cut off at whichever comes first of the turn timeout and the
+    /// player's remaining bank. The time taken is charged to `clock`, with the increment
+    /// credited per the time control; running out flags the clock. Without a time
+    /// control this is `execute_with_timeout`.
+    pub async fn execute_on_clock<F, T>(&self, clock: &mut PlayerClock, player_id: String, f: F) -> Result<T>
+    where
+        F: std::future::Future<Output = T>,
+    {
+        let Some(control) = &self.time_control else {
+            return self.execute_with_timeout(player_id, f).await;
+        };
+        
+        let bank = Duration::from_millis(clock.remaining_ms);
+        let started = Instant::now();
+        let result = timeout(self.turn_timeout.min(bank), f).await;
+        let used_ms = started.elapsed().as_millis() as u64;
+        
+        match result {
+            Ok(value) if clock.charge(used_ms, control) => Ok(value),
+            Ok(_) => Err(GameError::TurnTimeout { player_id }),
+            Err(_) => {
+                // Either the bank ran dry, or the turn timeout cut in with time still banked
+                clock.charge(if bank <= self.turn_timeout { u64::MAX } else { used_ms }, control);
+                Err(GameError::TurnTimeout { player_id })
+            }
+        }
+    }
+    
+    /// Collect actions for a turn until it can resolve, the deadline passes or the senders hang up
+    ///
+    /// Actions from players the turn is not waiting on are dropped.
+    pub async fn collect_turn(
+        &self,
+        turn: &Turn,
+        incoming: &mut mpsc::Receiver<PlayerAction>,
+    ) -> HashMap<String, PlayerAction> {
+        let deadline = Instant::now() + self.turn_timeout + self.grace_period;
+        let mut actions = HashMap::new();
+        
+        while !turn.is_ready(&actions) {
+            match timeout_at(deadline, incoming.recv()).await {
+                Ok(Some(action)) => {
+                    if turn.expects(&action.player_id) {
+                        actions.insert(action.player_id.clone(), action);
+                    }
+                }
+                Ok(None) | Err(_) => break,
+            }
+        }
+        
+        actions
+    }
+    
     /// Check if a deadline has passed
     pub fn is_expired(&self, deadline: Instant) -> bool {
         Instant::now() > deadline
@@ -0,0 +1,147 @@
+//! Durable storage for games, so they survive a restart and stay available once finished
+
+use crate::journal::JournalEntry;
+use async_trait::async_trait;
+use genius_core::{GameResult, GameSnapshot, Replay, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Everything stored for one game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredGame {
+    /// Config, state with round history, lifecycle phase and game internals
+    pub snapshot: GameSnapshot,
+    /// Recorded rounds and forfeits
+    pub replay: Replay,
+    /// Everything that happened to the game, in order
+    #[serde(default)]
+    pub journal: Vec<JournalEntry>,
+    /// Set once the game has been finalized; such games are not recovered
+    #[serde(default)]
+    pub result: Option<GameResult>,
+}
+
+impl StoredGame {
+    pub fn game_id(&self) -> Uuid {
+        self.snapshot.state.game_id
+    }
+
+    /// Whether the game was still being played when it was stored
+    pub fn is_in_flight(&self) -> bool {
+        self.result.is_none()
+    }
+}
+
+/// Where a `GameEngine` keeps its games
+///
+/// The engine saves a game after every change, replacing what was stored for it before.
+#[async_trait]
+pub trait GameStore: Send + Sync {
+    /// Store a game, replacing any earlier version of it
+    async fn save(&self, game: &StoredGame) -> Result<()>;
+
+    /// Load a stored game, if there is one
+    async fn load(&self, game_id: Uuid) -> Result<Option<StoredGame>>;
+
+    /// Ids of every stored game
+    async fn list(&self) -> Result<Vec<Uuid>>;
+
+    /// Forget a game; removing an unknown game is not an error
+    async fn remove(&self, game_id: Uuid) -> Result<()>;
+}
+
+/// Keeps games in memory; they last as long as the store does
+#[derive(Default)]
+pub struct MemoryStore {
+    games: RwLock<HashMap<Uuid, StoredGame>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl GameStore for MemoryStore {
+    async fn save(&self, game: &StoredGame) -> Result<()> {
+        self.games.write().await.insert(game.game_id(), game.clone());
+        Ok(())
+    }
+
+    async fn load(&self, game_id: Uuid) -> Result<Option<StoredGame>> {
+        Ok(self.games.read().await.get(&game_id).cloned())
+    }
+
+    async fn list(&self) -> Result<Vec<Uuid>> {
+        Ok(self.games.read().await.keys().copied().collect())
+    }
+
+    async fn remove(&self, game_id: Uuid) -> Result<()> {
+        self.games.write().await.remove(&game_id);
+        Ok(())
+    }
+}
+
+/// Keeps each game as a JSON file named after its id in one directory
+///
+/// Files are replaced atomically, so a crash mid-save leaves the previous version intact.
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    /// Store games under `dir`, creating it if needed
+    pub async fn open(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        tokio::fs::create_dir_all(&dir).await?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self, game_id: Uuid) -> PathBuf {
+        self.dir.join(format!("{}.json", game_id))
+    }
+}
+
+#[async_trait]
+impl GameStore for FileStore {
+    async fn save(&self, game: &StoredGame) -> Result<()> {
+        let path = self.path(game.game_id());
+        let partial = path.with_extension("json.tmp");
+        tokio::fs::write(&partial, serde_json::to_vec(game)?).await?;
+        tokio::fs::rename(&partial, &path).await?;
+        Ok(())
+    }
+
+    async fn load(&self, game_id: Uuid) -> Result<Option<StoredGame>> {
+        match tokio::fs::read(self.path(game_id)).await {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn list(&self) -> Result<Vec<Uuid>> {
+        let mut ids = Vec::new();
+        let mut entries = tokio::fs::read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                if let Some(id) = path.file_stem().and_then(|s| s.to_str()).and_then(|s| Uuid::parse_str(s).ok()) {
+                    ids.push(id);
+                }
+            }
+        }
+        Ok(ids)
+    }
+
+    async fn remove(&self, game_id: Uuid) -> Result<()> {
+        match tokio::fs::remove_file(self.path(game_id)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
@@ -5,7 +5,7 @@ use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock};
 use uuid::Uuid;
 
-use genius_core::{GameState, GameResult, GameEvent};
+use genius_core::{Observation, GameResult, GameEvent, RoundResult};
 
 /// Messages that can be streamed during game execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,7 +16,7 @@ pub enum StreamMessage {
     },
     GameStateUpdate {
         game_id: Uuid,
-        state: GameState,
+        observation: Observation,
     },
     RoundUpdate {
         game_id: Uuid,
@@ -39,6 +39,19 @@ pub struct RealTimeMetrics {
     pub active_players: u32,
     pub emergence_score: f32,
     pub rounds_per_minute: f32,
+    /// Timed decisions behind `average_response_time_ms`
+    #[serde(default)]
+    pub response_samples: u64,
+}
+
+impl RealTimeMetrics {
+    /// Fold the measured latencies of a round's actions into the running average
+    pub fn record_round(&mut self, result: &RoundResult) {
+        for latency in result.actions.values().filter_map(|a| a.latency_ms) {
+            self.response_samples += 1;
+            self.average_response_time_ms += (latency as f64 - self.average_response_time_ms) / self.response_samples as f64;
+        }
+    }
 }
 
 /// Manages streaming of game events
@@ -57,6 +70,7 @@ impl GameEventStreamer {
                 active_players: 0,
                 emergence_score: 0.0,
                 rounds_per_minute: 0.0,
+                response_samples: 0,
             })),
         }
     }
@@ -69,8 +83,9 @@ impl GameEventStreamer {
         let _ = self.sender.send(StreamMessage::GameStarted { game_id, players });
     }
     
-    pub async fn broadcast_state_update(&self, game_id: Uuid, state: GameState) {
-        let _ = self.sender.send(StreamMessage::GameStateUpdate { game_id, state });
+    /// Broadcast a state update; streams are public, so pass a spectator observation
+    pub async fn broadcast_state_update(&self, game_id: Uuid, observation: Observation) {
+        let _ = self.sender.send(StreamMessage::GameStateUpdate { game_id, observation });
     }
     
     pub async fn broadcast_round_update(&self, game_id: Uuid, round: u32, events: Vec<GameEvent>) {
@@ -81,6 +96,26 @@ impl GameEventStreamer {
         let _ = self.sender.send(StreamMessage::GameEnded { game_id, result });
     }
     
+    /// Stream a played round and update the metrics from its response times
+    pub async fn record_round(&self, game_id: Uuid, result: &RoundResult) {
+        let metrics = {
+            let mut metrics = self.metrics.write().await;
+            metrics.record_round(result);
+            metrics.clone()
+        };
+        let _ = self.sender.send(StreamMessage::RoundUpdate {
+            game_id,
+            round: result.round,
+            events: result.events.clone(),
+        });
+        let _ = self.sender.send(StreamMessage::AnalyticsUpdate { game_id, metrics });
+    }
+    
+    /// Current metrics
+    pub async fn metrics(&self) -> RealTimeMetrics {
+        self.metrics.read().await.clone()
+    }
+    
     pub async fn update_metrics(&self, metrics: RealTimeMetrics) {
         *self.metrics.write().await = metrics.clone();
         // Broadcast metrics update
@@ -0,0 +1,508 @@
+//! Tournaments: many head-to-head matches between AI providers, with standings
+
+use crate::{GameEngine, MatchRunner, TurnScheduler};
+use genius_ai::AIProvider;
+use genius_core::{GameConfig, GameError, GameOutcome, Result, Team};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// How entrants are paired
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Format {
+    /// Everyone plays everyone, `cycles` times over
+    RoundRobin { cycles: u32 },
+    /// Entrants on similar scores meet, without rematches where possible
+    Swiss { rounds: u32 },
+    /// Knocked out after one lost match
+    SingleElimination,
+    /// Knocked out after two lost matches, with a grand final between the last two
+    DoubleElimination,
+}
+
+impl Format {
+    /// Points for sitting a round out; only Swiss awards them
+    fn bye_points(&self) -> f64 {
+        match self {
+            Self::Swiss { .. } => 1.0,
+            _ => 0.0,
+        }
+    }
+}
+
+/// How a game between two entrants ended
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MatchOutcome {
+    Won { winner: String, loser: String },
+    Drawn,
+}
+
+/// One game played in a tournament
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchRecord {
+    /// Tournament round, starting at 1
+    pub round: u32,
+    pub game_id: Uuid,
+    /// The two entrants in seat order; with declared sides the first plays the first side
+    pub seats: [String; 2],
+    pub outcome: MatchOutcome,
+}
+
+impl MatchRecord {
+    /// Points the entrant scored in this game: 1 for a win, ½ for a draw
+    pub fn points_for(&self, entrant: &str) -> f64 {
+        match &self.outcome {
+            MatchOutcome::Won { winner, .. } if winner == entrant => 1.0,
+            MatchOutcome::Won { .. } => 0.0,
+            MatchOutcome::Drawn => 0.5,
+        }
+    }
+
+    fn opponent_of(&self, entrant: &str) -> &str {
+        if self.seats[0] == entrant { &self.seats[1] } else { &self.seats[0] }
+    }
+}
+
+/// A round an entrant sat out because nobody was left to pair them with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ByeRecord {
+    pub round: u32,
+    pub entrant: String,
+}
+
+/// An entrant's line in the final table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Standing {
+    pub entrant: String,
+    /// Position in the entry list, starting at 1
+    pub seed: u32,
+    pub played: u32,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub byes: u32,
+    pub points: f64,
+    /// Tiebreak: sum of the points of every opponent faced
+    pub buchholz: f64,
+    /// Tiebreak: points of the opponents beaten, plus half of those drawn with
+    pub sonneborn_berger: f64,
+    /// Round the entrant was knocked out in, for elimination formats
+    pub eliminated_in: Option<u32>,
+}
+
+/// Everything that happened in a tournament
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TournamentReport {
+    pub format: Format,
+    pub rounds_played: u32,
+    pub matches: Vec<MatchRecord>,
+    pub byes: Vec<ByeRecord>,
+    /// Best first; see `Tournament` for how ties are broken
+    pub standings: Vec<Standing>,
+    /// Points each entrant (row) scored against each opponent (column)
+    pub crosstable: BTreeMap<String, BTreeMap<String, f64>>,
+    /// Winner of an elimination bracket, or the top of the table
+    pub champion: String,
+}
+
+/// Runs a tournament of two-player games between AI providers
+///
+/// Every match is a fresh game built from the config template, played out by a
+/// `MatchRunner` and finalized. Seats are rotated so entrants play first about as
+/// often as second, and rematches swap seats. For asymmetric games, `sides` names
+/// the teams the two seats are declared on, e.g. `sides("black", "white")` for `MiniGo`.
+///
+/// Table standings are ordered by points, then Buchholz, Sonneborn-Berger, wins and
+/// seed. Elimination standings put entrants knocked out later first. A drawn
+/// elimination match is replayed with seats swapped up to the tiebreak game limit,
+/// after which the higher seed advances.
+pub struct Tournament {
+    engine: Arc<GameEngine>,
+    config: GameConfig,
+    format: Format,
+    entrants: Vec<(String, Arc<dyn AIProvider>)>,
+    scheduler: TurnScheduler,
+    sides: Vec<String>,
+    tiebreak_games: u32,
+}
+
+/// Running tally while the tournament is played
+struct Table {
+    matches: Vec<MatchRecord>,
+    byes: Vec<ByeRecord>,
+    losses: Vec<u32>,
+    eliminated_in: Vec<Option<u32>>,
+}
+
+impl Tournament {
+    /// Tournament in the given format over games created from `config`
+    ///
+    /// A seed in the config is varied per game so matches differ but the tournament
+    /// stays reproducible.
+    pub fn new(engine: Arc<GameEngine>, config: GameConfig, format: Format) -> Self {
+        Self {
+            engine,
+            config,
+            format,
+            entrants: Vec::new(),
+            scheduler: TurnScheduler::default(),
+            sides: Vec::new(),
+            tiebreak_games: 2,
+        }
+    }
+
+    /// Enter a provider under the given name; entrants are seeded in the order they are added
+    pub fn entrant(mut self, name: &str, provider: Arc<dyn AIProvider>) -> Self {
+        self.entrants.push((name.to_string(), provider));
+        self
+    }
+
+    /// Bound every decision by this scheduler's turn timeout and clock
+    pub fn scheduler(mut self, scheduler: TurnScheduler) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    /// Declare the first seat on team `first` and the second on team `second`
+    pub fn sides(mut self, first: &str, second: &str) -> Self {
+        self.sides = vec![first.to_string(), second.to_string()];
+        self
+    }
+
+    /// Extra games to play after a drawn elimination match before the higher seed advances
+    pub fn tiebreak_games(mut self, games: u32) -> Self {
+        self.tiebreak_games = games;
+        self
+    }
+
+    /// Play every round and produce the final standings
+    pub async fn run(&self) -> Result<TournamentReport> {
+        if self.entrants.len() < 2 {
+            return Err(GameError::MinPlayersNotMet { required: 2 });
+        }
+        let mut names = HashSet::new();
+        if let Some((name, _)) = self.entrants.iter().find(|(name, _)| !names.insert(name)) {
+            return Err(GameError::ConfigError {
+                reason: format!("Entrant '{}' is entered twice", name),
+            });
+        }
+
+        let mut table = Table {
+            matches: Vec::new(),
+            byes: Vec::new(),
+            losses: vec![0; self.entrants.len()],
+            eliminated_in: vec![None; self.entrants.len()],
+        };
+        let rounds_played = match self.format {
+            Format::RoundRobin { cycles } => self.run_round_robin(cycles, &mut table).await?,
+            Format::Swiss { rounds } => self.run_swiss(rounds, &mut table).await?,
+            Format::SingleElimination => self.run_elimination(1, &mut table).await?,
+            Format::DoubleElimination => self.run_elimination(2, &mut table).await?,
+        };
+
+        let standings = self.standings(&table);
+        let champion = standings[0].entrant.clone();
+        Ok(TournamentReport {
+            format: self.format,
+            rounds_played,
+            crosstable: self.crosstable(&table),
+            matches: table.matches,
+            byes: table.byes,
+            standings,
+            champion,
+        })
+    }
+
+    /// Circle-method pairings: every entrant meets every other once per cycle
+    async fn run_round_robin(&self, cycles: u32, table: &mut Table) -> Result<u32> {
+        let mut circle: Vec<Option<usize>> = (0..self.entrants.len()).map(Some).collect();
+        if circle.len() % 2 == 1 {
+            circle.push(None);
+        }
+
+        let mut round = 0;
+        for _ in 0..cycles {
+            for _ in 1..circle.len() {
+                round += 1;
+                for i in 0..circle.len() / 2 {
+                    match (circle[i], circle[circle.len() - 1 - i]) {
+                        (Some(a), Some(b)) => {
+                            self.play_pair(round, a, b, table).await?;
+                        }
+                        (Some(a), None) | (None, Some(a)) => self.give_bye(round, a, table),
+                        (None, None) => {}
+                    }
+                }
+                // Keep the first entrant fixed and rotate everyone else
+                let last = circle.pop().expect("circle has at least two places");
+                circle.insert(1, last);
+            }
+        }
+        Ok(round)
+    }
+
+    /// Pair entrants on equal or nearby points, avoiding rematches where possible
+    async fn run_swiss(&self, rounds: u32, table: &mut Table) -> Result<u32> {
+        for round in 1..=rounds {
+            let points = self.points(table);
+            let mut order: Vec<usize> = (0..self.entrants.len()).collect();
+            order.sort_by(|&a, &b| points[b].total_cmp(&points[a]).then(a.cmp(&b)));
+
+            // The lowest-placed entrant without a bye sits out, moving up if the rest cannot be paired
+            let mut bye = None;
+            if order.len() % 2 == 1 {
+                let had_bye: HashSet<&str> = table.byes.iter().map(|b| b.entrant.as_str()).collect();
+                let candidates: Vec<usize> = (0..order.len()).rev()
+                    .filter(|&pos| !had_bye.contains(self.entrants[order[pos]].0.as_str()))
+                    .collect();
+                let pos = candidates.iter().copied()
+                    .find(|&pos| {
+                        let mut rest = order.clone();
+                        rest.remove(pos);
+                        self.pair_without_rematches(&rest, table).is_some()
+                    })
+                    .or(candidates.first().copied())
+                    .unwrap_or(order.len() - 1);
+                bye = Some(order.remove(pos));
+            }
+
+            // Rematches only when there is no other way to pair everyone
+            let pairings = self.pair_without_rematches(&order, table)
+                .unwrap_or_else(|| order.chunks(2).map(|pair| (pair[0], pair[1])).collect());
+            if let Some(entrant) = bye {
+                self.give_bye(round, entrant, table);
+            }
+            for (a, b) in pairings {
+                self.play_pair(round, a, b, table).await?;
+            }
+        }
+        Ok(rounds)
+    }
+
+    /// Knock entrants out once they have lost `lives` matches
+    ///
+    /// Each round pairs entrants with the same number of losses, best seed against worst,
+    /// with the top seed of an odd group sitting out. Once every group is down to one
+    /// entrant the survivors meet across groups, which makes the grand final of a double
+    /// elimination; if the challenger wins it, the bracket resets and they play again.
+    async fn run_elimination(&self, lives: u32, table: &mut Table) -> Result<u32> {
+        let mut round = 0;
+        loop {
+            let alive: Vec<usize> = (0..self.entrants.len()).filter(|&i| table.losses[i] < lives).collect();
+            if alive.len() <= 1 {
+                return Ok(round);
+            }
+            round += 1;
+
+            let groups: Vec<Vec<usize>> = (0..lives)
+                .map(|losses| alive.iter().copied().filter(|&i| table.losses[i] == losses).collect())
+                .filter(|group: &Vec<usize>| !group.is_empty())
+                .collect();
+            let pairings: Vec<(usize, usize)> = if groups.iter().all(|group| group.len() == 1) {
+                vec![(alive[0], alive[1])]
+            } else {
+                let mut pairings = Vec::new();
+                for mut group in groups {
+                    if group.len() % 2 == 1 {
+                        self.give_bye(round, group.remove(0), table);
+                    }
+                    let half = group.len() / 2;
+                    pairings.extend((0..half).map(|i| (group[i], group[group.len() - 1 - i])));
+                }
+                pairings
+            };
+
+            for (a, b) in pairings {
+                let loser = self.play_knockout(round, a, b, table).await?;
+                table.losses[loser] += 1;
+                if table.losses[loser] == lives {
+                    table.eliminated_in[loser] = Some(round);
+                }
+            }
+        }
+    }
+
+    /// Play a knockout match, replaying draws, and return the loser
+    async fn play_knockout(&self, round: u32, a: usize, b: usize, table: &mut Table) -> Result<usize> {
+        for _ in 0..=self.tiebreak_games {
+            if let MatchOutcome::Won { loser, .. } = self.play_pair(round, a, b, table).await? {
+                return Ok(if loser == self.entrants[a].0 { a } else { b });
+            }
+        }
+        // Still level: the higher seed goes through
+        Ok(a.max(b))
+    }
+
+    /// Seat two entrants, play their game and record it
+    async fn play_pair(&self, round: u32, a: usize, b: usize, table: &mut Table) -> Result<MatchOutcome> {
+        let (first, second) = self.seat_order(a, b, table);
+        let (first_name, first_provider) = &self.entrants[first];
+        let (second_name, second_provider) = &self.entrants[second];
+
+        let mut config = self.config.clone();
+        config.seed = config.seed.map(|seed| seed.wrapping_add(table.matches.len() as u64));
+        if let [first_side, second_side] = self.sides.as_slice() {
+            config.teams = vec![Team::new(first_side, [first_name]), Team::new(second_side, [second_name])];
+        }
+
+        // Joining in seat order deals the first seat in first, so it moves first
+        let game_id = self.engine.open_lobby(config).await?.game_id;
+        let played = async {
+            self.engine.join_game(game_id, first_name).await?;
+            self.engine.join_game(game_id, second_name).await?;
+            self.engine.start_game(game_id).await?;
+            MatchRunner::new(self.engine.clone())
+                .seat(first_name, first_provider.clone())
+                .seat(second_name, second_provider.clone())
+                .scheduler(self.scheduler.clone())
+                .run(game_id)
+                .await
+        }
+        .await;
+
+        if let Err(e) = played {
+            // A match that cannot be played is not left behind in the engine
+            if self.engine.abort_game(game_id).await.is_ok() {
+                let _ = self.engine.finalize_game(game_id).await;
+            }
+            return Err(e);
+        }
+        let result = self.engine.finalize_game(game_id).await?;
+
+        let winner = match result.outcome {
+            GameOutcome::Winner { player_id } => Some(player_id),
+            GameOutcome::TeamWin { members, .. } => members.into_iter().find(|m| m == first_name || m == second_name),
+            GameOutcome::Draw { .. } | GameOutcome::NoWinner => None,
+        };
+        let outcome = match winner {
+            Some(winner) => {
+                let loser = if winner == *first_name { second_name } else { first_name };
+                MatchOutcome::Won { winner, loser: loser.clone() }
+            }
+            None => MatchOutcome::Drawn,
+        };
+
+        tracing::info!("Round {}: {} vs {}: {:?}", round, first_name, second_name, outcome);
+        table.matches.push(MatchRecord {
+            round,
+            game_id,
+            seats: [first_name.clone(), second_name.clone()],
+            outcome: outcome.clone(),
+        });
+        Ok(outcome)
+    }
+
+    /// Who takes the first seat: the reverse of their last meeting, otherwise whoever
+    /// has had it less often, otherwise the higher seed
+    fn seat_order(&self, a: usize, b: usize, table: &Table) -> (usize, usize) {
+        let (name_a, name_b) = (&self.entrants[a].0, &self.entrants[b].0);
+        let last_meeting = table.matches.iter().rev()
+            .find(|m| m.seats.contains(name_a) && m.seats.contains(name_b));
+        if let Some(last) = last_meeting {
+            return if last.seats[0] == *name_a { (b, a) } else { (a, b) };
+        }
+
+        let firsts = |name: &str| table.matches.iter().filter(|m| m.seats[0] == name).count();
+        match firsts(name_a).cmp(&firsts(name_b)) {
+            std::cmp::Ordering::Less => (a, b),
+            std::cmp::Ordering::Greater => (b, a),
+            std::cmp::Ordering::Equal => (a.min(b), a.max(b)),
+        }
+    }
+
+    fn give_bye(&self, round: u32, entrant: usize, table: &mut Table) {
+        table.byes.push(ByeRecord {
+            round,
+            entrant: self.entrants[entrant].0.clone(),
+        });
+    }
+
+    /// Pair entrants off in order, each with the highest-placed opponent they have not met,
+    /// backtracking when that leaves someone without a fresh opponent
+    fn pair_without_rematches(&self, order: &[usize], table: &Table) -> Option<Vec<(usize, usize)>> {
+        let Some((&a, rest)) = order.split_first() else {
+            return Some(Vec::new());
+        };
+        for (pos, &b) in rest.iter().enumerate() {
+            if self.have_met(a, b, table) {
+                continue;
+            }
+            let mut remaining = rest.to_vec();
+            remaining.remove(pos);
+            if let Some(mut pairings) = self.pair_without_rematches(&remaining, table) {
+                pairings.insert(0, (a, b));
+                return Some(pairings);
+            }
+        }
+        None
+    }
+
+    fn have_met(&self, a: usize, b: usize, table: &Table) -> bool {
+        let (name_a, name_b) = (&self.entrants[a].0, &self.entrants[b].0);
+        table.matches.iter().any(|m| m.seats.contains(name_a) && m.seats.contains(name_b))
+    }
+
+    /// Points per entrant, in seed order
+    fn points(&self, table: &Table) -> Vec<f64> {
+        self.entrants.iter()
+            .map(|(name, _)| {
+                let games: f64 = table.matches.iter().filter(|m| m.seats.contains(name)).map(|m| m.points_for(name)).sum();
+                let byes = table.byes.iter().filter(|b| b.entrant == *name).count() as f64;
+                games + byes * self.format.bye_points()
+            })
+            .collect()
+    }
+
+    fn standings(&self, table: &Table) -> Vec<Standing> {
+        let points = self.points(table);
+        let points_of = |name: &str| {
+            self.entrants.iter().position(|(n, _)| n == name).map(|i| points[i]).unwrap_or_default()
+        };
+
+        let mut standings: Vec<Standing> = self.entrants.iter().enumerate()
+            .map(|(i, (name, _))| {
+                let games: Vec<&MatchRecord> = table.matches.iter().filter(|m| m.seats.contains(name)).collect();
+                let scored = |m: &&MatchRecord| m.points_for(name);
+                Standing {
+                    entrant: name.clone(),
+                    seed: i as u32 + 1,
+                    played: games.len() as u32,
+                    wins: games.iter().filter(|m| scored(m) == 1.0).count() as u32,
+                    draws: games.iter().filter(|m| scored(m) == 0.5).count() as u32,
+                    losses: games.iter().filter(|m| scored(m) == 0.0).count() as u32,
+                    byes: table.byes.iter().filter(|b| b.entrant == *name).count() as u32,
+                    points: points[i],
+                    buchholz: games.iter().map(|m| points_of(m.opponent_of(name))).sum(),
+                    sonneborn_berger: games.iter().map(|m| scored(m) * points_of(m.opponent_of(name))).sum(),
+                    eliminated_in: table.eliminated_in[i],
+                }
+            })
+            .collect();
+
+        standings.sort_by(|a, b| {
+            let knocked_out = |s: &Standing| s.eliminated_in.unwrap_or(u32::MAX);
+            knocked_out(b).cmp(&knocked_out(a))
+                .then(b.points.total_cmp(&a.points))
+                .then(b.buchholz.total_cmp(&a.buchholz))
+                .then(b.sonneborn_berger.total_cmp(&a.sonneborn_berger))
+                .then(b.wins.cmp(&a.wins))
+                .then(a.seed.cmp(&b.seed))
+        });
+        standings
+    }
+
+    fn crosstable(&self, table: &Table) -> BTreeMap<String, BTreeMap<String, f64>> {
+        let mut crosstable: BTreeMap<String, BTreeMap<String, f64>> = self.entrants.iter()
+            .map(|(name, _)| (name.clone(), BTreeMap::new()))
+            .collect();
+        for m in &table.matches {
+            for (me, opponent) in [(&m.seats[0], &m.seats[1]), (&m.seats[1], &m.seats[0])] {
+                *crosstable.entry(me.clone()).or_default().entry(opponent.clone()).or_default() += m.points_for(me);
+            }
+        }
+        crosstable
+    }
+}
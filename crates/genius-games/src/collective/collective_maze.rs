@@ -1,17 +1,22 @@
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, GameError};
+use genius_core::{game_rng, GameRng, Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, EmergenceEvent, EmergenceType, GameEvent, Result, ActionSchema, LegalAction, Standings};
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 use rand::Rng;
 
+#[derive(Serialize, Deserialize)]
 pub struct CollectiveMaze {
     maze_size: usize,
     visibility_radius: usize,
     maze: Vec<Vec<Cell>>,
     agent_positions: HashMap<String, Position>,
+    /// Cells any agent has seen so far, the map the collective shares
+    #[serde(default)]
+    explored: HashSet<Position>,
     exit_positions: Vec<Position>,
     treasure_positions: Vec<Position>,
+    rng: GameRng,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -48,21 +53,21 @@ impl CollectiveMaze {
             visibility_radius: 5,
             maze: vec![],
             agent_positions: HashMap::new(),
+            explored: HashSet::new(),
             exit_positions: vec![],
             treasure_positions: vec![],
+            rng: game_rng(None),
         }
     }
     
     fn generate_maze(&mut self) {
-        let mut rng = rand::thread_rng();
-        
         // Initialize empty maze
         self.maze = vec![vec![Cell::Empty; self.maze_size]; self.maze_size];
         
         // Add walls (simple random walls for now)
         for y in 0..self.maze_size {
             for x in 0..self.maze_size {
-                if rng.gen_bool(0.3) && !self.is_border(x, y) {
+                if self.rng.random_bool(0.3) && !self.is_border(x, y) {
                     self.maze[y][x] = Cell::Wall;
                 }
             }
@@ -83,8 +88,8 @@ impl CollectiveMaze {
         
         // Add treasures
         for _ in 0..3 {
-            let x = rng.gen_range(10..self.maze_size - 10);
-            let y = rng.gen_range(10..self.maze_size - 10);
+            let x = self.rng.random_range(10..self.maze_size - 10);
+            let y = self.rng.random_range(10..self.maze_size - 10);
             let pos = Position { x, y };
             self.maze[y][x] = Cell::Treasure;
             self.treasure_positions.push(pos);
@@ -104,13 +109,17 @@ impl CollectiveMaze {
     }
     
     fn place_agents(&mut self, players: &[String]) {
-        let mut rng = rand::thread_rng();
+        let mut players: Vec<String> = players.iter()
+            .filter(|p| !self.agent_positions.contains_key(*p))
+            .cloned()
+            .collect();
+        players.sort();
         
-        for player in players {
+        for player in &players {
             // Find empty starting position
             loop {
-                let x = rng.gen_range(1..self.maze_size - 1);
-                let y = rng.gen_range(1..self.maze_size - 1);
+                let x = self.rng.random_range(1..self.maze_size - 1);
+                let y = self.rng.random_range(1..self.maze_size - 1);
                 
                 if self.maze[y][x] == Cell::Empty {
                     self.agent_positions.insert(player.clone(), Position { x, y });
@@ -187,26 +196,30 @@ impl CollectiveMaze {
         }
     }
     
-    fn calculate_emergence_score(&self, _shared_map: &HashMap<String, Position>) -> f32 {
-        // Measure how well agents are exploring together
-        let mut explored_cells = HashSet::new();
-        
+    /// Add everything the agents can see from where they stand to the shared map
+    fn record_explored(&mut self) {
+        let radius = self.visibility_radius as i32;
         for pos in self.agent_positions.values() {
-            // Add visible area around each agent
-            for dy in -(self.visibility_radius as i32)..=(self.visibility_radius as i32) {
-                for dx in -(self.visibility_radius as i32)..=(self.visibility_radius as i32) {
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
                     let x = (pos.x as i32 + dx) as usize;
                     let y = (pos.y as i32 + dy) as usize;
                     if x < self.maze_size && y < self.maze_size {
-                        explored_cells.insert(Position { x, y });
+                        self.explored.insert(Position { x, y });
                     }
                 }
             }
         }
-        
-        // Calculate coverage
-        let total_cells = self.maze_size * self.maze_size;
-        let coverage = explored_cells.len() as f32 / total_cells as f32;
+    }
+    
+    /// Share of the maze the collective has seen
+    fn coverage(&self) -> f32 {
+        self.explored.len() as f32 / (self.maze_size * self.maze_size).max(1) as f32
+    }
+    
+    fn calculate_emergence_score(&self) -> f32 {
+        // Measure how well agents are exploring together
+        let coverage = self.coverage();
         
         // Check for agent clustering (good for collective)
         let mut cluster_score = 0.0;
@@ -240,7 +253,7 @@ enum Direction {
     West,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 enum MoveResult {
     Success,
     Blocked,
@@ -250,7 +263,8 @@ enum MoveResult {
 
 #[async_trait]
 impl Game for CollectiveMaze {
-    async fn initialize(&mut self, _config: GameConfig) -> Result<GameState> {
+    async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        self.rng = game_rng(config.seed);
         self.generate_maze();
         
         Ok(GameState {
@@ -258,6 +272,10 @@ impl Game for CollectiveMaze {
             game_type: GameType::CollectiveMaze,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
@@ -266,23 +284,27 @@ impl Game for CollectiveMaze {
                 meta.insert("treasures_remaining".to_string(), serde_json::json!(self.treasure_positions.len()));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
     
     async fn process_round(&mut self, state: &GameState, actions: HashMap<String, PlayerAction>) -> Result<RoundResult> {
-        // Place agents on first round
-        if state.round == 0 && self.agent_positions.is_empty() {
-            let players: Vec<String> = actions.keys().cloned().collect();
-            self.place_agents(&players);
-        }
+        // Place anyone who was not dealt in before play started
+        let players: Vec<String> = actions.keys().cloned().collect();
+        self.place_agents(&players);
         
         let mut scores_delta = HashMap::new();
         let mut special_events = vec![];
         let mut escaped_agents = vec![];
         let mut _treasures_found = 0;
         
-        // Process each agent's move
-        for (player_id, action) in &actions {
+        // Process each agent's move in a stable order
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        
+        for player_id in player_ids {
+            let action = &actions[player_id];
             // Get local view for the agent
             let _local_view = self.get_local_view(player_id);
             
@@ -319,12 +341,22 @@ impl Game for CollectiveMaze {
         }
         
         // Calculate emergence score
-        let shared_map = HashMap::new(); // TODO: Implement shared mapping
-        let emergence_score = self.calculate_emergence_score(&shared_map);
+        self.record_explored();
+        let emergence_score = self.calculate_emergence_score();
         let emergence_detected = emergence_score > 0.6 && actions.len() > 5;
         
+        let mut events = Vec::new();
         if emergence_detected {
             special_events.push("Collective exploration pattern emerged!".to_string());
+            let mut involved_players: Vec<String> = actions.keys().cloned().collect();
+            involved_players.sort();
+            events.push(GameEvent::emergence(&EmergenceEvent {
+                round: state.round + 1,
+                event_type: EmergenceType::Custom("collective_exploration".to_string()),
+                description: "Agents coordinated exploration efficiently".to_string(),
+                emergence_score: 0.8,
+                involved_players,
+            }));
             
             // Bonus for all collective agents
             for player_id in actions.keys() {
@@ -348,13 +380,58 @@ impl Game for CollectiveMaze {
                 emergence_detected,
             },
             scores_delta,
+            events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Collective Maze")
+            .action("move", "Step one cell through the maze", serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "move": { "enum": ["north", "south", "east", "west"] }
+                },
+                "required": ["move"]
+            }))
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        let directions = [
+            ("north", Direction::North),
+            ("south", Direction::South),
+            ("east", Direction::East),
+            ("west", Direction::West),
+        ];
+        
+        let position = self.agent_positions.get(player_id);
+        directions.into_iter()
+            .filter(|(_, direction)| match position {
+                // Agents are placed as they join, so every direction is open before that
+                None => true,
+                Some(pos) => {
+                    let target = match direction {
+                        Direction::North => Position { x: pos.x, y: pos.y.saturating_sub(1) },
+                        Direction::South => Position { x: pos.x, y: (pos.y + 1).min(self.maze_size - 1) },
+                        Direction::East => Position { x: (pos.x + 1).min(self.maze_size - 1), y: pos.y },
+                        Direction::West => Position { x: pos.x.saturating_sub(1), y: pos.y },
+                    };
+                    target != *pos && self.maze[target.y][target.x] != Cell::Wall
+                }
+            })
+            .map(|(name, _)| LegalAction::new("move", serde_json::json!({ "move": name })))
+            .collect()
+    }
+    
+    async fn join(&mut self, _state: &GameState, player_id: &str) {
+        self.place_agents(&[player_id.to_string()]);
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         // Game ends when all agents escape or max rounds reached
-        let all_escaped = self.agent_positions.values()
+        let all_escaped = !self.agent_positions.is_empty() && self.agent_positions.values()
             .all(|pos| self.exit_positions.contains(pos));
         
         state.round >= 100 || all_escaped || 
@@ -362,28 +439,19 @@ impl Game for CollectiveMaze {
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        // Winner is highest scoring player
-        let winner = state.scores.iter()
-            .max_by_key(|(_, score)| *score)
-            .map(|(id, _)| id.clone())
-            .unwrap_or_else(|| "No winner".to_string());
-        
-        // Count emergence events
-        let emergence_events: Vec<EmergenceEvent> = state.history.iter()
-            .enumerate()
-            .filter_map(|(i, round_result)| {
-                if round_result.outcome.emergence_detected {
-                    Some(EmergenceEvent {
-                        round: i as u32,
-                        event_type: "collective_exploration".to_string(),
-                        description: "Agents coordinated exploration efficiently".to_string(),
-                        emergence_score: 0.8,
-                    })
-                } else {
-                    None
+        // Each side explores as one team; without declared teams the collective and
+        // SOTA agents are told apart by id prefix
+        let mut standings = Standings::new(&state.scores).declared_teams(&state.teams);
+        if state.teams.is_empty() {
+            for team in ["collective", "sota"] {
+                let prefix = format!("{}_", team);
+                let members: Vec<&String> = state.scores.keys().filter(|id| id.starts_with(&prefix)).collect();
+                if !members.is_empty() {
+                    standings = standings.team(team, members.into_iter().cloned());
                 }
-            })
-            .collect();
+            }
+        }
+        
         
         // Calculate collective vs individual performance
         let collective_total: i32 = state.scores.iter()
@@ -399,22 +467,29 @@ impl Game for CollectiveMaze {
         let collective_count = state.scores.keys().filter(|k| k.starts_with("collective_")).count().max(1);
         let sota_count = state.scores.keys().filter(|k| k.starts_with("sota_")).count().max(1);
         
-        // Calculate emergence frequency before moving emergence_events
-        let emergence_frequency = emergence_events.len() as f32 / state.round.max(1) as f32;
+        // Calculate emergence frequency
+        let emergence_frequency = state.history.iter()
+            .filter(|round_result| round_result.outcome.emergence_detected)
+            .count() as f32 / state.round.max(1) as f32;
         
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: state.scores.clone(),
             total_rounds: state.round,
-            emergence_events,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
+            emergence_events: vec![],
             analytics: GameAnalytics {
-                collective_coordination_score: emergence_frequency,
+                collective_coordination_score: self.coverage(),
                 decision_diversity_index: 0.0, // TODO
                 strategic_depth: state.round as f32 / 100.0,
                 emergence_frequency,
                 performance_differential: (collective_total / collective_count as i32) as f32 - 
                                          (sota_total / sota_count as i32) as f32,
+                custom_metrics: HashMap::new(),
             },
         }
     }
@@ -1,20 +1,21 @@
 //! Consciousness Cascade - Consciousness flows and amplifies through the network
 
 use genius_core::{
-    Game, GameConfig, GameState, GameType, PlayerAction, RoundResult,
-    RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType,
-    Result, GameError,
+    game_rng, GameRng, Game, GameConfig, GameState, PlayerAction, RoundResult,
+    RoundOutcome, GameResult, GameAnalytics, GameEvent, EventKind, EmergenceEvent, EmergenceType,
+    Result, ActionSchema, LegalAction, Standings,
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use rand::Rng;
 
 /// A consciousness node in the network
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsciousnessNode {
     pub player_id: String,
     pub consciousness_level: f32,
-    pub connections: HashMap<String, Connection>,
+    pub connections: BTreeMap<String, Connection>,
     pub thoughts: Vec<Thought>,
     pub resonance_frequency: f32,
     pub cascade_potential: f32,
@@ -56,14 +57,16 @@ pub enum CascadeActionType {
     Merge,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct ConsciousnessCascadeGame {
     round_number: u32,
-    nodes: HashMap<String, ConsciousnessNode>,
+    nodes: BTreeMap<String, ConsciousnessNode>,
     global_consciousness: f32,
     cascade_threshold: f32,
     thought_pool: Vec<Thought>,
-    resonance_clusters: Vec<HashSet<String>>,
+    resonance_clusters: Vec<BTreeSet<String>>,
     cascade_events: Vec<CascadeEvent>,
+    rng: GameRng,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,12 +81,13 @@ impl ConsciousnessCascadeGame {
     pub fn new() -> Self {
         Self {
             round_number: 0,
-            nodes: HashMap::new(),
+            nodes: BTreeMap::new(),
             global_consciousness: 0.0,
             cascade_threshold: 0.7,
             thought_pool: Vec::new(),
             resonance_clusters: Vec::new(),
             cascade_events: Vec::new(),
+            rng: game_rng(None),
         }
     }
     
@@ -92,9 +96,9 @@ impl ConsciousnessCascadeGame {
             let node = ConsciousnessNode {
                 player_id: player_id.clone(),
                 consciousness_level: 0.5,
-                connections: HashMap::new(),
+                connections: BTreeMap::new(),
                 thoughts: Vec::new(),
-                resonance_frequency: rand::random::<f32>() * 10.0,
+                resonance_frequency: self.rng.random::<f32>() * 10.0,
                 cascade_potential: 0.3,
             };
             
@@ -102,10 +106,11 @@ impl ConsciousnessCascadeGame {
         }
         
         // Initialize some random connections
-        let player_list: Vec<_> = self.nodes.keys().cloned().collect();
+        let mut player_list: Vec<_> = self.nodes.keys().cloned().collect();
+        player_list.sort();
         for i in 0..player_list.len() {
             for j in i+1..player_list.len() {
-                if rand::random::<f32>() < 0.3 {
+                if self.rng.random::<f32>() < 0.3 {
                     self.create_connection(&player_list[i], &player_list[j], 0.5);
                 }
             }
@@ -137,7 +142,7 @@ impl ConsciousnessCascadeGame {
         &mut self,
         origin: &str,
         thought: Thought,
-        visited: &mut HashSet<String>
+        visited: &mut BTreeSet<String>
     ) -> Vec<GameEvent> {
         let mut events = Vec::new();
         visited.insert(origin.to_string());
@@ -150,13 +155,14 @@ impl ConsciousnessCascadeGame {
             node.consciousness_level = (node.consciousness_level + thought.intensity * 0.1).min(1.0);
             
             // Propagate to connected nodes
-            let connections: Vec<_> = node.connections.iter()
+            let mut connections: Vec<_> = node.connections.iter()
                 .filter(|(target, _)| !visited.contains(*target))
                 .map(|(t, c)| (t.clone(), c.clone()))
                 .collect();
+            connections.sort_by(|a, b| a.0.cmp(&b.0));
                 
             for (target, connection) in connections {
-                if connection.flow_rate > rand::random::<f32>() {
+                if connection.flow_rate > self.rng.random::<f32>() {
                     // Thought mutates as it propagates
                     let mut propagated_thought = thought.clone();
                     propagated_thought.intensity *= connection.strength;
@@ -183,6 +189,7 @@ impl ConsciousnessCascadeGame {
     fn detect_cascade(&mut self) -> Option<Vec<GameEvent>> {
         let mut events = Vec::new();
         let mut cascade_detected = false;
+        let mut boosted = Vec::new();
         
         // Check each node for cascade potential
         for (player_id, node) in &self.nodes {
@@ -202,25 +209,26 @@ impl ConsciousnessCascadeGame {
                 self.cascade_events.push(cascade_event.clone());
                 
                 events.push(GameEvent {
-                    event_type: "consciousness_cascade".to_string(),
-                    description: format!("{} triggered a consciousness cascade affecting {} nodes!", 
-                        player_id, cascade_size),
-                    affected_players: cascade_event.affected_players.clone(),
-                    data: serde_json::json!({
+                    kind: EventKind::custom("consciousness_cascade", serde_json::json!({
                         "origin": player_id,
                         "cascade_size": cascade_size,
                         "intensity": node.consciousness_level,
-                    }),
+                    })),
+                    description: format!("{} triggered a consciousness cascade affecting {} nodes!", 
+                        player_id, cascade_size),
+                    affected_players: cascade_event.affected_players.clone(),
                 });
                 
-                // Boost connected nodes
-                for connected_id in node.connections.keys() {
-                    if let Some(connected_node) = self.nodes.get_mut(connected_id) {
-                        connected_node.consciousness_level = 
-                            (connected_node.consciousness_level * 1.5).min(1.0);
-                        connected_node.cascade_potential += 0.1;
-                    }
-                }
+                boosted.extend(node.connections.keys().cloned());
+            }
+        }
+        
+        // Boost connected nodes
+        for connected_id in boosted {
+            if let Some(connected_node) = self.nodes.get_mut(&connected_id) {
+                connected_node.consciousness_level = 
+                    (connected_node.consciousness_level * 1.5).min(1.0);
+                connected_node.cascade_potential += 0.1;
             }
         }
         
@@ -237,16 +245,17 @@ impl ConsciousnessCascadeGame {
             .map(|n| n.consciousness_level)
             .sum();
             
-        self.global_consciousness = total_consciousness / self.nodes.len() as f32;
+        // An empty network has no consciousness rather than NaN, which would not survive a snapshot
+        self.global_consciousness = total_consciousness / self.nodes.len().max(1) as f32;
     }
     
     fn find_resonance_clusters(&mut self) {
         self.resonance_clusters.clear();
-        let mut visited = HashSet::new();
+        let mut visited = BTreeSet::new();
         
         for player_id in self.nodes.keys() {
             if !visited.contains(player_id) {
-                let mut cluster = HashSet::new();
+                let mut cluster = BTreeSet::new();
                 self.explore_resonance_cluster(player_id, &mut cluster, &mut visited, 0.5);
                 
                 if cluster.len() > 1 {
@@ -259,8 +268,8 @@ impl ConsciousnessCascadeGame {
     fn explore_resonance_cluster(
         &self,
         player_id: &str,
-        cluster: &mut HashSet<String>,
-        visited: &mut HashSet<String>,
+        cluster: &mut BTreeSet<String>,
+        visited: &mut BTreeSet<String>,
         min_resonance: f32
     ) {
         visited.insert(player_id.to_string());
@@ -278,10 +287,6 @@ impl ConsciousnessCascadeGame {
     fn detect_global_emergence(&self) -> Option<EmergenceEvent> {
         // Check for global consciousness emergence
         if self.global_consciousness > 0.8 && self.cascade_events.len() > 3 {
-            let total_cascade_size: usize = self.cascade_events.iter()
-                .map(|e| e.cascade_size)
-                .sum();
-                
             Some(EmergenceEvent {
                 round: self.round_number,
                 event_type: EmergenceType::Custom("GlobalConsciousness".to_string()),
@@ -312,6 +317,7 @@ impl Default for ConsciousnessCascadeGame {
 #[async_trait]
 impl Game for ConsciousnessCascadeGame {
     async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        self.rng = game_rng(config.seed);
         let mut state = GameState::new(config.game_type.clone());
         
         let player_ids: Vec<String> = config.initial_players.iter()
@@ -344,34 +350,37 @@ impl Game for ConsciousnessCascadeGame {
         let mut scores_delta = HashMap::new();
         let mut events = Vec::new();
         
-        // Process player actions
-        for (player_id, action) in &actions {
+        // Process player actions in a stable order
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        
+        for player_id in player_ids {
+            let action = &actions[player_id];
             if let Ok(cascade_action) = serde_json::from_value::<CascadeAction>(action.data.clone()) {
                 match cascade_action.action_type {
                     CascadeActionType::SendThought => {
                         if let Some(content) = cascade_action.thought_content {
                             let thought = Thought {
-                                content,
+                                content: content.clone(),
                                 intensity: 0.7,
                                 propagation_count: 0,
                                 origin_player: player_id.clone(),
                                 mutations: Vec::new(),
                             };
                             
-                            let mut visited = HashSet::new();
+                            let mut visited = BTreeSet::new();
                             let mut prop_events = self.propagate_thought(player_id, thought, &mut visited);
                             events.append(&mut prop_events);
                             
                             scores_delta.insert(player_id.clone(), visited.len() as i32 * 5);
                             
                             events.push(GameEvent {
-                                event_type: "thought_sent".to_string(),
-                                description: format!("{} sent a thought through the network", player_id),
-                                affected_players: visited.into_iter().collect(),
-                                data: serde_json::json!({
+                                kind: EventKind::custom("thought_sent", serde_json::json!({
                                     "origin": player_id,
                                     "content": content,
-                                }),
+                                })),
+                                description: format!("{} sent a thought through the network", player_id),
+                                affected_players: visited.into_iter().collect(),
                             });
                         }
                     }
@@ -445,12 +454,8 @@ impl Game for ConsciousnessCascadeGame {
             
             // Bonus points for cascade participants
             for event in &cascade_events {
-                if let Some(players) = event.data.get("affected_players").and_then(|v| v.as_array()) {
-                    for player in players {
-                        if let Some(player_id) = player.as_str() {
-                            *scores_delta.entry(player_id.to_string()).or_insert(0) += 20;
-                        }
-                    }
+                for player_id in &event.affected_players {
+                    *scores_delta.entry(player_id.clone()).or_insert(0) += 20;
                 }
             }
         }
@@ -474,15 +479,7 @@ impl Game for ConsciousnessCascadeGame {
         let mut emergence_detected = false;
         if let Some(emergence) = self.detect_global_emergence() {
             emergence_detected = true;
-            events.push(GameEvent {
-                event_type: "emergence".to_string(),
-                description: emergence.description.clone(),
-                affected_players: emergence.involved_players.clone(),
-                data: serde_json::json!({
-                    "emergence_type": "global_consciousness",
-                    "score": self.global_consciousness,
-                }),
-            });
+            events.push(GameEvent::emergence(&emergence));
             
             // Massive bonus for achieving global consciousness
             for player_id in self.nodes.keys() {
@@ -490,15 +487,20 @@ impl Game for ConsciousnessCascadeGame {
             }
         }
         
+        let mut winners: Vec<String> = scores_delta.iter()
+            .filter(|(_, &score)| score > 10)
+            .map(|(player, _)| player.clone())
+            .collect();
+        let mut losers: Vec<String> = scores_delta.iter()
+            .filter(|(_, &score)| score < 0)
+            .map(|(player, _)| player.clone())
+            .collect();
+        winners.sort();
+        losers.sort();
+        
         let outcome = RoundOutcome {
-            winners: scores_delta.iter()
-                .filter(|(_, &score)| score > 10)
-                .map(|(player, _)| player.clone())
-                .collect(),
-            losers: scores_delta.iter()
-                .filter(|(_, &score)| score < 0)
-                .map(|(player, _)| player.clone())
-                .collect(),
+            winners,
+            losers,
             special_events: events.iter().map(|e| e.description.clone()).collect(),
             emergence_detected,
         };
@@ -509,6 +511,7 @@ impl Game for ConsciousnessCascadeGame {
             outcome,
             scores_delta,
             events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
@@ -518,10 +521,7 @@ impl Game for ConsciousnessCascadeGame {
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        let winner = state.scores.iter()
-            .max_by_key(|(_, &score)| score)
-            .map(|(player, _)| player.clone())
-            .unwrap_or_default();
+        let standings = Standings::new(&state.scores);
             
         let total_thoughts = self.nodes.values()
             .map(|n| n.thoughts.len())
@@ -533,7 +533,10 @@ impl Game for ConsciousnessCascadeGame {
             
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: state.scores.clone(),
             total_rounds: state.round,
             duration_ms: 0,
@@ -557,15 +560,59 @@ impl Game for ConsciousnessCascadeGame {
         }
     }
     
-    async fn get_valid_actions(&self, _state: &GameState, _player_id: &str) -> Vec<String> {
-        vec![
-            "send_thought".to_string(),
-            "open_channel".to_string(),
-            "resonate".to_string(),
-            "amplify".to_string(),
-            "block".to_string(),
-            "merge".to_string(),
-        ]
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Consciousness Cascade")
+            .action("send_thought", "Send a thought to propagate through your connections", ActionSchema::tagged_data(
+                "SendThought",
+                serde_json::json!({ "thought_content": { "type": "string" } }),
+                &["thought_content"],
+            ))
+            .action("open_channel", "Open connections to other players", ActionSchema::tagged_data(
+                "OpenChannel",
+                serde_json::json!({ "target_players": { "type": "array", "items": { "type": "string" } } }),
+                &["target_players"],
+            ))
+            .action("resonate", "Shift your resonance frequency (clamped to 0-10)", ActionSchema::tagged_data(
+                "Resonate",
+                serde_json::json!({ "frequency_adjustment": { "type": "number" } }),
+                &["frequency_adjustment"],
+            ))
+            .action("amplify", "Amplify the thoughts you hold", ActionSchema::tagged_data("Amplify", serde_json::json!({}), &[]))
+            .action("block", "Halve the flow of your connections", ActionSchema::tagged_data("Block", serde_json::json!({}), &[]))
+            .action("merge", "Merge consciousness with your connected nodes", ActionSchema::tagged_data("Merge", serde_json::json!({}), &[]))
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        let mut actions = vec![
+            LegalAction::new("send_thought", serde_json::json!({
+                "action_type": "SendThought",
+                "thought_content": format!("Thought from {}", player_id),
+            })),
+            LegalAction::new("resonate", serde_json::json!({ "action_type": "Resonate", "frequency_adjustment": 0.5 })),
+            LegalAction::new("resonate", serde_json::json!({ "action_type": "Resonate", "frequency_adjustment": -0.5 })),
+            LegalAction::new("amplify", serde_json::json!({ "action_type": "Amplify" })),
+            LegalAction::new("block", serde_json::json!({ "action_type": "Block" })),
+        ];
+        
+        let node = self.nodes.get(player_id);
+        let mut unconnected: Vec<&String> = self.nodes.keys()
+            .filter(|id| id.as_str() != player_id)
+            .filter(|id| node.is_none_or(|n| !n.connections.contains_key(*id)))
+            .collect();
+        unconnected.sort();
+        for target in unconnected {
+            actions.push(LegalAction::new("open_channel", serde_json::json!({
+                "action_type": "OpenChannel",
+                "target_players": [target],
+            })));
+        }
+        
+        if node.is_some_and(|n| !n.connections.is_empty()) {
+            actions.push(LegalAction::new("merge", serde_json::json!({ "action_type": "Merge" })));
+        }
+        
+        actions
     }
     
     async fn get_visualization_data(&self, _state: &GameState) -> serde_json::Value {
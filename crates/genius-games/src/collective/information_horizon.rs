@@ -1,13 +1,13 @@
 //! Information Horizon - Knowledge boundaries and emergence from partial information
 
 use genius_core::{
-    Game, GameConfig, GameState, GameType, PlayerAction, RoundResult,
-    RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType,
-    Result, GameError,
+    game_rng, GameRng, Game, GameConfig, GameState, PlayerAction, RoundResult,
+    RoundOutcome, GameResult, GameAnalytics, GameEvent, EventKind, EmergenceEvent, EmergenceType,
+    Result, ActionSchema, LegalAction, Standings,
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use rand::Rng;
 
 /// Information fragment that decays/transforms when shared
@@ -34,10 +34,10 @@ pub enum InfoCategory {
 /// Player's information state
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InfoAgent {
-    pub known_fragments: HashMap<String, InfoFragment>,
+    pub known_fragments: BTreeMap<String, InfoFragment>,
     pub bandwidth: u32, // How many fragments can share per round
     pub processing_power: f32, // Ability to reconstruct degraded info
-    pub trust_network: HashMap<String, f32>, // Trust in other players
+    pub trust_network: BTreeMap<String, f32>, // Trust in other players
     pub reconstruction_attempts: u32,
 }
 
@@ -59,28 +59,31 @@ pub enum InfoActionType {
     TrustUpdate,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct InformationHorizonGame {
     round_number: u32,
-    info_agents: HashMap<String, InfoAgent>,
-    global_fragments: HashMap<String, InfoFragment>,
+    info_agents: BTreeMap<String, InfoAgent>,
+    global_fragments: BTreeMap<String, InfoFragment>,
     hidden_pattern: String,
     decay_rate: f32,
     noise_rate: f32,
     emergence_threshold: f32,
     collective_knowledge: f32,
+    rng: GameRng,
 }
 
 impl InformationHorizonGame {
     pub fn new() -> Self {
         Self {
             round_number: 0,
-            info_agents: HashMap::new(),
-            global_fragments: HashMap::new(),
+            info_agents: BTreeMap::new(),
+            global_fragments: BTreeMap::new(),
             hidden_pattern: Self::generate_hidden_pattern(),
             decay_rate: 0.1,
             noise_rate: 0.05,
             emergence_threshold: 0.8,
             collective_knowledge: 0.0,
+            rng: game_rng(None),
         }
     }
     
@@ -96,16 +99,16 @@ impl InformationHorizonGame {
         // Distribute fragments of the pattern to different players
         for (idx, player_id) in player_ids.iter().enumerate() {
             let mut agent = InfoAgent {
-                known_fragments: HashMap::new(),
+                known_fragments: BTreeMap::new(),
                 bandwidth: 3,
                 processing_power: 0.7,
-                trust_network: HashMap::new(),
+                trust_network: BTreeMap::new(),
                 reconstruction_attempts: 0,
             };
             
             // Initialize trust network (start with moderate trust)
             for other_id in &player_ids {
-                if other_id != &player_id {
+                if other_id != player_id {
                     agent.trust_network.insert(other_id.clone(), 0.5);
                 }
             }
@@ -152,23 +155,22 @@ impl InformationHorizonGame {
         fragment_id: &str
     ) -> Option<GameEvent> {
         let sender = self.info_agents.get(sender_id)?;
-        let fragment = sender.known_fragments.get(fragment_id)?;
+        let trust = sender.trust_network.get(receiver_id).copied().unwrap_or(0.5);
         
         // Create degraded copy
-        let mut shared_fragment = fragment.clone();
+        let mut shared_fragment = sender.known_fragments.get(fragment_id)?.clone();
         shared_fragment.fidelity *= 1.0 - self.decay_rate;
         shared_fragment.shared_count += 1;
         shared_fragment.age += 1;
         
         // Add noise based on trust
-        let trust = sender.trust_network.get(receiver_id).unwrap_or(&0.5);
-        if *trust < 0.7 {
+        if trust < 0.7 {
             shared_fragment.fidelity *= 1.0 - self.noise_rate;
         }
         
         // Corrupt content if fidelity is too low
         if shared_fragment.fidelity < 0.5 {
-            shared_fragment.content = Self::corrupt_content(&shared_fragment.content, shared_fragment.fidelity);
+            shared_fragment.content = Self::corrupt_content(&mut self.rng, &shared_fragment.content, shared_fragment.fidelity);
         }
         
         // Give to receiver
@@ -177,20 +179,18 @@ impl InformationHorizonGame {
         }
         
         Some(GameEvent {
-            event_type: "info_shared".to_string(),
-            description: format!("{} shared info with {} (fidelity: {:.2})", 
-                sender_id, receiver_id, shared_fragment.fidelity),
-            affected_players: vec![sender_id.to_string(), receiver_id.to_string()],
-            data: serde_json::json!({
+            kind: EventKind::custom("info_shared", serde_json::json!({
                 "fragment_id": fragment_id,
                 "fidelity": shared_fragment.fidelity,
                 "trust_level": trust,
-            }),
+            })),
+            description: format!("{} shared info with {} (fidelity: {:.2})", 
+                sender_id, receiver_id, shared_fragment.fidelity),
+            affected_players: vec![sender_id.to_string(), receiver_id.to_string()],
         })
     }
     
-    fn corrupt_content(content: &str, fidelity: f32) -> String {
-        let mut rng = rand::rng();
+    fn corrupt_content(rng: &mut GameRng, content: &str, fidelity: f32) -> String {
         let corruption_chance = 1.0 - fidelity;
         
         content.chars().map(|c| {
@@ -245,23 +245,21 @@ impl InformationHorizonGame {
             let similarity = Self::calculate_pattern_similarity(&reconstructed, &self.hidden_pattern);
             
             Some(GameEvent {
-                event_type: "reconstruction_attempt".to_string(),
+                kind: EventKind::custom("reconstruction_attempt", serde_json::json!({
+                    "accuracy": similarity,
+                    "fragments_used": fragment_ids.len(),
+                })),
                 description: format!("{} reconstructed pattern with {:.0}% accuracy", 
                     player_id, similarity * 100.0),
                 affected_players: vec![player_id.to_string()],
-                data: serde_json::json!({
-                    "accuracy": similarity,
-                    "fragments_used": fragment_ids.len(),
-                }),
             })
         } else {
             Some(GameEvent {
-                event_type: "reconstruction_failed".to_string(),
+                kind: EventKind::custom("reconstruction_failed", serde_json::json!({
+                    "success_rate": reconstruction_success,
+                })),
                 description: format!("{} failed to reconstruct pattern", player_id),
                 affected_players: vec![player_id.to_string()],
-                data: serde_json::json!({
-                    "success_rate": reconstruction_success,
-                }),
             })
         }
     }
@@ -325,6 +323,7 @@ impl Default for InformationHorizonGame {
 #[async_trait]
 impl Game for InformationHorizonGame {
     async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        self.rng = game_rng(config.seed);
         let mut state = GameState::new(config.game_type.clone());
         
         // Initialize information distribution
@@ -364,8 +363,12 @@ impl Game for InformationHorizonGame {
             fragment.fidelity *= 0.98; // Natural decay
         }
         
-        // Process player actions
-        for (player_id, action) in &actions {
+        // Process player actions in a stable order
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        
+        for player_id in player_ids {
+            let action = &actions[player_id];
             if let Ok(info_action) = serde_json::from_value::<InfoAction>(action.data.clone()) {
                 match info_action.action_type {
                     InfoActionType::Share => {
@@ -389,7 +392,7 @@ impl Game for InformationHorizonGame {
                     InfoActionType::Reconstruct => {
                         if let Some(fragment_ids) = info_action.reconstruct {
                             if let Some(event) = self.attempt_reconstruction(player_id, &fragment_ids) {
-                                let is_success = event.event_type == "reconstruction_attempt";
+                                let is_success = event.event_type() == "reconstruction_attempt";
                                 events.push(event);
                                 
                                 if is_success {
@@ -441,13 +444,12 @@ impl Game for InformationHorizonGame {
                             scores_delta.insert(player_id.clone(), (analysis_score * 10.0) as i32);
                             
                             events.push(GameEvent {
-                                event_type: "analysis".to_string(),
-                                description: format!("{} analyzed their information", player_id),
-                                affected_players: vec![player_id.clone()],
-                                data: serde_json::json!({
+                                kind: EventKind::custom("analysis", serde_json::json!({
                                     "fragment_count": agent.known_fragments.len(),
                                     "avg_fidelity": analysis_score,
-                                }),
+                                })),
+                                description: format!("{} analyzed their information", player_id),
+                                affected_players: vec![player_id.clone()],
                             });
                         }
                     }
@@ -474,14 +476,7 @@ impl Game for InformationHorizonGame {
         let mut emergence_detected = false;
         if let Some(emergence) = self.detect_collective_emergence() {
             emergence_detected = true;
-            events.push(GameEvent {
-                event_type: "emergence".to_string(),
-                description: emergence.description.clone(),
-                affected_players: emergence.involved_players.clone(),
-                data: serde_json::json!({
-                    "knowledge_level": self.collective_knowledge,
-                }),
-            });
+            events.push(GameEvent::emergence(&emergence));
             
             // Bonus for all players
             for player_id in self.info_agents.keys() {
@@ -489,15 +484,20 @@ impl Game for InformationHorizonGame {
             }
         }
         
+        let mut winners: Vec<String> = scores_delta.iter()
+            .filter(|(_, &score)| score > 10)
+            .map(|(player, _)| player.clone())
+            .collect();
+        let mut losers: Vec<String> = scores_delta.iter()
+            .filter(|(_, &score)| score < 0)
+            .map(|(player, _)| player.clone())
+            .collect();
+        winners.sort();
+        losers.sort();
+        
         let outcome = RoundOutcome {
-            winners: scores_delta.iter()
-                .filter(|(_, &score)| score > 10)
-                .map(|(player, _)| player.clone())
-                .collect(),
-            losers: scores_delta.iter()
-                .filter(|(_, &score)| score < 0)
-                .map(|(player, _)| player.clone())
-                .collect(),
+            winners,
+            losers,
             special_events: events.iter().map(|e| e.description.clone()).collect(),
             emergence_detected,
         };
@@ -508,6 +508,7 @@ impl Game for InformationHorizonGame {
             outcome,
             scores_delta,
             events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
@@ -533,14 +534,14 @@ impl Game for InformationHorizonGame {
             );
         }
         
-        let winner = contribution_scores.iter()
-            .max_by_key(|(_, &score)| score)
-            .map(|(player, _)| player.clone())
-            .unwrap_or_default();
+        let standings = Standings::new(&contribution_scores);
             
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: state.scores.clone(),
             total_rounds: state.round,
             duration_ms: 0,
@@ -564,14 +565,77 @@ impl Game for InformationHorizonGame {
         }
     }
     
-    async fn get_valid_actions(&self, _state: &GameState, _player_id: &str) -> Vec<String> {
-        vec![
-            "share".to_string(),
-            "reconstruct".to_string(),
-            "combine".to_string(),
-            "analyze".to_string(),
-            "update_trust".to_string(),
-        ]
+    fn action_schema(&self) -> serde_json::Value {
+        let fragment_ids = serde_json::json!({ "type": "array", "items": { "type": "string" } });
+        
+        ActionSchema::new("Information Horizon")
+            .action("share", "Send fragments you know to other players", ActionSchema::tagged_data(
+                "Share",
+                serde_json::json!({
+                    "share_with": {
+                        "type": "array",
+                        "items": {
+                            "type": "array",
+                            "prefixItems": [{ "type": "string" }, { "type": "string" }],
+                            "description": "[receiver_id, fragment_id]"
+                        }
+                    }
+                }),
+                &["share_with"],
+            ))
+            .action("reconstruct", "Try to rebuild the hidden pattern from known fragments", ActionSchema::tagged_data(
+                "Reconstruct",
+                serde_json::json!({ "reconstruct": fragment_ids }),
+                &["reconstruct"],
+            ))
+            .action("combine", "Merge two or more known fragments into a new one", ActionSchema::tagged_data(
+                "Combine",
+                serde_json::json!({ "combine": { "type": "array", "items": { "type": "string" }, "minItems": 2 } }),
+                &["combine"],
+            ))
+            .action("analyze", "Assess the fidelity of what you know", ActionSchema::tagged_data("Analyze", serde_json::json!({}), &[]))
+            .action("update_trust", "Raise trust in every other player", ActionSchema::tagged_data("TrustUpdate", serde_json::json!({}), &[]))
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        let mut actions = vec![
+            LegalAction::new("analyze", serde_json::json!({ "action_type": "Analyze" })),
+            LegalAction::new("update_trust", serde_json::json!({ "action_type": "TrustUpdate" })),
+        ];
+        
+        let Some(agent) = self.info_agents.get(player_id) else {
+            return actions;
+        };
+        
+        let mut fragment_ids: Vec<&String> = agent.known_fragments.keys().collect();
+        fragment_ids.sort();
+        let mut receivers: Vec<&String> = agent.trust_network.keys().collect();
+        receivers.sort();
+        
+        for receiver in &receivers {
+            for fragment_id in &fragment_ids {
+                actions.push(LegalAction::new("share", serde_json::json!({
+                    "action_type": "Share",
+                    "share_with": [[receiver, fragment_id]],
+                })));
+            }
+        }
+        
+        if !fragment_ids.is_empty() {
+            actions.push(LegalAction::new("reconstruct", serde_json::json!({
+                "action_type": "Reconstruct",
+                "reconstruct": fragment_ids,
+            })));
+        }
+        if fragment_ids.len() >= 2 {
+            actions.push(LegalAction::new("combine", serde_json::json!({
+                "action_type": "Combine",
+                "combine": fragment_ids,
+            })));
+        }
+        
+        actions
     }
     
     async fn get_visualization_data(&self, _state: &GameState) -> serde_json::Value {
@@ -2,12 +2,12 @@
 
 use genius_core::{
     Game, GameConfig, GameState, GameResult, PlayerAction, RoundResult,
-    RoundOutcome, GameEvent, GameAnalytics, EmergenceEvent, EmergenceType,
-    Result, GameError,
+    RoundOutcome, GameEvent, EventKind, GameAnalytics, EmergenceEvent, EmergenceType,
+    Result, ActionSchema, LegalAction, Standings,
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// The depth of recursive thinking
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -36,22 +36,28 @@ pub struct MentalModel {
     model_complexity: f32,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct MirrorMindGame {
     round_number: u32,
-    player_models: HashMap<String, MentalModel>,
+    player_models: BTreeMap<String, MentalModel>,
+    #[serde(with = "genius_core::tuple_keys")]
     prediction_matrix: HashMap<(String, String), Vec<Prediction>>,
     emergence_threshold: f32,
     max_thinking_depth: u32,
+    /// Consecutive rounds the mental models have stayed converged
+    #[serde(default)]
+    converged_rounds: u32,
 }
 
 impl MirrorMindGame {
     pub fn new() -> Self {
         Self {
             round_number: 0,
-            player_models: HashMap::new(),
+            player_models: BTreeMap::new(),
             prediction_matrix: HashMap::new(),
             emergence_threshold: 0.75,
             max_thinking_depth: 4,
+            converged_rounds: 0,
         }
     }
     
@@ -90,12 +96,14 @@ impl MirrorMindGame {
         let avg_depth = thinking_depths.iter().sum::<f32>() / thinking_depths.len() as f32;
         
         if avg_depth > 3.0 {
+            let mut involved_players: Vec<String> = actions.keys().cloned().collect();
+            involved_players.sort();
             Some(EmergenceEvent {
                 round: self.round_number,
                 event_type: EmergenceType::Custom("RecursiveThinking".to_string()),
                 description: format!("Players achieving average thinking depth of {:.1} levels", avg_depth),
                 emergence_score: (avg_depth / self.max_thinking_depth as f32).min(1.0),
-                involved_players: actions.keys().cloned().collect(),
+                involved_players,
             })
         } else {
             None
@@ -104,7 +112,10 @@ impl MirrorMindGame {
     
     fn calculate_mental_model_convergence(&self) -> f32 {
         // Check if players' mental models are converging
-        let models: Vec<_> = self.player_models.values().collect();
+        // Only players who have predicted something have a model to compare
+        let models: Vec<_> = self.player_models.values()
+            .filter(|m| !m.accuracy_history.is_empty())
+            .collect();
         if models.len() < 2 {
             return 0.0;
         }
@@ -206,30 +217,30 @@ impl Game for MirrorMindGame {
         
         // Check for emergence
         if let Some(emergence) = self.detect_recursive_thinking(&actions) {
-            events.push(GameEvent {
-                event_type: "emergence".to_string(),
-                description: emergence.description.clone(),
-                affected_players: emergence.involved_players.clone(),
-                data: serde_json::json!({ "emergence_score": emergence.emergence_score }),
-            });
+            events.push(GameEvent::emergence(&emergence));
         }
         
         // Check for mental model convergence
         let convergence = self.calculate_mental_model_convergence();
+        if convergence > 0.95 {
+            self.converged_rounds += 1;
+        } else {
+            self.converged_rounds = 0;
+        }
         if convergence > self.emergence_threshold {
             events.push(GameEvent {
-                event_type: "convergence".to_string(),
+                kind: EventKind::custom("convergence", serde_json::json!({ "convergence_score": convergence })),
                 description: format!("Mental models converging at {:.0}% similarity", convergence * 100.0),
                 affected_players: state.players().into_iter().cloned().collect(),
-                data: serde_json::json!({ "convergence_score": convergence }),
             });
         }
         
         // Determine round outcome
-        let winners = scores_delta.iter()
+        let mut winners: Vec<String> = scores_delta.iter()
             .filter(|(_, &score)| score > 0)
             .map(|(player, _)| player.clone())
             .collect();
+        winners.sort();
             
         let outcome = RoundOutcome {
             winners,
@@ -244,20 +255,27 @@ impl Game for MirrorMindGame {
             outcome,
             scores_delta,
             events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
+    async fn join(&mut self, _state: &GameState, player_id: &str) {
+        self.player_models.entry(player_id.to_string()).or_insert_with(|| MentalModel {
+            predictions: HashMap::new(),
+            accuracy_history: Vec::new(),
+            model_complexity: 0.0,
+        });
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
-        // Game ends when maximum rounds reached or perfect convergence
-        state.round >= 50 || self.calculate_mental_model_convergence() > 0.95
+        // Game ends when maximum rounds reached or convergence holds for five rounds running,
+        // so one round of matching moves doesn't end it
+        state.round >= 50 || self.converged_rounds >= 5
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        let winner = state.scores.iter()
-            .max_by_key(|(_, &score)| score)
-            .map(|(player, _)| player.clone())
-            .unwrap_or_default();
+        let standings = Standings::new(&state.scores);
             
         let convergence = self.calculate_mental_model_convergence();
         let avg_thinking_depth = self.player_models.values()
@@ -266,7 +284,10 @@ impl Game for MirrorMindGame {
             
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: state.scores.clone(),
             total_rounds: state.round,
             duration_ms: 0, // Would be calculated from timestamps
@@ -287,12 +308,72 @@ impl Game for MirrorMindGame {
         }
     }
     
-    async fn get_valid_actions(&self, _state: &GameState, _player_id: &str) -> Vec<String> {
-        vec![
-            "predict_action".to_string(),
-            "predict_prediction".to_string(),
-            "model_theory_of_mind".to_string(),
-        ]
+    fn action_schema(&self) -> serde_json::Value {
+        let mirror_action = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "base_action": { "type": "string" },
+                "predictions": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "target_player": { "type": "string" },
+                            "predicted_action": { "type": "string", "description": "The action_type you expect the target to submit" },
+                            "thinking_level": { "enum": ["Level0", "Level1", "Level2", "Level3", "Level4"] },
+                            "confidence": { "type": "number", "minimum": 0.0, "maximum": 1.0 }
+                        },
+                        "required": ["target_player", "predicted_action", "thinking_level", "confidence"]
+                    }
+                },
+                "reasoning_chain": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["base_action", "predictions", "reasoning_chain"]
+        });
+        
+        ActionSchema::new("Mirror Mind")
+            .action("predict_action", "Predict what others will do", mirror_action.clone())
+            .action("predict_prediction", "Predict what others predict you will do", mirror_action.clone())
+            .action("model_theory_of_mind", "Reason several levels deep about other minds", mirror_action)
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        let mut others: Vec<&String> = state.players().into_iter()
+            .filter(|p| p.as_str() != player_id)
+            .collect();
+        others.sort();
+        
+        let levels = [
+            ("predict_action", ThinkingLevel::Level1, "I think you will"),
+            ("predict_prediction", ThinkingLevel::Level2, "I think you think I will"),
+            ("model_theory_of_mind", ThinkingLevel::Level3, "I think you think I think you will"),
+        ];
+        
+        (1..=levels.len())
+        .map(|depth| {
+            let (action_type, _, _) = levels[depth - 1];
+            // Reasoning at a depth means modelling everyone else at each level up to it,
+            // expecting them to reason at the same depth
+            let predictions: Vec<Prediction> = levels[..depth].iter()
+                .flat_map(|&(_, level, _)| others.iter().map(move |other| Prediction {
+                    target_player: other.to_string(),
+                    predicted_action: action_type.to_string(),
+                    thinking_level: level,
+                    confidence: 0.5,
+                }))
+                .collect();
+            let reasoning_chain: Vec<String> = levels[..depth].iter()
+                .map(|&(_, _, step)| format!("{} {}", step, action_type))
+                .collect();
+            
+            LegalAction::new(action_type, serde_json::json!({
+                "base_action": action_type,
+                "predictions": predictions,
+                "reasoning_chain": reasoning_chain,
+            }))
+        })
+        .collect()
     }
     
     async fn get_visualization_data(&self, _state: &GameState) -> serde_json::Value {
@@ -1,25 +1,29 @@
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, GameError};
+use genius_core::{game_rng, GameRng, Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, ActionSchema, LegalAction, ParamSchema, Standings};
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use uuid::Uuid;
 use rand::Rng;
 
+const DEFAULT_MEASUREMENT_THRESHOLD: f32 = 0.7;
+
+#[derive(Serialize, Deserialize)]
 pub struct QuantumConsensus {
     measurement_threshold: f32,
     entanglement_strength: f32,
     quantum_states: HashMap<String, QuantumState>,
     consensus_history: Vec<ConsensusResult>,
+    rng: GameRng,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct QuantumState {
     superposition: Vec<f32>, // Probability amplitudes for different choices
     entangled_with: Vec<String>, // Other players this player is entangled with
     coherence: f32, // How well the quantum state is maintained
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct ConsensusResult {
     #[allow(dead_code)]
     round: u32,
@@ -38,22 +42,21 @@ impl Default for QuantumConsensus {
 impl QuantumConsensus {
     pub fn new() -> Self {
         Self {
-            measurement_threshold: 0.7,
+            measurement_threshold: DEFAULT_MEASUREMENT_THRESHOLD,
             entanglement_strength: 0.5,
             quantum_states: HashMap::new(),
             consensus_history: Vec::new(),
+            rng: game_rng(None),
         }
     }
     
     fn initialize_quantum_state(&mut self, player_id: &str, num_choices: usize) {
-        let mut rng = rand::thread_rng();
-        
         // Initialize with random superposition
         let mut amplitudes = vec![];
         let mut sum_squares = 0.0;
         
         for _ in 0..num_choices {
-            let amp = rng.gen::<f32>();
+            let amp = self.rng.random::<f32>();
             sum_squares += amp * amp;
             amplitudes.push(amp);
         }
@@ -97,7 +100,7 @@ impl QuantumConsensus {
                 }
                 "phase" => {
                     // Random phase shift
-                    let phase = rand::thread_rng().gen::<f32>() * 2.0 * std::f32::consts::PI;
+                    let phase = self.rng.random::<f32>() * 2.0 * std::f32::consts::PI;
                     for (i, amp) in state.superposition.iter_mut().enumerate() {
                         *amp *= (phase * i as f32).cos();
                     }
@@ -116,10 +119,9 @@ impl QuantumConsensus {
         }
     }
     
-    fn measure_state(&self, player_id: &str) -> usize {
+    fn measure_state(&mut self, player_id: &str) -> usize {
         if let Some(state) = self.quantum_states.get(player_id) {
-            let mut rng = rand::thread_rng();
-            let r = rng.gen::<f32>();
+            let r = self.rng.random::<f32>();
             
             let mut cumulative = 0.0;
             for (i, &amp) in state.superposition.iter().enumerate() {
@@ -186,9 +188,10 @@ impl QuantumConsensus {
         if recent_consensus >= 4 && avg_correlation > 0.7 {
             Some(EmergenceEvent {
                 round: state.round,
-                event_type: "quantum_consensus".to_string(),
+                event_type: EmergenceType::Custom("quantum_consensus".to_string()),
                 description: "Collective achieved quantum entanglement consensus".to_string(),
                 emergence_score: avg_correlation,
+                involved_players: state.active_players().into_iter().cloned().collect(),
             })
         } else {
             None
@@ -199,18 +202,20 @@ impl QuantumConsensus {
 #[async_trait]
 impl Game for QuantumConsensus {
     async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
-        // Extract parameters from config
-        if let Some(threshold) = config.special_rules.get("measurement_threshold") {
-            if let Ok(t) = threshold.parse::<f32>() {
-                self.measurement_threshold = t;
-            }
-        }
+        self.rng = game_rng(config.seed);
+        
+        let params = self.parameters().validate(&config.params)?;
+        self.measurement_threshold = params.float("measurement_threshold") as f32;
         
         Ok(GameState {
             game_id: Uuid::new_v4(),
             game_type: GameType::QuantumConsensus,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
@@ -223,21 +228,28 @@ impl Game for QuantumConsensus {
                 }));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
     
     async fn process_round(&mut self, state: &GameState, actions: HashMap<String, PlayerAction>) -> Result<RoundResult> {
         let num_choices = 3; // Default to 3 quantum states
         
+        // Quantum operations draw from the RNG, so always visit players in a stable order
+        let mut player_ids: Vec<String> = actions.keys().cloned().collect();
+        player_ids.sort();
+        
         // Initialize quantum states for new players
-        for player_id in actions.keys() {
+        for player_id in &player_ids {
             if !self.quantum_states.contains_key(player_id) {
                 self.initialize_quantum_state(player_id, num_choices);
             }
         }
         
         // Process quantum operations from actions
-        for (player_id, action) in &actions {
+        for player_id in &player_ids {
+            let action = &actions[player_id];
             match action.action_type.as_str() {
                 "hadamard" => self.apply_quantum_gate(player_id, "hadamard"),
                 "phase" => self.apply_quantum_gate(player_id, "phase"),
@@ -249,7 +261,7 @@ impl Game for QuantumConsensus {
                 }
                 _ => {
                     // Random quantum operation
-                    if rand::thread_rng().gen_bool(0.5) {
+                    if self.rng.random_bool(0.5) {
                         self.apply_quantum_gate(player_id, "hadamard");
                     }
                 }
@@ -265,14 +277,14 @@ impl Game for QuantumConsensus {
         }
         
         // Create entanglements between collective players
-        let collective_players: Vec<String> = actions.keys()
+        let collective_players: Vec<String> = player_ids.iter()
             .filter(|id| id.starts_with("collective_"))
             .cloned()
             .collect();
         
         for i in 0..collective_players.len() {
             for j in i+1..collective_players.len() {
-                if rand::thread_rng().gen::<f32>() < self.entanglement_strength {
+                if self.rng.random::<f32>() < self.entanglement_strength {
                     self.entangle_players(&collective_players[i], &collective_players[j]);
                 }
             }
@@ -282,7 +294,7 @@ impl Game for QuantumConsensus {
         let mut measurements = HashMap::new();
         let mut choice_counts = vec![0; num_choices];
         
-        for player_id in actions.keys() {
+        for player_id in &player_ids {
             let measurement = self.measure_state(player_id);
             measurements.insert(player_id.clone(), measurement);
             if measurement < choice_counts.len() {
@@ -337,15 +349,17 @@ impl Game for QuantumConsensus {
         let emergence_event = self.detect_quantum_emergence(state);
         
         // Determine winners and losers
-        let winners: Vec<String> = measurements.iter()
+        let mut winners: Vec<String> = measurements.iter()
             .filter(|(_, &m)| consensus_achieved && m == consensus_choice)
             .map(|(id, _)| id.clone())
             .collect();
         
-        let losers: Vec<String> = measurements.iter()
+        let mut losers: Vec<String> = measurements.iter()
             .filter(|(_, &m)| consensus_achieved && m != consensus_choice)
             .map(|(id, _)| id.clone())
             .collect();
+        winners.sort();
+        losers.sort();
         
         let mut special_events = vec![];
         if consensus_achieved {
@@ -368,10 +382,44 @@ impl Game for QuantumConsensus {
                 emergence_detected: emergence_event.is_some(),
             },
             scores_delta,
+            events: emergence_event.iter()
+                .map(GameEvent::emergence)
+                .collect(),
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
+    fn parameters(&self) -> ParamSchema {
+        ParamSchema::new()
+            .float("measurement_threshold", "Fraction of players who must agree for consensus to be reached", DEFAULT_MEASUREMENT_THRESHOLD as f64, 0.0..=1.0)
+    }
+    
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Quantum Consensus")
+            .simple_action("hadamard", "Apply a Hadamard gate to your superposition")
+            .simple_action("phase", "Apply a phase gate to your superposition")
+            .action("entangle", "Entangle your state with another player", serde_json::json!({
+                "type": "string",
+                "description": "Player id to entangle with"
+            }))
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        let mut actions = vec![LegalAction::simple("hadamard"), LegalAction::simple("phase")];
+        
+        let entangled = self.quantum_states.get(player_id).map(|s| &s.entangled_with);
+        let mut targets: Vec<&String> = state.players().into_iter()
+            .filter(|p| p.as_str() != player_id)
+            .filter(|p| entangled.is_none_or(|e| !e.contains(p)))
+            .collect();
+        targets.sort();
+        
+        actions.extend(targets.into_iter().map(|t| LegalAction::new("entangle", serde_json::json!(t))));
+        actions
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         state.round >= 50 || 
         state.scores.values().any(|&score| score >= 200 || score <= -50) ||
@@ -380,27 +428,8 @@ impl Game for QuantumConsensus {
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        let winner = state.scores.iter()
-            .max_by_key(|(_, score)| *score)
-            .map(|(id, _)| id.clone())
-            .unwrap_or_else(|| "No winner".to_string());
+        let standings = Standings::new(&state.scores);
         
-        // Collect emergence events
-        let emergence_events: Vec<EmergenceEvent> = state.history.iter()
-            .enumerate()
-            .filter_map(|(i, round_result)| {
-                if round_result.outcome.emergence_detected {
-                    Some(EmergenceEvent {
-                        round: i as u32,
-                        event_type: "quantum_consensus".to_string(),
-                        description: "Quantum entanglement consensus achieved".to_string(),
-                        emergence_score: 0.9,
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect();
         
         // Calculate analytics
         let total_consensus = self.consensus_history.iter()
@@ -425,20 +454,27 @@ impl Game for QuantumConsensus {
         let avg_collective = collective_scores.iter().sum::<i32>() as f32 / collective_scores.len().max(1) as f32;
         let avg_single = single_scores.iter().sum::<i32>() as f32 / single_scores.len().max(1) as f32;
         
-        let emergence_frequency = emergence_events.len() as f32 / state.round.max(1) as f32;
+        let emergence_frequency = state.history.iter()
+            .filter(|round_result| round_result.outcome.emergence_detected)
+            .count() as f32 / state.round.max(1) as f32;
         
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: state.scores.clone(),
             total_rounds: state.round,
-            emergence_events,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
+            emergence_events: vec![],
             analytics: GameAnalytics {
                 collective_coordination_score: consensus_rate,
                 decision_diversity_index: 1.0 - avg_correlation, // Diversity vs correlation
                 strategic_depth: avg_correlation,
                 emergence_frequency,
                 performance_differential: avg_collective - avg_single,
+                custom_metrics: HashMap::new(),
             },
         }
     }
@@ -2,12 +2,12 @@
 
 use genius_core::{
     Game, GameConfig, GameState, GameResult, PlayerAction, RoundResult,
-    RoundOutcome, GameEvent, GameAnalytics, EmergenceEvent, EmergenceType,
-    Result, GameError,
+    RoundOutcome, GameEvent, EventKind, GameAnalytics, EmergenceEvent, EmergenceType,
+    Result, ActionSchema, LegalAction, Standings,
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// The state of a reality fragment
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -25,7 +25,7 @@ pub struct RealityFragment {
     id: String,
     description: String,
     current_state: RealityState,
-    belief_votes: HashMap<String, bool>,
+    belief_votes: BTreeMap<String, bool>,
     stability: f32,
     glitch_probability: f32,
 }
@@ -48,9 +48,10 @@ pub struct RealityGlitch {
     consequence: String,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct RealityConsensusGame {
     round_number: u32,
-    reality_fragments: HashMap<String, RealityFragment>,
+    reality_fragments: BTreeMap<String, RealityFragment>,
     glitches: Vec<RealityGlitch>,
     consensus_threshold: f32,
     reality_stability: f32,
@@ -61,7 +62,7 @@ impl RealityConsensusGame {
     pub fn new() -> Self {
         Self {
             round_number: 0,
-            reality_fragments: HashMap::new(),
+            reality_fragments: BTreeMap::new(),
             glitches: Vec::new(),
             consensus_threshold: 0.66, // 2/3 majority needed
             reality_stability: 1.0,
@@ -86,7 +87,7 @@ impl RealityConsensusGame {
                 id: id.to_string(),
                 description: description.to_string(),
                 current_state: RealityState::Stable,
-                belief_votes: HashMap::new(),
+                belief_votes: BTreeMap::new(),
                 stability: 1.0,
                 glitch_probability: 0.0,
             });
@@ -124,16 +125,15 @@ impl RealityConsensusGame {
             
             if old_state != fragment.current_state {
                 events.push(GameEvent {
-                    event_type: "reality_shift".to_string(),
-                    description: format!("Reality fragment '{}' shifted from {:?} to {:?}", 
-                        fragment_id, old_state, fragment.current_state),
-                    affected_players: fragment.belief_votes.keys().cloned().collect(),
-                    data: serde_json::json!({
+                    kind: EventKind::custom("reality_shift", serde_json::json!({
                         "fragment": fragment_id,
                         "old_state": old_state,
                         "new_state": fragment.current_state,
                         "consensus": consensus,
-                    }),
+                    })),
+                    description: format!("Reality fragment '{}' shifted from {:?} to {:?}", 
+                        fragment_id, old_state, fragment.current_state),
+                    affected_players: fragment.belief_votes.keys().cloned().collect(),
                 });
             }
             
@@ -149,18 +149,17 @@ impl RealityConsensusGame {
                         fragment_id: fragment_id.to_string(),
                         severity: fragment.glitch_probability,
                         affected_players: dissenters.clone(),
-                        consequence: self.generate_glitch_consequence(fragment_id, fragment.glitch_probability),
+                        consequence: Self::generate_glitch_consequence(fragment_id, fragment.glitch_probability),
                     };
                     
                     events.push(GameEvent {
-                        event_type: "reality_glitch".to_string(),
-                        description: format!("Reality glitch in '{}': {}", fragment_id, glitch.consequence),
-                        affected_players: glitch.affected_players.clone(),
-                        data: serde_json::json!({
+                        kind: EventKind::custom("reality_glitch", serde_json::json!({
                             "fragment": fragment_id,
                             "severity": glitch.severity,
                             "consequence": glitch.consequence,
-                        }),
+                        })),
+                        description: format!("Reality glitch in '{}': {}", fragment_id, glitch.consequence),
+                        affected_players: glitch.affected_players.clone(),
                     });
                     
                     self.glitches.push(glitch);
@@ -171,7 +170,7 @@ impl RealityConsensusGame {
         events
     }
     
-    fn generate_glitch_consequence(&self, fragment_id: &str, severity: f32) -> String {
+    fn generate_glitch_consequence(fragment_id: &str, severity: f32) -> String {
         match fragment_id {
             "gravity" => format!("Players experience {:.0}% gravity reversal", severity * 100.0),
             "time" => format!("Time loops back {:.0} rounds", (severity * 3.0).ceil()),
@@ -282,15 +281,19 @@ impl Game for RealityConsensusGame {
         let mut events = Vec::new();
         
         // Apply entropy - reality naturally decays without maintenance
-        self.reality_stability *= (1.0 - self.entropy_rate);
+        self.reality_stability *= 1.0 - self.entropy_rate;
         
         // Clear previous round's votes
         for fragment in self.reality_fragments.values_mut() {
             fragment.belief_votes.clear();
         }
         
-        // Process player beliefs
-        for (player_id, action) in &actions {
+        // Process player beliefs in a stable order
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        
+        for player_id in player_ids {
+            let action = &actions[player_id];
             if let Ok(belief) = serde_json::from_value::<BeliefAction>(action.data.clone()) {
                 // Record belief vote
                 if let Some(fragment) = self.reality_fragments.get_mut(&belief.fragment_id) {
@@ -309,38 +312,40 @@ impl Game for RealityConsensusGame {
                         id: proposal_id.clone(),
                         description: new_reality.clone(),
                         current_state: RealityState::Unstable,
-                        belief_votes: HashMap::new(),
+                        belief_votes: BTreeMap::new(),
                         stability: 0.5,
                         glitch_probability: 0.5,
                     });
                     
                     events.push(GameEvent {
-                        event_type: "reality_proposal".to_string(),
-                        description: format!("{} proposes new reality: {}", player_id, new_reality),
-                        affected_players: vec![player_id.clone()],
-                        data: serde_json::json!({
+                        kind: EventKind::custom("reality_proposal", serde_json::json!({
                             "proposer": player_id,
                             "fragment_id": proposal_id,
                             "description": new_reality,
-                        }),
+                        })),
+                        description: format!("{} proposes new reality: {}", player_id, new_reality),
+                        affected_players: vec![player_id.clone()],
                     });
                 }
             }
         }
         
         // Update reality states based on consensus
-        let fragment_ids: Vec<_> = self.reality_fragments.keys().cloned().collect();
+        let mut fragment_ids: Vec<_> = self.reality_fragments.keys().cloned().collect();
+        fragment_ids.sort();
         for fragment_id in fragment_ids {
-            let consensus = self.reality_fragments.get(&fragment_id)
-                .map(|f| self.calculate_consensus(f))
-                .unwrap_or(0.0);
+            // A fragment nobody voted on this round holds its state
+            let consensus = match self.reality_fragments.get(&fragment_id) {
+                Some(f) if !f.belief_votes.is_empty() => self.calculate_consensus(f),
+                _ => continue,
+            };
                 
             let mut fragment_events = self.update_reality_state(&fragment_id, consensus);
             events.append(&mut fragment_events);
         }
         
         // Award points based on consensus participation
-        for (fragment_id, fragment) in &self.reality_fragments {
+        for fragment in self.reality_fragments.values() {
             let consensus = self.calculate_consensus(fragment);
             
             if consensus >= self.consensus_threshold {
@@ -362,21 +367,11 @@ impl Game for RealityConsensusGame {
         
         // Check for emergence events
         if let Some(collapse) = self.detect_reality_collapse() {
-            events.push(GameEvent {
-                event_type: "emergence".to_string(),
-                description: collapse.description.clone(),
-                affected_players: collapse.involved_players.clone(),
-                data: serde_json::json!({ "type": "reality_collapse" }),
-            });
+            events.push(GameEvent::emergence(&collapse));
         }
         
         if let Some(transcendent) = self.detect_transcendent_consensus() {
-            events.push(GameEvent {
-                event_type: "emergence".to_string(),
-                description: transcendent.description.clone(),
-                affected_players: transcendent.involved_players.clone(),
-                data: serde_json::json!({ "type": "transcendent_reality" }),
-            });
+            events.push(GameEvent::emergence(&transcendent));
             
             // Bonus points for achieving transcendence
             for player_id in &transcendent.involved_players {
@@ -387,17 +382,22 @@ impl Game for RealityConsensusGame {
         // Update global stability
         self.reality_stability = self.calculate_global_stability();
         
+        let mut winners: Vec<String> = scores_delta.iter()
+            .filter(|(_, &score)| score > 0)
+            .map(|(player, _)| player.clone())
+            .collect();
+        let mut losers: Vec<String> = scores_delta.iter()
+            .filter(|(_, &score)| score < 0)
+            .map(|(player, _)| player.clone())
+            .collect();
+        winners.sort();
+        losers.sort();
+        
         let outcome = RoundOutcome {
-            winners: scores_delta.iter()
-                .filter(|(_, &score)| score > 0)
-                .map(|(player, _)| player.clone())
-                .collect(),
-            losers: scores_delta.iter()
-                .filter(|(_, &score)| score < 0)
-                .map(|(player, _)| player.clone())
-                .collect(),
+            winners,
+            losers,
             special_events: events.iter().map(|e| e.description.clone()).collect(),
-            emergence_detected: events.iter().any(|e| e.event_type == "emergence"),
+            emergence_detected: events.iter().any(|e| matches!(e.kind, EventKind::Emergence { .. })),
         };
         
         Ok(RoundResult {
@@ -406,6 +406,7 @@ impl Game for RealityConsensusGame {
             outcome,
             scores_delta,
             events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
@@ -424,17 +425,17 @@ impl Game for RealityConsensusGame {
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        let winner = state.scores.iter()
-            .max_by_key(|(_, &score)| score)
-            .map(|(player, _)| player.clone())
-            .unwrap_or_default();
+        let standings = Standings::new(&state.scores);
             
         let final_stability = self.calculate_global_stability();
         let glitch_rate = self.glitches.len() as f32 / state.round as f32;
         
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: state.scores.clone(),
             total_rounds: state.round,
             duration_ms: 0,
@@ -456,14 +457,63 @@ impl Game for RealityConsensusGame {
         }
     }
     
-    async fn get_valid_actions(&self, _state: &GameState, _player_id: &str) -> Vec<String> {
-        vec![
-            "believe".to_string(),
-            "disbelieve".to_string(),
-            "propose_reality".to_string(),
-            "strengthen_belief".to_string(),
-            "question_reality".to_string(),
-        ]
+    fn action_schema(&self) -> serde_json::Value {
+        let belief = |proposal_required: bool| {
+            let mut required = vec!["fragment_id", "believes", "conviction_strength"];
+            if proposal_required {
+                required.push("proposed_reality");
+            }
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "fragment_id": { "type": "string" },
+                    "believes": { "type": "boolean" },
+                    "conviction_strength": { "type": "number", "minimum": 0.0, "maximum": 1.0 },
+                    "proposed_reality": { "type": ["string", "null"] }
+                },
+                "required": required
+            })
+        };
+        
+        ActionSchema::new("Reality Consensus")
+            .action("believe", "Vote that a reality fragment is true", belief(false))
+            .action("disbelieve", "Vote that a reality fragment is false", belief(false))
+            .action("strengthen_belief", "Vote with conviction above 0.8 for a bonus", belief(false))
+            .action("question_reality", "Cast a weak vote against a fragment", belief(false))
+            .action("propose_reality", "Vote on a fragment and propose a new one", belief(true))
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        let mut fragment_ids: Vec<&String> = self.reality_fragments.keys().collect();
+        fragment_ids.sort();
+        
+        let mut actions = Vec::new();
+        for fragment_id in &fragment_ids {
+            for (action_type, believes, conviction) in [
+                ("believe", true, 0.6),
+                ("disbelieve", false, 0.6),
+                ("strengthen_belief", true, 0.9),
+                ("question_reality", false, 0.3),
+            ] {
+                actions.push(LegalAction::new(action_type, serde_json::json!({
+                    "fragment_id": fragment_id,
+                    "believes": believes,
+                    "conviction_strength": conviction,
+                })));
+            }
+        }
+        
+        if let Some(fragment_id) = fragment_ids.first() {
+            actions.push(LegalAction::new("propose_reality", serde_json::json!({
+                "fragment_id": fragment_id,
+                "believes": true,
+                "conviction_strength": 0.6,
+                "proposed_reality": format!("Reality as {} sees it", player_id),
+            })));
+        }
+        
+        actions
     }
     
     async fn get_visualization_data(&self, _state: &GameState) -> serde_json::Value {
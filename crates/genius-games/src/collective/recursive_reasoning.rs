@@ -1,14 +1,16 @@
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, GameError};
+use genius_core::{game_rng, GameRng, Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, EmergenceEvent, EmergenceType, GameEvent, Result, ActionSchema, LegalAction, Standings};
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use uuid::Uuid;
 use rand::Rng;
 
+#[derive(Serialize, Deserialize)]
 pub struct RecursiveReasoning {
     pub max_depth: usize,
     pub guessing_rounds: usize,
     pub thinking_depth_scores: HashMap<String, Vec<usize>>,
+    rng: GameRng,
 }
 
 impl Default for RecursiveReasoning {
@@ -23,6 +25,7 @@ impl RecursiveReasoning {
             max_depth: 5,
             guessing_rounds: 10,
             thinking_depth_scores: HashMap::new(),
+            rng: game_rng(None),
         }
     }
     
@@ -43,11 +46,9 @@ impl RecursiveReasoning {
         calculated_depth.max(1)
     }
     
-    fn generate_puzzle(&self, round: u32) -> RecursivePuzzle {
-        let mut rng = rand::thread_rng();
-        
+    fn generate_puzzle(&mut self, round: u32) -> RecursivePuzzle {
         // Create nested reasoning puzzle
-        let base_number = rng.gen_range(1..=100);
+        let base_number = self.rng.random_range(1..=100);
         let depth = ((round / 3) as usize).min(self.max_depth - 1) + 1;
         
         RecursivePuzzle {
@@ -84,19 +85,14 @@ impl RecursiveReasoning {
     }
     
     fn detect_meta_reasoning(&self, guesses: &HashMap<String, i32>, puzzle: &RecursivePuzzle) -> bool {
-        // Check if collective agents are modeling each other's reasoning
-        let collective_guesses: Vec<_> = guesses.iter()
-            .filter(|(id, _)| id.starts_with("collective_"))
-            .map(|(_, &guess)| guess)
-            .collect();
-        
-        if collective_guesses.len() < 3 {
+        // Check if the players are modeling each other's reasoning, whichever side they are on
+        if guesses.len() < 3 {
             return false;
         }
         
         // Check for clustering around meta-levels
         let mut depth_clusters = vec![0; self.max_depth];
-        for &guess in &collective_guesses {
+        for &guess in guesses.values() {
             for depth in 1..=self.max_depth {
                 let expected = self.calculate_optimal_answer(puzzle.base_value, depth);
                 if (guess - expected).abs() < 5 {
@@ -121,12 +117,18 @@ struct RecursivePuzzle {
 
 #[async_trait]
 impl Game for RecursiveReasoning {
-    async fn initialize(&mut self, _config: GameConfig) -> Result<GameState> {
+    async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        self.rng = game_rng(config.seed);
+        
         Ok(GameState {
             game_id: Uuid::new_v4(),
             game_type: GameType::RecursiveReasoning,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
@@ -134,6 +136,8 @@ impl Game for RecursiveReasoning {
                 meta.insert("current_depth".to_string(), serde_json::json!(1));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
     
@@ -169,6 +173,19 @@ impl Game for RecursiveReasoning {
             .map(|(id, _)| id.clone())
             .collect();
         
+        let mut events = Vec::new();
+        if emergence_detected {
+            let mut involved_players: Vec<String> = actions.keys().cloned().collect();
+            involved_players.sort();
+            events.push(GameEvent::emergence(&EmergenceEvent {
+                round: state.round + 1,
+                event_type: EmergenceType::Custom("meta_reasoning".to_string()),
+                description: "Collective demonstrated multi-level recursive reasoning".to_string(),
+                emergence_score: 0.85,
+                involved_players,
+            }));
+        }
+        
         let special_events = if emergence_detected {
             vec!["Meta-reasoning emergence detected!".to_string()]
         } else {
@@ -185,39 +202,44 @@ impl Game for RecursiveReasoning {
                 emergence_detected,
             },
             scores_delta,
+            events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Recursive Reasoning")
+            .action("guess", "Guess the answer to this round's nested puzzle", serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "guess": { "type": "integer", "minimum": 0 }
+                },
+                "required": ["guess"]
+            }))
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, _player_id: &str) -> Vec<LegalAction> {
+        // No puzzle can have an answer above the deepest reasoning on the largest base
+        let max_answer = self.calculate_optimal_answer(100, self.max_depth);
+        (0..=max_answer)
+            .map(|guess| LegalAction::new("guess", serde_json::json!({ "guess": guess })))
+            .collect()
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         state.round >= self.guessing_rounds as u32 ||
         state.scores.values().any(|&score| score >= 1000)
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        let winner = state.scores.iter()
-            .max_by_key(|(_, score)| *score)
-            .map(|(id, _)| id.clone())
-            .unwrap_or_else(|| "No winner".to_string());
-        
-        // Count emergence events
-        let emergence_events: Vec<EmergenceEvent> = state.history.iter()
-            .enumerate()
-            .filter_map(|(i, round_result)| {
-                if round_result.outcome.emergence_detected {
-                    Some(EmergenceEvent {
-                        round: i as u32,
-                        event_type: "meta_reasoning".to_string(),
-                        description: "Collective demonstrated multi-level recursive reasoning".to_string(),
-                        emergence_score: 0.85,
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let standings = Standings::new(&state.scores);
+        
         
-        let emergence_frequency = emergence_events.len() as f32 / state.round.max(1) as f32;
+        let emergence_frequency = state.history.iter()
+            .filter(|round_result| round_result.outcome.emergence_detected)
+            .count() as f32 / state.round.max(1) as f32;
         
         // Calculate average thinking depth
         let avg_depth: f32 = self.thinking_depth_scores.values()
@@ -229,16 +251,21 @@ impl Game for RecursiveReasoning {
         
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: state.scores.clone(),
             total_rounds: state.round,
-            emergence_events,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
+            emergence_events: vec![],
             analytics: GameAnalytics {
                 collective_coordination_score: emergence_frequency,
                 decision_diversity_index: 0.0,
                 strategic_depth: avg_depth / self.max_depth as f32,
                 emergence_frequency,
                 performance_differential: 0.0,
+                custom_metrics: HashMap::new(),
             },
         }
     }
@@ -1,10 +1,11 @@
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, GameError};
+use genius_core::{game_rng, GameRng, Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, EmergenceEvent, EmergenceType, GameEvent, Result, ActionSchema, LegalAction, Standings};
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use uuid::Uuid;
 use rand::Rng;
 
+#[derive(Serialize, Deserialize)]
 pub struct SwarmOptimization {
     pub dimensions: usize,
     pub search_space_min: f64,
@@ -15,8 +16,10 @@ pub struct SwarmOptimization {
     pub velocities: HashMap<String, Vec<f64>>,
     pub global_best_position: Vec<f64>,
     pub global_best_fitness: f64,
+    rng: GameRng,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct Particle {
     pub id: usize,
     pub position: Vec<f64>,
@@ -42,7 +45,9 @@ impl SwarmOptimization {
             best_positions: HashMap::new(),
             velocities: HashMap::new(),
             global_best_position: vec![],
-            global_best_fitness: f64::NEG_INFINITY,
+            // Lowest finite value rather than -inf, which JSON snapshots cannot hold
+            global_best_fitness: f64::MIN,
+            rng: game_rng(None),
         }
     }
     
@@ -53,8 +58,8 @@ impl SwarmOptimization {
         
         for particle in particles.iter_mut() {
             for i in 0..self.dimensions {
-                let r1 = rand::thread_rng().gen::<f64>();
-                let r2 = rand::thread_rng().gen::<f64>();
+                let r1 = self.rng.random::<f64>();
+                let r2 = self.rng.random::<f64>();
                 
                 // Update velocity
                 let cognitive = c1 * r1 * (particle.personal_best[i] - particle.position[i]);
@@ -101,12 +106,10 @@ impl SwarmOptimization {
     }
     
     fn generate_target(&mut self, round: u32) {
-        let mut rng = rand::thread_rng();
-        
         // Dynamic target that shifts over time
         self.target_position = (0..self.dimensions)
             .map(|i| {
-                let base = rng.gen_range(self.search_space_min..self.search_space_max);
+                let base = self.rng.random_range(self.search_space_min..self.search_space_max);
                 let shift = (round as f64 / 10.0).sin() * 20.0;
                 base + shift * (i as f64 / self.dimensions as f64)
             })
@@ -133,16 +136,14 @@ impl SwarmOptimization {
     }
     
     fn initialize_agent(&mut self, agent_id: &str) {
-        let mut rng = rand::thread_rng();
-        
         // Random initial position
         let position: Vec<f64> = (0..self.dimensions)
-            .map(|_| rng.gen_range(self.search_space_min..self.search_space_max))
+            .map(|_| self.rng.random_range(self.search_space_min..self.search_space_max))
             .collect();
         
         // Random initial velocity
         let velocity: Vec<f64> = (0..self.dimensions)
-            .map(|_| rng.gen_range(-10.0..10.0))
+            .map(|_| self.rng.random_range(-10.0..10.0))
             .collect();
         
         self.agent_positions.insert(agent_id.to_string(), position.clone());
@@ -219,7 +220,8 @@ struct ProposedMove {
 
 #[async_trait]
 impl Game for SwarmOptimization {
-    async fn initialize(&mut self, _config: GameConfig) -> Result<GameState> {
+    async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        self.rng = game_rng(config.seed);
         self.generate_target(0);
         
         Ok(GameState {
@@ -227,6 +229,10 @@ impl Game for SwarmOptimization {
             game_type: GameType::SwarmOptimization,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
@@ -237,19 +243,25 @@ impl Game for SwarmOptimization {
                 }));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
     
     async fn process_round(&mut self, state: &GameState, actions: HashMap<String, PlayerAction>) -> Result<RoundResult> {
         // Update target position dynamically
-        if state.round % 10 == 0 {
+        if state.round.is_multiple_of(10) {
             self.generate_target(state.round);
         }
         
         let mut scores_delta = HashMap::new();
         
-        // Process agent moves
-        for (agent_id, action) in &actions {
+        // Process agent moves in a stable order
+        let mut agent_ids: Vec<&String> = actions.keys().collect();
+        agent_ids.sort();
+        
+        for agent_id in agent_ids {
+            let action = &actions[agent_id];
             // Initialize new agents
             if !self.agent_positions.contains_key(agent_id) {
                 self.initialize_agent(agent_id);
@@ -298,6 +310,20 @@ impl Game for SwarmOptimization {
                 }
             }
         }
+        winners.sort();
+        
+        let mut events = Vec::new();
+        if emergence_detected {
+            let mut involved_players: Vec<String> = actions.keys().cloned().collect();
+            involved_players.sort();
+            events.push(GameEvent::emergence(&EmergenceEvent {
+                round: state.round + 1,
+                event_type: EmergenceType::Custom("swarm_convergence".to_string()),
+                description: "Collective achieved coordinated optimization".to_string(),
+                emergence_score: 0.9,
+                involved_players,
+            }));
+        }
         
         let special_events = if emergence_detected {
             vec!["Swarm convergence achieved!".to_string()]
@@ -317,10 +343,56 @@ impl Game for SwarmOptimization {
                 emergence_detected,
             },
             scores_delta,
+            events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Swarm Optimization")
+            .action("move", "Shift your position along any dimensions of the search space", serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "moves": {
+                        "type": "object",
+                        "description": format!("Map of dimension index (0-{}) to the delta to apply", self.dimensions - 1),
+                        "propertyNames": { "pattern": "^[0-9]+$" },
+                        "additionalProperties": { "type": "number" }
+                    }
+                },
+                "required": ["moves"]
+            }))
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        let step = (self.search_space_max - self.search_space_min) / 20.0;
+        let mut actions = vec![LegalAction::new("move", serde_json::json!({ "moves": {} }))];
+        
+        for dim in 0..self.dimensions {
+            for delta in [step, -step] {
+                actions.push(LegalAction::new("move", serde_json::json!({
+                    "moves": { dim.to_string(): delta }
+                })));
+            }
+        }
+        
+        // Jump straight to the best position the swarm has found so far
+        if let Some(position) = self.agent_positions.get(player_id) {
+            if self.global_best_position.len() == position.len() {
+                let moves: serde_json::Map<String, serde_json::Value> = position.iter()
+                    .zip(&self.global_best_position)
+                    .enumerate()
+                    .map(|(dim, (current, best))| (dim.to_string(), serde_json::json!(best - current)))
+                    .collect();
+                actions.push(LegalAction::new("move", serde_json::json!({ "moves": moves })));
+            }
+        }
+        
+        actions
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         state.round >= 50 || 
         self.global_best_fitness > -1.0 || // Near-perfect solution
@@ -328,36 +400,16 @@ impl Game for SwarmOptimization {
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        // Winner is agent closest to global best
-        let mut best_agent = "No winner".to_string();
-        let mut best_distance = f64::INFINITY;
-        
-        for (agent_id, position) in &self.agent_positions {
-            let fitness = self.fitness_function(position);
-            if fitness > -best_distance {
-                best_distance = -fitness;
-                best_agent = agent_id.clone();
-            }
-        }
-        
-        // Count emergence events
-        let emergence_events: Vec<EmergenceEvent> = state.history.iter()
-            .enumerate()
-            .filter_map(|(i, round_result)| {
-                if round_result.outcome.emergence_detected {
-                    Some(EmergenceEvent {
-                        round: i as u32,
-                        event_type: "swarm_convergence".to_string(),
-                        description: "Collective achieved coordinated optimization".to_string(),
-                        emergence_score: 0.9,
-                    })
-                } else {
-                    None
-                }
-            })
+        // Agents place by the fitness of their final position, to the nearest thousandth
+        let fitness_scores: HashMap<String, i32> = self.agent_positions.iter()
+            .map(|(agent_id, position)| (agent_id.clone(), (self.fitness_function(position) * 1000.0).round() as i32))
             .collect();
+        let standings = Standings::new(&fitness_scores);
+        
         
-        let emergence_frequency = emergence_events.len() as f32 / state.round.max(1) as f32;
+        let emergence_frequency = state.history.iter()
+            .filter(|round_result| round_result.outcome.emergence_detected)
+            .count() as f32 / state.round.max(1) as f32;
         
         // Calculate diversity
         let position_variance = if self.agent_positions.len() > 1 {
@@ -375,19 +427,26 @@ impl Game for SwarmOptimization {
         } else {
             0.0
         };
+        let diversity = (position_variance.sqrt() / 100.0) as f32;
         
         GameResult {
             game_id: state.game_id,
-            winner: best_agent,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: state.scores.clone(),
             total_rounds: state.round,
-            emergence_events,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
+            emergence_events: vec![],
             analytics: GameAnalytics {
-                collective_coordination_score: emergence_frequency,
-                decision_diversity_index: (position_variance.sqrt() / 100.0) as f32,
+                // How tightly the whole swarm has gathered, whoever is in it
+                collective_coordination_score: (1.0 - diversity).max(0.0),
+                decision_diversity_index: diversity,
                 strategic_depth: (self.global_best_fitness / -1000.0) as f32,
                 emergence_frequency,
                 performance_differential: self.global_best_fitness as f32,
+                custom_metrics: HashMap::new(),
             },
         }
     }
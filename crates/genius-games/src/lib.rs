@@ -6,98 +6,251 @@ pub mod strategic;
 pub mod collective;
 pub mod survival;
 pub mod trust;
+pub mod replay;
 
-use genius_core::{Game, GameConfig, GameType, GameError};
-use std::sync::Arc;
+use genius_core::{Game, GameCategory, GameFactory, GameType, GameError, ParamSchema};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
 
-/// Factory function to create game instances
-pub fn create_game(game_type: GameType) -> Result<Box<dyn Game>, GameError> {
-    match game_type {
+/// Builds a fresh instance of one registered game
+pub type GameConstructor = Arc<dyn Fn() -> Box<dyn Game> + Send + Sync>;
+
+/// Every game that ships with the platform, in listing order
+const BUILTIN_GAMES: [GameType; 25] = [
+    // Strategic
+    GameType::MinorityGame,
+    GameType::ByzantineGenerals,
+    GameType::MiniGo,
+    GameType::MiniHoldem,
+    GameType::VoidWalker,
+    GameType::ObserverGame,
+    GameType::QuantumDreamer,
+    // Collective
+    GameType::CollectiveMaze,
+    GameType::SwarmOptimization,
+    GameType::RecursiveReasoning,
+    GameType::QuantumConsensus,
+    GameType::MirrorMind,
+    GameType::RealityConsensus,
+    GameType::InformationHorizon,
+    GameType::ConsciousnessCascade,
+    // Survival
+    GameType::BattleRoyale,
+    GameType::HungerGames,
+    GameType::SquidGame,
+    GameType::RussianRoulette,
+    GameType::KingOfTheHill,
+    GameType::LastStand,
+    // Trust
+    GameType::PrisonersDilemma,
+    GameType::TrustFall,
+    GameType::LiarsDice,
+    GameType::ConsciousnessPoker,
+];
+
+fn constructor<G: Game + 'static>(new: fn() -> G) -> GameConstructor {
+    Arc::new(move || Box::new(new()) as Box<dyn Game>)
+}
+
+/// Constructor for a built-in game; `None` for custom games, which only a `GameRegistry` can build
+fn builtin_constructor(game_type: &GameType) -> Option<GameConstructor> {
+    let constructor = match game_type {
         // Strategic Games
-        GameType::MinorityGame => Ok(Box::new(strategic::minority_game::MinorityGame::new())),
-        GameType::ByzantineGenerals => Ok(Box::new(strategic::byzantine_generals::ByzantineGeneralsGame::new())),
-        GameType::MiniGo => Ok(Box::new(strategic::mini_go::MiniGoGame::new())),
-        GameType::MiniHoldem => Ok(Box::new(strategic::mini_holdem::MiniHoldemGame::new())),
-        GameType::VoidWalker => Ok(Box::new(strategic::void_walker::VoidWalkerGame::new())),
-        GameType::ObserverGame => Ok(Box::new(strategic::observer_game::ObserverGame::new())),
-        GameType::QuantumDreamer => Ok(Box::new(strategic::quantum_dreamer::QuantumDreamerGame::new())),
+        GameType::MinorityGame => constructor(strategic::minority_game::MinorityGame::new),
+        GameType::ByzantineGenerals => constructor(strategic::byzantine_generals::ByzantineGenerals::new),
+        GameType::MiniGo => constructor(strategic::mini_go::MiniGoGame::new),
+        GameType::MiniHoldem => constructor(strategic::mini_holdem::MiniHoldemGame::new),
+        GameType::VoidWalker => constructor(strategic::void_walker::VoidWalkerGame::new),
+        GameType::ObserverGame => constructor(strategic::observer_game::ObserverGame::new),
+        GameType::QuantumDreamer => constructor(strategic::quantum_dreamer::QuantumDreamerGame::new),
         
         // Collective Intelligence Games
-        GameType::CollectiveMaze => Ok(Box::new(collective::collective_maze::CollectiveMazeGame::new())),
-        GameType::SwarmOptimization => Ok(Box::new(collective::swarm_optimization::SwarmOptimizationGame::new())),
-        GameType::RecursiveReasoning => Ok(Box::new(collective::recursive_reasoning::RecursiveReasoningGame::new())),
-        GameType::QuantumConsensus => Ok(Box::new(collective::quantum_consensus::QuantumConsensusGame::new())),
-        GameType::MirrorMind => Ok(Box::new(collective::mirror_mind::MirrorMindGame::new())),
-        GameType::RealityConsensus => Ok(Box::new(collective::reality_consensus::RealityConsensusGame::new())),
-        GameType::InformationHorizon => Ok(Box::new(collective::information_horizon::InformationHorizonGame::new())),
-        GameType::ConsciousnessCascade => Ok(Box::new(collective::consciousness_cascade::ConsciousnessCascadeGame::new())),
+        GameType::CollectiveMaze => constructor(collective::collective_maze::CollectiveMaze::new),
+        GameType::SwarmOptimization => constructor(collective::swarm_optimization::SwarmOptimization::new),
+        GameType::RecursiveReasoning => constructor(collective::recursive_reasoning::RecursiveReasoning::new),
+        GameType::QuantumConsensus => constructor(collective::quantum_consensus::QuantumConsensus::new),
+        GameType::MirrorMind => constructor(collective::mirror_mind::MirrorMindGame::new),
+        GameType::RealityConsensus => constructor(collective::reality_consensus::RealityConsensusGame::new),
+        GameType::InformationHorizon => constructor(collective::information_horizon::InformationHorizonGame::new),
+        GameType::ConsciousnessCascade => constructor(collective::consciousness_cascade::ConsciousnessCascadeGame::new),
         
         // Survival Games
-        GameType::BattleRoyale => Ok(Box::new(survival::battle_royale::BattleRoyaleGame::new())),
-        GameType::HungerGames => Ok(Box::new(survival::hunger_games::HungerGamesGame::new())),
-        GameType::SquidGame => Ok(Box::new(survival::squid_game::SquidGame::new())),
-        GameType::RussianRoulette => Ok(Box::new(survival::russian_roulette::RussianRoulette::new())),
-        GameType::KingOfTheHill => Ok(Box::new(survival::king_of_the_hill::KingOfTheHill::new())),
-        GameType::LastStand => Ok(Box::new(survival::last_stand::LastStand::new())),
+        GameType::BattleRoyale => constructor(survival::battle_royale::BattleRoyaleGame::new),
+        GameType::HungerGames => constructor(survival::hunger_games::HungerGamesGame::new),
+        GameType::SquidGame => constructor(survival::squid_game::SquidGame::new),
+        GameType::RussianRoulette => constructor(survival::russian_roulette::RussianRoulette::new),
+        GameType::KingOfTheHill => constructor(survival::king_of_the_hill::KingOfTheHill::new),
+        GameType::LastStand => constructor(survival::last_stand::LastStand::new),
         
         // Trust Games
-        GameType::PrisonersDilemma => Ok(Box::new(trust::prisoners_dilemma::PrisonersDilemmaGame::new())),
-        GameType::TrustFall => Ok(Box::new(trust::trust_fall::TrustFall::new())),
-        GameType::LiarsDice => Ok(Box::new(trust::liars_dice::LiarsDiceGame::new())),
-        GameType::ConsciousnessPoker => Ok(Box::new(trust::consciousness_poker::ConsciousnessPokerGame::new())),
+        GameType::PrisonersDilemma => constructor(trust::prisoners_dilemma::PrisonersDilemmaGame::new),
+        GameType::TrustFall => constructor(trust::trust_fall::TrustFall::new),
+        GameType::LiarsDice => constructor(trust::liars_dice::LiarsDiceGame::new),
+        GameType::ConsciousnessPoker => constructor(trust::consciousness_poker::ConsciousnessPokerGame::new),
+        
+        GameType::Custom(_) => return None,
+    };
+    Some(constructor)
+}
+
+fn not_registered(game_type: &GameType) -> GameError {
+    GameError::ConfigError {
+        reason: format!("Game type {} is not registered", game_type.key()),
+    }
+}
+
+/// Factory function to create built-in game instances
+///
+/// Custom games are only known to a `GameRegistry`; use it as the engine's factory to run them.
+pub fn create_game(game_type: GameType) -> Result<Box<dyn Game>, GameError> {
+    builtin_constructor(&game_type)
+        .map(|new| new())
+        .ok_or_else(|| not_registered(&game_type))
+}
+
+/// Rebuild a game instance from internals captured with `Snapshot::snapshot`
+pub fn restore_game(game_type: GameType, snapshot: serde_json::Value) -> Result<Box<dyn Game>, GameError> {
+    let mut game = create_game(game_type)?;
+    game.restore(snapshot)?;
+    Ok(game)
+}
+
+/// Public description of a registered game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameInfo {
+    pub game_type: GameType,
+    pub name: String,
+    pub category: GameCategory,
+    /// Free-form details supplied at registration, e.g. a description or player limits
+    pub metadata: serde_json::Value,
+    /// Parameters accepted in `GameConfig::params`
+    pub parameters: ParamSchema,
+}
+
+struct Registration {
+    info: GameInfo,
+    constructor: GameConstructor,
+}
+
+impl Registration {
+    /// Whether `name` is this game's key or display name, ignoring case
+    fn answers_to(&self, name: &str) -> bool {
+        self.info.game_type.key().eq_ignore_ascii_case(name) || self.info.name.eq_ignore_ascii_case(name)
     }
 }
 
-/// Registry of all available games
+/// Runtime registry of available games
+///
+/// Cloning is cheap and clones share registrations, so a registry handed to the engine
+/// still sees games registered afterwards.
+#[derive(Clone)]
 pub struct GameRegistry {
-    games: Vec<GameType>,
+    games: Arc<RwLock<Vec<Registration>>>,
 }
 
 impl GameRegistry {
+    /// Registry holding every built-in game
     pub fn new() -> Self {
+        let games = BUILTIN_GAMES.into_iter()
+            .filter_map(|game_type| {
+                let constructor = builtin_constructor(&game_type)?;
+                let category = game_type.category();
+                Some(Self::registration(game_type, category, serde_json::json!({ "builtin": true }), constructor))
+            })
+            .collect();
+        
         Self {
-            games: vec![
-                // Strategic
-                GameType::MinorityGame,
-                GameType::ByzantineGenerals,
-                GameType::MiniGo,
-                GameType::MiniHoldem,
-                GameType::VoidWalker,
-                GameType::ObserverGame,
-                GameType::QuantumDreamer,
-                // Collective
-                GameType::CollectiveMaze,
-                GameType::SwarmOptimization,
-                GameType::RecursiveReasoning,
-                GameType::QuantumConsensus,
-                GameType::MirrorMind,
-                GameType::RealityConsensus,
-                GameType::InformationHorizon,
-                GameType::ConsciousnessCascade,
-                // Survival
-                GameType::BattleRoyale,
-                GameType::HungerGames,
-                GameType::SquidGame,
-                GameType::RussianRoulette,
-                GameType::KingOfTheHill,
-                GameType::LastStand,
-                // Trust
-                GameType::PrisonersDilemma,
-                GameType::TrustFall,
-                GameType::LiarsDice,
-                GameType::ConsciousnessPoker,
-            ],
+            games: Arc::new(RwLock::new(games)),
         }
     }
     
-    pub fn all_games(&self) -> &[GameType] {
-        &self.games
+    /// Registry with no games in it
+    pub fn empty() -> Self {
+        Self {
+            games: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+    
+    /// Register a game under its type; custom games use `GameType::Custom` with their own name
+    pub fn register<F>(
+        &self,
+        game_type: GameType,
+        category: GameCategory,
+        metadata: serde_json::Value,
+        factory: F,
+    ) -> Result<(), GameError>
+    where
+        F: Fn() -> Box<dyn Game> + Send + Sync + 'static,
+    {
+        // Built outside the lock, since describing the game constructs an instance of it
+        let registration = Self::registration(game_type, category, metadata, Arc::new(factory));
+        let mut games = self.games.write().expect("game registry lock poisoned");
+        // Names are resolved by key or display name, so neither may shadow another game's
+        let key = registration.info.game_type.key();
+        let taken = |name: &str| games.iter().any(|r| r.answers_to(name));
+        if taken(&key) || taken(&registration.info.name) {
+            return Err(GameError::ConfigError {
+                reason: format!("Game type {} is already registered", key),
+            });
+        }
+        games.push(registration);
+        Ok(())
+    }
+    
+    fn registration(game_type: GameType, category: GameCategory, metadata: serde_json::Value, constructor: GameConstructor) -> Registration {
+        let info = GameInfo {
+            name: game_type.display_name().to_string(),
+            game_type,
+            category,
+            metadata,
+            parameters: constructor().parameters(),
+        };
+        Registration { info, constructor }
+    }
+    
+    /// Create a fresh instance of a registered game
+    pub fn create_game(&self, game_type: &GameType) -> Result<Box<dyn Game>, GameError> {
+        // Release the lock before building, in case the game consults the registry itself
+        let constructor = self.games.read().expect("game registry lock poisoned")
+            .iter()
+            .find(|r| r.info.game_type == *game_type)
+            .map(|r| r.constructor.clone());
+        
+        constructor
+            .map(|new| new())
+            .ok_or_else(|| not_registered(game_type))
+    }
+    
+    /// Description of a registered game
+    pub fn info(&self, game_type: &GameType) -> Option<GameInfo> {
+        let games = self.games.read().expect("game registry lock poisoned");
+        games.iter()
+            .find(|r| r.info.game_type == *game_type)
+            .map(|r| r.info.clone())
     }
     
-    pub fn games_by_category(&self, category: genius_core::GameCategory) -> Vec<GameType> {
-        self.games.iter()
-            .filter(|game| game.category() == category)
-            .cloned()
+    /// Find a registered game by its key (`MiniGo`) or display name (`Mini Go`), ignoring case
+    pub fn resolve(&self, name: &str) -> Option<GameType> {
+        let games = self.games.read().expect("game registry lock poisoned");
+        games.iter()
+            .find(|r| r.answers_to(name))
+            .map(|r| r.info.game_type.clone())
+    }
+    
+    /// Descriptions of every registered game, in registration order
+    pub fn list(&self) -> Vec<GameInfo> {
+        let games = self.games.read().expect("game registry lock poisoned");
+        games.iter().map(|r| r.info.clone()).collect()
+    }
+    
+    pub fn all_games(&self) -> Vec<GameType> {
+        self.list().into_iter().map(|info| info.game_type).collect()
+    }
+    
+    pub fn games_by_category(&self, category: GameCategory) -> Vec<GameType> {
+        self.list().into_iter()
+            .filter(|info| info.category == category)
+            .map(|info| info.game_type)
             .collect()
     }
 }
@@ -106,4 +259,10 @@ impl Default for GameRegistry {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+impl GameFactory for GameRegistry {
+    fn create(&self, game_type: GameType) -> genius_core::Result<Box<dyn Game>> {
+        self.create_game(&game_type)
+    }
+}
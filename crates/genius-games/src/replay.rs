@@ -0,0 +1,125 @@
+//! Re-executing recorded replays to check that games still play out the same way
+
+use crate::create_game;
+use genius_core::{GameError, GameFactory, Replay, RoundResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Outcome of re-executing a replay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayVerification {
+    /// Rounds re-executed, including the divergent one if any
+    pub rounds_checked: u32,
+    pub divergence: Option<ReplayDivergence>,
+}
+
+impl ReplayVerification {
+    /// Whether every recorded round was reproduced
+    pub fn is_match(&self) -> bool {
+        self.divergence.is_none()
+    }
+}
+
+/// The first round whose re-execution did not match the recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayDivergence {
+    pub round: u32,
+    pub reason: String,
+    pub expected: RoundResult,
+    /// `None` when the round now fails to process at all
+    pub actual: Option<RoundResult>,
+}
+
+/// Re-execute a replay from its config and recorded actions, stopping at the first divergent round
+pub async fn verify_replay(replay: &Replay) -> Result<ReplayVerification, GameError> {
+    verify_replay_with(replay, &create_game).await
+}
+
+/// Re-execute a replay with games built by the given factory, e.g. a `GameRegistry` holding custom games
+pub async fn verify_replay_with(replay: &Replay, factory: &dyn GameFactory) -> Result<ReplayVerification, GameError> {
+    let mut game = factory.create(replay.config.game_type.clone())?;
+    let mut state = game.initialize(replay.config.clone()).await?;
+    state.teams = replay.config.teams.clone();
+    for player_id in &replay.players {
+        game.apply_join(&mut state, player_id).await;
+    }
+
+    for recorded in &replay.rounds {
+        for player_id in replay.forfeits_after(recorded.round - 1) {
+            game.apply_forfeit(&mut state, player_id).await;
+        }
+
+        let actual = match game.process_round(&state, recorded.actions.clone()).await {
+            // The engine's detector fills `emergence` after the game, so only the recording has it
+            Ok(actual) => RoundResult { emergence: recorded.result.emergence.clone(), ..actual },
+            Err(e) => {
+                return Ok(ReplayVerification {
+                    rounds_checked: recorded.round,
+                    divergence: Some(ReplayDivergence {
+                        round: recorded.round,
+                        reason: format!("round failed: {}", e),
+                        expected: recorded.result.clone(),
+                        actual: None,
+                    }),
+                });
+            }
+        };
+
+        if let Some(reason) = first_difference(&recorded.result, &actual) {
+            return Ok(ReplayVerification {
+                rounds_checked: recorded.round,
+                divergence: Some(ReplayDivergence {
+                    round: recorded.round,
+                    reason,
+                    expected: recorded.result.clone(),
+                    actual: Some(actual),
+                }),
+            });
+        }
+
+        game.apply_round(&mut state, &actual).await;
+    }
+
+    Ok(ReplayVerification {
+        rounds_checked: replay.rounds.len() as u32,
+        divergence: None,
+    })
+}
+
+/// Compare two results field by field, ignoring only when they were produced
+fn first_difference(expected: &RoundResult, actual: &RoundResult) -> Option<String> {
+    const FIELDS: [(&str, &str); 8] = [
+        ("/round", "round numbers"),
+        ("/actions", "actions"),
+        ("/scores_delta", "score changes"),
+        ("/outcome/winners", "winners"),
+        ("/outcome/losers", "losers"),
+        ("/outcome/special_events", "special events"),
+        ("/outcome/emergence_detected", "emergence flags"),
+        ("/events", "events"),
+    ];
+
+    let expected = without_timestamps(expected);
+    let actual = without_timestamps(actual);
+    if expected == actual {
+        return None;
+    }
+    let field = FIELDS.iter()
+        .find(|(path, _)| expected.pointer(path) != actual.pointer(path))
+        .map_or("results", |(_, name)| name);
+    Some(format!("{} differ", field))
+}
+
+/// A round result as JSON with the round and action timestamps removed
+fn without_timestamps(result: &RoundResult) -> Value {
+    let mut value = serde_json::to_value(result).unwrap_or(Value::Null);
+    if let Some(fields) = value.as_object_mut() {
+        fields.remove("timestamp");
+    }
+    if let Some(actions) = value.get_mut("actions").and_then(Value::as_object_mut) {
+        for action in actions.values_mut().filter_map(Value::as_object_mut) {
+            action.remove("timestamp");
+        }
+    }
+    value
+}
@@ -1,22 +1,29 @@
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, GameError};
+use genius_core::{game_rng, GameRng, Game, GameConfig, GameState, Observation, Observer, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, EmergenceEvent, EmergenceType, GameEvent, Result, ActionSchema, LegalAction, ParamSchema, Standings};
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 use rand::Rng;
 
+const DEFAULT_GENERALS: usize = 7;
+
+#[derive(Serialize, Deserialize)]
 pub struct ByzantineGenerals {
     n_generals: usize,
     n_traitors: usize,
     traitor_ids: HashSet<String>,
+    delivered_messages: Vec<Message>,
+    rng: GameRng,
 }
 
 impl Default for ByzantineGenerals {
     fn default() -> Self {
         Self {
-            n_generals: 7,
-            n_traitors: 2,
+            n_generals: DEFAULT_GENERALS,
+            n_traitors: (DEFAULT_GENERALS - 1) / 3,
             traitor_ids: HashSet::new(),
+            delivered_messages: Vec::new(),
+            rng: game_rng(None),
         }
     }
 }
@@ -27,12 +34,12 @@ impl ByzantineGenerals {
     }
     
     pub fn initialize_traitors(&mut self, players: &[String]) {
-        let mut rng = rand::thread_rng();
         let mut available_players = players.to_vec();
+        available_players.sort();
         
         // Randomly select traitors
         for _ in 0..self.n_traitors.min(players.len() / 3) {
-            if let Some(index) = (0..available_players.len()).nth(rng.gen_range(0..available_players.len())) {
+            if let Some(index) = (0..available_players.len()).nth(self.rng.random_range(0..available_players.len())) {
                 self.traitor_ids.insert(available_players.remove(index));
             }
         }
@@ -110,19 +117,21 @@ pub struct ConsensusResult {
 #[async_trait]
 impl Game for ByzantineGenerals {
     async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
-        // Extract player count from config
-        if let Some(player_count) = config.special_rules.get("n_generals") {
-            if let Ok(n) = player_count.parse::<usize>() {
-                self.n_generals = n;
-                self.n_traitors = (n - 1) / 3; // Byzantine fault tolerance
-            }
-        }
+        self.rng = game_rng(config.seed);
+        
+        let params = self.parameters().validate(&config.params)?;
+        self.n_generals = params.int("n_generals") as usize;
+        self.n_traitors = (self.n_generals - 1) / 3; // Byzantine fault tolerance
         
         Ok(GameState {
             game_id: Uuid::new_v4(),
             game_type: GameType::ByzantineGenerals,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
@@ -131,6 +140,8 @@ impl Game for ByzantineGenerals {
                 meta.insert("consensus_phase".to_string(), serde_json::json!("initializing"));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
     
@@ -145,7 +156,12 @@ impl Game for ByzantineGenerals {
         let mut all_messages: HashMap<String, Vec<Message>> = HashMap::new();
         let mut scores_delta = HashMap::new();
         
-        for (player_id, action) in &actions {
+        // Traitors draw from the RNG, so visit generals in a stable order
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        
+        for &player_id in &player_ids {
+            let action = &actions[player_id];
             // Parse messages from action data
             if let Some(messages) = action.data.get("messages").and_then(|m| m.as_array()) {
                 let mut player_messages = vec![];
@@ -161,7 +177,7 @@ impl Game for ByzantineGenerals {
                         };
                         
                         // Traitors can send conflicting messages
-                        let actual_decision = if self.is_traitor(player_id) && rand::thread_rng().gen_bool(0.5) {
+                        let actual_decision = if self.is_traitor(player_id) && self.rng.random_bool(0.5) {
                             match decision {
                                 Decision::Attack => Decision::Retreat,
                                 Decision::Retreat => Decision::Attack,
@@ -183,6 +199,9 @@ impl Game for ByzantineGenerals {
             }
         }
         
+        // Keep what actually arrived so each general can read their own inbox
+        self.delivered_messages = all_messages.values().flatten().cloned().collect();
+        
         // Verify consensus
         let consensus = self.verify_consensus(&all_messages);
         
@@ -192,7 +211,7 @@ impl Game for ByzantineGenerals {
         
         if consensus.consensus_reached {
             // Honest generals who participated win
-            for player_id in actions.keys() {
+            for &player_id in &player_ids {
                 if !self.is_traitor(player_id) {
                     winners.push(player_id.clone());
                     scores_delta.insert(player_id.clone(), 10);
@@ -204,7 +223,7 @@ impl Game for ByzantineGenerals {
             }
         } else {
             // Traitors win when consensus fails
-            for player_id in actions.keys() {
+            for &player_id in &player_ids {
                 if self.is_traitor(player_id) {
                     winners.push(player_id.clone());
                     scores_delta.insert(player_id.clone(), 15);
@@ -218,6 +237,19 @@ impl Game for ByzantineGenerals {
         // Check for collective emergence
         let emergence_detected = self.detect_emergence(&all_messages, &consensus);
         
+        let mut events = Vec::new();
+        if emergence_detected {
+            let mut involved_players: Vec<String> = actions.keys().cloned().collect();
+            involved_players.sort();
+            events.push(GameEvent::emergence(&EmergenceEvent {
+                round: state.round + 1,
+                event_type: EmergenceType::Custom("byzantine_resistance".to_string()),
+                description: "Collective achieved consensus despite Byzantine interference".to_string(),
+                emergence_score: 0.9,
+                involved_players,
+            }));
+        }
+        
         Ok(RoundResult {
             round: state.round + 1,
             actions: actions.clone(),
@@ -234,28 +266,99 @@ impl Game for ByzantineGenerals {
                 emergence_detected,
             },
             scores_delta,
+            events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
+    async fn get_observation(&self, state: &GameState, observer: &Observer) -> Observation {
+        let mut observation = Observation::public(state, observer.clone());
+        
+        // Messages are private channels: only the sender keeps their own orders in the history
+        for round in &mut observation.history {
+            round.actions.retain(|player_id, _| observer.is_player(player_id));
+        }
+        
+        let inbox: Vec<&Message> = self.delivered_messages.iter()
+            .filter(|msg| observer.is_player(&msg.to))
+            .collect();
+        let is_traitor = observer.player_id().is_some_and(|id| self.is_traitor(id));
+        
+        // Traitors know who their co-conspirators are; loyal generals do not
+        let known_traitors: Vec<&String> = if is_traitor {
+            let mut traitors: Vec<&String> = self.traitor_ids.iter().collect();
+            traitors.sort();
+            traitors
+        } else {
+            vec![]
+        };
+        
+        observation.with_view(serde_json::json!({
+            "n_generals": self.n_generals,
+            "n_traitors": self.n_traitors,
+            "inbox": inbox,
+            "is_traitor": is_traitor,
+            "known_traitors": known_traitors,
+        }))
+    }
+    
+    fn parameters(&self) -> ParamSchema {
+        ParamSchema::new()
+            .integer("n_generals", "Number of generals; up to a third of them are traitors", DEFAULT_GENERALS as i64, 4..=100)
+    }
+    
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Byzantine Generals")
+            .action("send", "Send your decision to other generals", serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "messages": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "to": { "type": "string" },
+                                "decision": { "enum": ["attack", "retreat"] }
+                            },
+                            "required": ["to", "decision"]
+                        }
+                    }
+                },
+                "required": ["messages"]
+            }))
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        let mut others: Vec<&String> = state.players().into_iter()
+            .filter(|p| p.as_str() != player_id)
+            .collect();
+        others.sort();
+        
+        ["attack", "retreat"].into_iter()
+            .map(|decision| {
+                let messages: Vec<serde_json::Value> = others.iter()
+                    .map(|to| serde_json::json!({ "to": to, "decision": decision }))
+                    .collect();
+                LegalAction::new("send", serde_json::json!({ "messages": messages }))
+            })
+            .collect()
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         state.round >= 10 || 
         state.scores.values().any(|&score| score >= 100 || score <= -50)
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        // Determine winner: highest score among honest generals
-        let mut best_honest_score = -1000;
-        let mut best_honest_player = "No winner".to_string();
+        // Honest generals and traitors each play as a team
+        let (traitors, honest): (Vec<&String>, Vec<&String>) = state.scores.keys()
+            .partition(|id| self.is_traitor(id));
+        let standings = Standings::new(&state.scores)
+            .team("honest", honest.into_iter().cloned())
+            .team("traitors", traitors.into_iter().cloned());
         
-        for (player_id, &score) in &state.scores {
-            if !self.is_traitor(player_id) && score > best_honest_score {
-                best_honest_score = score;
-                best_honest_player = player_id.clone();
-            }
-        }
-        
-        // Check if traitors collectively won
         let traitor_total: i32 = state.scores.iter()
             .filter(|(id, _)| self.is_traitor(id))
             .map(|(_, &score)| score)
@@ -266,43 +369,28 @@ impl Game for ByzantineGenerals {
             .map(|(_, &score)| score)
             .sum();
         
-        let winner = if traitor_total > honest_total {
-            "Traitors (Byzantine)".to_string()
-        } else {
-            best_honest_player
-        };
-        
-        // Count emergence events
-        let emergence_events: Vec<EmergenceEvent> = state.history.iter()
-            .enumerate()
-            .filter_map(|(i, round_result)| {
-                if round_result.outcome.emergence_detected {
-                    Some(EmergenceEvent {
-                        round: i as u32,
-                        event_type: "byzantine_resistance".to_string(),
-                        description: "Collective achieved consensus despite Byzantine interference".to_string(),
-                        emergence_score: 0.9,
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect();
         
-        let emergence_frequency = emergence_events.len() as f32 / state.round.max(1) as f32;
+        let emergence_frequency = state.history.iter()
+            .filter(|round_result| round_result.outcome.emergence_detected)
+            .count() as f32 / state.round.max(1) as f32;
         
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: state.scores.clone(),
             total_rounds: state.round,
-            emergence_events,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
+            emergence_events: vec![],
             analytics: GameAnalytics {
                 collective_coordination_score: self.calculate_coordination_score(state),
                 decision_diversity_index: 0.0, // TODO
                 strategic_depth: state.round as f32 / 10.0,
                 emergence_frequency,
                 performance_differential: (honest_total - traitor_total) as f32,
+                custom_metrics: HashMap::new(),
             },
         }
     }
@@ -1,30 +1,36 @@
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, GameError};
+use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EventKind, EmergenceEvent, EmergenceType, Result, ActionSchema, LegalAction, Turn, ParamSchema, Standings};
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
-const BOARD_SIZE: usize = 9;
-const KOMI: f32 = 5.5; // Compensation for white going second
+const DEFAULT_BOARD_SIZE: usize = 9;
+const DEFAULT_KOMI: f32 = 5.5; // Compensation for white going second
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 enum Stone {
     Empty,
     Black,
     White,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct MiniGoGame {
-    board: [[Stone; BOARD_SIZE]; BOARD_SIZE],
+    board: Vec<Vec<Stone>>,
+    board_size: usize,
+    komi: f32,
     current_player: Stone,
     captures: HashMap<String, usize>, // Player -> captured stones
     ko_point: Option<(usize, usize)>, // Prevent immediate recapture
     pass_count: usize,
     move_history: Vec<MoveRecord>,
     players: HashMap<String, Stone>, // Player ID -> Stone color
+    /// Players who left mid-game, with the round they left in
+    #[serde(default)]
+    forfeited: HashMap<String, u32>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct MoveRecord {
     #[allow(dead_code)]
     player: String,
@@ -32,7 +38,7 @@ struct MoveRecord {
     captures: Vec<(usize, usize)>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 enum GoAction {
     Place(usize, usize),
     Pass,
@@ -47,16 +53,27 @@ impl Default for MiniGoGame {
 impl MiniGoGame {
     pub fn new() -> Self {
         Self {
-            board: [[Stone::Empty; BOARD_SIZE]; BOARD_SIZE],
+            board: vec![vec![Stone::Empty; DEFAULT_BOARD_SIZE]; DEFAULT_BOARD_SIZE],
+            board_size: DEFAULT_BOARD_SIZE,
+            komi: DEFAULT_KOMI,
             current_player: Stone::Black,
             captures: HashMap::new(),
             ko_point: None,
             pass_count: 0,
             move_history: Vec::new(),
             players: HashMap::new(),
+            forfeited: HashMap::new(),
         }
     }
     
+    /// Color whose players have all left, conceding the game
+    fn resigned_color(&self) -> Option<Stone> {
+        [Stone::Black, Stone::White].into_iter().find(|&color| {
+            let mut seated = self.players.iter().filter(|(_, &c)| c == color).peekable();
+            seated.peek().is_some() && seated.all(|(id, _)| self.forfeited.contains_key(id))
+        })
+    }
+    
     fn count_liberties(&self, row: usize, col: usize) -> usize {
         let stone = self.board[row][col];
         if stone == Stone::Empty {
@@ -84,8 +101,8 @@ impl MiniGoGame {
             let new_row = row as i32 + dr;
             let new_col = col as i32 + dc;
             
-            if new_row >= 0 && new_row < BOARD_SIZE as i32 && 
-               new_col >= 0 && new_col < BOARD_SIZE as i32 {
+            if new_row >= 0 && new_row < self.board_size as i32 && 
+               new_col >= 0 && new_col < self.board_size as i32 {
                 let r = new_row as usize;
                 let c = new_col as usize;
                 
@@ -105,8 +122,8 @@ impl MiniGoGame {
     fn capture_opponent_stones(&mut self, opponent_stone: Stone) -> Vec<(usize, usize)> {
         let mut captured = Vec::new();
         
-        for row in 0..BOARD_SIZE {
-            for col in 0..BOARD_SIZE {
+        for row in 0..self.board_size {
+            for col in 0..self.board_size {
                 if self.board[row][col] == opponent_stone && self.count_liberties(row, col) == 0 {
                     captured.push((row, col));
                 }
@@ -135,14 +152,10 @@ impl MiniGoGame {
         }
         
         // Temporarily place stone
-        let mut temp_board = self.board;
-        temp_board[row][col] = stone;
+        let mut temp_game = self.clone();
+        temp_game.board[row][col] = stone;
         
         // Check for suicide (placing a stone with no liberties and not capturing)
-        let temp_game = MiniGoGame {
-            board: temp_board,
-            ..self.clone()
-        };
         
         let has_liberties = temp_game.count_liberties(row, col) > 0;
         
@@ -153,9 +166,9 @@ impl MiniGoGame {
             Stone::Empty => return false,
         };
         
-        let would_capture = (0..BOARD_SIZE).any(|r| {
-            (0..BOARD_SIZE).any(|c| {
-                temp_board[r][c] == opponent && temp_game.count_liberties(r, c) == 0
+        let would_capture = (0..self.board_size).any(|r| {
+            (0..self.board_size).any(|c| {
+                temp_game.board[r][c] == opponent && temp_game.count_liberties(r, c) == 0
             })
         });
         
@@ -167,8 +180,8 @@ impl MiniGoGame {
         let mut white_territory = 0;
         let mut visited = HashSet::new();
         
-        for row in 0..BOARD_SIZE {
-            for col in 0..BOARD_SIZE {
+        for row in 0..self.board_size {
+            for col in 0..self.board_size {
                 if self.board[row][col] == Stone::Empty && !visited.contains(&(row, col)) {
                     let (territory_owner, territory_size) = self.flood_fill_territory(row, col, &mut visited);
                     match territory_owner {
@@ -202,8 +215,8 @@ impl MiniGoGame {
                 let new_row = row as i32 + dr;
                 let new_col = col as i32 + dc;
                 
-                if new_row >= 0 && new_row < BOARD_SIZE as i32 && 
-                   new_col >= 0 && new_col < BOARD_SIZE as i32 {
+                if new_row >= 0 && new_row < self.board_size as i32 && 
+                   new_col >= 0 && new_col < self.board_size as i32 {
                     let r = new_row as usize;
                     let c = new_col as usize;
                     
@@ -256,9 +269,10 @@ impl MiniGoGame {
         if strategic_moves > 7 {
             Some(EmergenceEvent {
                 round: state.round,
-                event_type: "go_strategy_emergence".to_string(),
+                event_type: EmergenceType::Custom("go_strategy_emergence".to_string()),
                 description: "Players discovered advanced Go strategies".to_string(),
                 emergence_score: strategic_moves as f32 / 10.0,
+                involved_players: state.active_players().into_iter().cloned().collect(),
             })
         } else {
             None
@@ -266,34 +280,41 @@ impl MiniGoGame {
     }
 }
 
-// Implement Clone manually to avoid the array size issue
-impl Clone for MiniGoGame {
-    fn clone(&self) -> Self {
-        Self {
-            board: self.board,
-            current_player: self.current_player,
-            captures: self.captures.clone(),
-            ko_point: self.ko_point,
-            pass_count: self.pass_count,
-            move_history: self.move_history.clone(),
-            players: self.players.clone(),
-        }
-    }
-}
-
 #[async_trait]
 impl Game for MiniGoGame {
-    async fn initialize(&mut self, _config: GameConfig) -> Result<GameState> {
+    async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        let params = self.parameters().validate(&config.params)?;
+        self.board_size = params.int("board_size") as usize;
+        self.komi = params.float("komi") as f32;
+        self.board = vec![vec![Stone::Empty; self.board_size]; self.board_size];
+        
+        // Teams named "black" and "white" fix the colors up front instead of by first move
+        for team in &config.teams {
+            let color = match team.name.as_str() {
+                "black" => Stone::Black,
+                "white" => Stone::White,
+                _ => continue,
+            };
+            for member in &team.members {
+                self.players.insert(member.clone(), color);
+                self.captures.insert(member.clone(), 0);
+            }
+        }
+        
         Ok(GameState {
             game_id: Uuid::new_v4(),
             game_type: GameType::MiniGo,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
-                meta.insert("board_size".to_string(), serde_json::json!(BOARD_SIZE));
-                meta.insert("komi".to_string(), serde_json::json!(KOMI));
+                meta.insert("board_size".to_string(), serde_json::json!(self.board_size));
+                meta.insert("komi".to_string(), serde_json::json!(self.komi));
                 meta.insert("rules".to_string(), serde_json::json!({
                     "capture": "Remove opponent stones with no liberties",
                     "ko": "Cannot immediately recapture",
@@ -301,12 +322,16 @@ impl Game for MiniGoGame {
                 }));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
     
     async fn process_round(&mut self, state: &GameState, actions: HashMap<String, PlayerAction>) -> Result<RoundResult> {
-        // Assign colors to new players
-        for player_id in actions.keys() {
+        // Assign colors to new players, in a stable order
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        for player_id in player_ids {
             if !self.players.contains_key(player_id) {
                 let color = if self.players.is_empty() {
                     Stone::Black
@@ -314,7 +339,7 @@ impl Game for MiniGoGame {
                     Stone::White
                 } else {
                     // For more than 2 players, alternate
-                    if self.players.len() % 2 == 0 {
+                    if self.players.len().is_multiple_of(2) {
                         Stone::Black
                     } else {
                         Stone::White
@@ -327,17 +352,19 @@ impl Game for MiniGoGame {
         
         let mut round_captures = HashMap::new();
         let mut moves_made = Vec::new();
+        let mut events = Vec::new();
         
-        // Process moves in order (black first, then white)
+        // Process moves in order (black first, then white, then by player)
         let mut sorted_actions: Vec<_> = actions.iter().collect();
         sorted_actions.sort_by_key(|(id, _)| {
-            self.players.get(*id).map(|&color| {
+            let rank = self.players.get(*id).map(|&color| {
                 match color {
                     Stone::Black => 0,
                     Stone::White => 1,
                     Stone::Empty => 2,
                 }
-            }).unwrap_or(3)
+            }).unwrap_or(3);
+            (rank, *id)
         });
         
         for (player_id, action) in sorted_actions {
@@ -360,7 +387,7 @@ impl Game for MiniGoGame {
                         (0, 0) // Default position
                     };
                     
-                    if row < BOARD_SIZE && col < BOARD_SIZE && 
+                    if row < self.board_size && col < self.board_size && 
                        self.is_valid_move(row, col, player_color) {
                         // Place stone
                         self.board[row][col] = player_color;
@@ -384,6 +411,12 @@ impl Game for MiniGoGame {
                         // Update captures count
                         *self.captures.entry(player_id.clone()).or_insert(0) += captured.len();
                         round_captures.insert(player_id.clone(), captured.len());
+                        if !captured.is_empty() {
+                            events.push(GameEvent::new(
+                                EventKind::Captured { player_id: player_id.clone(), count: captured.len() as u32 },
+                                format!("{} captured {} stone(s)", player_id, captured.len()),
+                            ));
+                        }
                         
                         // Record move
                         self.move_history.push(MoveRecord {
@@ -399,6 +432,15 @@ impl Game for MiniGoGame {
             }
         }
         
+        // The opponent of whoever moved last is next to play
+        if let Some(&color) = moves_made.last().and_then(|(player_id, _)| self.players.get(player_id)) {
+            self.current_player = match color {
+                Stone::Black => Stone::White,
+                Stone::White => Stone::Black,
+                Stone::Empty => self.current_player,
+            };
+        }
+        
         // Calculate scores
         let mut scores_delta = HashMap::new();
         
@@ -410,7 +452,7 @@ impl Game for MiniGoGame {
                 let captures = self.captures.get(player_id).copied().unwrap_or(0);
                 let score = match color {
                     Stone::Black => black_territory + captures,
-                    Stone::White => white_territory + captures + KOMI as usize,
+                    Stone::White => white_territory + captures + self.komi as usize,
                     Stone::Empty => 0,
                 };
                 scores_delta.insert(player_id.clone(), score as i32);
@@ -427,15 +469,17 @@ impl Game for MiniGoGame {
         
         // Determine winners and losers for this round
         let max_score = scores_delta.values().max().copied().unwrap_or(0);
-        let winners: Vec<String> = scores_delta.iter()
+        let mut winners: Vec<String> = scores_delta.iter()
             .filter(|(_, &score)| score == max_score && score > 0)
             .map(|(id, _)| id.clone())
             .collect();
         
-        let losers: Vec<String> = scores_delta.iter()
+        let mut losers: Vec<String> = scores_delta.iter()
             .filter(|(_, &score)| score < max_score)
             .map(|(id, _)| id.clone())
             .collect();
+        winners.sort();
+        losers.sort();
         
         let mut special_events = vec![];
         if self.pass_count >= 2 {
@@ -443,6 +487,7 @@ impl Game for MiniGoGame {
         }
         if let Some(event) = &emergence_event {
             special_events.push(event.description.clone());
+            events.push(GameEvent::emergence(event));
         }
         
         Ok(RoundResult {
@@ -455,12 +500,72 @@ impl Game for MiniGoGame {
                 emergence_detected: emergence_event.is_some(),
             },
             scores_delta,
+            events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
+    fn parameters(&self) -> ParamSchema {
+        ParamSchema::new()
+            .integer("board_size", "Width and height of the board", DEFAULT_BOARD_SIZE as i64, 5..=19)
+            .float("komi", "Points added to white's score for moving second", DEFAULT_KOMI as f64, 0.0..=15.0)
+    }
+    
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Mini Go")
+            .action("place", "Place a stone on an empty intersection", serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "row": { "type": "integer", "minimum": 0, "maximum": self.board_size - 1 },
+                    "col": { "type": "integer", "minimum": 0, "maximum": self.board_size - 1 }
+                },
+                "required": ["row", "col"]
+            }))
+            .simple_action("pass", "Pass your turn; two consecutive passes end the game")
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        // Players without a color yet get the next one in the assignment order
+        let color = self.players.get(player_id).copied().unwrap_or(
+            if self.players.len().is_multiple_of(2) { Stone::Black } else { Stone::White }
+        );
+        
+        let mut actions = Vec::new();
+        for row in 0..self.board_size {
+            for col in 0..self.board_size {
+                if self.is_valid_move(row, col, color) {
+                    actions.push(LegalAction::new("place", serde_json::json!({ "row": row, "col": col })));
+                }
+            }
+        }
+        actions.push(LegalAction::simple("pass"));
+        actions
+    }
+    
+    async fn current_turn(&self, _state: &GameState) -> Turn {
+        // Colors are handed out as players first act, so the game is open until both are seated
+        if self.players.len() < 2 {
+            return Turn::simultaneous(Vec::new());
+        }
+        
+        let mut to_move: Vec<String> = self.players.iter()
+            .filter(|(id, &color)| color == self.current_player && !self.forfeited.contains_key(*id))
+            .map(|(id, _)| id.clone())
+            .collect();
+        to_move.sort();
+        Turn::one_of(to_move)
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
-        self.pass_count >= 2 || state.round >= 200
+        self.pass_count >= 2 || state.round >= 200 || self.resigned_color().is_some()
+    }
+    
+    async fn forfeit(&mut self, state: &GameState, player_id: &str) {
+        if self.players.contains_key(player_id) {
+            self.forfeited.entry(player_id.to_string()).or_insert(state.round);
+        }
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
@@ -480,7 +585,7 @@ impl Game for MiniGoGame {
                     s
                 }
                 Stone::White => {
-                    let s = white_territory + captures + KOMI as usize;
+                    let s = white_territory + captures + self.komi as usize;
                     white_total += s;
                     s
                 }
@@ -489,29 +594,29 @@ impl Game for MiniGoGame {
             final_scores.insert(player_id.clone(), score as i32);
         }
         
-        let winner = final_scores.iter()
-            .max_by_key(|(_, score)| *score)
-            .map(|(id, _)| id.clone())
-            .unwrap_or_else(|| "No winner".to_string());
-        
-        // Collect emergence events
-        let emergence_events: Vec<EmergenceEvent> = state.history.iter()
-            .enumerate()
-            .filter_map(|(i, round_result)| {
-                if round_result.outcome.emergence_detected {
-                    Some(EmergenceEvent {
-                        round: i as u32,
-                        event_type: "go_strategy".to_string(),
-                        description: "Advanced Go strategies emerged".to_string(),
-                        emergence_score: 0.8,
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect();
+        // A color whose players have all left concedes, scoring nothing
+        let resigned = self.resigned_color();
+        for (player_id, &color) in &self.players {
+            if Some(color) == resigned {
+                final_scores.insert(player_id.clone(), 0);
+            }
+        }
+        
+        // Equal totals are a draw; with several players per color each color plays as a team.
+        // Anyone who left places below everyone who stayed
+        let mut standings = self.forfeited.iter()
+            .fold(Standings::new(&final_scores), |standings, (player, &round)| standings.eliminated(player, round));
+        if self.players.len() > 2 {
+            for (team, color) in [("black", Stone::Black), ("white", Stone::White)] {
+                let members = self.players.iter().filter(|(_, &c)| c == color).map(|(p, _)| p.clone());
+                standings = standings.team(team, members);
+            }
+        }
+        
         
-        let emergence_frequency = emergence_events.len() as f32 / state.round.max(1) as f32;
+        let emergence_frequency = state.history.iter()
+            .filter(|round_result| round_result.outcome.emergence_detected)
+            .count() as f32 / state.round.max(1) as f32;
         
         // Calculate strategy depth based on move patterns
         let strategic_moves = self.move_history.iter()
@@ -524,16 +629,21 @@ impl Game for MiniGoGame {
         
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores,
             total_rounds: state.round,
-            emergence_events,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
+            emergence_events: vec![],
             analytics: GameAnalytics {
                 collective_coordination_score: 0.0, // Not applicable for Go
                 decision_diversity_index: 0.5, // Could analyze move variety
                 strategic_depth,
                 emergence_frequency,
                 performance_differential: (black_total as f32 - white_total as f32).abs(),
+                custom_metrics: HashMap::new(),
             },
         }
     }
@@ -1,14 +1,15 @@
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, GameError};
+use genius_core::{game_rng, GameRng, Game, GameConfig, GameState, Observation, Observer, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EventKind, EmergenceEvent, EmergenceType, Result, GameError, ActionSchema, LegalAction, Turn, ParamSchema, Standings};
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 use rand::seq::SliceRandom;
 
-const STARTING_CHIPS: i32 = 1000;
-const SMALL_BLIND: i32 = 10;
-const BIG_BLIND: i32 = 20;
+const DEFAULT_STARTING_CHIPS: i32 = 1000;
+const DEFAULT_SMALL_BLIND: i32 = 10;
+const DEFAULT_BIG_BLIND: i32 = 20;
 
+#[derive(Serialize, Deserialize)]
 pub struct MiniHoldemGame {
     chips: HashMap<String, i32>,
     hands: HashMap<String, Hand>,
@@ -22,32 +23,38 @@ pub struct MiniHoldemGame {
     current_player_idx: usize,
     betting_round: BettingRound,
     hand_history: Vec<HandResult>,
+    /// Round in which each busted player lost their last chip
+    busted: HashMap<String, u32>,
     deck: Vec<Card>,
+    starting_chips: i32,
+    small_blind: i32,
+    big_blind: i32,
+    rng: GameRng,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 struct Card {
     rank: Rank,
     suit: Suit,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 enum Rank {
     Two = 2, Three = 3, Four = 4, Five = 5, Six = 6, Seven = 7,
     Eight = 8, Nine = 9, Ten = 10, Jack = 11, Queen = 12, King = 13, Ace = 14,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 enum Suit {
     Hearts, Diamonds, Clubs, Spades,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct Hand {
     cards: Vec<Card>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 enum BettingRound {
     PreFlop,
     Flop,
@@ -56,7 +63,7 @@ enum BettingRound {
     Showdown,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct HandResult {
     #[allow(dead_code)]
     winner: String,
@@ -68,7 +75,7 @@ struct HandResult {
     bluff_success: bool,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 enum HandRank {
     HighCard = 0,
     Pair = 1,
@@ -89,8 +96,9 @@ impl Default for MiniHoldemGame {
 
 impl MiniHoldemGame {
     pub fn new() -> Self {
+        let mut rng = game_rng(None);
         let mut deck = Self::create_deck();
-        deck.shuffle(&mut rand::thread_rng());
+        deck.shuffle(&mut rng);
         
         Self {
             chips: HashMap::new(),
@@ -105,7 +113,12 @@ impl MiniHoldemGame {
             current_player_idx: 0,
             betting_round: BettingRound::PreFlop,
             hand_history: Vec::new(),
+            busted: HashMap::new(),
             deck,
+            starting_chips: DEFAULT_STARTING_CHIPS,
+            small_blind: DEFAULT_SMALL_BLIND,
+            big_blind: DEFAULT_BIG_BLIND,
+            rng,
         }
     }
     
@@ -128,7 +141,7 @@ impl MiniHoldemGame {
     fn deal_cards(&mut self) {
         // Reset deck and shuffle
         self.deck = Self::create_deck();
-        self.deck.shuffle(&mut rand::thread_rng());
+        self.deck.shuffle(&mut self.rng);
         
         // Deal 2 cards to each player
         for player in &self.active_players.clone() {
@@ -155,14 +168,14 @@ impl MiniHoldemGame {
             let small_blind_player = self.active_players[small_blind_idx].clone();
             let big_blind_player = self.active_players[big_blind_idx].clone();
             
-            self.player_bets.insert(small_blind_player.clone(), SMALL_BLIND);
-            self.player_bets.insert(big_blind_player.clone(), BIG_BLIND);
+            self.player_bets.insert(small_blind_player.clone(), self.small_blind);
+            self.player_bets.insert(big_blind_player.clone(), self.big_blind);
             
-            *self.chips.get_mut(&small_blind_player).unwrap() -= SMALL_BLIND;
-            *self.chips.get_mut(&big_blind_player).unwrap() -= BIG_BLIND;
+            *self.chips.get_mut(&small_blind_player).unwrap() -= self.small_blind;
+            *self.chips.get_mut(&big_blind_player).unwrap() -= self.big_blind;
             
-            self.pot = SMALL_BLIND + BIG_BLIND;
-            self.current_bet = BIG_BLIND;
+            self.pot = self.small_blind + self.big_blind;
+            self.current_bet = self.big_blind;
             self.current_player_idx = (big_blind_idx + 1) % self.active_players.len();
         }
     }
@@ -220,6 +233,29 @@ impl MiniHoldemGame {
         false
     }
     
+    /// Give a new player a starting stack and the next seat at the table
+    fn seat_player(&mut self, player_id: &str) {
+        if !self.chips.contains_key(player_id) {
+            self.chips.insert(player_id.to_string(), self.starting_chips);
+            self.active_players.push(player_id.to_string());
+        }
+    }
+    
+    /// Player whose bet is next, skipping anyone folded or already all-in
+    fn player_to_act(&self) -> Option<&String> {
+        let seats = self.active_players.len();
+        (0..seats)
+            .map(|offset| &self.active_players[(self.current_player_idx + offset) % seats])
+            .find(|p| !self.folded_players.contains(*p) && self.chips.get(*p).copied().unwrap_or(0) > 0)
+    }
+    
+    /// Pass the action to the seat after the given player
+    fn pass_action_from(&mut self, player_id: &str) {
+        if let Some(seat) = self.active_players.iter().position(|p| p == player_id) {
+            self.current_player_idx = (seat + 1) % self.active_players.len();
+        }
+    }
+    
     fn advance_betting_round(&mut self) {
         self.betting_round = match self.betting_round {
             BettingRound::PreFlop => {
@@ -268,7 +304,7 @@ impl MiniHoldemGame {
                                 all_cards[l], all_cards[m]
                             ];
                             let rank = self.classify_hand(&hand);
-                            if rank > best_rank {
+                            if best_cards.is_empty() || rank > best_rank {
                                 best_rank = rank;
                                 best_cards = hand;
                             }
@@ -321,7 +357,8 @@ impl MiniHoldemGame {
             if !self.folded_players.contains(player) {
                 if let Some(hand) = self.hands.get(player) {
                     let (rank, cards) = self.evaluate_hand(&hand.cards, &self.community_cards);
-                    if rank > best_rank || (rank == best_rank && self.compare_hands(&cards, &best_cards) > 0) {
+                    // The first hand looked at leads until something beats it, high card or not
+                    if best_cards.is_empty() || rank > best_rank || (rank == best_rank && self.compare_hands(&cards, &best_cards) > 0) {
                         best_rank = rank;
                         best_cards = cards;
                         best_player = player.clone();
@@ -379,9 +416,10 @@ impl MiniHoldemGame {
         if bluff_rate > 0.3 && bluff_success_rate > 0.5 {
             Some(EmergenceEvent {
                 round: state.round,
-                event_type: "poker_psychology".to_string(),
+                event_type: EmergenceType::Custom("poker_psychology".to_string()),
                 description: "Players mastered bluffing and psychological warfare".to_string(),
                 emergence_score: bluff_success_rate,
+                involved_players: state.active_players().into_iter().cloned().collect(),
             })
         } else {
             None
@@ -391,18 +429,36 @@ impl MiniHoldemGame {
 
 #[async_trait]
 impl Game for MiniHoldemGame {
-    async fn initialize(&mut self, _config: GameConfig) -> Result<GameState> {
+    async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        self.rng = game_rng(config.seed);
+        self.deck = Self::create_deck();
+        self.deck.shuffle(&mut self.rng);
+        
+        let params = self.parameters().validate(&config.params)?;
+        self.starting_chips = params.int("starting_chips") as i32;
+        self.small_blind = params.int("small_blind") as i32;
+        self.big_blind = params.int("big_blind") as i32;
+        if self.big_blind < self.small_blind {
+            return Err(GameError::ConfigError {
+                reason: format!("big_blind ({}) must be at least small_blind ({})", self.big_blind, self.small_blind),
+            });
+        }
+        
         Ok(GameState {
             game_id: Uuid::new_v4(),
             game_type: GameType::MiniHoldem,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
-                meta.insert("starting_chips".to_string(), serde_json::json!(STARTING_CHIPS));
-                meta.insert("small_blind".to_string(), serde_json::json!(SMALL_BLIND));
-                meta.insert("big_blind".to_string(), serde_json::json!(BIG_BLIND));
+                meta.insert("starting_chips".to_string(), serde_json::json!(self.starting_chips));
+                meta.insert("small_blind".to_string(), serde_json::json!(self.small_blind));
+                meta.insert("big_blind".to_string(), serde_json::json!(self.big_blind));
                 meta.insert("game_rules".to_string(), serde_json::json!({
                     "betting": "No-limit hold'em",
                     "hand_rankings": "Standard poker rankings",
@@ -410,31 +466,33 @@ impl Game for MiniHoldemGame {
                 }));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
     
     async fn process_round(&mut self, state: &GameState, actions: HashMap<String, PlayerAction>) -> Result<RoundResult> {
-        // Initialize new players
-        for player_id in actions.keys() {
-            if !self.chips.contains_key(player_id) {
-                self.chips.insert(player_id.clone(), STARTING_CHIPS);
-                self.active_players.push(player_id.clone());
-            }
+        // Initialize new players, seated in a stable order
+        let mut new_players: Vec<&String> = actions.keys().collect();
+        new_players.sort();
+        for player_id in new_players {
+            self.seat_player(player_id);
         }
         
-        // Remove eliminated players
-        self.active_players.retain(|p| self.chips.get(p).copied().unwrap_or(0) > 0);
-        
         // Start new hand if needed
         if (self.betting_round == BettingRound::Showdown || self.hands.is_empty()) && self.active_players.len() >= 2 {
             self.deal_cards();
         }
         
-        // Process betting actions
+        // Process betting actions in a stable order
         let mut actions_processed = false;
         let mut round_complete = false;
         
-        for (player_id, action) in &actions {
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        
+        for player_id in player_ids {
+            let action = &actions[player_id];
             if !self.active_players.contains(player_id) {
                 continue;
             }
@@ -444,6 +502,7 @@ impl Game for MiniHoldemGame {
             
             if self.process_action(player_id, poker_action, amount) {
                 actions_processed = true;
+                self.pass_action_from(player_id);
             }
         }
         
@@ -471,6 +530,7 @@ impl Game for MiniHoldemGame {
         let mut winners = vec![];
         let mut scores_delta = HashMap::new();
         let mut special_events = vec![];
+        let mut events = Vec::new();
         
         if round_complete {
             if active_unfold == 1 || self.betting_round == BettingRound::River {
@@ -503,10 +563,28 @@ impl Game for MiniHoldemGame {
                     });
                     
                     special_events.push(format!("{} wins {} chips with {:?}", winner, self.pot, winning_rank));
+                    events.push(GameEvent::new(
+                        EventKind::PotWon { player_id: winner.clone(), amount: self.pot },
+                        format!("{} wins {} chips with {:?}", winner, self.pot, winning_rank),
+                    ));
                 }
                 
+                for player in &self.active_players {
+                    if self.chips.get(player).copied().unwrap_or(0) == 0 && !self.busted.contains_key(player) {
+                        self.busted.insert(player.clone(), state.round + 1);
+                        events.push(GameEvent::new(
+                            EventKind::Eliminated { player_id: player.clone(), by: Some(winner.clone()).filter(|w| !w.is_empty()) },
+                            format!("{} is out of chips", player),
+                        ));
+                    }
+                }
+                
+                // Players out of chips leave the table before the next hand; anyone all-in
+                // stays seated until the hand they are all-in for is settled
+                self.active_players.retain(|p| self.chips.get(p).copied().unwrap_or(0) > 0);
+                
                 // Start new hand
-                self.dealer_position = (self.dealer_position + 1) % self.active_players.len();
+                self.dealer_position = (self.dealer_position + 1) % self.active_players.len().max(1);
                 if self.active_players.len() >= 2 {
                     self.deal_cards();
                 }
@@ -520,6 +598,7 @@ impl Game for MiniHoldemGame {
         let emergence_event = self.detect_bluffing_emergence(state);
         if let Some(event) = &emergence_event {
             special_events.push(event.description.clone());
+            events.push(GameEvent::emergence(event));
         }
         
         // Determine losers (players who lost chips this round)
@@ -538,42 +617,136 @@ impl Game for MiniHoldemGame {
                 emergence_detected: emergence_event.is_some(),
             },
             scores_delta,
+            events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
+    async fn get_observation(&self, state: &GameState, observer: &Observer) -> Observation {
+        // Hole cards are only visible to the player holding them
+        let hole_cards = observer.player_id()
+            .and_then(|id| self.hands.get(id))
+            .map(|hand| hand.cards.clone());
+        
+        Observation::public(state, observer.clone()).with_view(serde_json::json!({
+            "chips": self.chips,
+            "pot": self.pot,
+            "current_bet": self.current_bet,
+            "player_bets": self.player_bets,
+            "folded_players": self.folded_players,
+            "active_players": self.active_players,
+            "dealer_position": self.dealer_position,
+            "betting_round": self.betting_round,
+            "community_cards": self.community_cards,
+            "hole_cards": hole_cards,
+        }))
+    }
+    
+    fn parameters(&self) -> ParamSchema {
+        ParamSchema::new()
+            .integer("starting_chips", "Chips each player is seated with", DEFAULT_STARTING_CHIPS as i64, 100..=1_000_000)
+            .integer("small_blind", "Forced bet of the player after the dealer", DEFAULT_SMALL_BLIND as i64, 1..=10_000)
+            .integer("big_blind", "Forced bet of the second player after the dealer; also the minimum raise", DEFAULT_BIG_BLIND as i64, 1..=10_000)
+    }
+    
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Mini Hold'em")
+            .simple_action("fold", "Give up the hand")
+            .simple_action("call", "Match the current bet (checks when nothing is owed)")
+            .action("raise", "Raise the current bet by at least its size", serde_json::json!({
+                "type": "integer",
+                "minimum": self.big_blind,
+                "description": "Chips added on top of the current bet, at least the current bet and the big blind; legal moves give each player's range"
+            }))
+            .simple_action("all-in", "Bet every chip you have")
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        if self.folded_players.contains(player_id) {
+            return vec![];
+        }
+        
+        let mut actions = vec![LegalAction::simple("fold"), LegalAction::simple("call")];
+        
+        let chips = self.chips.get(player_id).copied().unwrap_or(self.starting_chips);
+        let player_bet = self.player_bets.get(player_id).copied().unwrap_or(0);
+        let max_raise = chips + player_bet - self.current_bet;
+        let min_raise = self.current_bet.max(self.big_blind);
+        if min_raise <= max_raise {
+            actions.push(LegalAction::amount("raise", min_raise as i64, max_raise as i64));
+        }
+        
+        if chips > 0 {
+            actions.push(LegalAction::simple("all-in"));
+        }
+        actions
+    }
+    
+    async fn eliminated_players(&self, _state: &GameState) -> Vec<String> {
+        let mut out: Vec<String> = self.busted.keys().cloned().collect();
+        out.sort();
+        out
+    }
+    
+    async fn join(&mut self, _state: &GameState, player_id: &str) {
+        self.seat_player(player_id);
+    }
+    
+    async fn forfeit(&mut self, state: &GameState, player_id: &str) {
+        let Some(seat) = self.active_players.iter().position(|p| p == player_id) else {
+            return;
+        };
+        // The player folds and leaves the table with nothing
+        self.active_players.remove(seat);
+        self.folded_players.insert(player_id.to_string());
+        self.chips.insert(player_id.to_string(), 0);
+        self.busted.entry(player_id.to_string()).or_insert(state.round);
+        
+        // Keep the action with whoever was due to act next
+        if !self.active_players.is_empty() {
+            if seat < self.current_player_idx {
+                self.current_player_idx -= 1;
+            }
+            self.current_player_idx %= self.active_players.len();
+        }
+        
+        // The last player seated takes whatever is in the pot
+        if let [winner] = self.active_players.as_slice() {
+            *self.chips.entry(winner.clone()).or_insert(0) += std::mem::take(&mut self.pot);
+        }
+    }
+    
+    async fn current_turn(&self, _state: &GameState) -> Turn {
+        // Players take their seats in the opening round, then betting goes around the table
+        if self.active_players.len() < 2 {
+            return Turn::simultaneous(Vec::new());
+        }
+        // The first hand is dealt with the opening round, and the seat after the big blind opens it
+        if self.hands.is_empty() {
+            let seats = self.active_players.len();
+            return Turn::sequential(self.active_players[(self.dealer_position + 3) % seats].clone());
+        }
+        match self.player_to_act() {
+            Some(player) => Turn::sequential(player.clone()),
+            None => Turn::simultaneous(self.active_players.clone()),
+        }
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         self.active_players.len() <= 1 || state.round >= 200
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        let winner = if self.active_players.len() == 1 {
-            self.active_players[0].clone()
-        } else {
-            self.chips.iter()
-                .max_by_key(|(_, chips)| *chips)
-                .map(|(id, _)| id.clone())
-                .unwrap_or_else(|| "No winner".to_string())
-        };
+        // Busted players place by how long they lasted; equal stacks at the end share the win
+        let standings = self.busted.iter()
+            .fold(Standings::new(&self.chips), |standings, (player, &round)| standings.eliminated(player, round));
         
-        // Collect emergence events
-        let emergence_events: Vec<EmergenceEvent> = state.history.iter()
-            .enumerate()
-            .filter_map(|(i, round_result)| {
-                if round_result.outcome.emergence_detected {
-                    Some(EmergenceEvent {
-                        round: i as u32,
-                        event_type: "poker_psychology".to_string(),
-                        description: "Advanced poker psychology emerged".to_string(),
-                        emergence_score: 0.8,
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect();
         
-        let emergence_frequency = emergence_events.len() as f32 / state.round.max(1) as f32;
+        let emergence_frequency = state.history.iter()
+            .filter(|round_result| round_result.outcome.emergence_detected)
+            .count() as f32 / state.round.max(1) as f32;
         
         // Analyze hand history
         let total_hands = self.hand_history.len() as f32;
@@ -597,16 +770,21 @@ impl Game for MiniHoldemGame {
         
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: self.chips.clone(),
             total_rounds: state.round,
-            emergence_events,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
+            emergence_events: vec![],
             analytics: GameAnalytics {
                 collective_coordination_score: 0.0, // Not applicable
                 decision_diversity_index: showdown_rate, // Variety of play styles
                 strategic_depth: 1.0 - showdown_rate, // More folding = deeper strategy
                 emergence_frequency,
-                performance_differential: bluff_rate, // Bluffing success as key metric
+                performance_differential: bluff_rate, // Bluffing success as key metric,
+                custom_metrics: HashMap::new(),
             },
         }
     }
@@ -1,9 +1,10 @@
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, GameError};
+use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, ActionSchema, LegalAction, Standings};
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use uuid::Uuid;
 
+#[derive(Serialize, Deserialize)]
 pub struct MinorityGame {
     history_window: usize,
 }
@@ -64,11 +65,16 @@ impl MinorityGame {
                 
                 // Perfect distribution indicates emergence
                 if zeros > 0 && ones > 0 && (zeros as f32 - ones as f32).abs() < 2.0 {
+                    let mut involved_players: Vec<String> = collective_actions.iter()
+                        .map(|(id, _)| (*id).clone())
+                        .collect();
+                    involved_players.sort();
                     return Some(EmergenceEvent {
                         round: state.round,
-                        event_type: "perfect_distribution".to_string(),
+                        event_type: EmergenceType::SpontaneousCoordination,
                         description: "Collective achieved near-perfect minority distribution".to_string(),
                         emergence_score: 0.9,
+                        involved_players,
                     });
                 }
             }
@@ -85,12 +91,18 @@ impl Game for MinorityGame {
             game_type: GameType::MinorityGame,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
                 meta.insert("history_window".to_string(), serde_json::json!(self.history_window));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
     
@@ -104,7 +116,8 @@ impl Game for MinorityGame {
         }
         
         // Determine minority
-        let (winning_choice, winners) = self.calculate_minority(&choices);
+        let (winning_choice, mut winners) = self.calculate_minority(&choices);
+        winners.sort();
         
         // Calculate score changes
         let mut scores_delta = HashMap::new();
@@ -121,10 +134,11 @@ impl Game for MinorityGame {
         // Check for emergence
         let emergence_event = self.detect_emergence(state, &actions);
         
-        let losers: Vec<String> = choices.keys()
+        let mut losers: Vec<String> = choices.keys()
             .filter(|k| !winners.contains(k))
             .cloned()
             .collect();
+        losers.sort();
         
         Ok(RoundResult {
             round: state.round + 1,
@@ -140,37 +154,35 @@ impl Game for MinorityGame {
                 emergence_detected: emergence_event.is_some(),
             },
             scores_delta,
+            events: emergence_event.iter()
+                .map(GameEvent::emergence)
+                .collect(),
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Minority Game")
+            .action("choose", "Pick a side; players on the smaller side win", serde_json::json!({ "enum": [0, 1] }))
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, _player_id: &str) -> Vec<LegalAction> {
+        vec![
+            LegalAction::new("choose", serde_json::json!(0)),
+            LegalAction::new("choose", serde_json::json!(1)),
+        ]
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         state.round >= 100 || 
         state.scores.values().any(|&score| score >= 500 || score <= -200)
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        let winner = state.scores.iter()
-            .max_by_key(|(_, score)| *score)
-            .map(|(id, _)| id.clone())
-            .unwrap_or_else(|| "No winner".to_string());
+        let standings = Standings::new(&state.scores);
         
-        // Collect emergence events
-        let emergence_events: Vec<EmergenceEvent> = state.history.iter()
-            .enumerate()
-            .filter_map(|(i, round_result)| {
-                if round_result.outcome.emergence_detected {
-                    Some(EmergenceEvent {
-                        round: i as u32,
-                        event_type: "minority_distribution".to_string(),
-                        description: "Collective achieved optimal distribution".to_string(),
-                        emergence_score: 0.8,
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect();
         
         // Calculate analytics
         let collective_scores: Vec<i32> = state.scores.iter()
@@ -186,20 +198,44 @@ impl Game for MinorityGame {
         let avg_collective = collective_scores.iter().sum::<i32>() as f32 / collective_scores.len().max(1) as f32;
         let avg_single = single_scores.iter().sum::<i32>() as f32 / single_scores.len().max(1) as f32;
         
-        let emergence_frequency = emergence_events.len() as f32 / state.round.max(1) as f32;
+        let emergence_frequency = state.history.iter()
+            .filter(|round_result| round_result.outcome.emergence_detected)
+            .count() as f32 / state.round.max(1) as f32;
+        
+        // Strategic depth is how often players on the losing side switch for the next round
+        let mut losses = 0;
+        let mut switches = 0;
+        for rounds in state.history.windows(2) {
+            let (previous, next) = (&rounds[0], &rounds[1]);
+            for loser in &previous.outcome.losers {
+                let choice = |round: &RoundResult| round.actions.get(loser).and_then(|a| a.data.as_i64());
+                if let (Some(before), Some(after)) = (choice(previous), choice(next)) {
+                    losses += 1;
+                    if before != after {
+                        switches += 1;
+                    }
+                }
+            }
+        }
+        let strategic_depth = switches as f32 / losses.max(1) as f32;
         
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: state.scores.clone(),
             total_rounds: state.round,
-            emergence_events,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
+            emergence_events: vec![],
             analytics: GameAnalytics {
                 collective_coordination_score: 0.0, // TODO: Calculate from history
                 decision_diversity_index: 0.0, // TODO: Calculate diversity
-                strategic_depth: state.round as f32 / 100.0,
+                strategic_depth,
                 emergence_frequency,
                 performance_differential: avg_collective - avg_single,
+                custom_metrics: HashMap::new(),
             },
         }
     }
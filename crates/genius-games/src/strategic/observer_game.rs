@@ -1,9 +1,9 @@
 //! The Observer Game - Quantum measurement and observation effects
 
 use genius_core::{
-    Game, GameConfig, GameState, GameType, PlayerAction, RoundResult,
-    RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType,
-    Result, GameError,
+    game_rng, GameRng, Game, GameConfig, GameState, PlayerAction, RoundResult,
+    RoundOutcome, GameResult, GameAnalytics, GameEvent, EventKind, EmergenceEvent, EmergenceType,
+    Result, ActionSchema, LegalAction, Standings,
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -64,6 +64,7 @@ pub enum MeasurementType {
     Energy,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct ObserverGame {
     round_number: u32,
     quantum_states: HashMap<String, QuantumState>,
@@ -73,6 +74,7 @@ pub struct ObserverGame {
     decoherence_rate: f32,
     measurement_backaction: f32,
     quantum_objectives: Vec<QuantumObjective>,
+    rng: GameRng,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -93,6 +95,7 @@ impl ObserverGame {
             decoherence_rate: 0.05,
             measurement_backaction: 0.3,
             quantum_objectives: Self::generate_objectives(),
+            rng: game_rng(None),
         }
     }
     
@@ -116,8 +119,8 @@ impl ObserverGame {
         ]
     }
     
-    fn initialize_quantum_field(&mut self, num_players: usize) {
-        let mut rng = rand::rng();
+    fn initialize_quantum_field(&mut self, _num_players: usize) {
+        let rng = &mut self.rng;
         
         // Create quantum states in superposition
         for i in 0..self.total_states {
@@ -183,20 +186,19 @@ impl ObserverGame {
         
         if state.is_collapsed {
             return Some(GameEvent {
-                event_type: "already_collapsed".to_string(),
-                description: format!("{} observed already collapsed state {}", observer_id, state_id),
-                affected_players: vec![observer_id.to_string()],
-                data: serde_json::json!({
+                kind: EventKind::custom("already_collapsed", serde_json::json!({
                     "state_id": state_id,
                     "collapsed_value": state.collapsed_state,
-                }),
+                })),
+                description: format!("{} observed already collapsed state {}", observer_id, state_id),
+                affected_players: vec![observer_id.to_string()],
             });
         }
         
         observer.observations_made += 1;
         
         // Observation collapses the state based on probabilities
-        let mut rng = rand::rng();
+        let rng = &mut self.rng;
         let observation_strength = observer.observation_power * (1.0 - self.measurement_backaction);
         
         if rng.random::<f32>() < observation_strength {
@@ -204,55 +206,57 @@ impl ObserverGame {
             let random_val = rng.random::<f32>();
             let mut cumulative_prob = 0.0;
             
-            for possible in &state.superposition {
-                cumulative_prob += possible.amplitude * possible.amplitude;
-                if random_val < cumulative_prob {
-                    state.collapsed_state = Some(possible.state_value.clone());
-                    state.is_collapsed = true;
-                    self.collapsed_count += 1;
-                    observer.collapsed_states.push(state_id.to_string());
-                    
-                    // Collapse entangled states
-                    let entangled = state.entangled_with.clone();
-                    for entangled_id in entangled {
-                        if let Some(entangled_state) = self.quantum_states.get_mut(&entangled_id) {
-                            if !entangled_state.is_collapsed {
-                                // Entangled states collapse to correlated values
-                                entangled_state.collapsed_state = Some(
-                                    if possible.state_value == "0" { "1" } else { "0" }.to_string()
-                                );
-                                entangled_state.is_collapsed = true;
-                                self.collapsed_count += 1;
-                            }
+            let collapsed_to = state.superposition.iter()
+                .find(|possible| {
+                    cumulative_prob += possible.amplitude * possible.amplitude;
+                    random_val < cumulative_prob
+                })
+                .map(|possible| possible.state_value.clone());
+            
+            if let Some(collapsed_to) = collapsed_to {
+                state.collapsed_state = Some(collapsed_to.clone());
+                state.is_collapsed = true;
+                self.collapsed_count += 1;
+                observer.collapsed_states.push(state_id.to_string());
+                
+                // Collapse entangled states
+                let entangled = state.entangled_with.clone();
+                for entangled_id in &entangled {
+                    if let Some(entangled_state) = self.quantum_states.get_mut(entangled_id) {
+                        if !entangled_state.is_collapsed {
+                            // Entangled states collapse to correlated values
+                            entangled_state.collapsed_state = Some(
+                                if collapsed_to == "0" { "1" } else { "0" }.to_string()
+                            );
+                            entangled_state.is_collapsed = true;
+                            self.collapsed_count += 1;
                         }
                     }
-                    
-                    return Some(GameEvent {
-                        event_type: "observation_collapse".to_string(),
-                        description: format!("{} collapsed {} to {}", 
-                            observer_id, state_id, possible.state_value),
-                        affected_players: vec![observer_id.to_string()],
-                        data: serde_json::json!({
-                            "state_id": state_id,
-                            "collapsed_to": possible.state_value,
-                            "measurement_type": format!("{:?}", measurement_type),
-                            "entangled_collapsed": state.entangled_with.len(),
-                        }),
-                    });
                 }
+                
+                return Some(GameEvent {
+                    kind: EventKind::custom("observation_collapse", serde_json::json!({
+                        "state_id": state_id,
+                        "collapsed_to": collapsed_to,
+                        "measurement_type": format!("{:?}", measurement_type),
+                        "entangled_collapsed": entangled.len(),
+                    })),
+                    description: format!("{} collapsed {} to {}", 
+                        observer_id, state_id, collapsed_to),
+                    affected_players: vec![observer_id.to_string()],
+                });
             }
         } else {
             // Weak measurement - disturb but don't collapse
             state.coherence *= 1.0 - self.measurement_backaction;
             
             return Some(GameEvent {
-                event_type: "weak_measurement".to_string(),
-                description: format!("{} performed weak measurement on {}", observer_id, state_id),
-                affected_players: vec![observer_id.to_string()],
-                data: serde_json::json!({
+                kind: EventKind::custom("weak_measurement", serde_json::json!({
                     "state_id": state_id,
                     "coherence": state.coherence,
-                }),
+                })),
+                description: format!("{} performed weak measurement on {}", observer_id, state_id),
+                affected_players: vec![observer_id.to_string()],
             });
         }
         
@@ -286,8 +290,7 @@ impl ObserverGame {
         }
         
         // Create entanglement with probability based on mastery
-        let mut rng = rand::rng();
-        if rng.random::<f32>() < observer.entanglement_mastery {
+        if self.rng.random::<f32>() < observer.entanglement_mastery {
             // Entangle the states
             for i in 0..state_ids.len() {
                 for j in 0..state_ids.len() {
@@ -302,36 +305,38 @@ impl ObserverGame {
             }
             
             Some(GameEvent {
-                event_type: "entanglement_created".to_string(),
+                kind: EventKind::custom("entanglement_created", serde_json::json!({
+                    "entangled_states": state_ids,
+                })),
                 description: format!("{} entangled {} states", observer_id, state_ids.len()),
                 affected_players: vec![observer_id.to_string()],
-                data: serde_json::json!({
-                    "entangled_states": state_ids,
-                }),
             })
         } else {
             Some(GameEvent {
-                event_type: "entanglement_failed".to_string(),
+                kind: EventKind::custom("entanglement_failed", serde_json::json!({})),
                 description: format!("{} failed to create entanglement", observer_id),
                 affected_players: vec![observer_id.to_string()],
-                data: serde_json::json!({}),
             })
         }
     }
     
     fn apply_decoherence(&mut self) {
-        let mut rng = rand::rng();
+        // Visit states in a stable order so random collapses are reproducible
+        let mut state_ids: Vec<String> = self.quantum_states.keys().cloned().collect();
+        state_ids.sort();
         
-        for state in self.quantum_states.values_mut() {
-            if !state.is_collapsed {
-                state.coherence *= 1.0 - self.decoherence_rate;
-                
-                // Random collapse if coherence too low
-                if state.coherence < 0.3 && rng.random::<f32>() < 0.5 {
-                    let random_idx = rng.random_range(0..state.superposition.len());
-                    state.collapsed_state = Some(state.superposition[random_idx].state_value.clone());
-                    state.is_collapsed = true;
-                    self.collapsed_count += 1;
+        for state_id in state_ids {
+            if let Some(state) = self.quantum_states.get_mut(&state_id) {
+                if !state.is_collapsed {
+                    state.coherence *= 1.0 - self.decoherence_rate;
+                    
+                    // Random collapse if coherence too low
+                    if state.coherence < 0.3 && self.rng.random::<f32>() < 0.5 {
+                        let random_idx = self.rng.random_range(0..state.superposition.len());
+                        state.collapsed_state = Some(state.superposition[random_idx].state_value.clone());
+                        state.is_collapsed = true;
+                        self.collapsed_count += 1;
+                    }
                 }
             }
         }
@@ -345,7 +350,7 @@ impl ObserverGame {
                 match objective.description.as_str() {
                     "Create entangled pair" => {
                         let has_entangled = self.quantum_states.values()
-                            .any(|s| s.entangled_with.len() >= 1 && 
+                            .any(|s| !s.entangled_with.is_empty() && 
                                     observer.collapsed_states.contains(&s.id));
                         completed.push((objective.clone(), has_entangled));
                     }
@@ -364,7 +369,7 @@ impl ObserverGame {
     
     fn detect_quantum_emergence(&self) -> Option<EmergenceEvent> {
         let entangled_ratio = self.quantum_states.values()
-            .filter(|s| s.entangled_with.len() > 0)
+            .filter(|s| !s.entangled_with.is_empty())
             .count() as f32 / self.total_states as f32;
             
         let superposition_ratio = self.quantum_states.values()
@@ -372,12 +377,14 @@ impl ObserverGame {
             .count() as f32 / self.total_states as f32;
             
         if entangled_ratio > 0.5 && superposition_ratio > 0.3 {
+            let mut involved_players: Vec<String> = self.observers.keys().cloned().collect();
+            involved_players.sort();
             Some(EmergenceEvent {
                 round: self.round_number,
                 event_type: EmergenceType::Custom("QuantumCoherence".to_string()),
                 description: "Quantum field achieving macroscopic coherence!".to_string(),
                 emergence_score: (entangled_ratio + superposition_ratio) / 2.0,
-                involved_players: self.observers.keys().cloned().collect(),
+                involved_players,
             })
         } else {
             None
@@ -394,6 +401,7 @@ impl Default for ObserverGame {
 #[async_trait]
 impl Game for ObserverGame {
     async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        self.rng = game_rng(config.seed);
         let mut state = GameState::new(config.game_type.clone());
         
         let player_ids: Vec<String> = config.initial_players.iter()
@@ -431,14 +439,18 @@ impl Game for ObserverGame {
         // Apply natural decoherence
         self.apply_decoherence();
         
-        // Process observer actions
-        for (player_id, action) in &actions {
+        // Process observer actions in a stable order
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        
+        for player_id in player_ids {
+            let action = &actions[player_id];
             if let Ok(obs_action) = serde_json::from_value::<ObserverAction>(action.data.clone()) {
                 match obs_action.action_type {
                     ObserverActionType::Observe => {
                         if let (Some(target), Some(mtype)) = (obs_action.target_state, obs_action.measurement_type) {
                             if let Some(event) = self.perform_observation(player_id, &target, &mtype) {
-                                let is_collapse = event.event_type == "observation_collapse";
+                                let is_collapse = event.event_type() == "observation_collapse";
                                 events.push(event);
                                 
                                 if is_collapse {
@@ -452,7 +464,7 @@ impl Game for ObserverGame {
                     ObserverActionType::EntangleStates => {
                         if let Some(targets) = obs_action.entangle_with {
                             if let Some(event) = self.create_entanglement(player_id, targets) {
-                                let is_success = event.event_type == "entanglement_created";
+                                let is_success = event.event_type() == "entanglement_created";
                                 events.push(event);
                                 
                                 if is_success {
@@ -472,10 +484,9 @@ impl Game for ObserverGame {
                             scores_delta.insert(player_id.clone(), 5);
                             
                             events.push(GameEvent {
-                                event_type: "quantum_vision".to_string(),
+                                kind: EventKind::custom("quantum_vision", serde_json::json!({})),
                                 description: format!("{} gained quantum vision", player_id),
                                 affected_players: vec![player_id.clone()],
-                                data: serde_json::json!({}),
                             });
                         }
                     }
@@ -483,24 +494,21 @@ impl Game for ObserverGame {
                         // Attempt to uncollapse a state (very difficult)
                         if let Some(target) = obs_action.target_state {
                             if let Some(state) = self.quantum_states.get_mut(&target) {
-                                if state.is_collapsed && state.coherence > 0.5 {
-                                    let mut rng = rand::rng();
-                                    if rng.random::<f32>() < 0.1 { // 10% chance
+                                if state.is_collapsed && state.coherence > 0.5
+                                    && self.rng.random::<f32>() < 0.1 { // 10% chance
                                         state.is_collapsed = false;
                                         state.collapsed_state = None;
                                         self.collapsed_count -= 1;
                                         
                                         scores_delta.insert(player_id.clone(), 30);
                                         events.push(GameEvent {
-                                            event_type: "quantum_erasure".to_string(),
+                                            kind: EventKind::custom("quantum_erasure", serde_json::json!({
+                                                "erased_state": target,
+                                            })),
                                             description: format!("{} erased quantum measurement!", player_id),
                                             affected_players: vec![player_id.clone()],
-                                            data: serde_json::json!({
-                                                "erased_state": target,
-                                            }),
                                         });
                                     }
-                                }
                             }
                         }
                     }
@@ -512,20 +520,21 @@ impl Game for ObserverGame {
             }
         }
         
-        // Check objectives
-        for (player_id, observer) in &self.observers {
+        // Check objectives, observer by observer in a stable order
+        let mut observer_ids: Vec<&String> = self.observers.keys().collect();
+        observer_ids.sort();
+        for player_id in observer_ids {
             let completed_objectives = self.check_quantum_objectives(player_id);
             for (objective, completed) in completed_objectives {
                 if completed {
                     *scores_delta.entry(player_id.clone()).or_insert(0) += objective.reward;
                     
                     events.push(GameEvent {
-                        event_type: "objective_complete".to_string(),
+                        kind: EventKind::custom("objective_complete", serde_json::json!({
+                            "reward": objective.reward,
+                        })),
                         description: format!("{} completed: {}", player_id, objective.description),
                         affected_players: vec![player_id.clone()],
-                        data: serde_json::json!({
-                            "reward": objective.reward,
-                        }),
                     });
                 }
             }
@@ -535,14 +544,7 @@ impl Game for ObserverGame {
         let mut emergence_detected = false;
         if let Some(emergence) = self.detect_quantum_emergence() {
             emergence_detected = true;
-            events.push(GameEvent {
-                event_type: "emergence".to_string(),
-                description: emergence.description.clone(),
-                affected_players: emergence.involved_players.clone(),
-                data: serde_json::json!({
-                    "emergence_score": emergence.emergence_score,
-                }),
-            });
+            events.push(GameEvent::emergence(&emergence));
             
             // Bonus for maintaining quantum coherence
             for player_id in self.observers.keys() {
@@ -550,11 +552,14 @@ impl Game for ObserverGame {
             }
         }
         
+        let mut winners: Vec<String> = scores_delta.iter()
+            .filter(|(_, &score)| score > 15)
+            .map(|(player, _)| player.clone())
+            .collect();
+        winners.sort();
+        
         let outcome = RoundOutcome {
-            winners: scores_delta.iter()
-                .filter(|(_, &score)| score > 15)
-                .map(|(player, _)| player.clone())
-                .collect(),
+            winners,
             losers: vec![],
             special_events: events.iter().map(|e| e.description.clone()).collect(),
             emergence_detected,
@@ -566,6 +571,7 @@ impl Game for ObserverGame {
             outcome,
             scores_delta,
             events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
@@ -591,10 +597,7 @@ impl Game for ObserverGame {
             );
         }
         
-        let winner = final_scores.iter()
-            .max_by_key(|(_, &score)| score)
-            .map(|(player, _)| player.clone())
-            .unwrap_or_default();
+        let standings = Standings::new(&final_scores);
             
         let superposition_remaining = self.quantum_states.values()
             .filter(|s| !s.is_collapsed)
@@ -602,7 +605,10 @@ impl Game for ObserverGame {
             
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: state.scores.clone(),
             total_rounds: state.round,
             duration_ms: 0,
@@ -619,7 +625,7 @@ impl Game for ObserverGame {
                         self.collapsed_count as f32 / self.total_states as f32);
                     metrics.insert("entanglement_ratio".to_string(),
                         self.quantum_states.values()
-                            .filter(|s| s.entangled_with.len() > 0)
+                            .filter(|s| !s.entangled_with.is_empty())
                             .count() as f32 / self.total_states as f32);
                     metrics.insert("avg_coherence".to_string(),
                         self.quantum_states.values()
@@ -631,14 +637,73 @@ impl Game for ObserverGame {
         }
     }
     
-    async fn get_valid_actions(&self, _state: &GameState, _player_id: &str) -> Vec<String> {
-        vec![
-            "observe".to_string(),
-            "entangle".to_string(),
-            "weak_measure".to_string(),
-            "quantum_erase".to_string(),
-            "interfere".to_string(),
-        ]
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Observer Game")
+            .action("observe", "Measure a quantum state, possibly collapsing it", ActionSchema::tagged_data(
+                "Observe",
+                serde_json::json!({
+                    "target_state": { "type": "string" },
+                    "measurement_type": { "enum": ["Position", "Momentum", "Spin", "Energy"] }
+                }),
+                &["target_state", "measurement_type"],
+            ))
+            .action("entangle", "Entangle two or more uncollapsed states", ActionSchema::tagged_data(
+                "EntangleStates",
+                serde_json::json!({ "entangle_with": { "type": "array", "items": { "type": "string" }, "minItems": 2 } }),
+                &["entangle_with"],
+            ))
+            .action("weak_measure", "Gain quantum vision without collapsing anything", ActionSchema::tagged_data(
+                "WeakMeasurement", serde_json::json!({}), &[],
+            ))
+            .action("quantum_erase", "Try to uncollapse a coherent collapsed state", ActionSchema::tagged_data(
+                "QuantumErase",
+                serde_json::json!({ "target_state": { "type": "string" } }),
+                &["target_state"],
+            ))
+            .action("interfere", "Create interference between superposed states", ActionSchema::tagged_data(
+                "Interfere", serde_json::json!({}), &[],
+            ))
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, _player_id: &str) -> Vec<LegalAction> {
+        let mut actions = vec![
+            LegalAction::new("weak_measure", serde_json::json!({ "action_type": "WeakMeasurement" })),
+            LegalAction::new("interfere", serde_json::json!({ "action_type": "Interfere" })),
+        ];
+        
+        let mut state_ids: Vec<&String> = self.quantum_states.keys().collect();
+        state_ids.sort();
+        let (collapsed, superposed): (Vec<&String>, Vec<&String>) = state_ids.into_iter()
+            .partition(|id| self.quantum_states[*id].is_collapsed);
+        
+        for state_id in &superposed {
+            for measurement in [MeasurementType::Position, MeasurementType::Momentum, MeasurementType::Spin, MeasurementType::Energy] {
+                actions.push(LegalAction::new("observe", serde_json::json!({
+                    "action_type": "Observe",
+                    "target_state": state_id,
+                    "measurement_type": measurement,
+                })));
+            }
+        }
+        
+        for (i, first) in superposed.iter().enumerate() {
+            for second in &superposed[i + 1..] {
+                actions.push(LegalAction::new("entangle", serde_json::json!({
+                    "action_type": "EntangleStates",
+                    "entangle_with": [first, second],
+                })));
+            }
+        }
+        
+        for state_id in collapsed.into_iter().filter(|id| self.quantum_states[*id].coherence > 0.5) {
+            actions.push(LegalAction::new("quantum_erase", serde_json::json!({
+                "action_type": "QuantumErase",
+                "target_state": state_id,
+            })));
+        }
+        
+        actions
     }
     
     async fn get_visualization_data(&self, _state: &GameState) -> serde_json::Value {
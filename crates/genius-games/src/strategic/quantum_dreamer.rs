@@ -1,13 +1,13 @@
 //! Quantum Dreamer - Dream states collapse and merge across parallel realities
 
 use genius_core::{
-    Game, GameConfig, GameState, GameType, PlayerAction, RoundResult,
-    RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType,
-    Result, GameError,
+    game_rng, GameRng, Game, GameConfig, GameState, PlayerAction, RoundResult,
+    RoundOutcome, GameResult, GameAnalytics, GameEvent, EventKind, EmergenceEvent, EmergenceType,
+    Result, ActionSchema, LegalAction, Standings,
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use rand::Rng;
 
 /// A dream state that exists in quantum superposition
@@ -19,7 +19,7 @@ pub struct DreamState {
     pub coherence: f32,
     pub lucidity: f32,
     pub parent_dreamer: String,
-    pub shared_dreamers: HashSet<String>,
+    pub shared_dreamers: BTreeSet<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,7 +30,7 @@ pub struct DreamSymbol {
     pub stability: f32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum SymbolType {
     Door,      // Transitions between states
     Mirror,    // Self-reflection
@@ -73,14 +73,17 @@ pub enum DreamActionType {
     DreamWalk,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct QuantumDreamerGame {
     round_number: u32,
-    dreamers: HashMap<String, Dreamer>,
-    dream_states: HashMap<String, DreamState>,
+    // Ordered so rounds iterate dreamers and dreams the same way every time
+    dreamers: BTreeMap<String, Dreamer>,
+    dream_states: BTreeMap<String, DreamState>,
     reality_bleeds: Vec<RealityBleed>,
     collective_unconscious: CollectiveUnconscious,
     lucidity_threshold: f32,
     reality_stability: f32,
+    rng: GameRng,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -102,8 +105,8 @@ impl QuantumDreamerGame {
     pub fn new() -> Self {
         Self {
             round_number: 0,
-            dreamers: HashMap::new(),
-            dream_states: HashMap::new(),
+            dreamers: BTreeMap::new(),
+            dream_states: BTreeMap::new(),
             reality_bleeds: Vec::new(),
             collective_unconscious: CollectiveUnconscious {
                 shared_symbols: HashMap::new(),
@@ -112,6 +115,7 @@ impl QuantumDreamerGame {
             },
             lucidity_threshold: 0.7,
             reality_stability: 1.0,
+            rng: game_rng(None),
         }
     }
     
@@ -120,7 +124,7 @@ impl QuantumDreamerGame {
         for player_id in &player_ids {
             let mut symbol_mastery = HashMap::new();
             for symbol in &[SymbolType::Door, SymbolType::Mirror, SymbolType::Light] {
-                symbol_mastery.insert(symbol.clone(), rand::random::<f32>() * 0.5);
+                symbol_mastery.insert(symbol.clone(), self.rng.random::<f32>() * 0.5);
             }
             
             self.dreamers.insert(player_id.clone(), Dreamer {
@@ -159,7 +163,7 @@ impl QuantumDreamerGame {
                 coherence: 0.6,
                 lucidity: 0.3,
                 parent_dreamer: player_id.clone(),
-                shared_dreamers: HashSet::new(),
+                shared_dreamers: BTreeSet::new(),
             };
             
             self.dream_states.insert(dream_id.clone(), dream);
@@ -178,13 +182,12 @@ impl QuantumDreamerGame {
         // Check if dreamer can enter (lucidity check)
         if dreamer.lucidity_level < 0.3 && dream.parent_dreamer != dreamer_id {
             return Some(GameEvent {
-                event_type: "dream_blocked".to_string(),
-                description: format!("{} lacks lucidity to enter foreign dream", dreamer_id),
-                affected_players: vec![dreamer_id.to_string()],
-                data: serde_json::json!({
+                kind: EventKind::custom("dream_blocked", serde_json::json!({
                     "required_lucidity": 0.3,
                     "current_lucidity": dreamer.lucidity_level,
-                }),
+                })),
+                description: format!("{} lacks lucidity to enter foreign dream", dreamer_id),
+                affected_players: vec![dreamer_id.to_string()],
             });
         }
         
@@ -199,13 +202,12 @@ impl QuantumDreamerGame {
         dreamer.lucidity_level = (dreamer.lucidity_level + dream.lucidity * 0.1).min(1.0);
         
         Some(GameEvent {
-            event_type: "dream_entered".to_string(),
-            description: format!("{} entered the dream '{}'", dreamer_id, dream_id),
-            affected_players: vec![dreamer_id.to_string(), dream.parent_dreamer.clone()],
-            data: serde_json::json!({
+            kind: EventKind::custom("dream_entered", serde_json::json!({
                 "dream_id": dream_id,
                 "new_lucidity": dreamer.lucidity_level,
-            }),
+            })),
+            description: format!("{} entered the dream '{}'", dreamer_id, dream_id),
+            affected_players: vec![dreamer_id.to_string(), dream.parent_dreamer.clone()],
         })
     }
     
@@ -251,18 +253,18 @@ impl QuantumDreamerGame {
             .or_insert(0.0) += 0.1;
             
         Some(GameEvent {
-            event_type: "symbol_created".to_string(),
-            description: format!("{} manifested {} in dream", dreamer_id, meaning),
-            affected_players: dream.shared_dreamers.iter().cloned().collect(),
-            data: serde_json::json!({
+            kind: EventKind::custom("symbol_created", serde_json::json!({
                 "symbol": symbol_type,
                 "power": symbol.power,
                 "dream_id": dream_id,
-            }),
+            })),
+            description: format!("{} manifested {} in dream", dreamer_id, meaning),
+            affected_players: dream.shared_dreamers.iter().cloned().collect(),
         })
     }
     
     fn manipulate_dream(&mut self, dreamer_id: &str, dream_id: &str) -> Option<GameEvent> {
+        let nearby_dreams = self.find_nearby_dreams(dream_id);
         let dreamer = self.dreamers.get_mut(dreamer_id)?;
         let dream = self.dream_states.get_mut(dream_id)?;
         
@@ -282,10 +284,10 @@ impl QuantumDreamerGame {
         dreamer.lucidity_level = (dreamer.lucidity_level + 0.05).min(1.0);
         
         // Chance of reality bleed
-        if dream.reality_level > 0.7 && rand::random::<f32>() < 0.3 {
+        if dream.reality_level > 0.7 && self.rng.random::<f32>() < 0.3 {
             let bleed = RealityBleed {
                 source_dream: dream_id.to_string(),
-                affected_dreams: self.find_nearby_dreams(dream_id),
+                affected_dreams: nearby_dreams,
                 bleed_strength: dream.reality_level - 0.7,
                 symbol_leakage: dream.symbols.iter()
                     .map(|s| s.symbol_type.clone())
@@ -296,26 +298,24 @@ impl QuantumDreamerGame {
             self.reality_bleeds.push(bleed.clone());
             
             return Some(GameEvent {
-                event_type: "reality_bleed".to_string(),
-                description: format!("Dream '{}' bleeding into reality!", dream_id),
-                affected_players: dream.shared_dreamers.iter().cloned().collect(),
-                data: serde_json::json!({
+                kind: EventKind::custom("reality_bleed", serde_json::json!({
                     "dream_id": dream_id,
                     "reality_level": dream.reality_level,
                     "affected_dreams": bleed.affected_dreams,
-                }),
+                })),
+                description: format!("Dream '{}' bleeding into reality!", dream_id),
+                affected_players: dream.shared_dreamers.iter().cloned().collect(),
             });
         }
         
         Some(GameEvent {
-            event_type: "dream_manipulated".to_string(),
-            description: format!("{} shaped the dream fabric", dreamer_id),
-            affected_players: vec![dreamer_id.to_string()],
-            data: serde_json::json!({
+            kind: EventKind::custom("dream_manipulated", serde_json::json!({
                 "dream_id": dream_id,
                 "new_reality": dream.reality_level,
                 "new_coherence": dream.coherence,
-            }),
+            })),
+            description: format!("{} shaped the dream fabric", dreamer_id),
+            affected_players: vec![dreamer_id.to_string()],
         })
     }
     
@@ -371,14 +371,13 @@ impl QuantumDreamerGame {
         }
         
         events.push(GameEvent {
-            event_type: "dream_collapsed".to_string(),
-            description: format!("Dream '{}' collapsed into reality!", dream_id),
-            affected_players: affected_dreamers,
-            data: serde_json::json!({
+            kind: EventKind::custom("dream_collapsed", serde_json::json!({
                 "dream_id": dream_id,
                 "final_reality": dream.reality_level,
                 "symbols_absorbed": dream.symbols.len(),
-            }),
+            })),
+            description: format!("Dream '{}' collapsed into reality!", dream_id),
+            affected_players: affected_dreamers,
         });
         
         // Remove the dream
@@ -405,7 +404,7 @@ impl QuantumDreamerGame {
         if max_frequency >= self.dreamers.len() {
             Some(EmergenceEvent {
                 round: self.round_number,
-                event_type: EmergenceType::Custom("Synchronicity".to_string()),
+                event_type: EmergenceType::Custom("synchronicity".to_string()),
                 description: "Meaningful patterns emerging across all dreams!".to_string(),
                 emergence_score: self.collective_unconscious.synchronicity_level,
                 involved_players: self.dreamers.keys().cloned().collect(),
@@ -421,7 +420,7 @@ impl QuantumDreamerGame {
             if dream.shared_dreamers.len() >= self.dreamers.len() {
                 return Some(EmergenceEvent {
                     round: self.round_number,
-                    event_type: EmergenceType::Custom("CollectiveDream".to_string()),
+                    event_type: EmergenceType::Custom("collective_dream".to_string()),
                     description: "All dreamers united in a single dream reality!".to_string(),
                     emergence_score: dream.coherence,
                     involved_players: self.dreamers.keys().cloned().collect(),
@@ -442,6 +441,7 @@ impl Default for QuantumDreamerGame {
 #[async_trait]
 impl Game for QuantumDreamerGame {
     async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        self.rng = game_rng(config.seed);
         let mut state = GameState::new(config.game_type.clone());
         
         let player_ids: Vec<String> = config.initial_players.iter()
@@ -479,14 +479,18 @@ impl Game for QuantumDreamerGame {
             self.collective_unconscious.shared_symbols.values().sum::<f32>() 
             / self.collective_unconscious.shared_symbols.len().max(1) as f32;
         
-        // Process dream actions
-        for (player_id, action) in &actions {
+        // Process dream actions in a stable order
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        
+        for player_id in player_ids {
+            let action = &actions[player_id];
             if let Ok(dream_action) = serde_json::from_value::<DreamAction>(action.data.clone()) {
                 match dream_action.action_type {
                     DreamActionType::EnterDream => {
                         if let Some(target) = dream_action.target_dream {
                             if let Some(event) = self.enter_dream(player_id, &target) {
-                                let success = event.event_type != "dream_blocked";
+                                let success = event.event_type() != "dream_blocked";
                                 events.push(event);
                                 
                                 if success {
@@ -507,7 +511,7 @@ impl Game for QuantumDreamerGame {
                     DreamActionType::ManipulateDream => {
                         if let Some(dream_id) = dream_action.target_dream {
                             if let Some(event) = self.manipulate_dream(player_id, &dream_id) {
-                                let is_bleed = event.event_type == "reality_bleed";
+                                let is_bleed = event.event_type() == "reality_bleed";
                                 events.push(event);
                                 
                                 if is_bleed {
@@ -528,13 +532,12 @@ impl Game for QuantumDreamerGame {
                                 scores_delta.insert(player_id.clone(), 5);
                                 
                                 events.push(GameEvent {
-                                    event_type: "dream_shared".to_string(),
-                                    description: format!("{} opened their dream to others", player_id),
-                                    affected_players: dream.shared_dreamers.iter().cloned().collect(),
-                                    data: serde_json::json!({
+                                    kind: EventKind::custom("dream_shared", serde_json::json!({
                                         "dream_id": dream_id,
                                         "dreamer_count": dream.shared_dreamers.len(),
-                                    }),
+                                    })),
+                                    description: format!("{} opened their dream to others", player_id),
+                                    affected_players: dream.shared_dreamers.iter().cloned().collect(),
                                 });
                             }
                         }
@@ -554,12 +557,11 @@ impl Game for QuantumDreamerGame {
                             
                             if dreamer.lucidity_level > self.lucidity_threshold {
                                 events.push(GameEvent {
-                                    event_type: "lucid_mastery".to_string(),
+                                    kind: EventKind::custom("lucid_mastery", serde_json::json!({
+                                        "lucidity": dreamer.lucidity_level,
+                                    })),
                                     description: format!("{} achieved lucid mastery!", player_id),
                                     affected_players: vec![player_id.clone()],
-                                    data: serde_json::json!({
-                                        "lucidity": dreamer.lucidity_level,
-                                    }),
                                 });
                                 scores_delta.insert(player_id.clone(), 15);
                             }
@@ -573,13 +575,12 @@ impl Game for QuantumDreamerGame {
                                 scores_delta.insert(player_id.clone(), walk_count as i32 * 3);
                                 
                                 events.push(GameEvent {
-                                    event_type: "dream_walk".to_string(),
+                                    kind: EventKind::custom("dream_walk", serde_json::json!({
+                                        "dreams_visited": walk_count,
+                                    })),
                                     description: format!("{} walks between {} dreams", 
                                         player_id, walk_count),
                                     affected_players: vec![player_id.clone()],
-                                    data: serde_json::json!({
-                                        "dreams_visited": walk_count,
-                                    }),
                                 });
                             }
                         }
@@ -614,15 +615,7 @@ impl Game for QuantumDreamerGame {
         
         if let Some(sync_event) = self.detect_synchronicity() {
             emergence_detected = true;
-            events.push(GameEvent {
-                event_type: "emergence".to_string(),
-                description: sync_event.description.clone(),
-                affected_players: sync_event.involved_players.clone(),
-                data: serde_json::json!({
-                    "type": "synchronicity",
-                    "level": self.collective_unconscious.synchronicity_level,
-                }),
-            });
+            events.push(GameEvent::emergence(&sync_event));
             
             // Bonus for synchronicity
             for player_id in self.dreamers.keys() {
@@ -632,14 +625,7 @@ impl Game for QuantumDreamerGame {
         
         if let Some(collective_event) = self.detect_collective_dream() {
             emergence_detected = true;
-            events.push(GameEvent {
-                event_type: "emergence".to_string(),
-                description: collective_event.description.clone(),
-                affected_players: collective_event.involved_players.clone(),
-                data: serde_json::json!({
-                    "type": "collective_dream",
-                }),
-            });
+            events.push(GameEvent::emergence(&collective_event));
             
             // Major bonus for collective dreaming
             for player_id in self.dreamers.keys() {
@@ -647,11 +633,14 @@ impl Game for QuantumDreamerGame {
             }
         }
         
+        let mut winners: Vec<String> = scores_delta.iter()
+            .filter(|(_, &score)| score > 15)
+            .map(|(player, _)| player.clone())
+            .collect();
+        winners.sort();
+        
         let outcome = RoundOutcome {
-            winners: scores_delta.iter()
-                .filter(|(_, &score)| score > 15)
-                .map(|(player, _)| player.clone())
-                .collect(),
+            winners,
             losers: vec![],
             special_events: events.iter().map(|e| e.description.clone()).collect(),
             emergence_detected,
@@ -663,6 +652,7 @@ impl Game for QuantumDreamerGame {
             outcome,
             scores_delta,
             events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
@@ -674,10 +664,7 @@ impl Game for QuantumDreamerGame {
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        let winner = state.scores.iter()
-            .max_by_key(|(_, &score)| score)
-            .map(|(player, _)| player.clone())
-            .unwrap_or_default();
+        let standings = Standings::new(&state.scores);
             
         let avg_lucidity = self.dreamers.values()
             .map(|d| d.lucidity_level)
@@ -689,7 +676,10 @@ impl Game for QuantumDreamerGame {
             
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: state.scores.clone(),
             total_rounds: state.round,
             duration_ms: 0,
@@ -713,16 +703,102 @@ impl Game for QuantumDreamerGame {
         }
     }
     
-    async fn get_valid_actions(&self, _state: &GameState, _player_id: &str) -> Vec<String> {
-        vec![
-            "enter_dream".to_string(),
-            "create_symbol".to_string(),
-            "manipulate_dream".to_string(),
-            "share_dream".to_string(),
-            "collapse_dream".to_string(),
-            "lucid_control".to_string(),
-            "dream_walk".to_string(),
-        ]
+    fn action_schema(&self) -> serde_json::Value {
+        let dream = serde_json::json!({ "target_dream": { "type": "string" } });
+        
+        ActionSchema::new("Quantum Dreamer")
+            .action("enter_dream", "Enter a dream (foreign dreams need lucidity 0.3)", ActionSchema::tagged_data(
+                "EnterDream", dream.clone(), &["target_dream"],
+            ))
+            .action("create_symbol", "Create a symbol inside a dream", ActionSchema::tagged_data(
+                "CreateSymbol",
+                serde_json::json!({
+                    "target_dream": { "type": "string" },
+                    "symbol": { "enum": ["Door", "Mirror", "Shadow", "Light", "Void", "Key", "Maze", "Bridge"] }
+                }),
+                &["target_dream", "symbol"],
+            ))
+            .action("manipulate_dream", "Push a dream you hold fragments of toward reality", ActionSchema::tagged_data(
+                "ManipulateDream", dream.clone(), &["target_dream"],
+            ))
+            .action("share_dream", "Open a dream to other dreamers", ActionSchema::tagged_data(
+                "ShareDream",
+                serde_json::json!({
+                    "target_dream": { "type": "string" },
+                    "other_dreamers": { "type": "array", "items": { "type": "string" } }
+                }),
+                &["target_dream", "other_dreamers"],
+            ))
+            .action("collapse_dream", "Collapse a dream whose reality level is at least 0.8", ActionSchema::tagged_data(
+                "CollapseDream", dream, &["target_dream"],
+            ))
+            .action("lucid_control", "Raise your lucidity", ActionSchema::tagged_data("LucidControl", serde_json::json!({}), &[]))
+            .action("dream_walk", "Walk between your dreams (needs lucidity above 0.6)", ActionSchema::tagged_data("DreamWalk", serde_json::json!({}), &[]))
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        let mut actions = vec![LegalAction::new("lucid_control", serde_json::json!({ "action_type": "LucidControl" }))];
+        
+        let Some(dreamer) = self.dreamers.get(player_id) else {
+            return actions;
+        };
+        
+        let mut dream_ids: Vec<&String> = self.dream_states.keys().collect();
+        dream_ids.sort();
+        let mut others: Vec<&String> = state.players().into_iter()
+            .filter(|p| p.as_str() != player_id)
+            .collect();
+        others.sort();
+        
+        for dream_id in dream_ids {
+            let dream = &self.dream_states[dream_id];
+            let inside = dreamer.active_dreams.contains(dream_id);
+            
+            if !inside {
+                actions.push(LegalAction::new("enter_dream", serde_json::json!({
+                    "action_type": "EnterDream",
+                    "target_dream": dream_id,
+                })));
+            } else {
+                if dreamer.dream_power >= 0.3 {
+                    for symbol in [
+                        SymbolType::Door, SymbolType::Mirror, SymbolType::Shadow, SymbolType::Light,
+                        SymbolType::Void, SymbolType::Key, SymbolType::Maze, SymbolType::Bridge,
+                    ] {
+                        actions.push(LegalAction::new("create_symbol", serde_json::json!({
+                            "action_type": "CreateSymbol",
+                            "target_dream": dream_id,
+                            "symbol": symbol,
+                        })));
+                    }
+                }
+                actions.push(LegalAction::new("manipulate_dream", serde_json::json!({
+                    "action_type": "ManipulateDream",
+                    "target_dream": dream_id,
+                })));
+                if !others.is_empty() {
+                    actions.push(LegalAction::new("share_dream", serde_json::json!({
+                        "action_type": "ShareDream",
+                        "target_dream": dream_id,
+                        "other_dreamers": others,
+                    })));
+                }
+            }
+            
+            if dream.reality_level >= 0.8 {
+                actions.push(LegalAction::new("collapse_dream", serde_json::json!({
+                    "action_type": "CollapseDream",
+                    "target_dream": dream_id,
+                })));
+            }
+        }
+        
+        if dreamer.lucidity_level > 0.6 {
+            actions.push(LegalAction::new("dream_walk", serde_json::json!({ "action_type": "DreamWalk" })));
+        }
+        
+        actions
     }
     
     async fn get_visualization_data(&self, _state: &GameState) -> serde_json::Value {
@@ -1,9 +1,9 @@
 //! Void Walker - Creating something from nothing through consensus
 
 use genius_core::{
-    Game, GameConfig, GameState, GameType, PlayerAction, RoundResult,
-    RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType,
-    Result, GameError,
+    Game, GameConfig, GameState, PlayerAction, RoundResult,
+    RoundOutcome, GameResult, GameAnalytics, GameEvent, EventKind, EmergenceEvent, EmergenceType,
+    Result, ActionSchema, LegalAction, Standings,
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -72,6 +72,7 @@ pub struct ResourceAction {
     pub amount: i32,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct VoidWalkerGame {
     round_number: u32,
     active_rules: Vec<RuleProposal>,
@@ -81,6 +82,9 @@ pub struct VoidWalkerGame {
     consensus_threshold: f32,
     reality_complexity: f32,
     resource_types: HashSet<String>,
+    /// Kinds of rule the walkers have put up for a vote
+    #[serde(default)]
+    debated_categories: Vec<RuleCategory>,
 }
 
 impl VoidWalkerGame {
@@ -94,6 +98,7 @@ impl VoidWalkerGame {
             consensus_threshold: 0.66,
             reality_complexity: 0.0,
             resource_types: HashSet::new(),
+            debated_categories: Vec::new(),
         }
     }
     
@@ -109,13 +114,17 @@ impl VoidWalkerGame {
     }
     
     fn process_rule_proposal(&mut self, proposer: &str, rule: RuleProposal) -> GameEvent {
-        let rule_id = format!("rule_{}", self.round_number);
+        // Several walkers can propose in one round, so the id counts the proposals on the table
+        let rule_id = format!("rule_{}_{}", self.round_number, self.pending_rules.len());
         let mut new_rule = rule;
         new_rule.id = rule_id.clone();
         new_rule.proposer = proposer.to_string();
         new_rule.round_proposed = self.round_number;
+        if !self.debated_categories.contains(&new_rule.category) {
+            self.debated_categories.push(new_rule.category.clone());
+        }
         
-        self.pending_rules.push(new_rule);
+        self.pending_rules.push(new_rule.clone());
         
         if let Some(walker) = self.void_walkers.get_mut(proposer) {
             walker.proposed_rules.push(rule_id.clone());
@@ -123,13 +132,12 @@ impl VoidWalkerGame {
         }
         
         GameEvent {
-            event_type: "rule_proposed".to_string(),
-            description: format!("{} proposed: {}", proposer, rule.rule_text),
-            affected_players: vec![proposer.to_string()],
-            data: serde_json::json!({
+            kind: EventKind::custom("rule_proposed", serde_json::json!({
                 "rule_id": rule_id,
-                "category": rule.category,
-            }),
+                "category": new_rule.category,
+            })),
+            description: format!("{} proposed: {}", proposer, new_rule.rule_text),
+            affected_players: vec![proposer.to_string()],
         }
     }
     
@@ -170,13 +178,12 @@ impl VoidWalkerGame {
                 }
                 
                 events.push(GameEvent {
-                    event_type: "rule_activated".to_string(),
+                    kind: EventKind::RuleChange {
+                        rule: rule.rule_text.clone(),
+                        proposer: Some(rule.proposer.clone()),
+                    },
                     description: format!("Rule activated: {}", rule.rule_text),
                     affected_players: self.void_walkers.keys().cloned().collect(),
-                    data: serde_json::json!({
-                        "rule_id": rule.id,
-                        "vote_ratio": vote_ratio,
-                    }),
                 });
             }
         }
@@ -306,8 +313,12 @@ impl Game for VoidWalkerGame {
         let mut scores_delta = HashMap::new();
         let mut events = Vec::new();
         
-        // Process void actions
-        for (player_id, action) in &actions {
+        // Process void actions in a stable order
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        
+        for player_id in player_ids {
+            let action = &actions[player_id];
             if let Ok(void_action) = serde_json::from_value::<VoidAction>(action.data.clone()) {
                 match void_action.action_type {
                     VoidActionType::ProposeRule => {
@@ -336,12 +347,11 @@ impl Game for VoidWalkerGame {
                             scores_delta.insert(player_id.clone(), 3);
                             
                             events.push(GameEvent {
-                                event_type: "meditation".to_string(),
+                                kind: EventKind::custom("meditation", serde_json::json!({
+                                    "enlightenment": walker.enlightenment_level,
+                                })),
                                 description: format!("{} meditated in the void", player_id),
                                 affected_players: vec![player_id.clone()],
-                                data: serde_json::json!({
-                                    "enlightenment": walker.enlightenment_level,
-                                }),
                             });
                         }
                     }
@@ -349,7 +359,7 @@ impl Game for VoidWalkerGame {
                         if let Some(resource_action) = void_action.resource_action {
                             if let Some(walker) = self.void_walkers.get_mut(player_id) {
                                 if let Some(amount) = walker.resources.get_mut(&resource_action.resource_name) {
-                                    *amount = (*amount as i32 + resource_action.amount).max(0) as i32;
+                                    *amount = (*amount + resource_action.amount).max(0);
                                     scores_delta.insert(player_id.clone(), resource_action.amount.abs());
                                 }
                             }
@@ -362,12 +372,11 @@ impl Game for VoidWalkerGame {
                                 scores_delta.insert(player_id.clone(), 20);
                                 
                                 events.push(GameEvent {
-                                    event_type: "void_creation".to_string(),
+                                    kind: EventKind::custom("void_creation", serde_json::json!({
+                                        "creation_power": walker.enlightenment_level,
+                                    })),
                                     description: format!("{} created something from nothing!", player_id),
                                     affected_players: vec![player_id.clone()],
-                                    data: serde_json::json!({
-                                        "creation_power": walker.enlightenment_level,
-                                    }),
                                 });
                             }
                         }
@@ -387,15 +396,7 @@ impl Game for VoidWalkerGame {
         let mut emergence_detected = false;
         if let Some(emergence) = self.detect_reality_emergence() {
             emergence_detected = true;
-            events.push(GameEvent {
-                event_type: "emergence".to_string(),
-                description: emergence.description.clone(),
-                affected_players: emergence.involved_players.clone(),
-                data: serde_json::json!({
-                    "emergence_type": "reality_emerged",
-                    "stability": self.void_stability,
-                }),
-            });
+            events.push(GameEvent::emergence(&emergence));
             
             // Bonus for all players
             for player_id in self.void_walkers.keys() {
@@ -419,6 +420,7 @@ impl Game for VoidWalkerGame {
             outcome,
             scores_delta,
             events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
@@ -443,14 +445,14 @@ impl Game for VoidWalkerGame {
             );
         }
         
-        let winner = contribution_scores.iter()
-            .max_by_key(|(_, &score)| score)
-            .map(|(player, _)| player.clone())
-            .unwrap_or_default();
+        let standings = Standings::new(&contribution_scores);
             
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: state.scores.clone(),
             total_rounds: state.round,
             duration_ms: 0,
@@ -458,7 +460,8 @@ impl Game for VoidWalkerGame {
             analytics: GameAnalytics {
                 collective_coordination_score: self.void_stability,
                 decision_diversity_index: self.resource_types.len() as f32 / 10.0,
-                strategic_depth: self.reality_complexity,
+                // Breadth of the rulebook under debate, deepened by every law of physics enacted
+                strategic_depth: (self.debated_categories.len() as f32 / 5.0 + self.reality_complexity).min(1.0),
                 emergence_frequency: 0.0,
                 performance_differential: 0.0,
                 custom_metrics: {
@@ -472,14 +475,117 @@ impl Game for VoidWalkerGame {
         }
     }
     
-    async fn get_valid_actions(&self, _state: &GameState, _player_id: &str) -> Vec<String> {
-        vec![
-            "propose_rule".to_string(),
-            "vote_on_rule".to_string(),
-            "use_resource".to_string(),
-            "meditate".to_string(),
-            "create_from_void".to_string(),
-        ]
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Void Walker")
+            .action("propose_rule", "Propose a rule for the others to vote into reality", ActionSchema::tagged_data(
+                "ProposeRule",
+                serde_json::json!({
+                    "rule_proposal": {
+                        "type": "object",
+                        "description": "id, proposer and round_proposed are overwritten by the game",
+                        "properties": {
+                            "id": { "type": "string" },
+                            "proposer": { "type": "string" },
+                            "rule_text": { "type": "string" },
+                            "category": { "enum": ["Physics", "Resource", "Interaction", "Winning", "Meta"] },
+                            "votes": { "type": "object", "additionalProperties": { "type": "boolean" } },
+                            "round_proposed": { "type": "integer", "minimum": 0 },
+                            "is_active": { "type": "boolean" }
+                        },
+                        "required": ["id", "proposer", "rule_text", "category", "votes", "round_proposed", "is_active"]
+                    }
+                }),
+                &["rule_proposal"],
+            ))
+            .action("vote_on_rule", "Vote on a pending rule", ActionSchema::tagged_data(
+                "VoteOnRule",
+                serde_json::json!({
+                    "vote_on_rule": {
+                        "type": "array",
+                        "prefixItems": [{ "type": "string" }, { "type": "boolean" }],
+                        "description": "[rule_id, in_favor]"
+                    }
+                }),
+                &["vote_on_rule"],
+            ))
+            .action("use_resource", "Change one of your resources by an amount", ActionSchema::tagged_data(
+                "UseResource",
+                serde_json::json!({
+                    "resource_action": {
+                        "type": "object",
+                        "properties": {
+                            "resource_name": { "type": "string" },
+                            "action": { "type": "string" },
+                            "amount": { "type": "integer" }
+                        },
+                        "required": ["resource_name", "action", "amount"]
+                    }
+                }),
+                &["resource_action"],
+            ))
+            .action("meditate", "Gain void energy and enlightenment", ActionSchema::tagged_data("Meditate", serde_json::json!({}), &[]))
+            .action("create_from_void", "Spend 50 energy to create (needs enlightenment 3)", ActionSchema::tagged_data("CreateFromVoid", serde_json::json!({}), &[]))
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        // Building reality comes first: back the rules on the table, then put new ones forward
+        let mut actions = Vec::new();
+        
+        for rule in self.pending_rules.iter().filter(|r| !r.votes.contains_key(player_id)) {
+            for in_favor in [true, false] {
+                actions.push(LegalAction::new("vote_on_rule", serde_json::json!({
+                    "action_type": "VoteOnRule",
+                    "vote_on_rule": [rule.id, in_favor],
+                })));
+            }
+        }
+        
+        // The leading proposal cycles through the categories from round to round
+        let mut categories = vec![RuleCategory::Physics, RuleCategory::Resource, RuleCategory::Interaction, RuleCategory::Winning, RuleCategory::Meta];
+        let lead = state.round as usize % categories.len();
+        categories.rotate_left(lead);
+        for category in categories {
+            let rule_text = match category {
+                RuleCategory::Resource => format!("Let the void create essence_{}", state.round),
+                _ => format!("{:?} shall bend to {}", category, player_id),
+            };
+            let rule = RuleProposal {
+                id: String::new(),
+                proposer: player_id.to_string(),
+                rule_text,
+                category,
+                votes: HashMap::new(),
+                round_proposed: self.round_number,
+                is_active: false,
+            };
+            actions.push(LegalAction::new("propose_rule", serde_json::json!({
+                "action_type": "ProposeRule",
+                "rule_proposal": rule,
+            })));
+        }
+        
+        actions.push(LegalAction::new("meditate", serde_json::json!({ "action_type": "Meditate" })));
+        
+        if let Some(walker) = self.void_walkers.get(player_id) {
+            let mut resources: Vec<&String> = walker.resources.iter()
+                .filter(|(_, &amount)| amount > 0)
+                .map(|(name, _)| name)
+                .collect();
+            resources.sort();
+            for resource in resources {
+                actions.push(LegalAction::new("use_resource", serde_json::json!({
+                    "action_type": "UseResource",
+                    "resource_action": { "resource_name": resource, "action": "use", "amount": -1 },
+                })));
+            }
+            
+            if walker.void_energy >= 50.0 && walker.enlightenment_level >= 3 {
+                actions.push(LegalAction::new("create_from_void", serde_json::json!({ "action_type": "CreateFromVoid" })));
+            }
+        }
+        
+        actions
     }
     
     async fn get_visualization_data(&self, _state: &GameState) -> serde_json::Value {
@@ -1,14 +1,14 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use uuid::Uuid;
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, Result, GameError};
+use genius_core::{game_rng, GameRng, Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EventKind, Result, ActionSchema, LegalAction, ParamSchema, Standings};
 
-const MAP_SIZE: usize = 20;
-const INITIAL_SAFE_ZONE: usize = 20;
+const DEFAULT_MAP_SIZE: usize = 20;
 const ZONE_SHRINK_RATE: usize = 2;
-const STORM_DAMAGE: i32 = 10;
-const COMBAT_DAMAGE: i32 = 20;
+const DEFAULT_STORM_DAMAGE: i32 = 10;
+const DEFAULT_COMBAT_DAMAGE: i32 = 20;
+const DEFAULT_MAX_ROUNDS: u32 = 50;
 const LOOT_BONUS: i32 = 5;
 const SURVIVAL_POINTS: i32 = 1;
 
@@ -17,12 +17,15 @@ pub struct BattleRoyaleGame {
     map_size: usize,
     safe_zone_radius: usize,
     safe_zone_center: (usize, usize),
-    player_positions: HashMap<String, Position>,
-    player_health: HashMap<String, i32>,
-    player_loot: HashMap<String, i32>,
+    player_positions: BTreeMap<String, Position>,
+    player_health: BTreeMap<String, i32>,
+    player_loot: BTreeMap<String, i32>,
     eliminated_players: Vec<String>,
     round_actions: Vec<BRAction>,
     max_rounds: u32,
+    storm_damage: i32,
+    combat_damage: i32,
+    rng: GameRng,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -60,26 +63,29 @@ impl Default for BattleRoyaleGame {
 impl BattleRoyaleGame {
     pub fn new() -> Self {
         Self {
-            map_size: MAP_SIZE,
-            safe_zone_radius: INITIAL_SAFE_ZONE,
-            safe_zone_center: (MAP_SIZE / 2, MAP_SIZE / 2),
-            player_positions: HashMap::new(),
-            player_health: HashMap::new(),
-            player_loot: HashMap::new(),
+            map_size: DEFAULT_MAP_SIZE,
+            safe_zone_radius: DEFAULT_MAP_SIZE,
+            safe_zone_center: (DEFAULT_MAP_SIZE / 2, DEFAULT_MAP_SIZE / 2),
+            player_positions: BTreeMap::new(),
+            player_health: BTreeMap::new(),
+            player_loot: BTreeMap::new(),
             eliminated_players: Vec::new(),
             round_actions: Vec::new(),
-            max_rounds: 50,
+            max_rounds: DEFAULT_MAX_ROUNDS,
+            storm_damage: DEFAULT_STORM_DAMAGE,
+            combat_damage: DEFAULT_COMBAT_DAMAGE,
+            rng: game_rng(None),
         }
     }
 
-    fn spawn_players(&mut self, players: Vec<String>) {
+    fn spawn_players(&mut self, mut players: Vec<String>) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        players.sort();
         
         for player in players {
             // Spawn players randomly within the initial safe zone
-            let angle = rng.gen::<f32>() * 2.0 * std::f32::consts::PI;
-            let radius = rng.gen::<f32>() * (self.safe_zone_radius as f32 * 0.8);
+            let angle = self.rng.random::<f32>() * 2.0 * std::f32::consts::PI;
+            let radius = self.rng.random::<f32>() * (self.safe_zone_radius as f32 * 0.8);
             
             let x = (self.safe_zone_center.0 as f32 + radius * angle.cos()) as usize;
             let y = (self.safe_zone_center.1 as f32 + radius * angle.sin()) as usize;
@@ -113,7 +119,7 @@ impl BattleRoyaleGame {
         
         for (player, pos) in &self.player_positions {
             if !self.is_in_safe_zone(pos) && !self.eliminated_players.contains(player) {
-                damage_events.push((player.clone(), STORM_DAMAGE));
+                damage_events.push((player.clone(), self.storm_damage));
             }
         }
         
@@ -149,6 +155,9 @@ impl BattleRoyaleGame {
     }
 
     fn can_attack(&self, attacker: &str, target: &str) -> bool {
+        if self.eliminated_players.iter().any(|p| p == target) {
+            return false;
+        }
         if let (Some(pos1), Some(pos2)) = (self.player_positions.get(attacker), self.player_positions.get(target)) {
             let dx = (pos1.x as i32 - pos2.x as i32).abs();
             let dy = (pos1.y as i32 - pos2.y as i32).abs();
@@ -161,7 +170,7 @@ impl BattleRoyaleGame {
     fn attack_player(&mut self, attacker: &str, target: &str) {
         if self.can_attack(attacker, target) {
             if let Some(health) = self.player_health.get_mut(target) {
-                *health -= COMBAT_DAMAGE;
+                *health -= self.combat_damage;
                 if *health <= 0 {
                     self.eliminated_players.push(target.to_string());
                     
@@ -178,15 +187,14 @@ impl BattleRoyaleGame {
 
     fn loot_area(&mut self, player: &str) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
         
-        if rng.gen::<f32>() < 0.7 { // 70% chance to find loot
+        if self.rng.random::<f32>() < 0.7 { // 70% chance to find loot
             if let Some(loot) = self.player_loot.get_mut(player) {
                 *loot += LOOT_BONUS;
             }
             
             // Heal if lucky
-            if rng.gen::<f32>() < 0.3 { // 30% chance to find healing
+            if self.rng.random::<f32>() < 0.3 { // 30% chance to find healing
                 if let Some(health) = self.player_health.get_mut(player) {
                     *health = (*health + 20).min(100);
                 }
@@ -205,6 +213,10 @@ impl BattleRoyaleGame {
             .count();
         let combat_intensity = combat_actions as f32 / history.len().max(1) as f32;
         
+        // Unspoken truces: how often players held their fire instead of attacking
+        let total_actions: usize = history.iter().map(|r| r.actions.len()).sum();
+        let truce_rate = 1.0 - combat_actions as f32 / total_actions.max(1) as f32;
+        
         // Strategic diversity (different action types used)
         let action_diversity = history.iter()
             .flat_map(|r| r.actions.values())
@@ -213,11 +225,12 @@ impl BattleRoyaleGame {
             .len() as f32 / 4.0; // 4 possible actions
         
         GameAnalytics {
-            collective_coordination_score: 0.0, // Battle Royale is not collaborative
+            collective_coordination_score: truce_rate,
             decision_diversity_index: action_diversity,
             strategic_depth: combat_intensity * 0.5 + survival_rate * 0.5,
             emergence_frequency: 0.0,
             performance_differential: 1.0 - survival_rate,
+            custom_metrics: HashMap::new(),
         }
     }
 }
@@ -225,17 +238,25 @@ impl BattleRoyaleGame {
 #[async_trait]
 impl Game for BattleRoyaleGame {
     async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
-        if let Some(rounds_str) = config.special_rules.get("max_rounds") {
-            if let Ok(rounds) = rounds_str.parse::<u32>() {
-                self.max_rounds = rounds;
-            }
-        }
+        self.rng = game_rng(config.seed);
+        
+        let params = self.parameters().validate(&config.params)?;
+        self.map_size = params.int("map_size") as usize;
+        self.safe_zone_radius = self.map_size;
+        self.safe_zone_center = (self.map_size / 2, self.map_size / 2);
+        self.storm_damage = params.int("storm_damage") as i32;
+        self.combat_damage = params.int("combat_damage") as i32;
+        self.max_rounds = params.int("max_rounds") as u32;
         
         Ok(GameState {
             game_id: Uuid::new_v4(),
             game_type: GameType::BattleRoyale,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
@@ -244,6 +265,8 @@ impl Game for BattleRoyaleGame {
                 meta.insert("safe_zone_center".to_string(), serde_json::json!(self.safe_zone_center));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
 
@@ -256,8 +279,14 @@ impl Game for BattleRoyaleGame {
 
         // Process actions
         self.round_actions.clear();
+        let mut events = Vec::new();
+        
+        // Resolve actions in a stable order so combat and looting are reproducible
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
         
-        for (player_id, action) in &actions {
+        for player_id in player_ids {
+            let action = &actions[player_id];
             if self.eliminated_players.contains(player_id) {
                 continue;
             }
@@ -274,7 +303,14 @@ impl Game for BattleRoyaleGame {
                 }
                 "attack" => {
                     if let Ok(target) = serde_json::from_value::<String>(action.data.clone()) {
+                        let out_before = self.eliminated_players.len();
                         self.attack_player(player_id, &target);
+                        if self.eliminated_players.len() > out_before {
+                            events.push(GameEvent::new(
+                                EventKind::Eliminated { player_id: target.clone(), by: Some(player_id.clone()) },
+                                format!("{} eliminated {}", player_id, target),
+                            ));
+                        }
                         self.round_actions.push(BRAction::Attack {
                             player: player_id.clone(),
                             target,
@@ -297,12 +333,26 @@ impl Game for BattleRoyaleGame {
         }
         
         // Shrink safe zone every 5 rounds
-        if state.round > 0 && state.round % 5 == 0 {
+        if state.round > 0 && state.round.is_multiple_of(5) {
+            let radius_before = self.safe_zone_radius;
             self.shrink_safe_zone();
+            if self.safe_zone_radius < radius_before {
+                events.push(GameEvent::new(
+                    EventKind::ZoneShrink { size: self.safe_zone_radius as f32 },
+                    format!("Safe zone shrank to radius {}", self.safe_zone_radius),
+                ));
+            }
         }
         
         // Apply storm damage
+        let out_before = self.eliminated_players.len();
         self.apply_storm_damage();
+        for player in &self.eliminated_players[out_before..] {
+            events.push(GameEvent::new(
+                EventKind::Eliminated { player_id: player.clone(), by: None },
+                format!("{} was caught by the storm", player),
+            ));
+        }
         
         // Calculate scores (survival bonus + loot)
         let mut scores_delta = HashMap::new();
@@ -334,33 +384,119 @@ impl Game for BattleRoyaleGame {
                 emergence_detected: false,
             },
             scores_delta,
+            events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
 
+    fn parameters(&self) -> ParamSchema {
+        ParamSchema::new()
+            .integer("map_size", "Width and height of the map; the safe zone starts this wide", DEFAULT_MAP_SIZE as i64, 5..=100)
+            .integer("storm_damage", "Health lost each round outside the safe zone", DEFAULT_STORM_DAMAGE as i64, 0..=100)
+            .integer("combat_damage", "Health lost when hit by an attack", DEFAULT_COMBAT_DAMAGE as i64, 1..=100)
+            .integer("max_rounds", "Rounds before the match ends", DEFAULT_MAX_ROUNDS as i64, 1..=1000)
+    }
+    
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Battle Royale")
+            .action("move", "Move one cell, diagonals included", serde_json::json!({
+                "enum": ["North", "South", "East", "West", "Northeast", "Northwest", "Southeast", "Southwest"]
+            }))
+            .action("attack", "Attack a player within 2 cells", serde_json::json!({
+                "type": "string",
+                "description": "Target player id"
+            }))
+            .simple_action("loot", "Search your cell for loot")
+            .simple_action("hide", "Stay hidden this round")
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        if self.eliminated_players.iter().any(|p| p == player_id) {
+            return vec![];
+        }
+        
+        let mut actions: Vec<LegalAction> = [
+            Direction::North, Direction::South, Direction::East, Direction::West,
+            Direction::Northeast, Direction::Northwest, Direction::Southeast, Direction::Southwest,
+        ]
+        .into_iter()
+        .map(|direction| LegalAction::new("move", serde_json::json!(direction)))
+        .collect();
+        
+        let mut targets: Vec<&String> = self.player_positions.keys()
+            .filter(|p| p.as_str() != player_id && !self.eliminated_players.contains(p))
+            .filter(|p| self.can_attack(player_id, p))
+            .collect();
+        targets.sort();
+        actions.extend(targets.into_iter().map(|t| LegalAction::new("attack", serde_json::json!(t))));
+        
+        actions.push(LegalAction::simple("loot"));
+        actions.push(LegalAction::simple("hide"));
+        actions
+    }
+    
+    async fn eliminated_players(&self, _state: &GameState) -> Vec<String> {
+        self.eliminated_players.clone()
+    }
+    
+    async fn join(&mut self, _state: &GameState, player_id: &str) {
+        if !self.player_positions.contains_key(player_id) {
+            self.spawn_players(vec![player_id.to_string()]);
+        }
+    }
+    
+    async fn forfeit(&mut self, _state: &GameState, player_id: &str) {
+        if let Some(health) = self.player_health.get_mut(player_id) {
+            *health = 0;
+            if !self.eliminated_players.iter().any(|p| p == player_id) {
+                self.eliminated_players.push(player_id.to_string());
+            }
+        }
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         let survivors = self.player_positions.len() - self.eliminated_players.len();
         survivors <= 1 || state.round >= self.max_rounds || self.safe_zone_radius == 0
     }
 
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        let winner = self.player_positions.keys()
-            .find(|p| !self.eliminated_players.contains(p))
-            .cloned()
-            .unwrap_or_else(|| "No Winner".to_string());
+        let survivors: Vec<&String> = self.player_positions.keys()
+            .filter(|p| !self.eliminated_players.contains(p))
+            .collect();
         
         let mut final_scores = state.scores.clone();
         
-        // Bonus for winner
-        if winner != "No Winner" {
-            *final_scores.entry(winner.clone()).or_insert(0) += 50;
+        // Bonus for the last player standing
+        if let [winner] = survivors.as_slice() {
+            *final_scores.entry((*winner).clone()).or_insert(0) += 50;
         }
         
+        // The fallen forfeit the points they banked while alive, as in Squid Game
+        for player in &self.eliminated_players {
+            if let Some(score) = final_scores.get_mut(player) {
+                *score = (*score).min(0);
+            }
+        }
+        
+        // Every round lists everyone eliminated so far, so a player went out in the first round naming them
+        let standings = self.eliminated_players.iter().fold(Standings::new(&final_scores), |standings, player| {
+            let round = state.history.iter()
+                .position(|r| r.outcome.losers.contains(player))
+                .map_or(state.round, |i| i as u32 + 1);
+            standings.eliminated(player, round)
+        });
+        
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores,
             total_rounds: state.round,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
             emergence_events: vec![],
             analytics: self.calculate_analytics(&state.history),
         }
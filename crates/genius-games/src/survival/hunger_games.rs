@@ -1,8 +1,8 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use uuid::Uuid;
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, GameError};
+use genius_core::{game_rng, GameRng, Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EventKind, EmergenceEvent, EmergenceType, Result, ActionSchema, LegalAction, ParamSchema, Standings};
 
 const INITIAL_HEALTH: i32 = 100;
 const HUNGER_DAMAGE: i32 = 5;
@@ -11,16 +11,18 @@ const COMBAT_DAMAGE: i32 = 25;
 const RESOURCE_HEAL: i32 = 15;
 const ALLIANCE_BONUS: i32 = 10;
 const BETRAYAL_PENALTY: i32 = 20;
+const DEFAULT_MAX_ROUNDS: u32 = 100;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HungerGamesGame {
-    tributes: HashMap<String, TributeStatus>,
-    alliances: HashMap<String, String>, // player -> alliance_id
-    resources: HashMap<String, ResourceCache>,
+    tributes: BTreeMap<String, TributeStatus>,
+    alliances: BTreeMap<String, String>, // player -> alliance_id
+    resources: BTreeMap<String, ResourceCache>,
     environment_events: Vec<EnvironmentEvent>,
     eliminated_tributes: Vec<String>,
     cornucopia_claimed: bool,
     max_rounds: u32,
+    rng: GameRng,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,17 +76,6 @@ enum EnvironmentEvent {
     MuttAttack { location: Location },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-enum HGAction {
-    Hunt { player: String, target: Option<String> },
-    Gather { player: String },
-    FormAlliance { player: String, with: String },
-    BetrayAlliance { player: String },
-    Hide { player: String },
-    MoveTo { player: String, location: Location },
-    UseItem { player: String, item: String },
-}
-
 impl Default for HungerGamesGame {
     fn default() -> Self {
         Self::new()
@@ -94,18 +85,19 @@ impl Default for HungerGamesGame {
 impl HungerGamesGame {
     pub fn new() -> Self {
         Self {
-            tributes: HashMap::new(),
-            alliances: HashMap::new(),
+            tributes: BTreeMap::new(),
+            alliances: BTreeMap::new(),
             resources: Self::initialize_resources(),
             environment_events: Vec::new(),
             eliminated_tributes: Vec::new(),
             cornucopia_claimed: false,
-            max_rounds: 100,
+            max_rounds: DEFAULT_MAX_ROUNDS,
+            rng: game_rng(None),
         }
     }
 
-    fn initialize_resources() -> HashMap<String, ResourceCache> {
-        let mut resources = HashMap::new();
+    fn initialize_resources() -> BTreeMap<String, ResourceCache> {
+        let mut resources = BTreeMap::new();
         
         resources.insert("cornucopia".to_string(), ResourceCache {
             location: Location::Cornucopia,
@@ -134,8 +126,11 @@ impl HungerGamesGame {
         resources
     }
 
-    fn spawn_tributes(&mut self, players: Vec<String>) {
-        for (i, player) in players.into_iter().enumerate() {
+    fn spawn_tributes(&mut self, mut players: Vec<String>) {
+        // Districts are assigned by seat, so keep seating stable
+        players.sort();
+        for player in players {
+            let seat = self.tributes.len() as u32;
             self.tributes.insert(player, TributeStatus {
                 health: INITIAL_HEALTH,
                 hunger: 50,
@@ -143,7 +138,7 @@ impl HungerGamesGame {
                 items: vec![],
                 kills: 0,
                 position: Position {
-                    district: (i as u32 % 12) + 1,
+                    district: (seat % 12) + 1,
                     location: Location::Plains,
                 },
             });
@@ -237,7 +232,9 @@ impl HungerGamesGame {
     fn can_attack(&self, attacker: &str, target: &str) -> bool {
         if let (Some(att_status), Some(tar_status)) = 
             (self.tributes.get(attacker), self.tributes.get(target)) {
-            att_status.position.location == tar_status.position.location
+            // A tribute who fell earlier this round can't be attacked again
+            !self.eliminated_tributes.iter().any(|t| t == target)
+                && att_status.position.location == tar_status.position.location
         } else {
             false
         }
@@ -246,7 +243,10 @@ impl HungerGamesGame {
     fn gather_resources(&mut self, player: &str) {
         if let Some(status) = self.tributes.get_mut(player) {
             // Check for resources at current location
-            for (_, cache) in self.resources.iter_mut() {
+            let mut cache_ids: Vec<String> = self.resources.keys().cloned().collect();
+            cache_ids.sort();
+            for cache_id in &cache_ids {
+                let Some(cache) = self.resources.get_mut(cache_id) else { continue };
                 if cache.location == status.position.location && !cache.items.is_empty() && !cache.discovered_by.contains(&player.to_string()) {
                     // Take an item
                     if let Some(item) = cache.items.pop() {
@@ -258,8 +258,7 @@ impl HungerGamesGame {
             
             // Basic foraging
             use rand::Rng;
-            let mut rng = rand::thread_rng();
-            if rng.gen::<f32>() < 0.4 {
+            if self.rng.random::<f32>() < 0.4 {
                 match status.position.location {
                     Location::Forest => status.items.push(Item::Food),
                     Location::River => status.items.push(Item::Water),
@@ -301,8 +300,9 @@ impl HungerGamesGame {
     }
 
     fn form_alliance(&mut self, player1: &str, player2: &str) {
+        use rand::Rng;
         use uuid::Uuid;
-        let alliance_id = Uuid::new_v4().to_string();
+        let alliance_id = Uuid::from_u128(self.rng.random()).to_string();
         
         self.alliances.insert(player1.to_string(), alliance_id.clone());
         self.alliances.insert(player2.to_string(), alliance_id);
@@ -331,7 +331,7 @@ impl HungerGamesGame {
             
             // Apply betrayal penalty to others
             for member in members {
-                if member != player {
+                if member != player && !self.eliminated_tributes.contains(&member) {
                     if let Some(status) = self.tributes.get_mut(&member) {
                         status.health -= BETRAYAL_PENALTY;
                         if status.health <= 0 {
@@ -345,13 +345,13 @@ impl HungerGamesGame {
 
     fn trigger_environment_event(&mut self, round: u32) {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
         
-        if round % 10 == 0 && rng.gen::<f32>() < 0.5 {
+        if round.is_multiple_of(10) && rng.random::<f32>() < 0.5 {
             let locations = [Location::Forest, Location::Plains, Location::Mountain];
-            let location = locations[rng.gen_range(0..locations.len())];
+            let location = locations[rng.random_range(0..locations.len())];
             
-            let event = match rng.gen_range(0..4) {
+            let event = match rng.random_range(0..4) {
                 0 => EnvironmentEvent::Wildfire { location },
                 1 => EnvironmentEvent::Flood { location },
                 2 => EnvironmentEvent::PoisonFog { location },
@@ -412,6 +412,7 @@ impl HungerGamesGame {
             strategic_depth: combat_intensity * 0.4 + alliance_rate * 0.6,
             emergence_frequency: self.environment_events.len() as f32 / history.len().max(1) as f32,
             performance_differential: 1.0 - survival_rate,
+            custom_metrics: HashMap::new(),
         }
     }
 }
@@ -419,17 +420,20 @@ impl HungerGamesGame {
 #[async_trait]
 impl Game for HungerGamesGame {
     async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
-        if let Some(rounds_str) = config.special_rules.get("max_rounds") {
-            if let Ok(rounds) = rounds_str.parse::<u32>() {
-                self.max_rounds = rounds;
-            }
-        }
+        self.rng = game_rng(config.seed);
+        
+        let params = self.parameters().validate(&config.params)?;
+        self.max_rounds = params.int("max_rounds") as u32;
         
         Ok(GameState {
             game_id: Uuid::new_v4(),
             game_type: GameType::HungerGames,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
@@ -437,6 +441,8 @@ impl Game for HungerGamesGame {
                 meta.insert("sponsors_active".to_string(), serde_json::json!(true));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
 
@@ -449,8 +455,16 @@ impl Game for HungerGamesGame {
 
         // Process actions
         let mut round_events = Vec::new();
+        let mut events = Vec::new();
+        let out_before = self.eliminated_tributes.len();
+        let mut killed_by = HashMap::new();
         
-        for (player_id, action) in &actions {
+        // Resolve actions in a stable order so hunts and foraging are reproducible
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        
+        for player_id in player_ids {
+            let action = &actions[player_id];
             if self.eliminated_tributes.contains(player_id) {
                 continue;
             }
@@ -460,6 +474,7 @@ impl Game for HungerGamesGame {
                     if let Ok(Some(target_player)) = serde_json::from_value::<Option<String>>(action.data.clone()) {
                         if self.combat(player_id, &target_player) {
                             round_events.push(format!("{} eliminated {}", player_id, target_player));
+                            killed_by.insert(target_player, player_id.clone());
                         }
                     }
                 }
@@ -470,11 +485,29 @@ impl Game for HungerGamesGame {
                     if let Ok(ally) = serde_json::from_value::<String>(action.data.clone()) {
                         self.form_alliance(player_id, &ally);
                         round_events.push(format!("{} allied with {}", player_id, ally));
+                        events.push(GameEvent::new(
+                            EventKind::AllianceFormed { members: vec![player_id.clone(), ally.clone()] },
+                            format!("{} allied with {}", player_id, ally),
+                        ));
                     }
                 }
                 "betray" => {
+                    let mut victims: Vec<String> = match self.alliances.get(player_id) {
+                        Some(alliance_id) => self.alliances.iter()
+                            .filter(|(p, id)| *id == alliance_id && *p != player_id)
+                            .map(|(p, _)| p.clone())
+                            .collect(),
+                        None => Vec::new(),
+                    };
+                    victims.sort();
                     self.betray_alliance(player_id);
                     round_events.push(format!("{} betrayed their alliance", player_id));
+                    if !victims.is_empty() {
+                        events.push(GameEvent::new(
+                            EventKind::AllianceBetrayed { betrayer: player_id.clone(), victims },
+                            format!("{} betrayed their alliance", player_id),
+                        ));
+                    }
                 }
                 "move" => {
                     if let Ok(location) = serde_json::from_value::<Location>(action.data.clone()) {
@@ -501,6 +534,15 @@ impl Game for HungerGamesGame {
         // Trigger environment events
         self.trigger_environment_event(state.round);
         
+        for player in &self.eliminated_tributes[out_before..] {
+            let by = killed_by.get(player).cloned();
+            let description = match &by {
+                Some(killer) => format!("{} was eliminated by {}", player, killer),
+                None => format!("{} succumbed to the arena", player),
+            };
+            events.push(GameEvent::new(EventKind::Eliminated { player_id: player.clone(), by }, description));
+        }
+        
         // Calculate scores
         let mut scores_delta = HashMap::new();
         for (player, status) in &self.tributes {
@@ -519,6 +561,19 @@ impl Game for HungerGamesGame {
         
         round_events.push(format!("{} tributes remaining", survivors.len()));
         
+        let emergence_detected = self.alliances.len() > 2;
+        if emergence_detected {
+            let mut involved_players: Vec<String> = actions.keys().cloned().collect();
+            involved_players.sort();
+            events.push(GameEvent::emergence(&EmergenceEvent {
+                round: state.round,
+                event_type: EmergenceType::Custom("Alliance Formation".to_string()),
+                description: "Tributes formed strategic alliances".to_string(),
+                emergence_score: 0.7,
+                involved_players,
+            }));
+        }
+        
         Ok(RoundResult {
             round: state.round,
             actions,
@@ -526,48 +581,158 @@ impl Game for HungerGamesGame {
                 winners: survivors,
                 losers: self.eliminated_tributes.clone(),
                 special_events: round_events,
-                emergence_detected: self.alliances.len() > 2,
+                emergence_detected,
             },
             scores_delta,
+            events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
 
+    fn parameters(&self) -> ParamSchema {
+        ParamSchema::new()
+            .integer("max_rounds", "Rounds played before the game ends", DEFAULT_MAX_ROUNDS as i64, 1..=10_000)
+    }
+
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Hunger Games")
+            .action("hunt", "Attack another tribute you can reach", serde_json::json!({
+                "type": ["string", "null"],
+                "description": "Target player id"
+            }))
+            .simple_action("gather", "Forage for resources at your location")
+            .action("form_alliance", "Ally with another tribute", serde_json::json!({
+                "type": "string",
+                "description": "Player id to ally with"
+            }))
+            .simple_action("betray", "Break your alliance, hurting its other members")
+            .action("move", "Travel to another location", serde_json::json!({
+                "enum": ["Cornucopia", "Forest", "River", "Mountain", "Cave", "Plains"]
+            }))
+            .action("use_item", "Consume an item you carry", serde_json::json!({
+                "enum": ["food", "water", "medicine"]
+            }))
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        if self.eliminated_tributes.iter().any(|p| p == player_id) {
+            return vec![];
+        }
+        
+        let mut actions = vec![LegalAction::simple("gather")];
+        
+        let mut others: Vec<&String> = self.tributes.keys()
+            .filter(|p| p.as_str() != player_id && !self.eliminated_tributes.contains(p))
+            .collect();
+        others.sort();
+        
+        for target in others.iter().filter(|t| self.can_attack(player_id, t)) {
+            actions.push(LegalAction::new("hunt", serde_json::json!(target)));
+        }
+        
+        let alliance = self.alliances.get(player_id);
+        for other in others.iter().filter(|o| alliance.is_none() || self.alliances.get(**o) != alliance) {
+            actions.push(LegalAction::new("form_alliance", serde_json::json!(other)));
+        }
+        if alliance.is_some() {
+            actions.push(LegalAction::simple("betray"));
+        }
+        
+        let current = self.tributes.get(player_id).map(|s| s.position.location);
+        for location in [Location::Cornucopia, Location::Forest, Location::River, Location::Mountain, Location::Cave, Location::Plains] {
+            if Some(location) != current {
+                actions.push(LegalAction::new("move", serde_json::json!(location)));
+            }
+        }
+        
+        if let Some(status) = self.tributes.get(player_id) {
+            for (name, held) in [
+                ("food", status.items.iter().any(|i| matches!(i, Item::Food))),
+                ("water", status.items.iter().any(|i| matches!(i, Item::Water))),
+                ("medicine", status.items.iter().any(|i| matches!(i, Item::Medicine))),
+            ] {
+                if held {
+                    actions.push(LegalAction::new("use_item", serde_json::json!(name)));
+                }
+            }
+        }
+        
+        actions
+    }
+    
+    async fn eliminated_players(&self, _state: &GameState) -> Vec<String> {
+        self.eliminated_tributes.clone()
+    }
+    
+    async fn join(&mut self, _state: &GameState, player_id: &str) {
+        if !self.tributes.contains_key(player_id) {
+            self.spawn_tributes(vec![player_id.to_string()]);
+        }
+    }
+    
+    async fn forfeit(&mut self, _state: &GameState, player_id: &str) {
+        if let Some(status) = self.tributes.get_mut(player_id) {
+            status.health = 0;
+            if !self.eliminated_tributes.iter().any(|p| p == player_id) {
+                self.eliminated_tributes.push(player_id.to_string());
+            }
+        }
+        self.alliances.remove(player_id);
+    }
+    
+    async fn coalitions(&self, _state: &GameState) -> Vec<Vec<String>> {
+        let mut groups: HashMap<&String, Vec<String>> = HashMap::new();
+        for (player, alliance_id) in &self.alliances {
+            groups.entry(alliance_id).or_default().push(player.clone());
+        }
+        groups.into_values().collect()
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         let survivors = self.tributes.len() - self.eliminated_tributes.len();
         survivors <= 1 || state.round >= self.max_rounds
     }
 
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        let winner = self.tributes.keys()
-            .find(|p| !self.eliminated_tributes.contains(p))
-            .cloned()
-            .unwrap_or_else(|| "No Victor".to_string());
+        let survivors: Vec<&String> = self.tributes.keys()
+            .filter(|p| !self.eliminated_tributes.contains(p))
+            .collect();
         
         let mut final_scores = state.scores.clone();
         
-        // Victory bonus
-        if winner != "No Victor" {
-            *final_scores.entry(winner.clone()).or_insert(0) += 100;
+        // Victory bonus for the last tribute standing
+        if let [victor] = survivors.as_slice() {
+            *final_scores.entry((*victor).clone()).or_insert(0) += 100;
         }
         
-        // Emergence events from alliances and betrayals
-        let emergence_events: Vec<EmergenceEvent> = state.history.iter()
-            .filter(|r| r.outcome.emergence_detected)
-            .map(|r| EmergenceEvent {
-                round: r.round,
-                event_type: "Alliance Formation".to_string(),
-                description: "Tributes formed strategic alliances".to_string(),
-                emergence_score: 0.7,
-            })
-            .collect();
+        // Fallen tributes keep none of what they banked, so only survivors finish ahead
+        for tribute in &self.eliminated_tributes {
+            if let Some(score) = final_scores.get_mut(tribute) {
+                *score = (*score).min(0);
+            }
+        }
+        
+        // Every round lists everyone eliminated so far, so a tribute fell in the first round naming them
+        let standings = self.eliminated_tributes.iter().fold(Standings::new(&final_scores), |standings, tribute| {
+            let round = state.history.iter()
+                .position(|r| r.outcome.losers.contains(tribute))
+                .map_or(state.round, |i| i as u32 + 1);
+            standings.eliminated(tribute, round)
+        });
+        
         
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores,
             total_rounds: state.round,
-            emergence_events,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
+            emergence_events: vec![],
             analytics: self.calculate_analytics(&state.history),
         }
     }
@@ -1,10 +1,11 @@
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, GameError};
+use genius_core::{game_rng, GameRng, Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EventKind, EmergenceEvent, EmergenceType, Result, ActionSchema, LegalAction, Standings};
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use uuid::Uuid;
 use rand::Rng;
 
+#[derive(Serialize, Deserialize)]
 pub struct KingOfTheHill {
     player_positions: HashMap<String, Position>,
     hill_controller: Option<String>,
@@ -17,9 +18,10 @@ pub struct KingOfTheHill {
     arena_size: f32,
     elimination_history: Vec<EliminationEvent>,
     alliance_network: HashMap<String, Vec<String>>,
+    rng: GameRng,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 struct Position {
     x: f32,
     y: f32,
@@ -57,11 +59,10 @@ impl Position {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct EliminationEvent {
     round: u32,
     player: String,
-    #[allow(dead_code)]
     reason: String,
     #[allow(dead_code)]
     final_position: Position,
@@ -90,19 +91,20 @@ impl KingOfTheHill {
             arena_size,
             elimination_history: Vec::new(),
             alliance_network: HashMap::new(),
+            rng: game_rng(None),
         }
     }
     
     fn spawn_player(&mut self, player_id: &str) {
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
         
         // Spawn at random edge position
-        let side = rng.gen_range(0..4);
+        let side = rng.random_range(0..4);
         let position = match side {
-            0 => Position { x: rng.gen_range(0.0..self.arena_size), y: 0.0 },
-            1 => Position { x: self.arena_size, y: rng.gen_range(0.0..self.arena_size) },
-            2 => Position { x: rng.gen_range(0.0..self.arena_size), y: self.arena_size },
-            _ => Position { x: 0.0, y: rng.gen_range(0.0..self.arena_size) },
+            0 => Position { x: rng.random_range(0.0..self.arena_size), y: 0.0 },
+            1 => Position { x: self.arena_size, y: rng.random_range(0.0..self.arena_size) },
+            2 => Position { x: rng.random_range(0.0..self.arena_size), y: self.arena_size },
+            _ => Position { x: 0.0, y: rng.random_range(0.0..self.arena_size) },
         };
         
         self.player_positions.insert(player_id.to_string(), position);
@@ -222,9 +224,10 @@ impl KingOfTheHill {
         if avg_distance < 20.0 {
             Some(EmergenceEvent {
                 round: state.round,
-                event_type: "alliance_formation".to_string(),
+                event_type: EmergenceType::Custom("alliance_formation".to_string()),
                 description: "Collective players formed strategic alliances".to_string(),
                 emergence_score: 1.0 - (avg_distance / self.arena_size),
+                involved_players: state.active_players().into_iter().cloned().collect(),
             })
         } else {
             None
@@ -234,12 +237,18 @@ impl KingOfTheHill {
 
 #[async_trait]
 impl Game for KingOfTheHill {
-    async fn initialize(&mut self, _config: GameConfig) -> Result<GameState> {
+    async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        self.rng = game_rng(config.seed);
+        
         Ok(GameState {
             game_id: Uuid::new_v4(),
             game_type: GameType::KingOfTheHill,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
@@ -263,13 +272,17 @@ impl Game for KingOfTheHill {
                 }));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
     
     async fn process_round(&mut self, state: &GameState, actions: HashMap<String, PlayerAction>) -> Result<RoundResult> {
-        // Initialize new players
-        for player_id in actions.keys() {
-            if !self.player_positions.contains_key(player_id) {
+        // Initialize new players in a stable order so spawn points are reproducible
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        for player_id in &player_ids {
+            if !self.player_positions.contains_key(*player_id) {
                 self.spawn_player(player_id);
             }
         }
@@ -281,9 +294,11 @@ impl Game for KingOfTheHill {
         
         let mut scores_delta = HashMap::new();
         let mut special_events = vec![];
+        let mut events = Vec::new();
         
         // Process player actions
-        for (player_id, action) in &actions {
+        for player_id in player_ids {
+            let action = &actions[player_id];
             if !self.is_player_alive(player_id) {
                 continue;
             }
@@ -362,6 +377,10 @@ impl Game for KingOfTheHill {
                             }
                             
                             special_events.push(format!("{} and {} formed an alliance!", player_id, target));
+                            events.push(GameEvent::new(
+                                EventKind::AllianceFormed { members: vec![player_id.clone(), target.to_string()] },
+                                format!("{} and {} formed an alliance", player_id, target),
+                            ));
                         }
                     }
                 }
@@ -398,13 +417,18 @@ impl Game for KingOfTheHill {
         let emergence_event = self.detect_alliance_emergence(state);
         if let Some(event) = &emergence_event {
             special_events.push(event.description.clone());
+            events.push(GameEvent::emergence(event));
         }
         
         // Determine round outcomes
-        let losers: Vec<String> = self.elimination_history.iter()
+        let eliminated: Vec<&EliminationEvent> = self.elimination_history.iter()
             .filter(|e| e.round == state.round + 1)
-            .map(|e| e.player.clone())
             .collect();
+        let losers: Vec<String> = eliminated.iter().map(|e| e.player.clone()).collect();
+        events.extend(eliminated.iter().map(|e| GameEvent::new(
+            EventKind::Eliminated { player_id: e.player.clone(), by: None },
+            format!("{}: {}", e.player, e.reason),
+        )));
         
         let winners = if let Some(controller) = &self.hill_controller {
             vec![controller.clone()]
@@ -413,9 +437,13 @@ impl Game for KingOfTheHill {
         };
         
         // Arena shrinks every 20 rounds
-        if (state.round + 1) % 20 == 0 && self.arena_size > 50.0 {
+        if (state.round + 1).is_multiple_of(20) && self.arena_size > 50.0 {
             self.arena_size *= 0.9;
             special_events.push(format!("⚠️ Arena shrinking! New size: {:.0}", self.arena_size));
+            events.push(GameEvent::new(
+                EventKind::ZoneShrink { size: self.arena_size },
+                format!("Arena shrank to {:.0}", self.arena_size),
+            ));
         }
         
         Ok(RoundResult {
@@ -428,10 +456,86 @@ impl Game for KingOfTheHill {
                 emergence_detected: emergence_event.is_some(),
             },
             scores_delta,
+            events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("King of the Hill")
+            .simple_action("move_to_hill", "Walk toward the hill")
+            .simple_action("push", "Shove every player within range away from you (5 round cooldown)")
+            .simple_action("defend", "Brace yourself, gaining strength and a little health")
+            .simple_action("charge", "Rush toward the hill at the cost of some health")
+            .action("form_alliance", "Ally with another player", serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "target": { "type": "string" }
+                },
+                "required": ["target"]
+            }))
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        if self.player_health.contains_key(player_id) && !self.is_player_alive(player_id) {
+            return vec![];
+        }
+        
+        let mut actions = vec![
+            LegalAction::simple("move_to_hill"),
+            LegalAction::simple("defend"),
+            LegalAction::simple("charge"),
+        ];
+        if self.push_cooldowns.get(player_id).copied().unwrap_or(0) == 0 {
+            actions.push(LegalAction::simple("push"));
+        }
+        
+        let allies = self.alliance_network.get(player_id);
+        let mut candidates: Vec<&String> = self.player_positions.keys()
+            .filter(|p| p.as_str() != player_id && self.is_player_alive(p))
+            .filter(|p| allies.is_none_or(|a| !a.contains(p)))
+            .collect();
+        candidates.sort();
+        actions.extend(candidates.into_iter()
+            .map(|target| LegalAction::new("form_alliance", serde_json::json!({ "target": target }))));
+        
+        actions
+    }
+    
+    async fn eliminated_players(&self, _state: &GameState) -> Vec<String> {
+        let mut out: Vec<String> = self.player_health.iter()
+            .filter(|(_, &health)| health <= 0)
+            .map(|(id, _)| id.clone())
+            .collect();
+        out.sort();
+        out
+    }
+    
+    async fn join(&mut self, _state: &GameState, player_id: &str) {
+        if !self.player_positions.contains_key(player_id) {
+            self.spawn_player(player_id);
+        }
+    }
+    
+    async fn forfeit(&mut self, _state: &GameState, player_id: &str) {
+        if let Some(health) = self.player_health.get_mut(player_id) {
+            *health = 0;
+        }
+        // Nobody stays allied with a player who walked away
+        self.alliance_network.remove(player_id);
+        for allies in self.alliance_network.values_mut() {
+            allies.retain(|ally| ally != player_id);
+        }
+    }
+    
+    async fn coalitions(&self, _state: &GameState) -> Vec<Vec<String>> {
+        self.alliance_network.iter()
+            .flat_map(|(player, allies)| allies.iter().map(move |ally| vec![player.clone(), ally.clone()]))
+            .collect()
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         let alive_count = self.player_health.values().filter(|&&h| h > 0).count();
         
@@ -440,18 +544,6 @@ impl Game for KingOfTheHill {
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        // Winner is player with most hill control time
-        let winner = self.control_duration.iter()
-            .max_by_key(|(_, &duration)| duration)
-            .map(|(id, _)| id.clone())
-            .unwrap_or_else(|| {
-                // Fallback to highest scorer
-                state.scores.iter()
-                    .max_by_key(|(_, &score)| score)
-                    .map(|(id, _)| id.clone())
-                    .unwrap_or_else(|| "No winner".to_string())
-            });
-        
         // Final scores include control time bonus
         let mut final_scores = state.scores.clone();
         for (player, &duration) in &self.control_duration {
@@ -465,24 +557,14 @@ impl Game for KingOfTheHill {
             }
         }
         
-        // Collect emergence events
-        let emergence_events: Vec<EmergenceEvent> = state.history.iter()
-            .enumerate()
-            .filter_map(|(i, round_result)| {
-                if round_result.outcome.emergence_detected {
-                    Some(EmergenceEvent {
-                        round: i as u32,
-                        event_type: "alliance_formation".to_string(),
-                        description: "Strategic alliances emerged".to_string(),
-                        emergence_score: 0.9,
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect();
+        // Fallen players place below those still standing; the rest place by score, which is dominated by hill time
+        let standings = self.elimination_history.iter()
+            .fold(Standings::new(&final_scores), |standings, e| standings.eliminated(&e.player, e.round));
+        
         
-        let emergence_frequency = emergence_events.len() as f32 / state.round.max(1) as f32;
+        let emergence_frequency = state.history.iter()
+            .filter(|round_result| round_result.outcome.emergence_detected)
+            .count() as f32 / state.round.max(1) as f32;
         
         // Calculate alliance metrics
         let alliance_count = self.alliance_network.values()
@@ -500,16 +582,21 @@ impl Game for KingOfTheHill {
         
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores,
             total_rounds: state.round,
-            emergence_events,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
+            emergence_events: vec![],
             analytics: GameAnalytics {
                 collective_coordination_score: alliance_count / self.player_positions.len().max(1) as f32,
                 decision_diversity_index: 0.7,
                 strategic_depth: 0.85,
                 emergence_frequency,
                 performance_differential: collective_control - sota_control,
+                custom_metrics: HashMap::new(),
             },
         }
     }
@@ -1,26 +1,29 @@
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, GameError};
+use genius_core::{game_rng, GameRng, Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, ActionSchema, LegalAction, Standings};
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use uuid::Uuid;
 use rand::Rng;
 
+#[derive(Serialize, Deserialize)]
 pub struct LastStand {
-    player_health: HashMap<String, i32>,
-    player_resources: HashMap<String, Resources>,
-    fortifications: HashMap<String, Fortification>,
+    player_health: BTreeMap<String, i32>,
+    player_resources: BTreeMap<String, Resources>,
+    fortifications: BTreeMap<String, Fortification>,
     wave_number: u32,
     threat_level: f32,
     enemies_spawned: Vec<Enemy>,
-    player_positions: HashMap<String, Position>,
+    player_positions: BTreeMap<String, Position>,
     shared_resources: Resources,
+    #[serde(with = "genius_core::tuple_keys")]
     cooperation_matrix: HashMap<(String, String), f32>,
     elimination_history: Vec<EliminationEvent>,
     #[allow(dead_code)]
-    survival_strategies: HashMap<String, String>,
+    survival_strategies: BTreeMap<String, String>,
+    rng: GameRng,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 struct Resources {
     ammo: i32,
     materials: i32,
@@ -42,7 +45,7 @@ impl Resources {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct Fortification {
     health: i32,
     defense_rating: f32,
@@ -54,7 +57,7 @@ struct Fortification {
     shared_with: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct Enemy {
     health: i32,
     damage: i32,
@@ -64,7 +67,7 @@ struct Enemy {
     enemy_type: EnemyType,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 enum EnemyType {
     Basic,
     Fast,
@@ -72,7 +75,7 @@ enum EnemyType {
     Swarm,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 struct Position {
     x: f32,
     y: f32,
@@ -84,12 +87,13 @@ impl Position {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct EliminationEvent {
     round: u32,
     player: String,
     #[allow(dead_code)]
     reason: String,
+    #[allow(dead_code)]
     final_wave: u32,
 }
 
@@ -102,27 +106,26 @@ impl Default for LastStand {
 impl LastStand {
     pub fn new() -> Self {
         Self {
-            player_health: HashMap::new(),
-            player_resources: HashMap::new(),
-            fortifications: HashMap::new(),
+            player_health: BTreeMap::new(),
+            player_resources: BTreeMap::new(),
+            fortifications: BTreeMap::new(),
             wave_number: 0,
             threat_level: 1.0,
             enemies_spawned: Vec::new(),
-            player_positions: HashMap::new(),
+            player_positions: BTreeMap::new(),
             shared_resources: Resources::new(),
             cooperation_matrix: HashMap::new(),
             elimination_history: Vec::new(),
-            survival_strategies: HashMap::new(),
+            survival_strategies: BTreeMap::new(),
+            rng: game_rng(None),
         }
     }
     
     fn spawn_player(&mut self, player_id: &str) {
-        let mut rng = rand::thread_rng();
-        
         // Spawn in defensive positions
         let position = Position {
-            x: rng.gen_range(40.0..60.0),
-            y: rng.gen_range(40.0..60.0),
+            x: self.rng.random_range(40.0..60.0),
+            y: self.rng.random_range(40.0..60.0),
         };
         
         self.player_positions.insert(player_id.to_string(), position);
@@ -134,20 +137,20 @@ impl LastStand {
         self.wave_number += 1;
         self.enemies_spawned.clear();
         
-        let mut rng = rand::thread_rng();
+        let rng = &mut self.rng;
         let enemy_count = (self.wave_number as f32 * self.threat_level * 2.0) as usize;
         
         for _ in 0..enemy_count {
             let enemy_type = if self.wave_number < 5 {
                 EnemyType::Basic
             } else if self.wave_number < 10 {
-                match rng.gen_range(0..3) {
+                match rng.random_range(0..3) {
                     0 => EnemyType::Basic,
                     1 => EnemyType::Fast,
                     _ => EnemyType::Tank,
                 }
             } else {
-                match rng.gen_range(0..4) {
+                match rng.random_range(0..4) {
                     0 => EnemyType::Basic,
                     1 => EnemyType::Fast,
                     2 => EnemyType::Tank,
@@ -163,12 +166,12 @@ impl LastStand {
             };
             
             // Spawn from edges
-            let side = rng.gen_range(0..4);
+            let side = rng.random_range(0..4);
             let position = match side {
-                0 => Position { x: rng.gen_range(0.0..100.0), y: 0.0 },
-                1 => Position { x: 100.0, y: rng.gen_range(0.0..100.0) },
-                2 => Position { x: rng.gen_range(0.0..100.0), y: 100.0 },
-                _ => Position { x: 0.0, y: rng.gen_range(0.0..100.0) },
+                0 => Position { x: rng.random_range(0.0..100.0), y: 0.0 },
+                1 => Position { x: 100.0, y: rng.random_range(0.0..100.0) },
+                2 => Position { x: rng.random_range(0.0..100.0), y: 100.0 },
+                _ => Position { x: 0.0, y: rng.random_range(0.0..100.0) },
             };
             
             self.enemies_spawned.push(Enemy {
@@ -182,7 +185,7 @@ impl LastStand {
     }
     
     fn process_combat(&mut self, round: u32) {
-        let mut damage_to_players = HashMap::new();
+        let mut damage_to_players = BTreeMap::new();
         let mut eliminated_enemies = Vec::new();
         
         // Enemy attacks
@@ -273,9 +276,10 @@ impl LastStand {
         if avg_cooperation > 0.7 && collective_cooperation > 0.8 {
             Some(EmergenceEvent {
                 round: state.round,
-                event_type: "resource_sharing_network".to_string(),
+                event_type: EmergenceType::Custom("resource_sharing_network".to_string()),
                 description: "Players developed efficient resource sharing strategies".to_string(),
                 emergence_score: avg_cooperation,
+                involved_players: state.active_players().into_iter().cloned().collect(),
             })
         } else {
             None
@@ -285,7 +289,8 @@ impl LastStand {
 
 #[async_trait]
 impl Game for LastStand {
-    async fn initialize(&mut self, _config: GameConfig) -> Result<GameState> {
+    async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        self.rng = game_rng(config.seed);
         self.shared_resources = Resources {
             ammo: 200,
             materials: 100,
@@ -297,6 +302,10 @@ impl Game for LastStand {
             game_type: GameType::LastStand,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
@@ -321,19 +330,23 @@ impl Game for LastStand {
                 }));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
     
     async fn process_round(&mut self, state: &GameState, actions: HashMap<String, PlayerAction>) -> Result<RoundResult> {
-        // Initialize new players
-        for player_id in actions.keys() {
-            if !self.player_health.contains_key(player_id) {
+        // Initialize new players in a stable order so spawn points are reproducible
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        for player_id in &player_ids {
+            if !self.player_health.contains_key(*player_id) {
                 self.spawn_player(player_id);
             }
         }
         
         // Spawn new wave every 5 rounds
-        if state.round % 5 == 0 {
+        if state.round.is_multiple_of(5) {
             self.spawn_wave();
         }
         
@@ -341,7 +354,8 @@ impl Game for LastStand {
         let mut special_events = vec![];
         
         // Process player actions
-        for (player_id, action) in &actions {
+        for player_id in player_ids {
+            let action = &actions[player_id];
             if self.player_health.get(player_id).copied().unwrap_or(0) <= 0 {
                 continue;
             }
@@ -349,8 +363,8 @@ impl Game for LastStand {
             let resources = self.player_resources.get_mut(player_id).unwrap();
             
             match action.action_type.as_str() {
-                "shoot" => {
-                    if resources.ammo >= 10 {
+                "shoot"
+                    if resources.ammo >= 10 => {
                         resources.ammo -= 10;
                         
                         // Damage nearby enemies
@@ -371,9 +385,8 @@ impl Game for LastStand {
                             special_events.push(format!("{} eliminated {} enemies!", player_id, kills));
                         }
                     }
-                }
-                "build" => {
-                    if resources.materials >= 20 {
+                "build"
+                    if resources.materials >= 20 => {
                         resources.materials -= 20;
                         
                         let player_pos = *self.player_positions.get(player_id).unwrap();
@@ -397,9 +410,8 @@ impl Game for LastStand {
                         
                         scores_delta.insert(player_id.clone(), 5);
                     }
-                }
-                "heal" => {
-                    if resources.medical >= 10 {
+                "heal"
+                    if resources.medical >= 10 => {
                         resources.medical -= 10;
                         let health = self.player_health.get_mut(player_id).unwrap();
                         let healed = (*health + 30).min(100) - *health;
@@ -409,7 +421,6 @@ impl Game for LastStand {
                             special_events.push(format!("{} healed for {} HP", player_id, healed));
                         }
                     }
-                }
                 "share" => {
                     if let Some(target) = action.data.get("target").and_then(|v| v.as_str()) {
                         if let Some(resource_type) = action.data.get("resource").and_then(|v| v.as_str()) {
@@ -458,12 +469,12 @@ impl Game for LastStand {
                     }
                 }
                 "scavenge" => {
-                    let mut rng = rand::thread_rng();
-                    if rng.gen_bool(0.3) {
+                    let rng = &mut self.rng;
+                    if rng.random_bool(0.3) {
                         // Found resources
-                        let found_ammo = rng.gen_range(0..20);
-                        let found_materials = rng.gen_range(0..10);
-                        let found_medical = rng.gen_range(0..5);
+                        let found_ammo = rng.random_range(0..20);
+                        let found_materials = rng.random_range(0..10);
+                        let found_medical = rng.random_range(0..5);
                         
                         resources.ammo += found_ammo;
                         resources.materials += found_materials;
@@ -528,7 +539,7 @@ impl Game for LastStand {
             .map(|e| e.player.clone())
             .collect();
         
-        if state.round % 5 == 0 {
+        if state.round.is_multiple_of(5) {
             special_events.insert(0, format!("🌊 Wave {} incoming! Threat level: {:.1}", 
                 self.wave_number, self.threat_level));
         }
@@ -538,7 +549,7 @@ impl Game for LastStand {
         }
         
         // Increase difficulty
-        if state.round % 10 == 0 {
+        if state.round.is_multiple_of(10) {
             self.threat_level *= 1.2;
             special_events.push(format!("⚠️ Threat level increased to {:.1}", self.threat_level));
         }
@@ -553,10 +564,93 @@ impl Game for LastStand {
                 emergence_detected: emergence_event.is_some(),
             },
             scores_delta,
+            events: emergence_event.iter()
+                .map(GameEvent::emergence)
+                .collect(),
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Last Stand")
+            .simple_action("shoot", "Spend 10 ammo to hit every enemy within range")
+            .simple_action("build", "Spend 20 materials to build or upgrade your fortification")
+            .simple_action("heal", "Spend 10 medical supplies to heal up to 30 HP")
+            .action("share", "Give resources to another survivor", serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "target": { "type": "string" },
+                    "resource": { "enum": ["ammo", "materials", "medical"] },
+                    "amount": { "type": "integer", "minimum": 1, "default": 10 }
+                },
+                "required": ["target", "resource"]
+            }))
+            .simple_action("scavenge", "Search for supplies")
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        if self.player_health.get(player_id).is_some_and(|&health| health <= 0) {
+            return vec![];
+        }
+        
+        // Survivors who have not spawned yet start with a full kit
+        let resources = self.player_resources.get(player_id).copied().unwrap_or_else(Resources::new);
+        let mut actions = Vec::new();
+        
+        if resources.ammo >= 10 {
+            actions.push(LegalAction::simple("shoot"));
+        }
+        if resources.materials >= 20 {
+            actions.push(LegalAction::simple("build"));
+        }
+        if resources.medical >= 10 {
+            actions.push(LegalAction::simple("heal"));
+        }
+        
+        let mut survivors: Vec<&String> = self.player_health.iter()
+            .filter(|(id, &health)| id.as_str() != player_id && health > 0)
+            .map(|(id, _)| id)
+            .collect();
+        survivors.sort();
+        for target in survivors {
+            for (resource, available) in [("ammo", resources.ammo), ("materials", resources.materials), ("medical", resources.medical)] {
+                if available >= 10 {
+                    actions.push(LegalAction::new("share", serde_json::json!({
+                        "target": target,
+                        "resource": resource,
+                        "amount": 10,
+                    })));
+                }
+            }
+        }
+        
+        actions.push(LegalAction::simple("scavenge"));
+        actions
+    }
+    
+    async fn eliminated_players(&self, _state: &GameState) -> Vec<String> {
+        let mut out: Vec<String> = self.player_health.iter()
+            .filter(|(_, &health)| health <= 0)
+            .map(|(id, _)| id.clone())
+            .collect();
+        out.sort();
+        out
+    }
+    
+    async fn join(&mut self, _state: &GameState, player_id: &str) {
+        if !self.player_health.contains_key(player_id) {
+            self.spawn_player(player_id);
+        }
+    }
+    
+    async fn forfeit(&mut self, _state: &GameState, player_id: &str) {
+        if let Some(health) = self.player_health.get_mut(player_id) {
+            *health = 0;
+        }
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         let alive_count = self.player_health.values().filter(|&&h| h > 0).count();
         
@@ -564,19 +658,6 @@ impl Game for LastStand {
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        // Winner survived the longest
-        let winner = self.player_health.iter()
-            .filter(|(_, &health)| health > 0)
-            .max_by_key(|(id, _)| state.scores.get(*id).unwrap_or(&0))
-            .map(|(id, _)| id.clone())
-            .unwrap_or_else(|| {
-                // Fallback to player who survived most waves
-                self.elimination_history.iter()
-                    .max_by_key(|e| e.final_wave)
-                    .map(|e| e.player.clone())
-                    .unwrap_or_else(|| "No winner".to_string())
-            });
-        
         // Final scores
         let mut final_scores = state.scores.clone();
         
@@ -593,24 +674,15 @@ impl Game for LastStand {
             *final_scores.entry(p2.clone()).or_insert(0) += (score * 50.0) as i32;
         }
         
-        // Collect emergence events
-        let emergence_events: Vec<EmergenceEvent> = state.history.iter()
-            .enumerate()
-            .filter_map(|(i, round_result)| {
-                if round_result.outcome.emergence_detected {
-                    Some(EmergenceEvent {
-                        round: i as u32,
-                        event_type: "resource_sharing_network".to_string(),
-                        description: "Cooperative survival strategies emerged".to_string(),
-                        emergence_score: 0.9,
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect();
+        // Holding out is a shared victory: every survivor places first, the fallen by how long they lasted
+        let standings = self.elimination_history.iter()
+            .fold(Standings::new(&final_scores), |standings, e| standings.eliminated(&e.player, e.round))
+            .survivors_share_first();
+        
         
-        let emergence_frequency = emergence_events.len() as f32 / state.round.max(1) as f32;
+        let emergence_frequency = state.history.iter()
+            .filter(|round_result| round_result.outcome.emergence_detected)
+            .count() as f32 / state.round.max(1) as f32;
         
         // Calculate survival metrics
         let collective_survivors = self.player_health.iter()
@@ -625,16 +697,21 @@ impl Game for LastStand {
         
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores,
             total_rounds: state.round,
-            emergence_events,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
+            emergence_events: vec![],
             analytics: GameAnalytics {
                 collective_coordination_score: avg_cooperation,
                 decision_diversity_index: 0.75,
                 strategic_depth: 0.9,
                 emergence_frequency,
                 performance_differential: collective_survivors - sota_survivors,
+                custom_metrics: HashMap::new(),
             },
         }
     }
@@ -1,10 +1,11 @@
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, GameError};
+use genius_core::{game_rng, GameRng, Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EventKind, EmergenceEvent, EmergenceType, Result, ActionSchema, LegalAction, Standings};
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use uuid::Uuid;
 use rand::Rng;
 
+#[derive(Serialize, Deserialize)]
 pub struct RussianRoulette {
     alive_players: HashMap<String, bool>,
     player_order: Vec<String>,
@@ -18,13 +19,12 @@ pub struct RussianRoulette {
     max_spins_per_round: u32,
     total_chambers: usize,
     bullets_loaded: usize,
+    rng: GameRng,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct EliminationEvent {
-    #[allow(dead_code)]
     round: u32,
-    #[allow(dead_code)]
     player: String,
     #[allow(dead_code)]
     action: String,
@@ -55,19 +55,28 @@ impl RussianRoulette {
             max_spins_per_round: 3,
             total_chambers: 6,
             bullets_loaded: 1,
+            rng: game_rng(None),
+        }
+    }
+    
+    /// Give a new player the next place in the firing order
+    fn seat_player(&mut self, player_id: &str) {
+        if !self.alive_players.contains_key(player_id) {
+            self.alive_players.insert(player_id.to_string(), true);
+            self.player_order.push(player_id.to_string());
+            self.passes_remaining.insert(player_id.to_string(), 3);
+            self.psychological_pressure.insert(player_id.to_string(), 0.0);
         }
     }
     
     fn load_bullets(&mut self) {
-        let mut rng = rand::thread_rng();
-        
         // Clear all chambers first
         self.chamber_positions = vec![false; self.total_chambers];
         
         // Randomly place bullets
         let mut placed = 0;
         while placed < self.bullets_loaded {
-            let pos = rng.gen_range(0..self.total_chambers);
+            let pos = self.rng.random_range(0..self.total_chambers);
             if !self.chamber_positions[pos] {
                 self.chamber_positions[pos] = true;
                 placed += 1;
@@ -75,12 +84,11 @@ impl RussianRoulette {
         }
         
         // Random starting position
-        self.current_chamber = rng.gen_range(0..self.total_chambers);
+        self.current_chamber = self.rng.random_range(0..self.total_chambers);
     }
     
     fn spin_cylinder(&mut self) {
-        let mut rng = rand::thread_rng();
-        self.current_chamber = rng.gen_range(0..self.total_chambers);
+        self.current_chamber = self.rng.random_range(0..self.total_chambers);
         self.spins_this_round += 1;
     }
     
@@ -96,7 +104,8 @@ impl RussianRoulette {
         self.psychological_pressure.insert(player.to_string(), (current + amount).min(1.0));
     }
     
-    fn get_next_alive_player(&self) -> Option<String> {
+    /// Move the turn on to the next player still alive, skipping the dead
+    fn get_next_alive_player(&mut self) -> Option<String> {
         if self.alive_players.is_empty() {
             return None;
         }
@@ -107,6 +116,7 @@ impl RussianRoulette {
         while attempts < self.player_order.len() {
             let player = &self.player_order[index % self.player_order.len()];
             if *self.alive_players.get(player).unwrap_or(&false) {
+                self.current_player_index = index % self.player_order.len();
                 return Some(player.clone());
             }
             index += 1;
@@ -135,9 +145,10 @@ impl RussianRoulette {
         if collective_pressure < sota_pressure * 0.7 {
             Some(EmergenceEvent {
                 round: state.round,
-                event_type: "psychological_resilience".to_string(),
+                event_type: EmergenceType::Custom("psychological_resilience".to_string()),
                 description: "Collective shows superior psychological resilience under pressure".to_string(),
                 emergence_score: 1.0 - collective_pressure,
+                involved_players: state.active_players().into_iter().cloned().collect(),
             })
         } else {
             None
@@ -155,7 +166,9 @@ impl RussianRoulette {
 
 #[async_trait]
 impl Game for RussianRoulette {
-    async fn initialize(&mut self, _config: GameConfig) -> Result<GameState> {
+    async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        self.rng = game_rng(config.seed);
+        
         // Initialize with one bullet
         self.bullets_loaded = 1;
         self.load_bullets();
@@ -165,6 +178,10 @@ impl Game for RussianRoulette {
             game_type: GameType::RussianRoulette,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
@@ -181,18 +198,17 @@ impl Game for RussianRoulette {
                 }));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
     
     async fn process_round(&mut self, state: &GameState, actions: HashMap<String, PlayerAction>) -> Result<RoundResult> {
-        // Initialize new players
-        for player_id in actions.keys() {
-            if !self.alive_players.contains_key(player_id) {
-                self.alive_players.insert(player_id.clone(), true);
-                self.player_order.push(player_id.clone());
-                self.passes_remaining.insert(player_id.clone(), 3);
-                self.psychological_pressure.insert(player_id.clone(), 0.0);
-            }
+        // Initialize new players, seated in a stable order
+        let mut new_players: Vec<&String> = actions.keys().collect();
+        new_players.sort();
+        for player_id in new_players {
+            self.seat_player(player_id);
         }
         
         // Reset round counters
@@ -200,95 +216,80 @@ impl Game for RussianRoulette {
         let mut scores_delta = HashMap::new();
         let mut eliminated_this_round = Vec::new();
         let mut special_events = vec![];
+        let mut events = Vec::new();
         
-        // Process each alive player's turn
+        // Process each alive player's turn, once each
+        let alive_at_start = self.alive_players.values().filter(|&&alive| alive).count();
         let mut turn_count = 0;
         while let Some(current_player) = self.get_next_alive_player() {
-            if turn_count >= self.alive_players.len() {
+            if turn_count >= alive_at_start {
                 break; // Prevent infinite loop
             }
             turn_count += 1;
             
-            if let Some(action) = actions.get(&current_player) {
-                match action.action_type.as_str() {
-                    "spin" => {
-                        if self.spins_this_round < self.max_spins_per_round {
-                            self.spin_cylinder();
-                            special_events.push(format!("{} spins the cylinder", current_player));
-                            self.increase_pressure(&current_player, 0.05);
-                        }
-                    }
-                    "pull" => {
-                        let bullet_fired = self.pull_trigger();
-                        
-                        if bullet_fired {
-                            // Player eliminated
-                            self.alive_players.insert(current_player.clone(), false);
-                            eliminated_this_round.push(current_player.clone());
-                            scores_delta.insert(current_player.clone(), -100);
-                            
-                            self.elimination_history.push(EliminationEvent {
-                                round: state.round + 1,
-                                player: current_player.clone(),
-                                action: "pulled trigger".to_string(),
-                                chamber_had_bullet: true,
-                                pressure_level: *self.psychological_pressure.get(&current_player).unwrap_or(&0.0),
-                            });
-                            
-                            special_events.push(format!("💀 {} eliminated! Chamber had a bullet!", current_player));
-                            
-                            // Reload bullets after elimination
-                            self.load_bullets();
-                        } else {
-                            // Survived - gain points and pressure
-                            let pressure = self.psychological_pressure.get(&current_player).copied().unwrap_or(0.0);
-                            let base_score = 50;
-                            let pressure_bonus = (pressure * 50.0) as i32;
-                            scores_delta.insert(current_player.clone(), base_score + pressure_bonus);
-                            
-                            self.increase_pressure(&current_player, 0.15);
-                            special_events.push(format!("✓ {} survives! *click*", current_player));
-                        }
-                        
-                        // Move to next player
-                        self.current_player_index += 1;
-                    }
-                    "pass" => {
-                        let passes_remaining = self.passes_remaining.get(&current_player).copied().unwrap_or(0);
-                        if passes_remaining > 0 {
-                            self.passes_remaining.insert(current_player.clone(), passes_remaining - 1);
-                            self.increase_pressure(&current_player, 0.1);
-                            scores_delta.insert(current_player.clone(), -10);
-                            special_events.push(format!("{} passes ({} remaining)", current_player, passes_remaining - 1));
-                            
-                            // Move to next player
-                            self.current_player_index += 1;
-                        } else {
-                            // Forced to pull
-                            special_events.push(format!("{} has no passes left - forced to pull!", current_player));
-                            // Recursively process as pull action
-                            let mut forced_action = action.clone();
-                            forced_action.action_type = "pull".to_string();
-                            let mut forced_actions = HashMap::new();
-                            forced_actions.insert(current_player.clone(), forced_action);
-                            
-                            // Process the forced pull
-                            continue;
-                        }
-                    }
-                    _ => {
-                        // Invalid action defaults to pull
-                        let mut default_action = action.clone();
-                        default_action.action_type = "pull".to_string();
-                        let mut default_actions = HashMap::new();
-                        default_actions.insert(current_player.clone(), default_action);
-                        continue;
-                    }
+            let action_type = actions.get(&current_player)
+                .map(|action| action.action_type.as_str())
+                .unwrap_or("pull");
+            
+            if action_type == "pass" {
+                let passes_remaining = self.passes_remaining.get(&current_player).copied().unwrap_or(0);
+                if passes_remaining > 0 {
+                    self.passes_remaining.insert(current_player.clone(), passes_remaining - 1);
+                    self.increase_pressure(&current_player, 0.1);
+                    scores_delta.insert(current_player.clone(), -10);
+                    special_events.push(format!("{} passes ({} remaining)", current_player, passes_remaining - 1));
+                    
+                    // Move to next player
+                    self.current_player_index += 1;
+                    continue;
                 }
+                // Forced to pull
+                special_events.push(format!("{} has no passes left - forced to pull!", current_player));
+            } else if action_type == "spin" && self.spins_this_round < self.max_spins_per_round {
+                // Spinning resets the odds, but the trigger still has to be pulled
+                self.spin_cylinder();
+                special_events.push(format!("{} spins the cylinder", current_player));
+                self.increase_pressure(&current_player, 0.05);
+            }
+            
+            // Anything else, including a missing or invalid action, is a pull
+            let bullet_fired = self.pull_trigger();
+            
+            if bullet_fired {
+                // Player eliminated
+                self.alive_players.insert(current_player.clone(), false);
+                eliminated_this_round.push(current_player.clone());
+                scores_delta.insert(current_player.clone(), -100);
+                
+                self.elimination_history.push(EliminationEvent {
+                    round: state.round + 1,
+                    player: current_player.clone(),
+                    action: "pulled trigger".to_string(),
+                    chamber_had_bullet: true,
+                    pressure_level: *self.psychological_pressure.get(&current_player).unwrap_or(&0.0),
+                });
+                
+                special_events.push(format!("💀 {} eliminated! Chamber had a bullet!", current_player));
+                events.push(GameEvent::new(
+                    EventKind::Eliminated { player_id: current_player.clone(), by: None },
+                    format!("{} pulled the trigger on a loaded chamber", current_player),
+                ));
+                
+                // Reload bullets after elimination
+                self.load_bullets();
             } else {
-                // No action provided - forced to pull
-                self.current_player_index += 1;
+                // Survived - gain points and pressure
+                let pressure = self.psychological_pressure.get(&current_player).copied().unwrap_or(0.0);
+                let base_score = 50;
+                let pressure_bonus = (pressure * 50.0) as i32;
+                scores_delta.insert(current_player.clone(), base_score + pressure_bonus);
+                
+                self.increase_pressure(&current_player, 0.15);
+                special_events.push(format!("✓ {} survives! *click*", current_player));
             }
+            
+            // Move to next player
+            self.current_player_index += 1;
         }
         
         // Award survival bonuses
@@ -303,19 +304,24 @@ impl Game for RussianRoulette {
         let emergence_event = self.detect_psychological_emergence(state);
         if let Some(event) = &emergence_event {
             special_events.push(event.description.clone());
+            events.push(GameEvent::emergence(event));
         }
         
         // Increase difficulty every 10 rounds
-        if (state.round + 1) % 10 == 0 {
+        if (state.round + 1).is_multiple_of(10) {
             self.increase_difficulty();
             special_events.push(format!("⚠️ Difficulty increased! Now {} bullets!", self.bullets_loaded));
+            events.push(GameEvent::new(
+                EventKind::RuleChange { rule: format!("{} bullets loaded", self.bullets_loaded), proposer: None },
+                format!("Difficulty increased to {} bullets", self.bullets_loaded),
+            ));
         }
         
         // Determine winners (survivors with highest scores)
-        let winners: Vec<String> = self.alive_players.iter()
-            .filter(|(_, &alive)| alive)
+        let winners: Vec<String> = self.player_order.iter()
+            .filter(|id| *self.alive_players.get(*id).unwrap_or(&false))
             .take(1)
-            .map(|(id, _)| id.clone())
+            .cloned()
             .collect();
         
         Ok(RoundResult {
@@ -328,10 +334,54 @@ impl Game for RussianRoulette {
                 emergence_detected: emergence_event.is_some(),
             },
             scores_delta,
+            events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Russian Roulette")
+            .simple_action("spin", "Spin the cylinder (limited spins per round)")
+            .simple_action("pull", "Pull the trigger")
+            .simple_action("pass", "Spend one of your passes to skip your turn")
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        if self.alive_players.get(player_id) == Some(&false) {
+            return vec![];
+        }
+        
+        let mut actions = vec![LegalAction::simple("pull")];
+        if self.spins_this_round < self.max_spins_per_round {
+            actions.push(LegalAction::simple("spin"));
+        }
+        if self.passes_remaining.get(player_id).is_none_or(|&passes| passes > 0) {
+            actions.push(LegalAction::simple("pass"));
+        }
+        actions
+    }
+    
+    async fn eliminated_players(&self, _state: &GameState) -> Vec<String> {
+        let mut out: Vec<String> = self.alive_players.iter()
+            .filter(|(_, &alive)| !alive)
+            .map(|(id, _)| id.clone())
+            .collect();
+        out.sort();
+        out
+    }
+    
+    async fn join(&mut self, _state: &GameState, player_id: &str) {
+        self.seat_player(player_id);
+    }
+    
+    async fn forfeit(&mut self, _state: &GameState, player_id: &str) {
+        if let Some(alive) = self.alive_players.get_mut(player_id) {
+            *alive = false;
+        }
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         let alive_count = self.alive_players.values().filter(|&&alive| alive).count();
         
@@ -339,18 +389,6 @@ impl Game for RussianRoulette {
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        // Winner is last player standing or highest scorer
-        let winner = self.alive_players.iter()
-            .filter(|(_, &alive)| alive)
-            .map(|(id, _)| id.clone())
-            .next()
-            .unwrap_or_else(|| {
-                state.scores.iter()
-                    .max_by_key(|(_, &score)| score)
-                    .map(|(id, _)| id.clone())
-                    .unwrap_or_else(|| "No winner".to_string())
-            });
-        
         // Calculate final scores with survival bonus
         let mut final_scores = state.scores.clone();
         for (player, &alive) in &self.alive_players {
@@ -359,24 +397,14 @@ impl Game for RussianRoulette {
             }
         }
         
-        // Collect emergence events
-        let emergence_events: Vec<EmergenceEvent> = state.history.iter()
-            .enumerate()
-            .filter_map(|(i, round_result)| {
-                if round_result.outcome.emergence_detected {
-                    Some(EmergenceEvent {
-                        round: i as u32,
-                        event_type: "psychological_resilience".to_string(),
-                        description: "Collective psychological strategies emerged".to_string(),
-                        emergence_score: 0.85,
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect();
+        // Survivors place first, then whoever pulled their fatal trigger latest
+        let standings = self.elimination_history.iter()
+            .fold(Standings::new(&final_scores), |standings, e| standings.eliminated(&e.player, e.round));
+        
         
-        let emergence_frequency = emergence_events.len() as f32 / state.round.max(1) as f32;
+        let emergence_frequency = state.history.iter()
+            .filter(|round_result| round_result.outcome.emergence_detected)
+            .count() as f32 / state.round.max(1) as f32;
         
         // Calculate psychological analytics
         let avg_pressure = self.psychological_pressure.values().sum::<f32>() / 
@@ -391,16 +419,21 @@ impl Game for RussianRoulette {
         
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores,
             total_rounds: state.round,
-            emergence_events,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
+            emergence_events: vec![],
             analytics: GameAnalytics {
                 collective_coordination_score: 1.0 - avg_pressure,
                 decision_diversity_index: 0.6,
                 strategic_depth: 0.8,
                 emergence_frequency,
                 performance_differential: collective_survival - sota_survival,
+                custom_metrics: HashMap::new(),
             },
         }
     }
@@ -1,10 +1,11 @@
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, GameError};
+use genius_core::{game_rng, GameRng, Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EventKind, EmergenceEvent, EmergenceType, Result, ActionSchema, LegalAction, Standings};
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use uuid::Uuid;
 use rand::Rng;
 
+#[derive(Serialize, Deserialize)]
 pub struct SquidGame {
     alive_players: HashMap<String, bool>,
     player_positions: HashMap<String, f32>,
@@ -15,9 +16,10 @@ pub struct SquidGame {
     elimination_history: Vec<EliminationEvent>,
     winners: Vec<String>,
     light_change_timer: u32,
+    rng: GameRng,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct EliminationEvent {
     round: u32,
     player: String,
@@ -45,6 +47,16 @@ impl SquidGame {
             elimination_history: Vec::new(),
             winners: Vec::new(),
             light_change_timer: 0,
+            rng: game_rng(None),
+        }
+    }
+    
+    /// Put a new player on the starting line
+    fn seat_player(&mut self, player_id: &str) {
+        if !self.alive_players.contains_key(player_id) {
+            self.alive_players.insert(player_id.to_string(), true);
+            self.player_positions.insert(player_id.to_string(), 0.0);
+            self.player_speeds.insert(player_id.to_string(), 0.0);
         }
     }
     
@@ -52,7 +64,10 @@ impl SquidGame {
         if !self.is_green_light {
             let mut eliminated = Vec::new();
             
-            for (player, &speed) in &self.player_speeds {
+            // In a stable order, so eliminations are recorded the same way every time
+            let mut moving: Vec<(&String, &f32)> = self.player_speeds.iter().collect();
+            moving.sort_by(|a, b| a.0.cmp(b.0));
+            for (player, &speed) in moving {
                 if speed > 0.01 && *self.alive_players.get(player).unwrap_or(&false) {
                     eliminated.push(player.clone());
                     
@@ -73,7 +88,10 @@ impl SquidGame {
     
     fn update_positions(&mut self) {
         if self.is_green_light {
-            for (player, &speed) in &self.player_speeds {
+            // Players reaching the line together finish in a stable order
+            let mut moving: Vec<(&String, &f32)> = self.player_speeds.iter().collect();
+            moving.sort_by(|a, b| a.0.cmp(b.0));
+            for (player, &speed) in moving {
                 if *self.alive_players.get(player).unwrap_or(&false) {
                     let current_pos = self.player_positions.get(player).copied().unwrap_or(0.0);
                     let new_pos = (current_pos + speed).min(self.finish_line);
@@ -90,10 +108,9 @@ impl SquidGame {
     
     fn toggle_light(&mut self) {
         self.is_green_light = !self.is_green_light;
-        let mut rng = rand::thread_rng();
         
         // Random duration between 2-8 seconds (simulated as rounds)
-        self.light_duration = rng.gen_range(2..=8);
+        self.light_duration = self.rng.random_range(2..=8);
         self.light_change_timer = self.light_duration;
     }
     
@@ -120,9 +137,10 @@ impl SquidGame {
         if collective_survival_rate > 0.7 && alive_collective > alive_sota {
             Some(EmergenceEvent {
                 round: state.round,
-                event_type: "survival_strategy".to_string(),
+                event_type: EmergenceType::Custom("survival_strategy".to_string()),
                 description: "Collective developed superior survival strategies".to_string(),
                 emergence_score: collective_survival_rate,
+                involved_players: state.active_players().into_iter().cloned().collect(),
             })
         } else {
             None
@@ -132,7 +150,9 @@ impl SquidGame {
 
 #[async_trait]
 impl Game for SquidGame {
-    async fn initialize(&mut self, _config: GameConfig) -> Result<GameState> {
+    async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        self.rng = game_rng(config.seed);
+        
         // Start with green light
         self.is_green_light = true;
         self.light_duration = 5;
@@ -143,6 +163,10 @@ impl Game for SquidGame {
             game_type: GameType::SquidGame,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
@@ -155,17 +179,15 @@ impl Game for SquidGame {
                 meta.insert("light_status".to_string(), serde_json::json!("green"));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
     
     async fn process_round(&mut self, state: &GameState, actions: HashMap<String, PlayerAction>) -> Result<RoundResult> {
         // Initialize new players
         for player_id in actions.keys() {
-            if !self.alive_players.contains_key(player_id) {
-                self.alive_players.insert(player_id.clone(), true);
-                self.player_positions.insert(player_id.clone(), 0.0);
-                self.player_speeds.insert(player_id.clone(), 0.0);
-            }
+            self.seat_player(player_id);
         }
         
         // Update light timer
@@ -227,12 +249,20 @@ impl Game for SquidGame {
             special_events.push(format!("{} players eliminated!", losers.len()));
         }
         
+        let mut events: Vec<GameEvent> = losers.iter()
+            .map(|player| GameEvent::new(
+                EventKind::Eliminated { player_id: player.clone(), by: None },
+                format!("{} moved on a red light", player),
+            ))
+            .collect();
+        
         if !winners.is_empty() {
             special_events.push(format!("{} reached the finish line!", winners.len()));
         }
         
         if let Some(event) = &emergence_event {
             special_events.push(event.description.clone());
+            events.push(GameEvent::emergence(event));
         }
         
         Ok(RoundResult {
@@ -245,10 +275,51 @@ impl Game for SquidGame {
                 emergence_detected: emergence_event.is_some(),
             },
             scores_delta,
+            events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Squid Game")
+            .simple_action("stop", "Stand still")
+            .simple_action("move_slow", "Advance 2 units")
+            .simple_action("move_normal", "Advance 5 units")
+            .simple_action("move_fast", "Advance 10 units")
+            .simple_action("move_risky", "Advance 15 units, very likely to be caught on red")
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        if self.alive_players.get(player_id) == Some(&false) || self.winners.iter().any(|w| w == player_id) {
+            return vec![];
+        }
+        
+        ["stop", "move_slow", "move_normal", "move_fast", "move_risky"].into_iter()
+            .map(LegalAction::simple)
+            .collect()
+    }
+    
+    async fn eliminated_players(&self, _state: &GameState) -> Vec<String> {
+        let mut out: Vec<String> = self.alive_players.iter()
+            .filter(|(_, &alive)| !alive)
+            .map(|(id, _)| id.clone())
+            .collect();
+        out.sort();
+        out
+    }
+    
+    async fn join(&mut self, _state: &GameState, player_id: &str) {
+        self.seat_player(player_id);
+    }
+    
+    async fn forfeit(&mut self, _state: &GameState, player_id: &str) {
+        if let Some(alive) = self.alive_players.get_mut(player_id) {
+            *alive = false;
+        }
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         let alive_count = self.alive_players.values().filter(|&&alive| alive).count();
         
@@ -258,17 +329,6 @@ impl Game for SquidGame {
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        // Winner is the player who got furthest or reached finish line
-        let winner = if !self.winners.is_empty() {
-            self.winners[0].clone()
-        } else {
-            self.player_positions.iter()
-                .filter(|(id, _)| *self.alive_players.get(*id).unwrap_or(&false))
-                .max_by(|(_, pos1), (_, pos2)| pos1.partial_cmp(pos2).unwrap())
-                .map(|(id, _)| id.clone())
-                .unwrap_or_else(|| "No winner".to_string())
-        };
-        
         // Final scores based on distance
         let final_scores: HashMap<String, i32> = self.player_positions.iter()
             .map(|(id, &pos)| {
@@ -283,24 +343,14 @@ impl Game for SquidGame {
             })
             .collect();
         
-        // Collect emergence events
-        let emergence_events: Vec<EmergenceEvent> = state.history.iter()
-            .enumerate()
-            .filter_map(|(i, round_result)| {
-                if round_result.outcome.emergence_detected {
-                    Some(EmergenceEvent {
-                        round: i as u32,
-                        event_type: "survival_strategy".to_string(),
-                        description: "Collective survival strategies emerged".to_string(),
-                        emergence_score: 0.8,
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect();
+        // Finishers lead on score; those caught moving place below everyone still in the game
+        let standings = self.elimination_history.iter()
+            .fold(Standings::new(&final_scores), |standings, e| standings.eliminated(&e.player, e.round));
+        
         
-        let emergence_frequency = emergence_events.len() as f32 / state.round.max(1) as f32;
+        let emergence_frequency = state.history.iter()
+            .filter(|round_result| round_result.outcome.emergence_detected)
+            .count() as f32 / state.round.max(1) as f32;
         
         // Calculate survival analytics
         let total_players = self.alive_players.len() as f32;
@@ -316,16 +366,21 @@ impl Game for SquidGame {
         
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores,
             total_rounds: state.round,
-            emergence_events,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
+            emergence_events: vec![],
             analytics: GameAnalytics {
                 collective_coordination_score: survival_rate,
                 decision_diversity_index: 0.5, // Could analyze movement patterns
                 strategic_depth: 0.7, // Based on timing decisions
                 emergence_frequency,
                 performance_differential: collective_survivors - sota_survivors,
+                custom_metrics: HashMap::new(),
             },
         }
     }
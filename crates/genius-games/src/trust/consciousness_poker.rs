@@ -1,14 +1,14 @@
 //! Consciousness Poker - A game of awareness levels and strategic deception
 
 use genius_core::{
-    Game, GameConfig, GameState, GameResult, PlayerAction, RoundResult,
-    RoundOutcome, GameEvent, GameAnalytics, EmergenceEvent, EmergenceType,
-    Result, GameError,
+    game_rng, GameRng, Game, GameConfig, GameState, GameResult, Observation, Observer, PlayerAction, RoundResult,
+    RoundOutcome, GameEvent, EventKind, GameAnalytics, EmergenceEvent, EmergenceType,
+    Result, ActionSchema, LegalAction, Standings,
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use rand::{thread_rng, Rng};
+use std::collections::{BTreeMap, HashMap};
+use rand::Rng;
 
 /// The level of consciousness/awareness
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
@@ -94,32 +94,33 @@ pub enum PokerActionType {
     AllIn,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct ConsciousnessPokerGame {
     round_number: u32,
-    players: HashMap<String, ConsciousnessPlayer>,
+    players: BTreeMap<String, ConsciousnessPlayer>,
     pot: i32,
     current_bet: i32,
     betting_round: u32,
     enlightenment_threshold: f32,
+    rng: GameRng,
 }
 
 impl ConsciousnessPokerGame {
     pub fn new() -> Self {
         Self {
             round_number: 0,
-            players: HashMap::new(),
+            players: BTreeMap::new(),
             pot: 0,
             current_bet: 0,
             betting_round: 0,
             enlightenment_threshold: 0.8,
+            rng: game_rng(None),
         }
     }
     
     fn assign_consciousness_levels(&mut self, player_ids: Vec<String>) {
-        let mut rng = thread_rng();
-        
         for player_id in player_ids {
-            let level = ConsciousnessLevel::from_u8(rng.gen_range(1..=9));
+            let level = ConsciousnessLevel::from_u8(self.rng.random_range(1..=9));
             
             self.players.insert(player_id, ConsciousnessPlayer {
                 actual_level: level,
@@ -130,16 +131,16 @@ impl ConsciousnessPokerGame {
         }
     }
     
-    fn get_visible_information(&self, observer_id: &str) -> serde_json::Value {
-        let observer = match self.players.get(observer_id) {
-            Some(p) => p,
-            None => return serde_json::json!({}),
-        };
+    fn get_visible_information(&self, observer: &Observer) -> serde_json::Value {
+        // Spectators and unknown players only see what everyone at the table can see
+        let observer_level = observer.player_id()
+            .and_then(|id| self.players.get(id))
+            .map(|p| p.actual_level);
         
         let mut visible_players = HashMap::new();
         
         for (player_id, player) in &self.players {
-            if player_id == observer_id {
+            if observer.is_player(player_id) {
                 // Always see yourself fully
                 visible_players.insert(player_id.clone(), serde_json::json!({
                     "actual_level": player.actual_level,
@@ -154,7 +155,7 @@ impl ConsciousnessPokerGame {
                 });
                 
                 // Higher consciousness can see through lower levels
-                if observer.actual_level.can_perceive(player.actual_level) {
+                if observer_level.is_some_and(|level| level.can_perceive(player.actual_level)) {
                     info["actual_level"] = serde_json::json!(player.actual_level);
                 }
                 
@@ -166,7 +167,7 @@ impl ConsciousnessPokerGame {
             "players": visible_players,
             "pot": self.pot,
             "current_bet": self.current_bet,
-            "your_level": observer.actual_level,
+            "your_level": observer_level,
         })
     }
     
@@ -191,7 +192,7 @@ impl ConsciousnessPokerGame {
         if enlightenment_ratio > self.enlightenment_threshold {
             Some(EmergenceEvent {
                 round: self.round_number,
-                event_type: EmergenceType::Custom("CollectiveEnlightenment".to_string()),
+                event_type: EmergenceType::Custom("collective_enlightenment".to_string()),
                 description: format!("{:.0}% of players have achieved high consciousness", enlightenment_ratio * 100.0),
                 emergence_score: enlightenment_ratio,
                 involved_players: self.players.keys().cloned().collect(),
@@ -204,8 +205,19 @@ impl ConsciousnessPokerGame {
     fn resolve_betting_round(&mut self, actions: &HashMap<String, PlayerAction>) -> Vec<GameEvent> {
         let mut events = Vec::new();
         let mut folded_players = Vec::new();
+        let mut perceived_players = Vec::new();
+        
+        // Levels as they stood when the round began, for challenges
+        let levels: HashMap<String, (ConsciousnessLevel, Option<ConsciousnessLevel>)> = self.players.iter()
+            .map(|(id, p)| (id.clone(), (p.actual_level, p.claimed_level)))
+            .collect();
+        
+        // Bets are settled in a stable order, since calls match the bet standing so far
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
         
-        for (player_id, action) in actions {
+        for player_id in player_ids {
+            let action = &actions[player_id];
             if let Ok(poker_action) = serde_json::from_value::<PokerAction>(action.data.clone()) {
                 if let Some(player) = self.players.get_mut(player_id) {
                     match poker_action.action_type {
@@ -237,10 +249,9 @@ impl ConsciousnessPokerGame {
                             self.current_bet = self.current_bet.max(all_in);
                             
                             events.push(GameEvent {
-                                event_type: "all_in".to_string(),
+                                kind: EventKind::custom("all_in", serde_json::json!({ "amount": all_in })),
                                 description: format!("{} goes all in with {} chips!", player_id, all_in),
                                 affected_players: vec![player_id.clone()],
-                                data: serde_json::json!({ "amount": all_in }),
                             });
                         }
                         PokerActionType::Fold => {
@@ -251,30 +262,30 @@ impl ConsciousnessPokerGame {
                                 player.claimed_level = Some(level);
                                 
                                 events.push(GameEvent {
-                                    event_type: "consciousness_claim".to_string(),
+                                    kind: EventKind::custom("consciousness_claim", serde_json::json!({ "claimed_level": level })),
                                     description: format!("{} claims consciousness level {}", player_id, level.as_u8()),
                                     affected_players: vec![player_id.clone()],
-                                    data: serde_json::json!({ "claimed_level": level }),
                                 });
                             }
                         }
                         PokerActionType::Challenge => {
                             if let Some(target) = poker_action.target_player {
-                                if let Some(target_player) = self.players.get(&target) {
+                                if let Some(&(actual_level, claimed_level)) = levels.get(&target) {
                                     // Challenger must have higher consciousness to see truth
-                                    if player.actual_level.can_perceive(target_player.actual_level) {
+                                    if player.actual_level.can_perceive(actual_level) {
                                         player.perception_history.push(PerceptionEvent {
                                             round: self.round_number,
                                             perceived_player: target.clone(),
-                                            actual_level: target_player.actual_level,
-                                            claimed_level: target_player.claimed_level.unwrap_or(ConsciousnessLevel::Level1),
+                                            actual_level,
+                                            claimed_level: claimed_level.unwrap_or(ConsciousnessLevel::Level1),
                                         });
+                                        perceived_players.push(player_id.clone());
+                                        perceived_players.push(target.clone());
                                         
                                         events.push(GameEvent {
-                                            event_type: "successful_perception".to_string(),
+                                            kind: EventKind::custom("successful_perception", serde_json::json!({ "perception_successful": true })),
                                             description: format!("{} successfully perceives {}'s true consciousness", player_id, target),
                                             affected_players: vec![player_id.clone(), target.clone()],
-                                            data: serde_json::json!({ "perception_successful": true }),
                                         });
                                     }
                                 }
@@ -285,6 +296,13 @@ impl ConsciousnessPokerGame {
             }
         }
         
+        // Seeing through someone raises both minds a level, so awareness spreads around the table
+        for player_id in perceived_players {
+            if let Some(player) = self.players.get_mut(&player_id) {
+                player.actual_level = ConsciousnessLevel::from_u8(player.actual_level.as_u8() + 1);
+            }
+        }
+        
         events
     }
 }
@@ -298,11 +316,12 @@ impl Default for ConsciousnessPokerGame {
 #[async_trait]
 impl Game for ConsciousnessPokerGame {
     async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        self.rng = game_rng(config.seed);
         let mut state = GameState::new(config.game_type.clone());
         
         // Assign consciousness levels to players
         let player_ids: Vec<String> = config.initial_players.iter()
-            .map(|p| p.player_id.clone())
+            .map(|p| p.id.to_string())
             .collect();
         self.assign_consciousness_levels(player_ids);
         
@@ -329,57 +348,62 @@ impl Game for ConsciousnessPokerGame {
         let mut scores_delta = HashMap::new();
         let mut events = self.resolve_betting_round(&actions);
         
-        // Award pot to highest consciousness player who didn't fold
+        // Award pot to highest consciousness player who didn't fold, all-in players included
         let active_players: Vec<_> = self.players.iter()
-            .filter(|(id, p)| p.chips > 0 && !events.iter().any(|e| 
-                e.event_type == "fold" && e.affected_players.contains(id)
+            .filter(|(id, _)| !events.iter().any(|e| 
+                e.event_type() == "fold" && e.affected_players.contains(id)
             ))
             .collect();
             
         if active_players.len() == 1 {
-            let (winner_id, _) = active_players[0];
-            scores_delta.insert(winner_id.clone(), self.pot);
+            let winner_id = active_players[0].0.clone();
+            let amount = self.pot;
+            scores_delta.insert(winner_id.clone(), amount);
             self.pot = 0;
+            self.current_bet = 0;
+            
+            // The winner takes the pot's chips back into play
+            if let Some(winner) = self.players.get_mut(&winner_id) {
+                winner.chips += amount;
+            }
             
             events.push(GameEvent {
-                event_type: "pot_won".to_string(),
+                kind: EventKind::PotWon { player_id: winner_id.clone(), amount },
                 description: format!("{} wins the pot!", winner_id),
                 affected_players: vec![winner_id.clone()],
-                data: serde_json::json!({ "amount": self.pot }),
             });
         } else if self.betting_round >= 3 {
             // Showdown: highest actual consciousness wins
             if let Some((winner_id, winner)) = active_players.into_iter()
-                .max_by_key(|(_, p)| p.actual_level) {
+                .max_by_key(|(_, p)| p.actual_level)
+                .map(|(id, p)| (id.clone(), p.clone())) {
                 
-                let deception_bonus = self.calculate_deception_bonus(winner);
+                let deception_bonus = self.calculate_deception_bonus(&winner);
                 let total_win = self.pot + (self.pot as f32 * deception_bonus) as i32;
                 
                 scores_delta.insert(winner_id.clone(), total_win);
+                if let Some(player) = self.players.get_mut(&winner_id) {
+                    player.chips += self.pot;
+                }
                 self.pot = 0;
+                self.current_bet = 0;
                 self.betting_round = 0;
                 
                 events.push(GameEvent {
-                    event_type: "showdown".to_string(),
-                    description: format!("{} wins with consciousness level {}!", winner_id, winner.actual_level.as_u8()),
-                    affected_players: vec![winner_id.clone()],
-                    data: serde_json::json!({ 
+                    kind: EventKind::custom("showdown", serde_json::json!({ 
                         "amount": total_win,
                         "actual_level": winner.actual_level,
                         "deception_bonus": deception_bonus,
-                    }),
+                    })),
+                    description: format!("{} wins with consciousness level {}!", winner_id, winner.actual_level.as_u8()),
+                    affected_players: vec![winner_id.clone()],
                 });
             }
         }
         
         // Check for collective enlightenment
         if let Some(emergence) = self.detect_enlightenment() {
-            events.push(GameEvent {
-                event_type: "emergence".to_string(),
-                description: emergence.description.clone(),
-                affected_players: emergence.involved_players.clone(),
-                data: serde_json::json!({ "emergence_type": "collective_enlightenment" }),
-            });
+            events.push(GameEvent::emergence(&emergence));
         }
         
         let outcome = RoundOutcome {
@@ -389,7 +413,7 @@ impl Game for ConsciousnessPokerGame {
                 .collect(),
             losers: vec![],
             special_events: events.iter().map(|e| e.description.clone()).collect(),
-            emergence_detected: events.iter().any(|e| e.event_type == "emergence"),
+            emergence_detected: events.iter().any(|e| matches!(e.kind, EventKind::Emergence { .. })),
         };
         
         Ok(RoundResult {
@@ -398,21 +422,19 @@ impl Game for ConsciousnessPokerGame {
             outcome,
             scores_delta,
             events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
     async fn is_game_over(&self, state: &GameState) -> bool {
-        // Game ends when only one player has chips or max rounds reached
+        // Game ends when only one player has chips and the pot is settled, or max rounds reached
         let active_players = self.players.values().filter(|p| p.chips > 0).count();
-        state.round >= 100 || active_players <= 1
+        state.round >= 100 || (active_players <= 1 && self.pot == 0)
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        let winner = state.scores.iter()
-            .max_by_key(|(_, &score)| score)
-            .map(|(player, _)| player.clone())
-            .unwrap_or_default();
+        let standings = Standings::new(&state.scores);
             
         // Calculate consciousness metrics
         let avg_consciousness = self.players.values()
@@ -425,7 +447,10 @@ impl Game for ConsciousnessPokerGame {
             
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: state.scores.clone(),
             total_rounds: state.round,
             duration_ms: 0,
@@ -446,19 +471,76 @@ impl Game for ConsciousnessPokerGame {
         }
     }
     
-    async fn get_valid_actions(&self, state: &GameState, player_id: &str) -> Vec<String> {
-        vec![
-            "claim_level".to_string(),
-            "challenge".to_string(),
-            "bet".to_string(),
-            "call".to_string(),
-            "raise".to_string(),
-            "fold".to_string(),
-            "all_in".to_string(),
-        ]
+    fn action_schema(&self) -> serde_json::Value {
+        let amount = serde_json::json!({ "bet_amount": { "type": "integer", "minimum": 1 } });
+        let empty = serde_json::json!({});
+        
+        ActionSchema::new("Consciousness Poker")
+            .action("claim_level", "Claim a consciousness level, truthfully or not", ActionSchema::tagged_data(
+                "ClaimLevel",
+                serde_json::json!({
+                    "claimed_level": {
+                        "enum": ["Level1", "Level2", "Level3", "Level4", "Level5", "Level6", "Level7", "Level8", "Level9"]
+                    }
+                }),
+                &["claimed_level"],
+            ))
+            .action("challenge", "Try to perceive another player's true level", ActionSchema::tagged_data(
+                "Challenge",
+                serde_json::json!({ "target_player": { "type": "string" } }),
+                &["target_player"],
+            ))
+            .action("bet", "Bet chips into the pot", ActionSchema::tagged_data("Bet", amount.clone(), &["bet_amount"]))
+            .action("call", "Match the current bet", ActionSchema::tagged_data("Call", empty.clone(), &[]))
+            .action("raise", "Set a new current bet", ActionSchema::tagged_data("Raise", amount, &["bet_amount"]))
+            .action("fold", "Drop out of the hand", ActionSchema::tagged_data("Fold", empty.clone(), &[]))
+            .action("all_in", "Bet every chip you have", ActionSchema::tagged_data("AllIn", empty, &[]))
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        let Some(player) = self.players.get(player_id) else {
+            return vec![];
+        };
+        
+        let mut actions: Vec<LegalAction> = (1..=9)
+            .map(|level| LegalAction::new("claim_level", serde_json::json!({
+                "action_type": "ClaimLevel",
+                "claimed_level": ConsciousnessLevel::from_u8(level),
+            })))
+            .collect();
+        
+        let mut others: Vec<&String> = self.players.keys().filter(|id| id.as_str() != player_id).collect();
+        others.sort();
+        for target in others {
+            actions.push(LegalAction::new("challenge", serde_json::json!({
+                "action_type": "Challenge",
+                "target_player": target,
+            })));
+        }
+        
+        if player.chips > 0 {
+            for amount in [10, 50, 100].into_iter().filter(|&a| a <= player.chips) {
+                actions.push(LegalAction::new("bet", serde_json::json!({ "action_type": "Bet", "bet_amount": amount })));
+            }
+            let raise = (self.current_bet * 2).max(10);
+            if raise <= player.chips {
+                actions.push(LegalAction::new("raise", serde_json::json!({ "action_type": "Raise", "bet_amount": raise })));
+            }
+            actions.push(LegalAction::new("call", serde_json::json!({ "action_type": "Call" })));
+            actions.push(LegalAction::new("all_in", serde_json::json!({ "action_type": "AllIn" })));
+        }
+        actions.push(LegalAction::new("fold", serde_json::json!({ "action_type": "Fold" })));
+        
+        actions
+    }
+    
+    async fn get_observation(&self, state: &GameState, observer: &Observer) -> Observation {
+        Observation::public(state, observer.clone())
+            .with_view(self.get_visible_information(observer))
     }
     
-    async fn get_visualization_data(&self, state: &GameState) -> serde_json::Value {
+    async fn get_visualization_data(&self, _state: &GameState) -> serde_json::Value {
         serde_json::json!({
             "players": self.players,
             "pot": self.pot,
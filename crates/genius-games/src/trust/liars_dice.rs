@@ -2,24 +2,33 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, Result, GameError};
+use genius_core::{game_rng, GameRng, Game, GameConfig, GameState, Observation, Observer, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EventKind, Result, ActionSchema, LegalAction, Turn, ParamSchema, Standings};
 
-const INITIAL_DICE: usize = 5;
-const CHALLENGE_PENALTY: i32 = 10;
-const SUCCESSFUL_BLUFF_BONUS: i32 = 5;
-const CORRECT_CHALLENGE_BONUS: i32 = 15;
+const DEFAULT_DICE: usize = 5;
+const DEFAULT_CHALLENGE_PENALTY: i32 = 10;
+const DEFAULT_BLUFF_BONUS: i32 = 5;
+const DEFAULT_CHALLENGE_BONUS: i32 = 15;
+const DEFAULT_MAX_ROUNDS: u32 = 100;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LiarsDiceGame {
     player_dice: HashMap<String, Vec<u8>>,
     current_bid: Option<Bid>,
     current_bidder: Option<String>,
+    /// Whether the standing bid was counted as a bluff when it was made
+    #[serde(default)]
+    current_bid_is_bluff: bool,
     betting_order: Vec<String>,
     current_turn_index: usize,
     eliminated_players: Vec<String>,
     round_history: Vec<RoundHistory>,
     max_rounds: u32,
+    dice_per_player: usize,
+    challenge_penalty: i32,
+    bluff_bonus: i32,
+    challenge_bonus: i32,
     bluff_statistics: HashMap<String, BluffStats>,
+    rng: GameRng,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,13 +56,6 @@ struct BluffStats {
     failed_challenges: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-enum LDAction {
-    Bid { player: String, quantity: usize, face_value: u8 },
-    Challenge { player: String },
-    Pass { player: String }, // For spectating after elimination
-}
-
 impl Default for LiarsDiceGame {
     fn default() -> Self {
         Self::new()
@@ -66,29 +68,43 @@ impl LiarsDiceGame {
             player_dice: HashMap::new(),
             current_bid: None,
             current_bidder: None,
+            current_bid_is_bluff: false,
             betting_order: Vec::new(),
             current_turn_index: 0,
             eliminated_players: Vec::new(),
             round_history: Vec::new(),
-            max_rounds: 100,
+            max_rounds: DEFAULT_MAX_ROUNDS,
+            dice_per_player: DEFAULT_DICE,
+            challenge_penalty: DEFAULT_CHALLENGE_PENALTY,
+            bluff_bonus: DEFAULT_BLUFF_BONUS,
+            challenge_bonus: DEFAULT_CHALLENGE_BONUS,
             bluff_statistics: HashMap::new(),
+            rng: game_rng(None),
         }
     }
 
-    fn roll_dice(count: usize) -> Vec<u8> {
+    fn roll_dice(rng: &mut GameRng, count: usize) -> Vec<u8> {
         use rand::Rng;
-        let mut rng = rand::thread_rng();
-        (0..count).map(|_| rng.gen_range(1..=6)).collect()
+        (0..count).map(|_| rng.random_range(1..=6)).collect()
     }
 
-    fn initialize_players(&mut self, players: Vec<String>) {
-        self.betting_order = players.clone();
+    fn initialize_players(&mut self, mut players: Vec<String>) {
+        players.sort();
         for player in players {
-            self.player_dice.insert(player.clone(), Self::roll_dice(INITIAL_DICE));
-            self.bluff_statistics.insert(player, BluffStats::default());
+            self.seat_player(player);
         }
     }
 
+    /// Deal a player a full hand and a place at the end of the betting order
+    fn seat_player(&mut self, player: String) {
+        if self.player_dice.contains_key(&player) {
+            return;
+        }
+        self.player_dice.insert(player.clone(), Self::roll_dice(&mut self.rng, self.dice_per_player));
+        self.bluff_statistics.insert(player.clone(), BluffStats::default());
+        self.betting_order.push(player);
+    }
+
     fn count_dice(&self, face_value: u8) -> usize {
         self.player_dice.values()
             .flat_map(|dice| dice.iter())
@@ -107,7 +123,7 @@ impl LiarsDiceGame {
         }
     }
 
-    fn process_bid(&mut self, player: &str, quantity: usize, face_value: u8) -> Result<(), String> {
+    fn process_bid(&mut self, player: &str, quantity: usize, face_value: u8) -> std::result::Result<(), String> {
         if self.eliminated_players.contains(&player.to_string()) {
             return Err("Player is eliminated".to_string());
         }
@@ -122,28 +138,29 @@ impl LiarsDiceGame {
             return Err("Invalid bid".to_string());
         }
 
+        // Check if this is likely a bluff
+        let player_dice_count = self.player_dice.get(player)
+            .map(|dice| dice.iter().filter(|&&d| d == face_value || d == 1).count())
+            .unwrap_or(0);
+        let is_bluff = quantity > self.player_dice.len() * 2 + player_dice_count;
+        
         // Update bluff statistics
         if let Some(stats) = self.bluff_statistics.get_mut(player) {
             stats.total_bids += 1;
-            
-            // Check if this is likely a bluff
-            let player_dice_count = self.player_dice.get(player)
-                .map(|dice| dice.iter().filter(|&&d| d == face_value || d == 1).count())
-                .unwrap_or(0);
-            
-            if quantity > self.player_dice.len() * 2 + player_dice_count {
+            if is_bluff {
                 stats.bluffs_made += 1;
             }
         }
 
         self.current_bid = Some(bid);
         self.current_bidder = Some(player.to_string());
+        self.current_bid_is_bluff = is_bluff;
         self.advance_turn();
         
         Ok(())
     }
 
-    fn process_challenge(&mut self, challenger: &str) -> Result<(bool, String), String> {
+    fn process_challenge(&mut self, challenger: &str) -> std::result::Result<(bool, String), String> {
         if self.eliminated_players.contains(&challenger.to_string()) {
             return Err("Player is eliminated".to_string());
         }
@@ -163,8 +180,10 @@ impl LiarsDiceGame {
             }
         }
         
+        // Only a bid counted as a bluff can be caught as one; a bid that merely
+        // turned out false was an honest misjudgement
         if let Some(bidder_stats) = self.bluff_statistics.get_mut(&bid.player) {
-            if was_bluff {
+            if was_bluff && self.current_bid_is_bluff {
                 bidder_stats.bluffs_caught += 1;
             }
         }
@@ -195,6 +214,7 @@ impl LiarsDiceGame {
         // Reset for next round
         self.current_bid = None;
         self.current_bidder = None;
+        self.current_bid_is_bluff = false;
         self.reroll_all_dice();
         
         Ok((was_bluff, winner))
@@ -207,10 +227,14 @@ impl LiarsDiceGame {
     }
 
     fn reroll_all_dice(&mut self) {
-        for (player, dice) in self.player_dice.iter_mut() {
-            if !self.eliminated_players.contains(player) {
+        // Roll in seating order so the dice are reproducible
+        for player in &self.betting_order {
+            if self.eliminated_players.contains(player) {
+                continue;
+            }
+            if let Some(dice) = self.player_dice.get_mut(player) {
                 let count = dice.len();
-                *dice = Self::roll_dice(count);
+                *dice = Self::roll_dice(&mut self.rng, count);
             }
         }
     }
@@ -252,36 +276,49 @@ impl LiarsDiceGame {
             strategic_depth: challenge_accuracy * 0.5 + bluff_rate * 0.5,
             emergence_frequency: 0.0,
             performance_differential: 1.0 - survival_rate,
+            custom_metrics: HashMap::new(),
         }
     }
 
-    #[allow(dead_code)]
     fn get_current_player(&self) -> Option<&String> {
-        self.betting_order.get(self.current_turn_index)
+        // Eliminations shrink the betting order, so wrap rather than run off the end
+        if self.betting_order.is_empty() {
+            return None;
+        }
+        self.betting_order.get(self.current_turn_index % self.betting_order.len())
     }
 }
 
 #[async_trait]
 impl Game for LiarsDiceGame {
     async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
-        if let Some(rounds_str) = config.special_rules.get("max_rounds") {
-            if let Ok(rounds) = rounds_str.parse::<u32>() {
-                self.max_rounds = rounds;
-            }
-        }
+        self.rng = game_rng(config.seed);
+        
+        let params = self.parameters().validate(&config.params)?;
+        self.dice_per_player = params.int("dice_per_player") as usize;
+        self.challenge_penalty = params.int("challenge_penalty") as i32;
+        self.bluff_bonus = params.int("bluff_bonus") as i32;
+        self.challenge_bonus = params.int("challenge_bonus") as i32;
+        self.max_rounds = params.int("max_rounds") as u32;
         
         Ok(GameState {
             game_id: Uuid::new_v4(),
             game_type: GameType::LiarsDice,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
-                meta.insert("dice_per_player".to_string(), serde_json::json!(INITIAL_DICE));
+                meta.insert("dice_per_player".to_string(), serde_json::json!(self.dice_per_player));
                 meta.insert("wild_ones".to_string(), serde_json::json!(true));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
 
@@ -293,11 +330,16 @@ impl Game for LiarsDiceGame {
         }
 
         let mut round_events = Vec::new();
+        let mut events = Vec::new();
         let mut scores_delta = HashMap::new();
         let mut challenge_occurred = false;
         
-        // Process actions from all players
-        for (player_id, action) in &actions {
+        // Process actions from all players in a stable order
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        
+        for player_id in player_ids {
+            let action = &actions[player_id];
             if self.eliminated_players.contains(player_id) {
                 continue;
             }
@@ -320,21 +362,33 @@ impl Game for LiarsDiceGame {
                         }
                     }
                 }
-                "challenge" => {
-                    if !challenge_occurred && self.current_bid.is_some() {
+                "challenge"
+                    if !challenge_occurred && self.current_bid.is_some() => {
+                        let bidder = self.current_bid.as_ref().map(|b| b.player.clone()).unwrap_or_default();
+                        let out_before = self.eliminated_players.len();
                         match self.process_challenge(player_id) {
                             Ok((was_bluff, winner)) => {
                                 challenge_occurred = true;
+                                events.push(GameEvent::new(
+                                    EventKind::BluffCalled { caller: player_id.clone(), bidder: bidder.clone(), was_bluff },
+                                    format!("{} called {}'s bid", player_id, bidder),
+                                ));
+                                for loser in &self.eliminated_players[out_before..] {
+                                    events.push(GameEvent::new(
+                                        EventKind::Eliminated { player_id: loser.clone(), by: Some(winner.clone()) },
+                                        format!("{} lost their last die", loser),
+                                    ));
+                                }
                                 if was_bluff {
                                     round_events.push(format!("{} successfully challenged! It was a bluff.", player_id));
-                                    scores_delta.insert(player_id.clone(), CORRECT_CHALLENGE_BONUS);
+                                    scores_delta.insert(player_id.clone(), self.challenge_bonus);
                                     if let Some(bid) = &self.current_bid {
-                                        *scores_delta.entry(bid.player.clone()).or_insert(0) -= CHALLENGE_PENALTY;
+                                        *scores_delta.entry(bid.player.clone()).or_insert(0) -= self.challenge_penalty;
                                     }
                                 } else {
                                     round_events.push(format!("{}'s challenge failed! The bid was valid.", player_id));
-                                    scores_delta.insert(player_id.clone(), -CHALLENGE_PENALTY);
-                                    scores_delta.insert(winner, SUCCESSFUL_BLUFF_BONUS);
+                                    scores_delta.insert(player_id.clone(), -self.challenge_penalty);
+                                    scores_delta.insert(winner, self.bluff_bonus);
                                 }
                             }
                             Err(e) => {
@@ -342,7 +396,6 @@ impl Game for LiarsDiceGame {
                             }
                         }
                     }
-                }
                 _ => {}
             }
         }
@@ -370,25 +423,136 @@ impl Game for LiarsDiceGame {
                 emergence_detected: false,
             },
             scores_delta,
+            events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
 
+    async fn get_observation(&self, state: &GameState, observer: &Observer) -> Observation {
+        // Everyone knows how many dice each player holds, but only the owner sees the faces
+        let dice_counts: HashMap<&String, usize> = self.player_dice.iter()
+            .map(|(id, dice)| (id, dice.len()))
+            .collect();
+        let your_dice = observer.player_id()
+            .and_then(|id| self.player_dice.get(id));
+        
+        Observation::public(state, observer.clone()).with_view(serde_json::json!({
+            "dice_counts": dice_counts,
+            "your_dice": your_dice,
+            "current_bid": self.current_bid,
+            "betting_order": self.betting_order,
+            "eliminated_players": self.eliminated_players,
+            "round_history": self.round_history,
+        }))
+    }
+
+    fn parameters(&self) -> ParamSchema {
+        ParamSchema::new()
+            .integer("dice_per_player", "Dice each player starts with", DEFAULT_DICE as i64, 1..=10)
+            .integer("challenge_penalty", "Points lost by whoever is wrong in a challenge", DEFAULT_CHALLENGE_PENALTY as i64, 0..=100)
+            .integer("bluff_bonus", "Points for a bid that survives a challenge", DEFAULT_BLUFF_BONUS as i64, 0..=100)
+            .integer("challenge_bonus", "Points for catching a bluff", DEFAULT_CHALLENGE_BONUS as i64, 0..=100)
+            .integer("max_rounds", "Rounds before the game ends", DEFAULT_MAX_ROUNDS as i64, 1..=1000)
+    }
+    
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Liar's Dice")
+            .action("bid", "Claim at least `quantity` dice show `face_value` (ones are wild)", serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "quantity": { "type": "integer", "minimum": 1 },
+                    "face_value": { "type": "integer", "minimum": 1, "maximum": 6 }
+                },
+                "required": ["quantity", "face_value"]
+            }))
+            .simple_action("challenge", "Call the current bid a bluff")
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        if self.eliminated_players.iter().any(|p| p == player_id) {
+            return vec![];
+        }
+        
+        // Before dice are dealt every seated player will hold a full hand
+        let dice_in_play = if self.player_dice.is_empty() {
+            self.dice_per_player * state.players().len().max(1)
+        } else {
+            self.player_dice.values().map(|dice| dice.len()).sum()
+        };
+        
+        let mut actions = Vec::new();
+        for quantity in 1..=dice_in_play {
+            for face_value in 1..=6u8 {
+                let bid = Bid { quantity, face_value, player: player_id.to_string() };
+                if self.is_valid_bid(&bid) {
+                    actions.push(LegalAction::new("bid", serde_json::json!({
+                        "quantity": quantity,
+                        "face_value": face_value,
+                    })));
+                }
+            }
+        }
+        
+        if self.current_bid.is_some() {
+            actions.push(LegalAction::simple("challenge"));
+        }
+        actions
+    }
+    
+    async fn eliminated_players(&self, _state: &GameState) -> Vec<String> {
+        self.eliminated_players.clone()
+    }
+    
+    async fn join(&mut self, _state: &GameState, player_id: &str) {
+        self.seat_player(player_id.to_string());
+    }
+    
+    async fn forfeit(&mut self, _state: &GameState, player_id: &str) {
+        if self.eliminated_players.iter().any(|p| p == player_id) {
+            return;
+        }
+        let Some(dice) = self.player_dice.get_mut(player_id) else {
+            return;
+        };
+        // The player's dice leave the table with them
+        dice.clear();
+        self.eliminated_players.push(player_id.to_string());
+        
+        // Keep the turn with whoever was due to bid next
+        if let Some(seat) = self.betting_order.iter().position(|p| p == player_id) {
+            let current = self.current_turn_index % self.betting_order.len();
+            self.betting_order.remove(seat);
+            self.current_turn_index = if seat < current { current - 1 } else { current };
+        }
+        
+        // A bid nobody can be held to is withdrawn
+        if self.current_bidder.as_deref() == Some(player_id) {
+            self.current_bid = None;
+            self.current_bidder = None;
+            self.current_bid_is_bluff = false;
+        }
+    }
+    
+    async fn current_turn(&self, _state: &GameState) -> Turn {
+        // Everyone joins in the opening round, then bidding goes around the table
+        match self.get_current_player() {
+            Some(player) => Turn::sequential(player.clone()),
+            None => Turn::simultaneous(Vec::new()),
+        }
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         let active_players = self.player_dice.len() - self.eliminated_players.len();
         active_players <= 1 || state.round >= self.max_rounds
     }
 
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        let winner = self.betting_order.iter()
-            .find(|p| !self.eliminated_players.contains(p))
-            .cloned()
-            .unwrap_or_else(|| "No Winner".to_string());
-        
         let mut final_scores = state.scores.clone();
         
-        // Victory bonus
-        if winner != "No Winner" {
+        // Victory bonus for the last player holding dice
+        if let [winner] = self.betting_order.as_slice() {
             *final_scores.entry(winner.clone()).or_insert(0) += 50;
         }
         
@@ -411,11 +575,23 @@ impl Game for LiarsDiceGame {
             *final_scores.entry(player.clone()).or_insert(0) += skill_bonus;
         }
         
+        // Every round lists everyone out so far, so a player lost their last die in the first round naming them
+        let standings = self.eliminated_players.iter().fold(Standings::new(&final_scores), |standings, player| {
+            let round = state.history.iter()
+                .position(|r| r.outcome.losers.contains(player))
+                .map_or(state.round, |i| i as u32 + 1);
+            standings.eliminated(player, round)
+        });
+        
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores,
             total_rounds: state.round,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
             emergence_events: vec![],
             analytics: self.calculate_analytics(&state.history),
         }
@@ -1,17 +1,21 @@
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, GameError};
+use genius_core::{game_rng, GameRng, Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, ActionSchema, LegalAction, ParamSchema, Standings};
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use uuid::Uuid;
 use rand::Rng;
 
+const DEFAULT_MAX_ROUNDS: u32 = 100;
+
+#[derive(Serialize, Deserialize)]
 pub struct PrisonersDilemmaGame {
     max_rounds: u32,
     reputation_scores: HashMap<String, f32>,
     history: Vec<PDRoundData>,
+    rng: GameRng,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct PDRoundData {
     actions: HashMap<String, PDAction>,
     #[allow(dead_code)]
@@ -20,7 +24,7 @@ struct PDRoundData {
     defections: Vec<(String, String)>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 enum PDAction {
     Cooperate,
     Defect,
@@ -35,9 +39,10 @@ impl Default for PrisonersDilemmaGame {
 impl PrisonersDilemmaGame {
     pub fn new() -> Self {
         Self {
-            max_rounds: 100,
+            max_rounds: DEFAULT_MAX_ROUNDS,
             reputation_scores: HashMap::new(),
             history: Vec::new(),
+            rng: game_rng(None),
         }
     }
     
@@ -130,9 +135,10 @@ impl PrisonersDilemmaGame {
         if cooperation_rate > 0.7 && tit_for_tat_ratio > 0.3 && avg_reputation > 0.6 {
             Some(EmergenceEvent {
                 round: state.round,
-                event_type: "tit_for_tat_emergence".to_string(),
+                event_type: EmergenceType::Custom("tit_for_tat_emergence".to_string()),
                 description: "Collective discovered tit-for-tat cooperation strategy".to_string(),
                 emergence_score: cooperation_rate,
+                involved_players: state.active_players().into_iter().cloned().collect(),
             })
         } else {
             None
@@ -143,18 +149,20 @@ impl PrisonersDilemmaGame {
 #[async_trait]
 impl Game for PrisonersDilemmaGame {
     async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
-        // Extract max rounds from config
-        if let Some(rounds_str) = config.special_rules.get("max_rounds") {
-            if let Ok(rounds) = rounds_str.parse::<u32>() {
-                self.max_rounds = rounds;
-            }
-        }
+        self.rng = game_rng(config.seed);
+        
+        let params = self.parameters().validate(&config.params)?;
+        self.max_rounds = params.int("max_rounds") as u32;
         
         Ok(GameState {
             game_id: Uuid::new_v4(),
             game_type: GameType::PrisonersDilemma,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
@@ -167,6 +175,8 @@ impl Game for PrisonersDilemmaGame {
                 }));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
     
@@ -183,8 +193,12 @@ impl Game for PrisonersDilemmaGame {
             }
         }
         
-        // Parse actions
-        for (player_id, action) in &actions {
+        // Parse actions in a stable order, since undecided players roll the RNG
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        
+        for player_id in player_ids {
+            let action = &actions[player_id];
             let pd_action = match action.action_type.as_str() {
                 "cooperate" => PDAction::Cooperate,
                 "defect" => PDAction::Defect,
@@ -197,7 +211,7 @@ impl Game for PrisonersDilemmaGame {
                             _ => {
                                 // Use reputation to decide
                                 let reputation = self.reputation_scores.get(player_id).copied().unwrap_or(0.5);
-                                if rand::thread_rng().gen::<f32>() < reputation {
+                                if self.rng.random::<f32>() < reputation {
                                     PDAction::Cooperate
                                 } else {
                                     PDAction::Defect
@@ -219,7 +233,8 @@ impl Game for PrisonersDilemmaGame {
         }
         
         // Calculate payoffs for all pairs
-        let players: Vec<String> = pd_actions.keys().cloned().collect();
+        let mut players: Vec<String> = pd_actions.keys().cloned().collect();
+        players.sort();
         for i in 0..players.len() {
             for j in i+1..players.len() {
                 let p1 = &players[i];
@@ -272,15 +287,17 @@ impl Game for PrisonersDilemmaGame {
             payoffs.values().sum::<i32>() / payoffs.len() as i32 
         };
         
-        let winners: Vec<String> = payoffs.iter()
+        let mut winners: Vec<String> = payoffs.iter()
             .filter(|(_, &score)| score > avg_payoff)
             .map(|(id, _)| id.clone())
             .collect();
         
-        let losers: Vec<String> = payoffs.iter()
+        let mut losers: Vec<String> = payoffs.iter()
             .filter(|(_, &score)| score <= avg_payoff)
             .map(|(id, _)| id.clone())
             .collect();
+        winners.sort();
+        losers.sort();
         
         let mut special_events = vec![];
         if let Some(event) = &emergence_event {
@@ -304,10 +321,30 @@ impl Game for PrisonersDilemmaGame {
                 emergence_detected: emergence_event.is_some(),
             },
             scores_delta: payoffs,
+            events: emergence_event.iter()
+                .map(GameEvent::emergence)
+                .collect(),
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
+    fn parameters(&self) -> ParamSchema {
+        ParamSchema::new()
+            .integer("max_rounds", "Rounds played before the game ends", DEFAULT_MAX_ROUNDS as i64, 1..=10_000)
+    }
+    
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Prisoner's Dilemma")
+            .simple_action("cooperate", "Stay silent and trust your partner")
+            .simple_action("defect", "Betray your partner")
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, _state: &GameState, _player_id: &str) -> Vec<LegalAction> {
+        vec![LegalAction::simple("cooperate"), LegalAction::simple("defect")]
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         state.round >= self.max_rounds || 
         state.scores.values().any(|&score| score >= 500 || score <= -100)
@@ -322,27 +359,8 @@ impl Game for PrisonersDilemmaGame {
             }
         }
         
-        let winner = total_scores.iter()
-            .max_by_key(|(_, score)| *score)
-            .map(|(id, _)| id.clone())
-            .unwrap_or_else(|| "No winner".to_string());
-        
-        // Collect emergence events
-        let emergence_events: Vec<EmergenceEvent> = state.history.iter()
-            .enumerate()
-            .filter_map(|(i, round_result)| {
-                if round_result.outcome.emergence_detected {
-                    Some(EmergenceEvent {
-                        round: i as u32,
-                        event_type: "tit_for_tat".to_string(),
-                        description: "Collective discovered optimal cooperation strategy".to_string(),
-                        emergence_score: 0.8,
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let standings = Standings::new(&total_scores);
+        
         
         // Calculate analytics
         let collective_scores: Vec<i32> = total_scores.iter()
@@ -358,7 +376,9 @@ impl Game for PrisonersDilemmaGame {
         let avg_collective = collective_scores.iter().sum::<i32>() as f32 / collective_scores.len().max(1) as f32;
         let avg_single = single_scores.iter().sum::<i32>() as f32 / single_scores.len().max(1) as f32;
         
-        let emergence_frequency = emergence_events.len() as f32 / state.round.max(1) as f32;
+        let emergence_frequency = state.history.iter()
+            .filter(|round_result| round_result.outcome.emergence_detected)
+            .count() as f32 / state.round.max(1) as f32;
         
         // Calculate cooperation rate
         let total_cooperations = self.history.iter()
@@ -371,16 +391,21 @@ impl Game for PrisonersDilemmaGame {
         
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores: total_scores,
             total_rounds: state.round,
-            emergence_events,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
+            emergence_events: vec![],
             analytics: GameAnalytics {
                 collective_coordination_score: cooperation_rate,
                 decision_diversity_index: 0.5, // Could calculate based on action variety
                 strategic_depth: self.reputation_scores.values().sum::<f32>() / self.reputation_scores.len().max(1) as f32,
                 emergence_frequency,
                 performance_differential: avg_collective - avg_single,
+                custom_metrics: HashMap::new(),
             },
         }
     }
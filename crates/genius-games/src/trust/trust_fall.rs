@@ -1,11 +1,13 @@
-use genius_core::{Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EmergenceEvent, EmergenceType, Result, GameError};
+use genius_core::{game_rng, GameRng, Game, GameConfig, GameState, GameType, PlayerAction, RoundResult, RoundOutcome, GameResult, GameAnalytics, GameEvent, EventKind, EmergenceEvent, EmergenceType, Result, ActionSchema, LegalAction, Standings};
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use uuid::Uuid;
 use rand::Rng;
 
+#[derive(Serialize, Deserialize)]
 pub struct TrustFall {
+    #[serde(with = "genius_core::tuple_keys")]
     trust_scores: HashMap<(String, String), f32>,
     reputation: HashMap<String, f32>,
     fall_heights: HashMap<String, u32>,
@@ -17,9 +19,10 @@ pub struct TrustFall {
     risk_tolerance: HashMap<String, f32>,
     trust_tokens: HashMap<String, i32>,
     current_round_falls: Vec<FallEvent>,
+    rng: GameRng,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct BetrayalEvent {
     #[allow(dead_code)]
     round: u32,
@@ -35,7 +38,7 @@ struct BetrayalEvent {
     reputation_loss: f32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct FallEvent {
     #[allow(dead_code)]
     faller: String,
@@ -69,6 +72,7 @@ impl TrustFall {
             risk_tolerance: HashMap::new(),
             trust_tokens: HashMap::new(),
             current_round_falls: Vec::new(),
+            rng: game_rng(None),
         }
     }
     
@@ -179,9 +183,10 @@ impl TrustFall {
         if collective_trust > 0.8 && collective_trust > sota_trust * 1.5 {
             Some(EmergenceEvent {
                 round: state.round,
-                event_type: "trust_network".to_string(),
+                event_type: EmergenceType::Custom("trust_network".to_string()),
                 description: "Collective developed strong trust networks enabling riskier strategies".to_string(),
                 emergence_score: collective_trust,
+                involved_players: state.active_players().into_iter().cloned().collect(),
             })
         } else {
             None
@@ -191,12 +196,18 @@ impl TrustFall {
 
 #[async_trait]
 impl Game for TrustFall {
-    async fn initialize(&mut self, _config: GameConfig) -> Result<GameState> {
+    async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        self.rng = game_rng(config.seed);
+        
         Ok(GameState {
             game_id: Uuid::new_v4(),
             game_type: GameType::TrustFall,
             round: 0,
             scores: HashMap::new(),
+            player_status: HashMap::new(),
+            teams: Vec::new(),
+            coalitions: Vec::new(),
+            clocks: HashMap::new(),
             history: vec![],
             metadata: {
                 let mut meta = HashMap::new();
@@ -222,6 +233,8 @@ impl Game for TrustFall {
                 meta.insert("trust_tokens".to_string(), serde_json::json!(3));
                 meta
             },
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
         })
     }
     
@@ -236,13 +249,18 @@ impl Game for TrustFall {
         self.current_round_falls.clear();
         let mut scores_delta = HashMap::new();
         let mut special_events = vec![];
+        let mut events = Vec::new();
         let mut eliminated_players = Vec::new();
         
         // First pass: collect fall requests and agreements
         let mut fall_requests: HashMap<String, (u32, Vec<String>, bool)> = HashMap::new();
         let mut catch_agreements: HashMap<String, Vec<String>> = HashMap::new();
         
-        for (player_id, action) in &actions {
+        let mut player_ids: Vec<&String> = actions.keys().collect();
+        player_ids.sort();
+        
+        for player_id in player_ids {
+            let action = &actions[player_id];
             if self.player_health.get(player_id).copied().unwrap_or(0) <= 0 {
                 continue;
             }
@@ -277,6 +295,10 @@ impl Game for TrustFall {
                     if let Some(target) = action.data.get("target").and_then(|v| v.as_str()) {
                         self.form_alliance(player_id, target);
                         special_events.push(format!("🤝 {} and {} formed an alliance", player_id, target));
+                        events.push(GameEvent::new(
+                            EventKind::AllianceFormed { members: vec![player_id.clone(), target.to_string()] },
+                            format!("{} and {} formed an alliance", player_id, target),
+                        ));
                         scores_delta.insert(player_id.clone(), 5);
                     }
                 }
@@ -294,7 +316,10 @@ impl Game for TrustFall {
             }
         }
         
-        // Process falls
+        // Process falls in a stable order, since each one rolls the RNG
+        let mut fall_requests: Vec<_> = fall_requests.into_iter().collect();
+        fall_requests.sort_by(|a, b| a.0.cmp(&b.0));
+        
         for (faller, (height, requested_catchers, guaranteed)) in fall_requests {
             // Determine actual catchers (those who agreed)
             let actual_catchers: Vec<String> = requested_catchers.iter()
@@ -318,6 +343,15 @@ impl Game for TrustFall {
                 
                 special_events.push(format!("💔 {} fell from height {} with no catchers! -{} HP", 
                     faller, height, damage));
+                events.push(GameEvent::new(
+                    EventKind::custom("trust_fall", serde_json::json!({
+                        "faller": faller,
+                        "catchers": [],
+                        "height": height,
+                        "caught": false,
+                    })),
+                    format!("{} took a fall from height {} with nobody there to catch them", faller, height),
+                ));
                 scores_delta.insert(faller.clone(), -(height as i32 * 10));
                 
                 continue;
@@ -330,8 +364,7 @@ impl Game for TrustFall {
                 self.calculate_catch_probability(&actual_catchers, height)
             };
             
-            let mut rng = rand::thread_rng();
-            let success = rng.gen::<f32>() < catch_prob;
+            let success = self.rng.random::<f32>() < catch_prob;
             
             if success {
                 // Successful catch
@@ -345,6 +378,19 @@ impl Game for TrustFall {
                 
                 special_events.push(format!("✓ {} successfully caught by {} from height {}! +{} points",
                     faller, actual_catchers.join(", "), height, points));
+                events.push(GameEvent::new(
+                    EventKind::custom("trust_fall", serde_json::json!({
+                        "faller": faller,
+                        "catchers": actual_catchers,
+                        "height": height,
+                        "caught": true,
+                    })),
+                    if actual_catchers.is_empty() {
+                        format!("{}'s trust token broke a fall from height {}", faller, height)
+                    } else {
+                        format!("{} caught {}'s fall from height {}", actual_catchers.join(", "), faller, height)
+                    },
+                ));
                 
                 // Update fall height record
                 let current_max = self.fall_heights.get(&faller).copied().unwrap_or(0);
@@ -365,6 +411,11 @@ impl Game for TrustFall {
                 for catcher in &actual_catchers {
                     *scores_delta.entry(catcher.clone()).or_insert(0) -= height as i32 * 20;
                     
+                    events.push(GameEvent::new(
+                        EventKind::AllianceBetrayed { betrayer: catcher.clone(), victims: vec![faller.clone()] },
+                        format!("{} let {}'s fall from height {} hit the ground", catcher, faller, height),
+                    ));
+                    
                     // Record betrayal
                     self.betrayal_history.push(BetrayalEvent {
                         round: state.round + 1,
@@ -395,8 +446,10 @@ impl Game for TrustFall {
         }
         
         // Award trust tokens to high-reputation players
-        for (player, &reputation) in &self.reputation {
-            if reputation >= 0.9 && state.round % 10 == 0 {
+        let mut reputations: Vec<(&String, &f32)> = self.reputation.iter().collect();
+        reputations.sort_by(|a, b| a.0.cmp(b.0));
+        for (player, &reputation) in reputations {
+            if reputation >= 0.9 && state.round.is_multiple_of(10) {
                 *self.trust_tokens.get_mut(player).unwrap() += 1;
                 special_events.push(format!("🎖️ {} earned a trust token for high reputation!", player));
             }
@@ -406,6 +459,7 @@ impl Game for TrustFall {
         let emergence_event = self.detect_trust_emergence(state);
         if let Some(event) = &emergence_event {
             special_events.push(event.description.clone());
+            events.push(GameEvent::emergence(event));
         }
         
         // Determine winners (highest trust network value)
@@ -415,6 +469,11 @@ impl Game for TrustFall {
             .map(|(id, _)| id.clone())
             .collect();
         
+        events.extend(eliminated_players.iter().map(|player| GameEvent::new(
+            EventKind::Eliminated { player_id: player.clone(), by: None },
+            format!("{} was hurt by one fall too many", player),
+        )));
+        
         Ok(RoundResult {
             round: state.round + 1,
             actions: actions.clone(),
@@ -425,10 +484,119 @@ impl Game for TrustFall {
                 emergence_detected: emergence_event.is_some(),
             },
             scores_delta,
+            events,
+            emergence: Vec::new(),
             timestamp: chrono::Utc::now(),
         })
     }
     
+    fn action_schema(&self) -> serde_json::Value {
+        let target = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "target": { "type": "string" }
+            },
+            "required": ["target"]
+        });
+        
+        ActionSchema::new("Trust Fall")
+            .action("fall", "Fall from a height and trust the named players to catch you", serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "height": { "type": "integer", "minimum": 0 },
+                    "catchers": { "type": "array", "items": { "type": "string" } },
+                    "use_token": { "type": "boolean", "default": false }
+                },
+                "required": ["height", "catchers"]
+            }))
+            .action("catch", "Agree to catch a falling player", serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "faller": { "type": "string" }
+                },
+                "required": ["faller"]
+            }))
+            .action("alliance", "Form a trust alliance with another player", target.clone())
+            .action("investigate", "Learn another player's reputation and catch record", target)
+            .build()
+    }
+    
+    async fn get_valid_actions(&self, state: &GameState, player_id: &str) -> Vec<LegalAction> {
+        let is_alive = |id: &str| self.player_health.get(id).is_none_or(|&health| health > 0);
+        if !is_alive(player_id) {
+            return vec![];
+        }
+        
+        let mut others: Vec<&String> = state.players().into_iter()
+            .filter(|p| p.as_str() != player_id && is_alive(p.as_str()))
+            .collect();
+        others.sort();
+        
+        // Alliances come first: trust is built before anyone is asked to fall
+        let allies = self.alliance_networks.get(player_id);
+        let mut actions: Vec<LegalAction> = others.iter()
+            .filter(|other| allies.is_none_or(|a| !a.contains(other)))
+            .map(|other| LegalAction::new("alliance", serde_json::json!({ "target": other })))
+            .collect();
+        
+        let has_token = self.trust_tokens.get(player_id).copied().unwrap_or(0) > 0;
+        if !others.is_empty() {
+            for height in [5, 10, 20] {
+                actions.push(LegalAction::new("fall", serde_json::json!({
+                    "height": height,
+                    "catchers": others,
+                    "use_token": false,
+                })));
+                if has_token {
+                    actions.push(LegalAction::new("fall", serde_json::json!({
+                        "height": height,
+                        "catchers": others,
+                        "use_token": true,
+                    })));
+                }
+            }
+        }
+        
+        for other in &others {
+            actions.push(LegalAction::new("catch", serde_json::json!({ "faller": other })));
+            actions.push(LegalAction::new("investigate", serde_json::json!({ "target": other })));
+        }
+        
+        actions
+    }
+    
+    async fn eliminated_players(&self, _state: &GameState) -> Vec<String> {
+        let mut out: Vec<String> = self.player_health.iter()
+            .filter(|(_, &health)| health <= 0)
+            .map(|(id, _)| id.clone())
+            .collect();
+        out.sort();
+        out
+    }
+    
+    async fn join(&mut self, _state: &GameState, player_id: &str) {
+        if !self.reputation.contains_key(player_id) {
+            self.initialize_player(player_id);
+        }
+    }
+    
+    async fn forfeit(&mut self, _state: &GameState, player_id: &str) {
+        if let Some(health) = self.player_health.get_mut(player_id) {
+            *health = 0;
+        }
+        // Nobody stays allied with a player who walked away
+        self.alliance_networks.remove(player_id);
+        for allies in self.alliance_networks.values_mut() {
+            allies.retain(|ally| ally != player_id);
+        }
+    }
+    
+    async fn coalitions(&self, _state: &GameState) -> Vec<Vec<String>> {
+        self.alliance_networks.iter()
+            .flat_map(|(player, allies)| allies.iter().map(move |ally| vec![player.clone(), ally.clone()]))
+            .collect()
+    }
+    
     async fn is_game_over(&self, state: &GameState) -> bool {
         let alive_count = self.player_health.values().filter(|&&h| h > 0).count();
         
@@ -437,23 +605,6 @@ impl Game for TrustFall {
     }
     
     async fn calculate_final_result(&self, state: &GameState) -> GameResult {
-        // Winner has highest combination of score and reputation
-        let winner = self.player_health.iter()
-            .filter(|(_, &health)| health > 0)
-            .map(|(id, _)| {
-                let score = state.scores.get(id).copied().unwrap_or(0);
-                let reputation = self.reputation.get(id).copied().unwrap_or(0.5);
-                let trust_value: f32 = self.trust_scores.iter()
-                    .filter(|((p1, _), _)| p1 == id)
-                    .map(|(_, &score)| score)
-                    .sum();
-                
-                (id.clone(), score as f32 + reputation * 1000.0 + trust_value * 100.0)
-            })
-            .max_by(|(_, v1), (_, v2)| v1.partial_cmp(v2).unwrap())
-            .map(|(id, _)| id)
-            .unwrap_or_else(|| "No winner".to_string());
-        
         // Calculate final scores with bonuses
         let mut final_scores = state.scores.clone();
         
@@ -473,24 +624,20 @@ impl Game for TrustFall {
             *final_scores.entry(player.clone()).or_insert(0) += max_height as i32 * 10;
         }
         
-        // Collect emergence events
-        let emergence_events: Vec<EmergenceEvent> = state.history.iter()
-            .enumerate()
-            .filter_map(|(i, round_result)| {
-                if round_result.outcome.emergence_detected {
-                    Some(EmergenceEvent {
-                        round: i as u32,
-                        event_type: "trust_network".to_string(),
-                        description: "Complex trust networks and reputation systems emerged".to_string(),
-                        emergence_score: 0.95,
-                    })
-                } else {
-                    None
-                }
-            })
-            .collect();
+        // Players who fell to zero health place below the living, by the round they went down in
+        let standings = self.player_health.iter()
+            .filter(|(_, &health)| health <= 0)
+            .fold(Standings::new(&final_scores), |standings, (player, _)| {
+                let round = state.history.iter()
+                    .position(|r| r.outcome.losers.contains(player))
+                    .map_or(state.round, |i| i as u32 + 1);
+                standings.eliminated(player, round)
+            });
+        
         
-        let emergence_frequency = emergence_events.len() as f32 / state.round.max(1) as f32;
+        let emergence_frequency = state.history.iter()
+            .filter(|round_result| round_result.outcome.emergence_detected)
+            .count() as f32 / state.round.max(1) as f32;
         
         // Calculate trust analytics
         let avg_trust = self.trust_scores.values().sum::<f32>() / self.trust_scores.len().max(1) as f32;
@@ -507,16 +654,21 @@ impl Game for TrustFall {
         
         GameResult {
             game_id: state.game_id,
-            winner,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
             final_scores,
             total_rounds: state.round,
-            emergence_events,
+            duration_ms: (state.updated_at - state.created_at).num_milliseconds().max(0) as u64,
+            emergence_events: vec![],
             analytics: GameAnalytics {
                 collective_coordination_score: avg_trust,
                 decision_diversity_index: 1.0 - betrayal_rate,
                 strategic_depth: 0.95,
                 emergence_frequency,
                 performance_differential: collective_reputation - sota_reputation,
+                custom_metrics: HashMap::new(),
             },
         }
     }
@@ -0,0 +1,100 @@
+//! End-to-end tests for published action schemas and legal moves
+
+use genius_core::player::{PlayerId, PlayerType};
+use genius_core::{GameConfig, GameType, LegalAction, Player, PlayerAction};
+use genius_games::{create_game, GameRegistry};
+use std::collections::{HashMap, HashSet};
+
+const PLAYERS: [&str; 4] = ["p0", "p1", "p2", "p3"];
+
+fn config(game_type: GameType) -> GameConfig {
+    GameConfig {
+        game_type,
+        rounds: 20,
+        time_limit_ms: 1000,
+        params: HashMap::new(),
+        initial_players: PLAYERS.iter()
+            .map(|&id| Player {
+                id: PlayerId::from_string(id.to_string()),
+                name: id.to_string(),
+                player_type: PlayerType::Human,
+                metadata: serde_json::Value::Null,
+            })
+            .collect(),
+        teams: vec![],
+        seed: Some(7),
+    }
+}
+
+/// Check a legal move against the schema branch for its action type
+fn conforms(variant: &serde_json::Value, action: &LegalAction) -> bool {
+    let data_schema = &variant["properties"]["data"];
+
+    if let Some(allowed) = data_schema["enum"].as_array() {
+        return allowed.contains(&action.data);
+    }
+    if data_schema["type"] == "null" {
+        return action.data.is_null();
+    }
+    if let Some(range) = action.range {
+        let minimum = data_schema["minimum"].as_i64().unwrap_or(i64::MIN);
+        return data_schema["type"] == "integer" && range.min >= minimum && range.min <= range.max;
+    }
+    if let Some(required) = data_schema["required"].as_array() {
+        return required.iter()
+            .filter_map(|key| key.as_str())
+            .all(|key| action.data.get(key).is_some());
+    }
+    true
+}
+
+#[tokio::test]
+async fn test_every_game_publishes_schema_matching_its_legal_moves() {
+    for game_type in GameRegistry::new().all_games().iter() {
+        let mut game = create_game(game_type.clone()).expect("Game should exist");
+        let mut state = game.initialize(config(game_type.clone())).await.expect("Game should initialize");
+        for player in PLAYERS {
+            state.add_player(player.to_string());
+        }
+
+        let schema = game.action_schema();
+        let variants = schema["oneOf"].as_array()
+            .unwrap_or_else(|| panic!("{:?} schema should list its actions under oneOf", game_type));
+        let by_type: HashMap<&str, &serde_json::Value> = variants.iter()
+            .map(|v| (v["properties"]["action_type"]["const"].as_str().expect("Variant should pin its action_type"), v))
+            .collect();
+        assert!(!by_type.is_empty(), "{:?} schema declares no actions", game_type);
+
+        let legal = game.get_valid_actions(&state, PLAYERS[0]).await;
+        assert!(!legal.is_empty(), "{:?} offers no legal moves to a fresh player", game_type);
+
+        for action in &legal {
+            let variant = by_type.get(action.action_type.as_str())
+                .unwrap_or_else(|| panic!("{:?} offers undeclared action {}", game_type, action.action_type));
+            assert!(conforms(variant, action), "{:?} move {:?} does not fit its schema", game_type, action);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_legal_moves_follow_game_state() {
+    let mut game = create_game(GameType::MiniGo).expect("Game should exist");
+    let mut state = game.initialize(config(GameType::MiniGo)).await.expect("Game should initialize");
+
+    let center = LegalAction::new("place", serde_json::json!({ "row": 4, "col": 4 }));
+    let before = game.get_valid_actions(&state, "p0").await;
+    assert!(before.contains(&center), "Center should be open on an empty board");
+
+    let mut actions = HashMap::new();
+    actions.insert("p0".to_string(), center.to_action("p0"));
+    actions.insert("p1".to_string(), PlayerAction::new("p1".to_string(), "pass".to_string(), serde_json::Value::Null));
+    let result = game.process_round(&state, actions).await.expect("Round should process");
+    state.apply_round(&result);
+
+    let after: HashSet<_> = game.get_valid_actions(&state, "p1").await
+        .into_iter()
+        .map(|a| a.data.to_string())
+        .collect();
+    assert!(!after.contains(&center.data.to_string()), "Occupied point should no longer be legal");
+    assert_eq!(after.len(), before.len() - 1);
+}
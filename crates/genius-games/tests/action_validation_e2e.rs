@@ -0,0 +1,132 @@
+//! End-to-end tests for engine-side action validation
+
+use genius_core::{GameConfig, GameError, GameType, PlayerAction};
+use genius_engine::GameEngine;
+use genius_games::create_game;
+use std::collections::HashMap;
+
+async fn minority_engine() -> (GameEngine, uuid::Uuid) {
+    let engine = GameEngine::new(create_game);
+    let config = GameConfig {
+        game_type: GameType::MinorityGame,
+        rounds: 10,
+        time_limit_ms: 1000,
+        params: HashMap::new(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(11),
+    };
+
+    let state = engine.create_game(config).await.expect("Game should be created");
+    (engine, state.game_id)
+}
+
+fn action(player: &str, action_type: &str, data: serde_json::Value) -> (String, PlayerAction) {
+    (player.to_string(), PlayerAction::new(player.to_string(), action_type.to_string(), data))
+}
+
+#[tokio::test]
+async fn test_illegal_actions_are_rejected_with_reasons() {
+    let (engine, game_id) = minority_engine().await;
+
+    let actions: HashMap<String, PlayerAction> = [
+        action("p0", "choose", serde_json::json!(1)),
+        action("p1", "choose", serde_json::json!(7)),
+        action("p2", "attack", serde_json::json!(0)),
+        ("p3".to_string(), PlayerAction::new("p0".to_string(), "choose".to_string(), serde_json::json!(0))),
+    ].into_iter().collect();
+
+    match engine.process_turn(game_id, actions).await {
+        Err(GameError::ActionsRejected { rejections }) => {
+            let players: Vec<&str> = rejections.iter().map(|r| r.player_id.as_str()).collect();
+            assert_eq!(players, vec!["p1", "p2", "p3"]);
+            assert!(rejections[1].reason.contains("choose"), "Reason should list the legal action types");
+            assert!(rejections.iter().all(|r| !r.reason.is_empty()));
+        }
+        other => panic!("Expected rejected actions, got {:?}", other.map(|r| r.round)),
+    }
+
+    // A rejected turn must not advance the game
+    assert_eq!(engine.get_game_state(game_id).await.unwrap().round, 0);
+}
+
+#[tokio::test]
+async fn test_legal_actions_are_processed() {
+    let (engine, game_id) = minority_engine().await;
+
+    let actions: HashMap<String, PlayerAction> = (0..5)
+        .map(|i| action(&format!("p{}", i), "choose", serde_json::json!(i % 2)))
+        .collect();
+
+    engine.process_turn(game_id, actions).await.expect("Legal actions should be accepted");
+    assert_eq!(engine.get_game_state(game_id).await.unwrap().round, 1);
+}
+
+#[tokio::test]
+async fn test_empty_and_missing_payloads_match_moves_without_data() {
+    let engine = GameEngine::new(create_game);
+    let config = GameConfig {
+        game_type: GameType::PrisonersDilemma,
+        rounds: 10,
+        time_limit_ms: 1000,
+        params: HashMap::new(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(11),
+    };
+    let game_id = engine.create_game(config).await.unwrap().game_id;
+
+    // Clients send `{}` or leave the payload out altogether
+    let missing: PlayerAction = serde_json::from_value(serde_json::json!({
+        "player_id": "ben",
+        "action_type": "defect",
+        "reasoning": null,
+        "confidence": null,
+        "timestamp": chrono::Utc::now(),
+    })).unwrap();
+    let actions: HashMap<String, PlayerAction> = [
+        action("ada", "cooperate", serde_json::json!({})),
+        ("ben".to_string(), missing),
+    ].into_iter().collect();
+
+    engine.process_turn(game_id, actions).await.expect("Payload-free moves should be accepted");
+
+    // A real payload is still checked
+    let actions: HashMap<String, PlayerAction> = [
+        action("ada", "cooperate", serde_json::json!({ "amount": 1 })),
+        action("ben", "defect", serde_json::Value::Null),
+    ].into_iter().collect();
+    assert!(matches!(engine.process_turn(game_id, actions).await, Err(GameError::ActionsRejected { .. })));
+}
+
+#[tokio::test]
+async fn test_raises_are_one_move_spanning_every_legal_amount() {
+    let engine = GameEngine::new(create_game);
+    let config = GameConfig {
+        game_type: GameType::MiniHoldem,
+        rounds: 10,
+        time_limit_ms: 1000,
+        params: HashMap::new(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(11),
+    };
+    let game_id = engine.open_lobby(config).await.unwrap().game_id;
+    engine.join_game(game_id, "ada").await.unwrap();
+    engine.join_game(game_id, "ben").await.unwrap();
+    engine.start_game(game_id).await.unwrap();
+
+    let player = engine.get_current_turn(game_id).await.unwrap().players[0].clone();
+    let legal = engine.get_valid_actions(game_id, &player).await.unwrap();
+    let raises: Vec<_> = legal.iter().filter(|m| m.action_type == "raise").collect();
+    assert_eq!(raises.len(), 1);
+    let range = raises[0].range.expect("Raise should cover a range of amounts");
+    assert!(range.max > range.min + 1);
+
+    for amount in [range.max + 1, range.min - 1] {
+        let actions: HashMap<String, PlayerAction> = [action(&player, "raise", serde_json::json!(amount))].into_iter().collect();
+        assert!(matches!(engine.process_turn(game_id, actions).await, Err(GameError::ActionsRejected { .. })), "{} is out of range", amount);
+    }
+    let actions: HashMap<String, PlayerAction> = [action(&player, "raise", serde_json::json!(range.min + 1))].into_iter().collect();
+    engine.process_turn(game_id, actions).await.expect("Any amount in the range should be accepted");
+}
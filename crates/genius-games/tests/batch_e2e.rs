@@ -0,0 +1,149 @@
+//! End-to-end tests for large batches of headless games
+
+use async_trait::async_trait;
+use genius_ai::{providers::MockProvider, AIDecision, AIProvider};
+use genius_core::{GameConfig, GameType, LegalAction, Observation, Result};
+use genius_engine::{BatchRunner, GameEngine};
+use genius_games::create_game;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Always plays the same move when it is legal
+struct FixedProvider {
+    action_type: &'static str,
+}
+
+#[async_trait]
+impl AIProvider for FixedProvider {
+    fn name(&self) -> &str {
+        "Fixed Provider"
+    }
+
+    async fn make_decision(&self, _observation: &Observation, player_id: &str, valid_actions: Vec<LegalAction>) -> Result<AIDecision> {
+        let choice = valid_actions.iter().find(|a| a.action_type == self.action_type).unwrap_or(&valid_actions[0]);
+        Ok(AIDecision {
+            action: choice.to_action(player_id),
+            reasoning: "Same as always".to_string(),
+            confidence: 1.0,
+        })
+    }
+}
+
+fn config(game_type: GameType, params: &[(&str, i64)]) -> GameConfig {
+    GameConfig {
+        game_type,
+        rounds: 20,
+        time_limit_ms: 1000,
+        params: params.iter().map(|(k, v)| (k.to_string(), serde_json::json!(v))).collect::<HashMap<_, _>>(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(99),
+    }
+}
+
+fn dilemma() -> GameConfig {
+    config(GameType::PrisonersDilemma, &[("max_rounds", 5)])
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_win_rates_and_score_distributions_are_aggregated() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let stats = BatchRunner::new(engine.clone(), dilemma(), 200)
+        .agent("hawk", |_| Arc::new(FixedProvider { action_type: "defect" }) as Arc<dyn AIProvider>)
+        .agent("dove", |_| Arc::new(FixedProvider { action_type: "cooperate" }) as Arc<dyn AIProvider>)
+        .run()
+        .await
+        .expect("Batch should run");
+
+    assert_eq!((stats.planned, stats.completed, stats.failed), (200, 200, 0));
+    let hawk = &stats.agents["hawk"];
+    let dove = &stats.agents["dove"];
+    assert_eq!((hawk.wins, hawk.losses), (200, 0));
+    assert_eq!((dove.wins, dove.losses), (0, 200));
+    assert_eq!(hawk.win_rate, 1.0);
+    assert!(hawk.win_rate_ci.low > 0.97 && hawk.win_rate_ci.high > 0.999);
+    assert!(dove.win_rate_ci.high < 0.03);
+
+    // Every game plays out the same way
+    assert_eq!(hawk.score.std_dev, 0.0);
+    assert!(hawk.score.min > dove.score.max);
+    assert_eq!(hawk.score.histogram.values().sum::<u32>(), 200);
+    assert_eq!(stats.rounds.histogram, [(5, 200)].into_iter().collect());
+
+    // Finished games are not left behind in the engine
+    assert!(engine.active_games().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_batches_are_reproducible_however_games_interleave() {
+    let run = |concurrency: usize| async move {
+        BatchRunner::new(Arc::new(GameEngine::new(create_game)), dilemma(), 60)
+            .agent("left", |seed| Arc::new(MockProvider::seeded(seed)) as Arc<dyn AIProvider>)
+            .agent("right", |seed| Arc::new(MockProvider::seeded(seed)) as Arc<dyn AIProvider>)
+            .concurrency(concurrency)
+            .run()
+            .await
+            .expect("Batch should run")
+    };
+
+    let serial = run(1).await;
+    let parallel = run(16).await;
+    assert_eq!(serial, parallel);
+    assert_eq!(serial.base_seed, 99);
+
+    let left = &serial.agents["left"];
+    assert_eq!(left.wins + left.draws + left.losses, 60);
+    assert!(left.score.mean_ci.low <= left.score.mean && left.score.mean <= left.score.mean_ci.high);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_statistics_stream_while_the_batch_runs() {
+    let runner = Arc::new(
+        BatchRunner::new(Arc::new(GameEngine::new(create_game)), config(GameType::MinorityGame, &[]), 50)
+            .agent("a", |seed| Arc::new(MockProvider::seeded(seed)) as Arc<dyn AIProvider>)
+            .agent("b", |seed| Arc::new(MockProvider::seeded(seed)) as Arc<dyn AIProvider>)
+            .agent("c", |seed| Arc::new(MockProvider::seeded(seed)) as Arc<dyn AIProvider>)
+            .report_every(10),
+    );
+    let mut progress = runner.progress();
+    let watcher = tokio::spawn(async move {
+        let mut seen = Vec::new();
+        while progress.changed().await.is_ok() {
+            let stats = progress.borrow_and_update().clone();
+            seen.push(stats.completed + stats.failed);
+            if stats.completed + stats.failed == stats.planned && stats.planned > 0 {
+                break;
+            }
+        }
+        seen
+    });
+
+    let stats = runner.run().await.expect("Batch should run");
+    let seen = watcher.await.unwrap();
+
+    assert_eq!(stats.completed + stats.failed, 50);
+    assert_eq!(seen.last(), Some(&50));
+    assert!(seen.windows(2).all(|w| w[0] <= w[1]));
+    assert!(seen.iter().all(|n| n % 10 == 0));
+
+    for agent in stats.agents.values() {
+        assert_eq!(agent.wins + agent.draws + agent.losses, stats.completed);
+    }
+    assert!(stats.emergence.values().all(|e| e.games <= stats.completed && (0.0..=1.0).contains(&e.rate)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_turn_based_games_are_dealt_in_and_played() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let stats = BatchRunner::new(engine.clone(), config(GameType::LiarsDice, &[("dice_per_player", 2), ("max_rounds", 30)]), 20)
+        .agent("a", |seed| Arc::new(MockProvider::seeded(seed)) as Arc<dyn AIProvider>)
+        .agent("b", |seed| Arc::new(MockProvider::seeded(seed)) as Arc<dyn AIProvider>)
+        .agent("c", |seed| Arc::new(MockProvider::seeded(seed)) as Arc<dyn AIProvider>)
+        .run()
+        .await
+        .expect("Batch should run");
+
+    assert_eq!((stats.completed, stats.failed), (20, 0));
+    assert!(stats.rounds.min > 0, "Every game should get past its first round");
+    assert!(engine.active_games().is_empty());
+}
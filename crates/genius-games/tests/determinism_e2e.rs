@@ -0,0 +1,60 @@
+//! End-to-end tests for seeded, reproducible games
+
+mod e2e_test_framework;
+
+use e2e_test_framework::*;
+use genius_core::GameType;
+use genius_games::GameRegistry;
+
+fn seeded_config(game_type: GameType, seed: u64) -> E2ETestConfig {
+    E2ETestConfig {
+        game_type,
+        num_players: 4,
+        max_rounds: 15,
+        seed: Some(seed),
+        ..Default::default()
+    }
+}
+
+/// Serialized round results with only the wall-clock timestamps removed
+fn round_fingerprints(result: &GameTestResult) -> Vec<serde_json::Value> {
+    result.round_results.iter()
+        .map(|r| {
+            let mut value = serde_json::to_value(r).expect("round results serialize");
+            value.as_object_mut().unwrap().remove("timestamp");
+            for action in value["actions"].as_object_mut().unwrap().values_mut() {
+                action.as_object_mut().unwrap().remove("timestamp");
+            }
+            value
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_same_seed_reproduces_every_game() {
+    let games = GameRegistry::new().all_games();
+    assert!(games.len() >= 25, "Every built-in game should be registered");
+
+    for game_type in games {
+        let first = E2ETestRunner::new(seeded_config(game_type.clone(), 42))
+            .run_game().await
+            .unwrap_or_else(|e| panic!("Seeded {:?} should complete: {}", game_type, e));
+        let second = E2ETestRunner::new(seeded_config(game_type.clone(), 42))
+            .run_game().await
+            .unwrap_or_else(|e| panic!("Seeded {:?} should complete: {}", game_type, e));
+
+        assert_eq!(first.total_rounds, second.total_rounds, "{:?} round count differs", game_type);
+        assert_eq!(
+            round_fingerprints(&first),
+            round_fingerprints(&second),
+            "{:?} rounds differ for the same seed",
+            game_type
+        );
+        assert_eq!(
+            first.final_result.final_scores,
+            second.final_result.final_scores,
+            "{:?} final scores differ for the same seed",
+            game_type
+        );
+    }
+}
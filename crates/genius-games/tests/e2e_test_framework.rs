@@ -1,10 +1,12 @@
 //! End-to-end test framework for games
+#![allow(dead_code)]
 
 use genius_core::{
-    Game, GameConfig, GameType, Player, PlayerType, PlayerAction, 
-    GameState, RoundResult, GameResult,
+    GameConfig, GameType, Player, PlayerType, 
+    GameState, RoundResult, GameResult, GameEvent, GameOutcome, Observer, EventKind, derive_seed,
 };
 use genius_games::create_game;
+use genius_engine::EmergenceDetector;
 use genius_ai::providers::mock::MockProvider;
 use genius_ai::provider::AIProvider;
 use std::collections::HashMap;
@@ -12,6 +14,7 @@ use std::time::Duration;
 use tokio::time::timeout;
 
 /// Test configuration for e2e tests
+#[derive(Clone)]
 pub struct E2ETestConfig {
     pub game_type: GameType,
     pub num_players: usize,
@@ -42,9 +45,15 @@ pub struct E2ETestRunner {
 
 impl E2ETestRunner {
     pub fn new(config: E2ETestConfig) -> Self {
+        // A seeded run also seeds the mock AI, so the whole match is reproducible
+        let ai_provider: Box<dyn AIProvider> = match config.seed {
+            Some(seed) => Box::new(MockProvider::seeded(derive_seed(seed, 1))),
+            None => Box::new(MockProvider::new()),
+        };
+        
         Self {
             config,
-            ai_provider: Box::new(MockProvider::new()),
+            ai_provider,
         }
     }
     
@@ -65,19 +74,22 @@ impl E2ETestRunner {
             game_type: self.config.game_type.clone(),
             rounds: self.config.max_rounds,
             time_limit_ms: self.config.timeout_per_round.as_millis() as u64,
-            special_rules: HashMap::new(),
+            params: HashMap::new(),
             initial_players: players.clone(),
+            teams: vec![],
+            seed: self.config.seed,
         };
         
         let mut state = game.initialize(game_config).await?;
         
-        // Add players to state
+        // Deal players in, as the engine does when a game starts
         for player in &players {
-            state.add_player(player.id.to_string());
+            game.apply_join(&mut state, &player.id.to_string()).await;
         }
         
         let mut round_results = Vec::new();
         let mut emergence_events = Vec::new();
+        let mut detector = EmergenceDetector::new();
         
         // Run game rounds
         while !game.is_game_over(&state).await && state.round < self.config.max_rounds {
@@ -88,18 +100,20 @@ impl E2ETestRunner {
             
             for player in &players {
                 let valid_actions = game.get_valid_actions(&state, &player.id.to_string()).await;
+                let observation = game.get_observation(&state, &Observer::Player(player.id.to_string())).await;
                 
-                // Use AI to decide action
+                // Use AI to decide action from the player's own view
                 let decision = timeout(
                     self.config.timeout_per_round,
-                    self.ai_provider.make_decision(&state, &player.id.to_string(), valid_actions)
+                    self.ai_provider.make_decision(&observation, &player.id.to_string(), valid_actions)
                 ).await??;
                 
                 actions.insert(player.id.to_string(), decision.action);
             }
             
-            // Process round
-            let round_result = game.process_round(&state, actions).await?;
+            // Process round, looking for emergence the way the engine does
+            let mut round_result = game.process_round(&state, actions).await?;
+            round_result.emergence = detector.analyze_round(&round_result);
             
             // Update state
             state.apply_score_deltas(&round_result.scores_delta);
@@ -107,11 +121,7 @@ impl E2ETestRunner {
             
             // Track emergence
             if self.config.enable_emergence_tracking {
-                for event in &round_result.events {
-                    if event.event_type == "emergence" {
-                        emergence_events.push(event.clone());
-                    }
-                }
+                emergence_events.extend(round_result.emergence_events().iter().map(GameEvent::emergence));
             }
             
             round_results.push(round_result);
@@ -182,10 +192,7 @@ impl GameTestResult {
     /// Check if specific emergence type occurred
     pub fn has_emergence_type(&self, emergence_type: &str) -> bool {
         self.emergence_events.iter().any(|e| {
-            e.data.get("emergence_type")
-                .and_then(|v| v.as_str())
-                .map(|s| s == emergence_type)
-                .unwrap_or(false)
+            matches!(&e.kind, EventKind::Emergence { emergence_type: t, .. } if format!("{:?}", t) == emergence_type)
         })
     }
 }
@@ -197,6 +204,7 @@ pub struct GameTestStats {
     pub successful_games: usize,
     pub avg_rounds: f32,
     pub avg_emergence_frequency: f32,
+    pub avg_coordination: f32,
     pub winner_distribution: HashMap<String, usize>,
     pub emergence_type_counts: HashMap<String, usize>,
 }
@@ -214,16 +222,24 @@ impl GameTestStats {
             .map(|r| r.emergence_frequency())
             .sum::<f32>() / total_games.max(1) as f32;
             
+        let avg_coordination = results.iter()
+            .map(|r| r.final_result.analytics.collective_coordination_score)
+            .sum::<f32>() / total_games.max(1) as f32;
+            
         let mut winner_distribution = HashMap::new();
         for result in &results {
+            // A draw is nobody's win, whoever the display winner happens to be
+            if matches!(result.final_result.outcome, GameOutcome::Draw { .. }) {
+                continue;
+            }
             *winner_distribution.entry(result.final_result.winner.clone()).or_insert(0) += 1;
         }
         
         let mut emergence_type_counts = HashMap::new();
         for result in &results {
             for event in &result.emergence_events {
-                if let Some(etype) = event.data.get("emergence_type").and_then(|v| v.as_str()) {
-                    *emergence_type_counts.entry(etype.to_string()).or_insert(0) += 1;
+                if let EventKind::Emergence { emergence_type, .. } = &event.kind {
+                    *emergence_type_counts.entry(format!("{:?}", emergence_type)).or_insert(0) += 1;
                 }
             }
         }
@@ -233,6 +249,7 @@ impl GameTestStats {
             successful_games,
             avg_rounds,
             avg_emergence_frequency,
+            avg_coordination,
             winner_distribution,
             emergence_type_counts,
         }
@@ -247,6 +264,7 @@ impl GameTestStats {
             self.successful_games as f32 / self.total_games as f32 * 100.0);
         println!("Average rounds: {:.1}", self.avg_rounds);
         println!("Average emergence frequency: {:.2}", self.avg_emergence_frequency);
+        println!("Average coordination: {:.2}", self.avg_coordination);
         
         println!("\nWinner distribution:");
         for (winner, count) in &self.winner_distribution {
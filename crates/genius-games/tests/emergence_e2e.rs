@@ -0,0 +1,141 @@
+//! End-to-end tests for emergence detection during play
+
+use genius_core::{EmergenceType, GameConfig, GameType, PlayerAction, RoundResult};
+use genius_engine::GameEngine;
+use genius_games::{create_game, replay::verify_replay};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+async fn dilemma(engine: &GameEngine) -> Uuid {
+    engine.create_game(GameConfig {
+        game_type: GameType::PrisonersDilemma,
+        rounds: 10,
+        time_limit_ms: 1000,
+        params: [("max_rounds".to_string(), serde_json::json!(10))].into_iter().collect::<HashMap<_, _>>(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(5),
+    }).await.unwrap().game_id
+}
+
+/// Both players make the same choice
+async fn play(engine: &GameEngine, game_id: Uuid, choice: &str) -> RoundResult {
+    let actions = ["ada", "ben"].into_iter()
+        .map(|p| (p.to_string(), PlayerAction::new(p.to_string(), choice.to_string(), serde_json::Value::Null)))
+        .collect();
+    engine.process_turn(game_id, actions).await.unwrap()
+}
+
+fn detected(round: &RoundResult, event_type: EmergenceType) -> bool {
+    round.emergence.iter().any(|e| e.event_type == event_type)
+}
+
+#[tokio::test]
+async fn test_detected_emergence_is_attached_to_rounds_and_results() {
+    let engine = GameEngine::new(create_game);
+    let game_id = dilemma(&engine).await;
+
+    let round = play(&engine, game_id, "defect").await;
+    assert!(detected(&round, EmergenceType::CollectiveStrategy));
+    assert!(detected(&round, EmergenceType::SpontaneousCoordination));
+    assert!(round.emergence.iter().all(|e| e.round == round.round && e.involved_players.len() == 2));
+    assert!(round.emergence_events().len() >= round.emergence.len());
+
+    for _ in 1..10 {
+        play(&engine, game_id, "defect").await;
+    }
+    let history = engine.get_game_state(game_id).await.unwrap().history;
+    let result = engine.finalize_game(game_id).await.unwrap();
+    let listed: usize = history.iter().map(|round| round.emergence_events().len()).sum();
+    assert_eq!(result.emergence_events.len(), listed, "Each round's emergence is listed once");
+    let mut collective: Vec<_> = result.emergence_events.iter()
+        .filter(|e| e.event_type == EmergenceType::CollectiveStrategy)
+        .map(|e| e.round)
+        .collect();
+    collective.dedup();
+    assert_eq!(collective, (1..=10).collect::<Vec<_>>(), "Found in every round");
+    assert!(result.emergence_events.windows(2).all(|w| w[0].round <= w[1].round));
+    assert!(result.analytics.emergence_frequency >= 2.0);
+    assert!(!result.emergence_events.iter().any(|e| e.event_type == EmergenceType::PhaseTransition), "Nothing changed");
+}
+
+#[tokio::test]
+async fn test_a_change_of_strategy_is_a_phase_transition() {
+    let engine = GameEngine::new(create_game);
+    let game_id = dilemma(&engine).await;
+
+    let mut rounds = Vec::new();
+    for choice in ["cooperate"; 5].into_iter().chain(["defect"; 5]) {
+        rounds.push(play(&engine, game_id, choice).await);
+    }
+
+    let transitions: Vec<_> = rounds.iter()
+        .filter(|r| detected(r, EmergenceType::PhaseTransition))
+        .map(|r| r.round)
+        .collect();
+    assert_eq!(transitions, vec![7], "Fires once the new behaviour has taken hold, and only once");
+
+    let transition = rounds[6].emergence.iter().find(|e| e.event_type == EmergenceType::PhaseTransition).unwrap();
+    assert!(transition.description.contains("'cooperate' since round 6"), "{}", transition.description);
+    assert_eq!(transition.involved_players, vec!["ada", "ben"]);
+    assert!(transition.emergence_score > 0.5 && transition.emergence_score <= 1.0);
+}
+
+#[tokio::test]
+async fn test_rewound_and_replayed_games_detect_the_same_emergence() {
+    let engine = GameEngine::new(create_game);
+    let game_id = dilemma(&engine).await;
+    for choice in ["cooperate"; 5].into_iter().chain(["defect"; 2]) {
+        play(&engine, game_id, choice).await;
+    }
+
+    // The detector is rebuilt along with the game, so the transition is found again
+    engine.rewind_game(game_id, 6).await.unwrap();
+    let again = play(&engine, game_id, "defect").await;
+    assert!(detected(&again, EmergenceType::PhaseTransition));
+
+    let journal = engine.get_journal(game_id).await.unwrap();
+    let replayed = GameEngine::new(create_game).replay_journal(&journal).await.unwrap();
+    let state = engine.get_game_state(game_id).await.unwrap();
+    let kinds = |history: &[RoundResult]| -> Vec<Vec<EmergenceType>> {
+        history.iter().map(|r| r.emergence.iter().map(|e| e.event_type.clone()).collect()).collect()
+    };
+    assert_eq!(kinds(&replayed.history), kinds(&state.history));
+
+    // Detection sits alongside the game's own results, so replays still verify
+    let verification = verify_replay(&engine.get_replay(game_id).await.unwrap()).await.unwrap();
+    assert!(verification.is_match(), "Unexpected divergence: {:?}", verification.divergence);
+}
+
+#[tokio::test]
+async fn test_emergence_a_game_reports_is_listed_once_per_round() {
+    let engine = GameEngine::new(create_game);
+    // The game only looks for steady cooperation among seated players, after ten rounds
+    let game_id = engine.open_lobby(GameConfig {
+        game_type: GameType::PrisonersDilemma,
+        rounds: 15,
+        time_limit_ms: 1000,
+        params: [("max_rounds".to_string(), serde_json::json!(15))].into_iter().collect::<HashMap<_, _>>(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(5),
+    }).await.unwrap().game_id;
+    for player in ["ada", "ben"] {
+        engine.join_game(game_id, player).await.unwrap();
+    }
+    engine.start_game(game_id).await.unwrap();
+
+    let mut rounds = Vec::new();
+    for _ in 0..15 {
+        rounds.push(play(&engine, game_id, "cooperate").await);
+    }
+    let reported: Vec<u32> = rounds.iter().filter(|r| r.outcome.emergence_detected).map(|r| r.round).collect();
+    assert!(!reported.is_empty(), "Steady cooperation should be reported by the game");
+
+    let result = engine.finalize_game(game_id).await.unwrap();
+    let custom: Vec<u32> = result.emergence_events.iter()
+        .filter(|e| matches!(e.event_type, EmergenceType::Custom(_)))
+        .map(|e| e.round)
+        .collect();
+    assert_eq!(custom, reported, "Listed in the rounds that reported it, and only once");
+}
@@ -0,0 +1,105 @@
+//! End-to-end tests for typed game events
+
+use genius_core::{
+    EmergenceType, EventKind, GameConfig, GameEvent, GameState, GameType, PlayerAction, RoundOutcome, RoundResult,
+};
+use genius_engine::{AnalyticsEngine, GameEngine};
+use genius_games::create_game;
+use std::collections::HashMap;
+
+#[test]
+fn test_event_payloads_name_the_players_involved() {
+    let event = GameEvent::new(
+        EventKind::Eliminated { player_id: "p2".to_string(), by: Some("p0".to_string()) },
+        "p0 knocked out p2",
+    );
+    assert_eq!(event.event_type(), "eliminated");
+    assert_eq!(event.affected_players, vec!["p2", "p0"]);
+
+    let json = serde_json::to_value(&event).unwrap();
+    assert_eq!(json["kind"]["type"], "eliminated");
+    assert_eq!(json["kind"]["by"], "p0");
+    let back: GameEvent = serde_json::from_value(json).unwrap();
+    assert_eq!(back.kind, event.kind);
+
+    let custom = GameEvent::new(EventKind::custom("meteor", serde_json::json!({"crater": 3})), "A meteor fell");
+    assert_eq!(custom.event_type(), "meteor");
+    assert!(custom.affected_players.is_empty());
+}
+
+#[tokio::test]
+async fn test_liars_dice_reports_called_bluffs() {
+    let engine = GameEngine::new(create_game);
+    let game_id = engine.open_lobby(GameConfig {
+        game_type: GameType::LiarsDice,
+        rounds: 10,
+        time_limit_ms: 1000,
+        params: HashMap::new(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(11),
+    }).await.unwrap().game_id;
+    for player in ["alice", "bob", "carol"] {
+        engine.join_game(game_id, player).await.unwrap();
+    }
+    engine.start_game(game_id).await.unwrap();
+
+    // The first seat opens with the lowest bid
+    let legal = engine.get_valid_actions(game_id, "alice").await.unwrap();
+    let bid = PlayerAction::new("alice".to_string(), legal[0].action_type.clone(), legal[0].data.clone());
+    engine.process_turn(game_id, [("alice".to_string(), bid)].into_iter().collect()).await.unwrap();
+
+    // The next player to act calls the standing bid
+    let caller = engine.get_current_turn(game_id).await.unwrap().players[0].clone();
+    let action = PlayerAction::new(caller.clone(), "challenge".to_string(), serde_json::json!({}));
+    let result = engine.process_turn(game_id, [(caller.clone(), action)].into_iter().collect()).await.unwrap();
+
+    let called = result.events.iter()
+        .find(|e| matches!(e.kind, EventKind::BluffCalled { .. }))
+        .expect("A challenge should report a called bluff");
+    match &called.kind {
+        EventKind::BluffCalled { caller: by, bidder, .. } => {
+            assert_eq!(by, &caller);
+            assert_eq!(bidder, "alice");
+        }
+        other => panic!("Unexpected event {:?}", other),
+    }
+
+    let state = engine.get_game_state(game_id).await.unwrap();
+    assert!(state.history.last().unwrap().events.iter().any(|e| e.event_type() == "bluff_called"));
+}
+
+#[tokio::test]
+async fn test_analytics_tally_emergence_events_by_kind() {
+    let analytics = AnalyticsEngine::new();
+    let game_id = uuid::Uuid::new_v4();
+    let state = GameState::new(GameType::MinorityGame);
+
+    let emergence = |emergence_type: EmergenceType| GameEvent::new(
+        EventKind::Emergence { emergence_type, score: 0.8 },
+        "Players coordinated",
+    );
+    let round = RoundResult {
+        round: 1,
+        actions: HashMap::new(),
+        outcome: RoundOutcome {
+            winners: vec![],
+            losers: vec![],
+            special_events: vec![],
+            emergence_detected: true,
+        },
+        scores_delta: HashMap::new(),
+        events: vec![
+            emergence(EmergenceType::SpontaneousCoordination),
+            emergence(EmergenceType::SpontaneousCoordination),
+            GameEvent::new(EventKind::ZoneShrink { size: 4.0 }, "The zone closed in"),
+        ],
+        emergence: Vec::new(),
+        timestamp: chrono::Utc::now(),
+    };
+    analytics.process_round(game_id, &state, &round).await;
+
+    let data = analytics.calculate_final_analytics(game_id).await.unwrap();
+    assert_eq!(data.emergence_analysis.emergence_types.len(), 1);
+    assert_eq!(data.emergence_analysis.emergence_types["SpontaneousCoordination"], 2);
+}
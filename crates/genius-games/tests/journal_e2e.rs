@@ -0,0 +1,107 @@
+//! End-to-end tests for the per-game event journal
+
+use genius_core::{GameConfig, GameError, GamePhase, GameType, PlayerAction};
+use genius_engine::{GameEngine, JournalEntry, JournalEvent, MemoryStore};
+use genius_games::create_game;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn config(max_rounds: i64) -> GameConfig {
+    GameConfig {
+        game_type: GameType::PrisonersDilemma,
+        rounds: 10,
+        time_limit_ms: 1000,
+        params: [("max_rounds".to_string(), serde_json::json!(max_rounds))].into_iter().collect::<HashMap<_, _>>(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(8),
+    }
+}
+
+fn both(alice: &str, bob: &str) -> HashMap<String, PlayerAction> {
+    [("alice", alice), ("bob", bob)]
+        .into_iter()
+        .map(|(player, choice)| (player.to_string(), PlayerAction::new(player.to_string(), choice.to_string(), serde_json::Value::Null)))
+        .collect()
+}
+
+fn kinds(entries: &[JournalEntry]) -> Vec<String> {
+    entries.iter()
+        .map(|e| serde_json::to_value(&e.event).unwrap()["type"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_every_transition_is_journaled_in_order() {
+    let engine = GameEngine::new(create_game).with_store(Arc::new(MemoryStore::new()));
+    let game_id = engine.open_lobby(config(2)).await.unwrap().game_id;
+    engine.join_game(game_id, "alice").await.unwrap();
+    engine.join_game(game_id, "bob").await.unwrap();
+    engine.start_game(game_id).await.unwrap();
+
+    engine.process_turn(game_id, both("cooperate", "defect")).await.unwrap();
+    assert!(matches!(engine.process_turn(game_id, both("dance", "defect")).await, Err(GameError::ActionsRejected { .. })));
+    engine.pause_game(game_id).await.unwrap();
+    engine.resume_game(game_id).await.unwrap();
+    engine.process_turn(game_id, both("defect", "defect")).await.unwrap();
+    let result = engine.finalize_game(game_id).await.unwrap();
+
+    // Finalized games keep their journal in the store
+    let journal = engine.get_journal(game_id).await.unwrap();
+    assert_eq!(kinds(&journal), vec![
+        "created", "player_joined", "player_joined", "phase_changed",
+        "round_processed", "actions_rejected", "phase_changed", "phase_changed",
+        "round_processed", "phase_changed", "finalized",
+    ]);
+    assert!(journal.iter().enumerate().all(|(i, e)| e.seq == i as u64 && e.game_id == game_id));
+    assert!(matches!(journal[9].event, JournalEvent::PhaseChanged { from: GamePhase::Running, to: GamePhase::Finished }));
+    match &journal[10].event {
+        JournalEvent::Finalized { result: journaled } => assert_eq!(journaled.final_scores, result.final_scores),
+        other => panic!("Unexpected entry {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_journal_can_be_tailed_live() {
+    let engine = GameEngine::new(create_game);
+    let game_id = engine.create_game(config(3)).await.unwrap().game_id;
+
+    let (backlog, mut live) = engine.tail_journal(game_id, 1).await.unwrap();
+    assert_eq!(kinds(&backlog), vec!["phase_changed"], "The backlog starts at the requested entry");
+
+    engine.process_turn(game_id, both("cooperate", "cooperate")).await.unwrap();
+    let entry = live.recv().await.unwrap();
+    assert_eq!(entry.seq, 2);
+    match entry.event {
+        JournalEvent::RoundProcessed { actions, result } => {
+            assert_eq!(actions.len(), 2);
+            assert_eq!(result.round, 1);
+        }
+        other => panic!("Unexpected entry {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_replaying_a_journal_rebuilds_the_game() {
+    let engine = GameEngine::new(create_game);
+    let game_id = engine.create_game(config(5)).await.unwrap().game_id;
+    engine.process_turn(game_id, both("cooperate", "defect")).await.unwrap();
+    engine.process_turn(game_id, both("defect", "defect")).await.unwrap();
+    engine.process_turn(game_id, both("cooperate", "cooperate")).await.unwrap();
+    engine.rewind_game(game_id, 1).await.unwrap();
+    engine.process_turn(game_id, both("defect", "cooperate")).await.unwrap();
+    engine.leave_game(game_id, "bob").await.unwrap();
+
+    let journal = engine.get_journal(game_id).await.unwrap();
+    let rebuilt = engine.replay_journal(&journal).await.unwrap();
+    let original = engine.get_game_state(game_id).await.unwrap();
+
+    assert_ne!(rebuilt.game_id, game_id);
+    assert_eq!(rebuilt.round, original.round);
+    assert_eq!(rebuilt.scores, original.scores);
+    assert_eq!(rebuilt.player_status, original.player_status);
+    assert_eq!(engine.get_phase(rebuilt.game_id).await.unwrap(), engine.get_phase(game_id).await.unwrap());
+    assert_eq!(kinds(&engine.get_journal(rebuilt.game_id).await.unwrap()), kinds(&journal));
+
+    assert!(matches!(engine.replay_journal(&journal[1..]).await, Err(GameError::InvalidState { .. })));
+}
@@ -0,0 +1,196 @@
+//! End-to-end tests for the game lifecycle: lobby, pause and resume, abort and forfeit
+
+use genius_core::{GameConfig, GameError, GameOutcome, GamePhase, GameType, PlayerAction, PlayerStatus};
+use genius_engine::GameEngine;
+use genius_games::{create_game, replay::verify_replay};
+use std::collections::HashMap;
+
+fn config(game_type: GameType) -> GameConfig {
+    GameConfig {
+        game_type,
+        rounds: 20,
+        time_limit_ms: 1000,
+        params: HashMap::new(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(5),
+    }
+}
+
+fn act(player: &str, action_type: &str, data: serde_json::Value) -> (String, PlayerAction) {
+    (player.to_string(), PlayerAction::new(player.to_string(), action_type.to_string(), data))
+}
+
+/// Open a lobby, seat the players and start play
+async fn seated(engine: &GameEngine, game_type: GameType, players: &[&str]) -> uuid::Uuid {
+    let game_id = engine.open_lobby(config(game_type)).await.unwrap().game_id;
+    for player in players {
+        engine.join_game(game_id, player).await.unwrap();
+    }
+    engine.start_game(game_id).await.unwrap();
+    game_id
+}
+
+fn cooperate(players: &[&str]) -> HashMap<String, PlayerAction> {
+    players.iter().map(|p| act(p, "cooperate", serde_json::Value::Null)).collect()
+}
+
+#[tokio::test]
+async fn test_lobby_holds_play_until_started() {
+    let engine = GameEngine::new(create_game);
+    let game_id = engine.open_lobby(config(GameType::PrisonersDilemma)).await.unwrap().game_id;
+    assert_eq!(engine.get_phase(game_id).await.unwrap(), GamePhase::Lobby);
+
+    engine.join_game(game_id, "alice").await.unwrap();
+    engine.join_game(game_id, "bob").await.unwrap();
+    engine.join_game(game_id, "carol").await.unwrap();
+    engine.leave_game(game_id, "carol").await.unwrap();
+    let state = engine.get_game_state(game_id).await.unwrap();
+    assert_eq!(state.active_players(), vec!["alice", "bob"], "Leaving the lobby just gives up the seat");
+
+    assert!(matches!(engine.process_turn(game_id, cooperate(&["alice", "bob"])).await, Err(GameError::GameNotStarted)));
+    assert!(matches!(engine.pause_game(game_id).await, Err(GameError::GameNotStarted)));
+    assert!(engine.get_valid_actions(game_id, "alice").await.unwrap().is_empty());
+
+    engine.start_game(game_id).await.unwrap();
+    assert!(matches!(engine.start_game(game_id).await, Err(GameError::GameAlreadyStarted)));
+    assert!(matches!(engine.join_game(game_id, "dave").await, Err(GameError::GameAlreadyStarted)));
+    engine.process_turn(game_id, cooperate(&["alice", "bob"])).await.expect("A started game should play");
+}
+
+#[tokio::test]
+async fn test_paused_games_refuse_rounds_until_resumed() {
+    let engine = GameEngine::new(create_game);
+    let game_id = engine.create_game(config(GameType::PrisonersDilemma)).await.unwrap().game_id;
+    assert_eq!(engine.get_phase(game_id).await.unwrap(), GamePhase::Running);
+
+    engine.pause_game(game_id).await.unwrap();
+    assert!(matches!(engine.process_turn(game_id, cooperate(&["p0", "p1"])).await, Err(GameError::InvalidState { .. })));
+    assert!(matches!(engine.pause_game(game_id).await, Err(GameError::InvalidState { .. })));
+
+    // Pausing survives a snapshot
+    let snapshot = engine.snapshot_game(game_id).await.unwrap();
+    let restored = GameEngine::new(create_game);
+    restored.restore_game(snapshot).await.unwrap();
+    assert_eq!(restored.get_phase(game_id).await.unwrap(), GamePhase::Paused);
+
+    engine.resume_game(game_id).await.unwrap();
+    assert!(matches!(engine.resume_game(game_id).await, Err(GameError::InvalidState { .. })));
+    engine.process_turn(game_id, cooperate(&["p0", "p1"])).await.expect("A resumed game should play");
+
+    engine.abort_game(game_id).await.unwrap();
+    assert!(matches!(engine.process_turn(game_id, cooperate(&["p0", "p1"])).await, Err(GameError::GameAlreadyEnded)));
+    assert!(matches!(engine.resume_game(game_id).await, Err(GameError::GameAlreadyEnded)));
+    assert!(matches!(engine.abort_game(game_id).await, Err(GameError::GameAlreadyEnded)));
+    assert_eq!(engine.finalize_game(game_id).await.unwrap().total_rounds, 1, "Aborted games still report a result");
+}
+
+#[tokio::test]
+async fn test_forfeit_passes_the_turn_and_is_replayed() {
+    let engine = GameEngine::new(create_game);
+    let game_id = seated(&engine, GameType::LiarsDice, &["alice", "bob", "carol"]).await;
+
+    // The first seat opens with the lowest bid
+    let legal = engine.get_valid_actions(game_id, "alice").await.unwrap();
+    let bid = act("alice", &legal[0].action_type, legal[0].data.clone());
+    engine.process_turn(game_id, [bid].into_iter().collect()).await.unwrap();
+
+    let quitter = engine.get_current_turn(game_id).await.unwrap().players[0].clone();
+    engine.leave_game(game_id, &quitter).await.unwrap();
+    assert!(matches!(engine.leave_game(game_id, "nobody").await, Err(GameError::PlayerNotFound { .. })));
+
+    let state = engine.get_game_state(game_id).await.unwrap();
+    assert_eq!(state.status(&quitter), PlayerStatus::Eliminated { round: 1 });
+    let next = engine.get_current_turn(game_id).await.unwrap().players;
+    assert_eq!(next.len(), 1, "The game should hand the turn to someone still playing");
+    assert_ne!(next[0], quitter);
+    assert!(engine.get_valid_actions(game_id, &quitter).await.unwrap().is_empty());
+
+    // The forfeit is part of the record, so rewinds and replays keep the player out
+    let replay = engine.get_replay(game_id).await.unwrap();
+    assert_eq!(replay.forfeits_after(1).collect::<Vec<_>>(), vec![quitter.as_str()]);
+    let rewound = engine.rewind_game(game_id, 1).await.unwrap();
+    assert_eq!(rewound.status(&quitter), PlayerStatus::Eliminated { round: 1 });
+    assert_eq!(engine.get_current_turn(game_id).await.unwrap().players, next);
+
+    let challenge = act(&next[0], "challenge", serde_json::Value::Null);
+    engine.process_turn(game_id, [challenge].into_iter().collect()).await.expect("Play should carry on without the quitter");
+    assert!(verify_replay(&engine.get_replay(game_id).await.unwrap()).await.unwrap().is_match());
+}
+
+#[tokio::test]
+async fn test_forfeits_survive_a_restore() {
+    let engine = GameEngine::new(create_game);
+    let game_id = seated(&engine, GameType::LiarsDice, &["alice", "bob", "carol"]).await;
+    let legal = engine.get_valid_actions(game_id, "alice").await.unwrap();
+    let bid = act("alice", &legal[0].action_type, legal[0].data.clone());
+    engine.process_turn(game_id, [bid].into_iter().collect()).await.unwrap();
+    engine.leave_game(game_id, "bob").await.unwrap();
+
+    // Both a restored snapshot and a replayed journal still know who left
+    let restored = GameEngine::new(create_game);
+    restored.restore_game(engine.snapshot_game(game_id).await.unwrap()).await.unwrap();
+    let journal = restored.get_journal(game_id).await.unwrap();
+    let rewound = restored.rewind_game(game_id, 1).await.unwrap();
+    assert_eq!(rewound.status("bob"), PlayerStatus::Eliminated { round: 1 });
+    assert_eq!(restored.get_current_turn(game_id).await.unwrap().players, vec!["carol"]);
+    assert_eq!(restored.get_replay(game_id).await.unwrap().forfeits_after(1).collect::<Vec<_>>(), vec!["bob"]);
+
+    let replayed = GameEngine::new(create_game);
+    let state = replayed.replay_journal(&journal).await.unwrap();
+    let rewound = replayed.rewind_game(state.game_id, 1).await.unwrap();
+    assert_eq!(rewound.status("bob"), PlayerStatus::Eliminated { round: 1 });
+}
+
+#[tokio::test]
+async fn test_lobby_joins_are_dealt_in_and_survive_rewinds() {
+    let engine = GameEngine::new(create_game);
+    let game_id = seated(&engine, GameType::LiarsDice, &["alice", "bob", "carol"]).await;
+    assert_eq!(engine.get_current_turn(game_id).await.unwrap().players, vec!["alice"]);
+    let reversed = seated(&engine, GameType::LiarsDice, &["carol", "bob", "alice"]).await;
+    assert_eq!(engine.get_current_turn(reversed).await.unwrap().players, vec!["carol"], "Players are dealt in as they sat down");
+
+    let legal = engine.get_valid_actions(game_id, "alice").await.unwrap();
+    let bid = act("alice", &legal[0].action_type, legal[0].data.clone());
+    engine.process_turn(game_id, [bid].into_iter().collect()).await.expect("Seated players should be able to play");
+    let next = engine.get_current_turn(game_id).await.unwrap().players;
+    assert_eq!(next, vec!["bob"]);
+
+    // Rewinds, forks and replays deal the same table again
+    let rewound = engine.rewind_game(game_id, 0).await.unwrap();
+    assert_eq!(rewound.active_players(), vec!["alice", "bob", "carol"]);
+    assert_eq!(engine.get_current_turn(game_id).await.unwrap().players, vec!["alice"]);
+
+    let bid = act("alice", &legal[0].action_type, legal[0].data.clone());
+    engine.process_turn(game_id, [bid].into_iter().collect()).await.unwrap();
+    let fork = engine.fork_game(game_id, 1).await.unwrap().game_id;
+    assert_eq!(engine.get_current_turn(fork).await.unwrap().players, next);
+    engine.process_turn(fork, [act("bob", "challenge", serde_json::Value::Null)].into_iter().collect())
+        .await
+        .expect("The fork should carry on with the same table");
+
+    let journal = engine.get_journal(game_id).await.unwrap();
+    let replayed = GameEngine::new(create_game);
+    let state = replayed.replay_journal(&journal).await.unwrap();
+    assert_eq!(state.active_players(), vec!["alice", "bob", "carol"]);
+    assert_eq!(replayed.get_current_turn(state.game_id).await.unwrap().players, next);
+    assert!(verify_replay(&engine.get_replay(game_id).await.unwrap()).await.unwrap().is_match());
+}
+
+#[tokio::test]
+async fn test_forfeiting_a_two_player_game_concedes_it() {
+    let engine = GameEngine::new(create_game);
+    let game_id = engine.create_game(config(GameType::MiniGo)).await.unwrap().game_id;
+    let place = |player: &str, row: usize, col: usize| act(player, "place", serde_json::json!({ "row": row, "col": col }));
+    engine.process_turn(game_id, [place("p0", 2, 2), place("p1", 6, 6)].into_iter().collect()).await.unwrap();
+
+    let quitter = engine.get_current_turn(game_id).await.unwrap().players[0].clone();
+    let stayer = if quitter == "p0" { "p1" } else { "p0" };
+    engine.leave_game(game_id, &quitter).await.unwrap();
+    assert_eq!(engine.get_phase(game_id).await.unwrap(), GamePhase::Finished);
+    assert!(matches!(engine.leave_game(game_id, stayer).await, Err(GameError::GameAlreadyEnded)));
+
+    let result = engine.finalize_game(game_id).await.unwrap();
+    assert_eq!(result.outcome, GameOutcome::Winner { player_id: stayer.to_string() });
+    assert_eq!(result.placements[1].eliminated_round, Some(1));
+}
@@ -0,0 +1,146 @@
+//! End-to-end tests for matches played autonomously by AI providers
+
+use async_trait::async_trait;
+use genius_ai::{providers::MockProvider, AIDecision, AIProvider};
+use genius_core::{GameConfig, GameError, GamePhase, GameType, LegalAction, Observation, Result};
+use genius_engine::{Fallback, GameEngine, MatchRunner, TurnScheduler};
+use genius_games::create_game;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Takes longer than any test is willing to wait
+struct SlowProvider;
+
+#[async_trait]
+impl AIProvider for SlowProvider {
+    fn name(&self) -> &str {
+        "Slow Provider"
+    }
+
+    async fn make_decision(&self, _observation: &Observation, player_id: &str, valid_actions: Vec<LegalAction>) -> Result<AIDecision> {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        Ok(AIDecision {
+            action: valid_actions[0].to_action(player_id),
+            reasoning: "Eventually".to_string(),
+            confidence: 1.0,
+        })
+    }
+}
+
+/// Always fails, or always plays a move no game accepts
+struct BrokenProvider {
+    illegal: bool,
+}
+
+#[async_trait]
+impl AIProvider for BrokenProvider {
+    fn name(&self) -> &str {
+        "Broken Provider"
+    }
+
+    async fn make_decision(&self, _observation: &Observation, player_id: &str, _valid_actions: Vec<LegalAction>) -> Result<AIDecision> {
+        if !self.illegal {
+            return Err(GameError::AIProviderError("backend unavailable".to_string()));
+        }
+        Ok(AIDecision {
+            action: LegalAction::simple("dance").to_action(player_id),
+            reasoning: "Why not".to_string(),
+            confidence: 0.1,
+        })
+    }
+}
+
+fn config(game_type: GameType, params: &[(&str, i64)]) -> GameConfig {
+    GameConfig {
+        game_type,
+        rounds: 20,
+        time_limit_ms: 1000,
+        params: params.iter().map(|(k, v)| (k.to_string(), serde_json::json!(v))).collect::<HashMap<_, _>>(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(9),
+    }
+}
+
+#[tokio::test]
+async fn test_providers_play_a_game_to_the_end() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let game_id = engine.create_game(config(GameType::PrisonersDilemma, &[("max_rounds", 5)])).await.unwrap().game_id;
+
+    let runner = MatchRunner::new(engine.clone())
+        .seat("alice", Arc::new(MockProvider::seeded(1)))
+        .seat("bob", Arc::new(MockProvider::seeded(2)));
+    let report = runner.run(game_id).await.expect("Match should run");
+
+    assert_eq!(report.phase, GamePhase::Finished);
+    assert_eq!(report.rounds_played, 5);
+    assert!(report.fallbacks.is_empty());
+
+    let state = engine.get_game_state(game_id).await.unwrap();
+    assert!(state.history.iter().all(|r| r.actions.len() == 2), "Both seats should act every round");
+    assert!(matches!(runner.run(game_id).await, Err(GameError::GameAlreadyEnded)));
+}
+
+#[tokio::test]
+async fn test_turn_based_games_ask_only_the_player_to_move() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let params = [("dice_per_player", 1), ("max_rounds", 30)];
+    let game_id = engine.open_lobby(config(GameType::LiarsDice, &params)).await.unwrap().game_id;
+    for player in ["alice", "bob", "carol"] {
+        engine.join_game(game_id, player).await.unwrap();
+    }
+    engine.start_game(game_id).await.unwrap();
+
+    let runner = ["alice", "bob", "carol"].into_iter().enumerate()
+        .fold(MatchRunner::new(engine.clone()), |runner, (i, player)| runner.seat(player, Arc::new(MockProvider::seeded(i as u64))));
+    let report = runner.run(game_id).await.expect("Match should run");
+
+    assert_eq!(report.phase, GamePhase::Finished);
+    assert!(report.fallbacks.is_empty(), "Mock providers only pick legal moves: {:?}", report.fallbacks);
+    let state = engine.get_game_state(game_id).await.unwrap();
+    assert!(state.history.iter().all(|r| r.actions.len() == 1));
+}
+
+#[tokio::test]
+async fn test_timeouts_and_failures_fall_back() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let game_id = engine.create_game(config(GameType::PrisonersDilemma, &[("max_rounds", 2)])).await.unwrap().game_id;
+
+    let report = MatchRunner::new(engine.clone())
+        .seat("slow", Arc::new(SlowProvider))
+        .seat("broken", Arc::new(BrokenProvider { illegal: false }))
+        .scheduler(TurnScheduler::new(Duration::from_millis(20)))
+        .fallback(Fallback::Action(LegalAction::simple("defect")))
+        .run(game_id)
+        .await
+        .expect("Match should run");
+
+    assert_eq!(report.rounds_played, 2);
+    assert_eq!(report.fallbacks.len(), 4);
+    assert!(report.fallbacks.iter().any(|f| f.player_id == "slow" && f.reason.contains("timeout")));
+    assert!(report.fallbacks.iter().any(|f| f.player_id == "broken" && f.reason.contains("backend unavailable")));
+
+    let state = engine.get_game_state(game_id).await.unwrap();
+    assert!(state.history.iter().flat_map(|r| r.actions.values()).all(|a| a.action_type == "defect"));
+}
+
+#[tokio::test]
+async fn test_rejected_moves_are_replaced() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let game_id = engine.create_game(config(GameType::PrisonersDilemma, &[("max_rounds", 1)])).await.unwrap().game_id;
+
+    let report = MatchRunner::new(engine.clone())
+        .seat("cheat", Arc::new(BrokenProvider { illegal: true }))
+        .seat("fair", Arc::new(MockProvider::deterministic()))
+        .run(game_id)
+        .await
+        .expect("Match should run");
+
+    assert_eq!(report.fallbacks.len(), 1);
+    assert_eq!(report.fallbacks[0].player_id, "cheat");
+    assert!(report.fallbacks[0].reason.starts_with("move rejected"));
+
+    let state = engine.get_game_state(game_id).await.unwrap();
+    assert_eq!(state.history[0].actions["cheat"].action_type, "cooperate", "The first legal move stands in");
+}
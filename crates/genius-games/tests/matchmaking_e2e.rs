@@ -0,0 +1,183 @@
+//! End-to-end tests for matchmaking queues
+
+use genius_core::{GameConfig, GameError, GamePhase, GameType, Player, PlayerAction, PlayerId, PlayerType};
+use genius_engine::{GameEngine, Matchmaker, QueueRules, RatingService, RatingSystem, TicketStatus};
+use genius_games::create_game;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn config() -> GameConfig {
+    GameConfig {
+        game_type: GameType::PrisonersDilemma,
+        rounds: 10,
+        time_limit_ms: 1000,
+        params: [("max_rounds".to_string(), serde_json::json!(1))].into_iter().collect::<HashMap<_, _>>(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(21),
+    }
+}
+
+fn bot(id: &str) -> Player {
+    Player {
+        id: PlayerId::from_string(id.to_string()),
+        name: id.to_string(),
+        player_type: PlayerType::AI { provider: "mock".to_string(), model: "test".to_string() },
+        metadata: serde_json::Value::Null,
+    }
+}
+
+/// Rate `player` by having them beat, or lose to, a fresh opponent a few times
+async fn rate(engine: &GameEngine, ratings: &RatingService, player: &str, wins: bool) {
+    for i in 0..3 {
+        let opponent = format!("{}_sparring_{}", player, i);
+        let game_id = engine.create_game(config()).await.unwrap().game_id;
+        let (mine, theirs) = if wins { ("defect", "cooperate") } else { ("cooperate", "defect") };
+        let actions = [(player, mine), (opponent.as_str(), theirs)].into_iter()
+            .map(|(p, choice)| (p.to_string(), PlayerAction::new(p.to_string(), choice.to_string(), serde_json::Value::Null)))
+            .collect();
+        engine.process_turn(game_id, actions).await.unwrap();
+        let result = engine.finalize_game(game_id).await.unwrap();
+        ratings.record(&result, &[bot(player), bot(&opponent)]).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_a_full_table_starts_a_game_right_away() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let matchmaker = Matchmaker::new(engine.clone());
+    matchmaker.open_queue(config(), QueueRules::new(2, 2)).await.unwrap();
+    let mut found = matchmaker.subscribe();
+
+    let status = matchmaker.enqueue(&GameType::PrisonersDilemma, bot("ada")).await.unwrap();
+    assert!(matches!(status, TicketStatus::Waiting { queued: 1, .. }));
+    let status = matchmaker.enqueue(&GameType::PrisonersDilemma, bot("ben")).await.unwrap();
+
+    let matched = found.recv().await.unwrap();
+    assert_eq!(matched.players, vec!["ada", "ben"]);
+    assert_eq!(status, TicketStatus::Matched { game_id: matched.game_id, players: matched.players.clone() });
+    assert_eq!(matchmaker.status("ada").await, Some(status));
+
+    assert_eq!(engine.get_phase(matched.game_id).await.unwrap(), GamePhase::Running);
+    let state = engine.get_game_state(matched.game_id).await.unwrap();
+    assert!(state.player_status.contains_key("ada") && state.player_status.contains_key("ben"));
+}
+
+#[tokio::test]
+async fn test_players_are_matched_with_similar_ratings() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let ratings = Arc::new(RatingService::new());
+    for (player, wins) in [("strong_1", true), ("strong_2", true), ("weak_1", false), ("weak_2", false)] {
+        rate(&engine, &ratings, player, wins).await;
+    }
+
+    let matchmaker = Matchmaker::new(engine.clone()).ratings(ratings, RatingSystem::Elo);
+    let rules = QueueRules::new(2, 2).rating_window(20.0, 0.0).max_wait(Duration::from_secs(60));
+    matchmaker.open_queue(config(), rules).await.unwrap();
+
+    let dilemma = GameType::PrisonersDilemma;
+    matchmaker.enqueue(&dilemma, bot("strong_1")).await.unwrap();
+    let status = matchmaker.enqueue(&dilemma, bot("weak_1")).await.unwrap();
+    assert!(matches!(status, TicketStatus::Waiting { queued: 2, .. }), "Too far apart to be matched");
+
+    let status = matchmaker.enqueue(&dilemma, bot("strong_2")).await.unwrap();
+    assert!(matches!(&status, TicketStatus::Matched { players, .. } if players == &vec!["strong_1".to_string(), "strong_2".to_string()]));
+    assert!(matches!(matchmaker.status("weak_1").await, Some(TicketStatus::Waiting { queued: 1, .. })));
+
+    let status = matchmaker.enqueue(&dilemma, bot("weak_2")).await.unwrap();
+    assert!(matches!(&status, TicketStatus::Matched { players, .. } if players == &vec!["weak_1".to_string(), "weak_2".to_string()]));
+}
+
+#[tokio::test]
+async fn test_waiting_too_long_seats_a_smaller_table() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let matchmaker = Matchmaker::new(engine.clone());
+    matchmaker.open_queue(config(), QueueRules::new(2, 4).max_wait(Duration::from_millis(50))).await.unwrap();
+
+    for player in ["ada", "ben", "cy"] {
+        matchmaker.enqueue(&GameType::PrisonersDilemma, bot(player)).await.unwrap();
+    }
+    assert!(matchmaker.tick().await.unwrap().is_empty(), "Waits for a full table at first");
+
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    let found = matchmaker.tick().await.unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].players, vec!["ada", "ben", "cy"]);
+    assert_eq!(engine.get_phase(found[0].game_id).await.unwrap(), GamePhase::Running);
+}
+
+#[tokio::test]
+async fn test_tickets_expire_and_can_be_withdrawn() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let matchmaker = Matchmaker::new(engine.clone());
+    let dilemma = GameType::PrisonersDilemma;
+
+    assert!(matches!(matchmaker.enqueue(&dilemma, bot("ada")).await, Err(GameError::ConfigError { .. })), "No queue yet");
+    assert!(matches!(matchmaker.open_queue(config(), QueueRules::new(3, 2)).await, Err(GameError::ConfigError { .. })));
+    matchmaker.open_queue(config(), QueueRules::new(2, 2).expire_after(Duration::from_millis(50))).await.unwrap();
+
+    matchmaker.enqueue(&dilemma, bot("ada")).await.unwrap();
+    assert!(matches!(matchmaker.enqueue(&dilemma, bot("ada")).await, Err(GameError::InvalidState { .. })));
+
+    let status = matchmaker.enqueue(&dilemma, bot("ben")).await.unwrap();
+    assert!(matches!(status, TicketStatus::Matched { .. }));
+    matchmaker.enqueue(&dilemma, bot("cy")).await.unwrap();
+    matchmaker.leave("cy").await.unwrap();
+    assert_eq!(matchmaker.status("cy").await, None);
+    assert!(matches!(matchmaker.leave("cy").await, Err(GameError::PlayerNotFound { .. })));
+
+    matchmaker.enqueue(&dilemma, bot("dot")).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    matchmaker.tick().await.unwrap();
+    assert_eq!(matchmaker.status("dot").await, Some(TicketStatus::Expired { game_type: dilemma }));
+}
+
+#[tokio::test]
+async fn test_turn_based_matches_deal_in_their_players() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let matchmaker = Matchmaker::new(engine.clone());
+    let dice = GameConfig { game_type: GameType::LiarsDice, params: HashMap::new(), ..config() };
+    matchmaker.open_queue(dice, QueueRules::new(3, 3)).await.unwrap();
+
+    for player in ["ada", "ben", "cy"] {
+        matchmaker.enqueue(&GameType::LiarsDice, bot(player)).await.unwrap();
+    }
+    let Some(TicketStatus::Matched { game_id, players }) = matchmaker.status("cy").await else {
+        panic!("A full table should be seated");
+    };
+
+    assert_eq!(engine.get_replay(game_id).await.unwrap().players, players);
+    let turn = engine.get_current_turn(game_id).await.unwrap();
+    assert_eq!(turn.players.len(), 1, "One player bids at a time");
+    assert!(players.contains(&turn.players[0]));
+}
+
+#[tokio::test]
+async fn test_a_broken_queue_does_not_hold_up_the_others() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let matchmaker = Matchmaker::new(engine.clone());
+    let broken = GameType::Custom("unregistered".to_string());
+    let relaxed = QueueRules::new(2, 4).max_wait(Duration::from_millis(50));
+    matchmaker.open_queue(GameConfig { game_type: broken.clone(), ..config() }, relaxed.clone()).await.unwrap();
+    matchmaker.open_queue(config(), relaxed).await.unwrap();
+
+    for player in ["ada", "ben"] {
+        matchmaker.enqueue(&broken, bot(player)).await.unwrap();
+    }
+    for player in ["cy", "dot"] {
+        matchmaker.enqueue(&GameType::PrisonersDilemma, bot(player)).await.unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(80)).await;
+
+    let found = matchmaker.tick().await.unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].players, vec!["cy", "dot"]);
+    assert!(matches!(matchmaker.status("ada").await, Some(TicketStatus::Waiting { queued: 2, .. })), "Still waiting in place");
+
+    // A new arrival whose match cannot start is not left in the queue
+    assert!(matches!(matchmaker.enqueue(&broken, bot("eve")).await, Err(GameError::ConfigError { .. })));
+    assert_eq!(matchmaker.status("eve").await, None);
+    assert!(matches!(matchmaker.status("ada").await, Some(TicketStatus::Waiting { queued: 2, .. })));
+    assert_eq!(engine.active_games(), vec![found[0].game_id], "No half-created games are left behind");
+}
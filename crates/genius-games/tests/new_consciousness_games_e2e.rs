@@ -3,7 +3,7 @@
 mod e2e_test_framework;
 
 use e2e_test_framework::*;
-use genius_core::GameType;
+use genius_core::{EmergenceType, EventKind, GameType};
 
 #[tokio::test]
 async fn test_consciousness_cascade() {
@@ -23,7 +23,7 @@ async fn test_consciousness_cascade() {
     // Check for cascade events
     let cascade_events = result.round_results.iter()
         .flat_map(|r| &r.events)
-        .filter(|e| e.event_type == "consciousness_cascade" || 
+        .filter(|e| e.event_type() == "consciousness_cascade" || 
                     e.description.contains("cascade"))
         .count();
         
@@ -72,7 +72,7 @@ async fn test_quantum_dreamer() {
     // Check for reality bleeds
     let reality_bleeds = result.round_results.iter()
         .flat_map(|r| &r.events)
-        .filter(|e| e.event_type == "reality_bleed")
+        .filter(|e| e.event_type() == "reality_bleed")
         .count();
         
     let metrics = &result.final_result.analytics.custom_metrics;
@@ -81,6 +81,10 @@ async fn test_quantum_dreamer() {
             *bleeds >= 0.0,
             "Should track reality bleed events"
         );
+        assert_eq!(
+            *bleeds as usize, reality_bleeds,
+            "Every reality bleed should be reported as an event"
+        );
     }
 }
 
@@ -112,7 +116,7 @@ async fn test_consciousness_cascade_emergence() {
     // Check thought propagation
     let thought_events = result.round_results.iter()
         .flat_map(|r| &r.events)
-        .filter(|e| e.event_type == "thought_sent")
+        .filter(|e| e.event_type() == "thought_sent")
         .count();
         
     assert!(
@@ -138,13 +142,13 @@ async fn test_quantum_dreamer_collective_unconscious() {
     let collective_events = result.emergence_events.iter()
         .filter(|e| e.description.contains("synchronicity") || 
                    e.description.contains("collective") ||
-                   e.data.get("type").and_then(|v| v.as_str()) == Some("collective_dream"))
+                   matches!(&e.kind, EventKind::Emergence { emergence_type: EmergenceType::Custom(t), .. } if t == "collective_dream"))
         .count();
         
     // Even without explicit collective events, check lucidity development
     let lucid_events = result.round_results.iter()
         .flat_map(|r| &r.events)
-        .filter(|e| e.event_type == "lucid_mastery")
+        .filter(|e| e.event_type() == "lucid_mastery")
         .count();
         
     assert!(
@@ -181,7 +185,7 @@ async fn test_new_consciousness_games_integration() {
         
         let runner = E2ETestRunner::new(config);
         let result = runner.run_game().await
-            .expect(&format!("{:?} should complete", game_type));
+            .unwrap_or_else(|_| panic!("{:?} should complete", game_type));
         
         if result.emergence_frequency() > 0.0 {
             games_with_emergence += 1;
@@ -229,7 +233,7 @@ async fn test_consciousness_games_scaling() {
             
             let runner = E2ETestRunner::new(config);
             let result = runner.run_game().await
-                .expect(&format!("{:?} with {} players should complete", game_type, num_players));
+                .unwrap_or_else(|_| panic!("{:?} with {} players should complete", game_type, num_players));
             
             let coordination = result.final_result.analytics.collective_coordination_score;
             let emergence = result.emergence_frequency();
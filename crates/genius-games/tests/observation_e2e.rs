@@ -0,0 +1,41 @@
+//! End-to-end tests for per-player observations
+
+use genius_core::{GameConfig, GameType, Observer, PlayerAction};
+use genius_games::create_game;
+use std::collections::HashMap;
+
+#[tokio::test]
+async fn test_liars_dice_hides_other_players_dice() {
+    let mut game = create_game(GameType::LiarsDice).expect("Game should exist");
+    let config = GameConfig {
+        game_type: GameType::LiarsDice,
+        rounds: 10,
+        time_limit_ms: 1000,
+        params: HashMap::new(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(7),
+    };
+
+    let mut state = game.initialize(config).await.expect("Game should initialize");
+    let players = ["alice", "bob", "carol"];
+    for player in players {
+        state.add_player(player.to_string());
+    }
+
+    // Dice are rolled when the first round is played
+    let actions: HashMap<String, PlayerAction> = players.iter()
+        .map(|p| (p.to_string(), PlayerAction::new(p.to_string(), "pass".to_string(), serde_json::json!({}))))
+        .collect();
+    game.process_round(&state, actions).await.expect("Round should process");
+
+    let alice = game.get_observation(&state, &Observer::Player("alice".to_string())).await;
+    let spectator = game.get_observation(&state, &Observer::Spectator).await;
+
+    assert!(alice.view["your_dice"].is_array(), "A player should see their own dice");
+    assert_eq!(alice.view["dice_counts"]["bob"], 5, "Dice counts are public");
+    assert!(spectator.view["your_dice"].is_null(), "Spectators should not see any dice");
+
+    let serialized = serde_json::to_string(&alice).unwrap();
+    assert!(!serialized.contains("player_dice"), "Raw dice table must not leak");
+}
@@ -0,0 +1,144 @@
+//! End-to-end tests for collecting simultaneous decisions in parallel
+
+use async_trait::async_trait;
+use genius_ai::{providers::MockProvider, AIDecision, AIProvider};
+use genius_core::{GameConfig, GameType, LegalAction, Observation, Result, TimeControl};
+use genius_engine::streaming::StreamMessage;
+use genius_engine::{Collection, Fallback, GameEngine, GameEventStreamer, MatchRunner, TurnScheduler};
+use genius_games::create_game;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Thinks for a fixed time, then plays its first legal move
+struct SlowProvider {
+    delay: Duration,
+}
+
+#[async_trait]
+impl AIProvider for SlowProvider {
+    fn name(&self) -> &str {
+        "Slow Provider"
+    }
+
+    async fn make_decision(&self, _observation: &Observation, player_id: &str, valid_actions: Vec<LegalAction>) -> Result<AIDecision> {
+        tokio::time::sleep(self.delay).await;
+        Ok(AIDecision {
+            action: valid_actions[0].to_action(player_id),
+            reasoning: "Thought it over".to_string(),
+            confidence: 1.0,
+        })
+    }
+}
+
+fn config(game_type: GameType, params: &[(&str, i64)]) -> GameConfig {
+    GameConfig {
+        game_type,
+        rounds: 100,
+        time_limit_ms: 1000,
+        params: params.iter().map(|(k, v)| (k.to_string(), serde_json::json!(v))).collect::<HashMap<_, _>>(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(21),
+    }
+}
+
+#[tokio::test]
+async fn test_many_agents_decide_at_once() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let game_id = engine.create_game(config(GameType::MinorityGame, &[])).await.unwrap().game_id;
+    let streamer = Arc::new(GameEventStreamer::new());
+
+    // Asked one by one, each round would take half a second
+    let runner = (0..25).fold(MatchRunner::new(engine.clone()), |runner, i| {
+        runner.seat(&format!("agent_{}", i), Arc::new(SlowProvider { delay: Duration::from_millis(20) }))
+    });
+    let started = Instant::now();
+    let report = runner
+        .collection(Collection::Parallel { round_deadline: Duration::from_secs(2) })
+        .streamer(streamer.clone())
+        .run(game_id)
+        .await
+        .expect("Match should run");
+
+    assert!(started.elapsed() < Duration::from_secs(15), "Rounds took {:?}", started.elapsed());
+    assert!(report.fallbacks.is_empty(), "Everyone should beat the deadline: {:?}", report.fallbacks);
+
+    let state = engine.get_game_state(game_id).await.unwrap();
+    let actions: Vec<_> = state.history.iter().flat_map(|r| r.actions.values()).collect();
+    assert_eq!(actions.len(), 25 * report.rounds_played as usize);
+    assert!(actions.iter().all(|a| a.latency_ms.is_some_and(|ms| ms >= 20)));
+
+    let metrics = streamer.metrics().await;
+    assert_eq!(metrics.response_samples, actions.len() as u64);
+    assert!(metrics.average_response_time_ms >= 20.0);
+}
+
+#[tokio::test]
+async fn test_stragglers_get_the_fallback_when_the_round_closes() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let game_id = engine.create_game(config(GameType::PrisonersDilemma, &[("max_rounds", 2)])).await.unwrap().game_id;
+    let streamer = Arc::new(GameEventStreamer::new());
+    let mut stream = streamer.subscribe();
+
+    let started = Instant::now();
+    let report = MatchRunner::new(engine.clone())
+        .seat("quick", Arc::new(MockProvider::deterministic()))
+        .seat("stuck", Arc::new(SlowProvider { delay: Duration::from_secs(5) }))
+        .scheduler(TurnScheduler::new(Duration::from_secs(10)))
+        .collection(Collection::Parallel { round_deadline: Duration::from_millis(50) })
+        .fallback(Fallback::Action(LegalAction::simple("defect")))
+        .streamer(streamer.clone())
+        .run(game_id)
+        .await
+        .expect("Match should run");
+
+    assert!(started.elapsed() < Duration::from_secs(2), "Rounds should close at the deadline");
+    assert_eq!(report.rounds_played, 2);
+    assert_eq!(report.fallbacks.len(), 2);
+    assert!(report.fallbacks.iter().all(|f| f.player_id == "stuck" && f.reason == "missed the round deadline"));
+
+    let state = engine.get_game_state(game_id).await.unwrap();
+    for round in &state.history {
+        assert!(round.actions["quick"].latency_ms.is_some());
+        assert_eq!(round.actions["stuck"].action_type, "defect");
+        assert_eq!(round.actions["stuck"].latency_ms, None, "Defaults were never timed");
+    }
+
+    // Only the timed decisions count towards the average
+    assert_eq!(streamer.metrics().await.response_samples, 2);
+    let mut analytics = 0;
+    while let Ok(message) = stream.try_recv() {
+        if let StreamMessage::AnalyticsUpdate { game_id: id, .. } = message {
+            assert_eq!(id, game_id);
+            analytics += 1;
+        }
+    }
+    assert_eq!(analytics, 2);
+}
+
+#[tokio::test]
+async fn test_stragglers_are_charged_for_the_round_they_missed() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let game_id = engine.create_game(config(GameType::PrisonersDilemma, &[("max_rounds", 3)])).await.unwrap().game_id;
+
+    let scheduler = TurnScheduler::new(Duration::from_secs(10)).with_time_control(TimeControl::fischer(250, 0));
+    let report = MatchRunner::new(engine.clone())
+        .seat("quick", Arc::new(MockProvider::deterministic()))
+        .seat("stuck", Arc::new(SlowProvider { delay: Duration::from_secs(5) }))
+        .scheduler(scheduler)
+        .collection(Collection::Parallel { round_deadline: Duration::from_millis(100) })
+        .run(game_id)
+        .await
+        .expect("Match should run");
+
+    // Two missed rounds fit in the bank; the third runs it dry
+    assert_eq!(report.rounds_played, 3);
+    let reasons: Vec<&str> = report.fallbacks.iter().map(|f| f.reason.as_str()).collect();
+    assert_eq!(reasons, vec!["missed the round deadline", "missed the round deadline", "out of time"]);
+
+    let state = engine.get_game_state(game_id).await.unwrap();
+    assert!(state.clocks["stuck"].flagged);
+    assert!(!state.clocks["quick"].flagged);
+    assert!(state.clocks["quick"].remaining_ms > 200);
+}
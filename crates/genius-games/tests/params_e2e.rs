@@ -0,0 +1,90 @@
+//! End-to-end tests for declared, validated game parameters
+
+use genius_core::{GameConfig, GameError, GameType, ParamKind};
+use genius_engine::GameEngine;
+use genius_games::{create_game, GameRegistry};
+use std::collections::HashMap;
+
+fn config(game_type: GameType, params: serde_json::Value) -> GameConfig {
+    GameConfig {
+        game_type,
+        rounds: 10,
+        time_limit_ms: 1000,
+        params: serde_json::from_value(params).unwrap(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(5),
+    }
+}
+
+#[tokio::test]
+async fn test_params_change_game_setup() {
+    let engine = GameEngine::new(create_game);
+    let state = engine.create_game(config(GameType::MiniGo, serde_json::json!({ "board_size": 5 })))
+        .await
+        .expect("A 5x5 board should be accepted");
+
+    // Every point on the smaller board plus passing
+    let actions = engine.get_valid_actions(state.game_id, "p0").await.unwrap();
+    assert_eq!(actions.len(), 26);
+}
+
+#[tokio::test]
+async fn test_invalid_params_are_rejected() {
+    let engine = GameEngine::new(create_game);
+
+    let bad = [
+        (GameType::MiniGo, serde_json::json!({ "board_size": 40 })),
+        (GameType::MiniGo, serde_json::json!({ "board_size": "big" })),
+        (GameType::PrisonersDilemma, serde_json::json!({ "max_round": 50 })),
+        (GameType::MiniHoldem, serde_json::json!({ "small_blind": 50, "big_blind": 20 })),
+    ];
+    for (game_type, params) in bad {
+        match engine.create_game(config(game_type.clone(), params.clone())).await {
+            Err(GameError::ConfigError { reason }) => assert!(!reason.is_empty()),
+            other => panic!("{:?} with {} should be rejected, got {:?}", game_type, params, other.map(|s| s.round)),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_effective_params_are_recorded_in_replay() {
+    let engine = GameEngine::new(create_game);
+    let state = engine.create_game(config(GameType::LiarsDice, serde_json::json!({ "dice_per_player": "3" })))
+        .await
+        .expect("Numeric strings should be accepted");
+
+    let replay = engine.get_replay(state.game_id).await.unwrap();
+    assert_eq!(replay.config.params["dice_per_player"], 3);
+    assert!(replay.config.params.contains_key("max_rounds"), "Defaults should be filled in");
+}
+
+#[tokio::test]
+async fn test_registry_describes_parameters() {
+    let registry = GameRegistry::new();
+
+    let go = registry.info(&GameType::MiniGo).unwrap();
+    let board_size = go.parameters.specs().iter().find(|p| p.name == "board_size").unwrap();
+    assert_eq!(board_size.kind, ParamKind::Integer { default: 9, min: 5, max: 19 });
+
+    // Games without settings declare an empty schema
+    let minority = registry.info(&GameType::MinorityGame).unwrap();
+    assert!(minority.parameters.specs().is_empty());
+    assert!(minority.parameters.validate(&HashMap::new()).is_ok());
+}
+
+#[tokio::test]
+async fn test_extreme_params_keep_legal_moves_small() {
+    let engine = GameEngine::new(create_game);
+    let params = serde_json::json!({ "starting_chips": 1_000_000, "small_blind": 1, "big_blind": 1 });
+    let game_id = engine.open_lobby(config(GameType::MiniHoldem, params)).await.unwrap().game_id;
+    engine.join_game(game_id, "ada").await.unwrap();
+    engine.join_game(game_id, "ben").await.unwrap();
+    engine.start_game(game_id).await.unwrap();
+
+    let player = engine.get_current_turn(game_id).await.unwrap().players[0].clone();
+    let legal = engine.get_valid_actions(game_id, &player).await.unwrap();
+    assert!(legal.len() <= 4, "A million-chip stack listed {} moves", legal.len());
+    let raise = legal.iter().find(|m| m.action_type == "raise").unwrap();
+    assert!(raise.range.unwrap().max >= 999_000);
+}
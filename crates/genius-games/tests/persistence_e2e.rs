@@ -0,0 +1,104 @@
+//! End-to-end tests for storing games and recovering them after a restart
+
+use genius_core::{GameConfig, GameError, GamePhase, GameType, PlayerAction};
+use genius_engine::{FileStore, GameEngine, GameStore, MemoryStore};
+use genius_games::create_game;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn config(max_rounds: i64) -> GameConfig {
+    GameConfig {
+        game_type: GameType::PrisonersDilemma,
+        rounds: 10,
+        time_limit_ms: 1000,
+        params: [("max_rounds".to_string(), serde_json::json!(max_rounds))].into_iter().collect::<HashMap<_, _>>(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(5),
+    }
+}
+
+fn both(alice: &str, bob: &str) -> HashMap<String, PlayerAction> {
+    [("alice", alice), ("bob", bob)]
+        .into_iter()
+        .map(|(player, choice)| (player.to_string(), PlayerAction::new(player.to_string(), choice.to_string(), serde_json::Value::Null)))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_in_flight_games_survive_a_restart() {
+    let dir = std::env::temp_dir().join(format!("genius-store-{}", Uuid::new_v4()));
+    let store: Arc<dyn GameStore> = Arc::new(FileStore::open(&dir).await.unwrap());
+
+    let engine = GameEngine::new(create_game).with_store(store.clone());
+    let game_id = engine.create_game(config(3)).await.unwrap().game_id;
+    engine.process_turn(game_id, both("cooperate", "defect")).await.unwrap();
+    engine.process_turn(game_id, both("defect", "defect")).await.unwrap();
+    let before = engine.get_game_state(game_id).await.unwrap();
+    drop(engine);
+
+    // A fresh engine over the same directory picks the game up where it stopped
+    let store: Arc<dyn GameStore> = Arc::new(FileStore::open(&dir).await.unwrap());
+    let engine = GameEngine::new(create_game).with_store(store);
+    assert_eq!(engine.recover().await.unwrap(), vec![game_id]);
+
+    let after = engine.get_game_state(game_id).await.unwrap();
+    assert_eq!(after.round, 2);
+    assert_eq!(after.scores, before.scores);
+    assert_eq!(engine.get_replay(game_id).await.unwrap().rounds.len(), 2);
+
+    engine.process_turn(game_id, both("cooperate", "cooperate")).await.unwrap();
+    assert_eq!(engine.get_phase(game_id).await.unwrap(), GamePhase::Finished);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_finalized_games_keep_their_result_and_replay() {
+    let store = Arc::new(MemoryStore::new());
+    let engine = GameEngine::new(create_game).with_store(store.clone());
+    let game_id = engine.create_game(config(1)).await.unwrap().game_id;
+    engine.process_turn(game_id, both("defect", "cooperate")).await.unwrap();
+
+    assert!(matches!(engine.get_result(game_id).await, Err(GameError::InvalidState { .. })));
+    let result = engine.finalize_game(game_id).await.unwrap();
+    assert!(engine.active_games().is_empty());
+
+    let stored = engine.get_result(game_id).await.unwrap();
+    assert_eq!(stored.winner, result.winner);
+    assert_eq!(stored.final_scores, result.final_scores);
+    assert_eq!(engine.get_replay(game_id).await.unwrap().rounds.len(), 1);
+
+    // Finished games are history, not something to resume
+    let restarted = GameEngine::new(create_game).with_store(store);
+    assert!(restarted.recover().await.unwrap().is_empty());
+    assert!(matches!(restarted.get_game_state(game_id).await, Err(GameError::GameNotFound { .. })));
+    assert!(restarted.get_result(game_id).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_lobbies_and_forfeits_are_recovered() {
+    let store = Arc::new(MemoryStore::new());
+    let engine = GameEngine::new(create_game).with_store(store.clone());
+
+    let lobby = engine.open_lobby(config(3)).await.unwrap().game_id;
+    engine.join_game(lobby, "carol").await.unwrap();
+
+    let running = engine.create_game(config(3)).await.unwrap().game_id;
+    engine.process_turn(running, both("cooperate", "cooperate")).await.unwrap();
+    engine.leave_game(running, "bob").await.unwrap();
+
+    let restarted = GameEngine::new(create_game).with_store(store);
+    let mut expected = vec![lobby, running];
+    expected.sort();
+    assert_eq!(restarted.recover().await.unwrap(), expected);
+
+    assert_eq!(restarted.get_phase(lobby).await.unwrap(), GamePhase::Lobby);
+    assert!(restarted.get_game_state(lobby).await.unwrap().player_status.contains_key("carol"));
+
+    let replay = restarted.get_replay(running).await.unwrap();
+    assert_eq!(replay.forfeits.len(), 1);
+    assert_eq!(replay.forfeits[0].player_id, "bob");
+    assert!(!restarted.get_game_state(running).await.unwrap().is_active("bob"));
+}
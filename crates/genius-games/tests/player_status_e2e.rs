@@ -0,0 +1,151 @@
+//! End-to-end tests for tracking who is alive, eliminated or spectating
+
+use async_trait::async_trait;
+use genius_core::{
+    ActionSchema, Game, GameAnalytics, GameConfig, GameError, GameResult, GameState, GameType,
+    LegalAction, PlayerAction, PlayerStatus, Result, RoundOutcome, RoundResult, Standings, Turn,
+};
+use genius_engine::GameEngine;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// Every player taps each round until they choose to walk away
+#[derive(Default, Serialize, Deserialize)]
+struct WalkAwayGame {
+    walked: BTreeSet<String>,
+}
+
+#[async_trait]
+impl Game for WalkAwayGame {
+    async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        Ok(GameState::new(config.game_type))
+    }
+
+    async fn process_round(&mut self, state: &GameState, actions: HashMap<String, PlayerAction>) -> Result<RoundResult> {
+        for (player, action) in &actions {
+            if action.action_type == "walk" {
+                self.walked.insert(player.clone());
+            }
+        }
+        Ok(RoundResult {
+            round: state.round + 1,
+            actions,
+            outcome: RoundOutcome {
+                winners: vec![],
+                losers: vec![],
+                special_events: vec![],
+                emergence_detected: false,
+            },
+            scores_delta: HashMap::new(),
+            events: vec![],
+            emergence: Vec::new(),
+            timestamp: chrono::Utc::now(),
+        })
+    }
+
+    async fn is_game_over(&self, _state: &GameState) -> bool {
+        false
+    }
+
+    async fn calculate_final_result(&self, state: &GameState) -> GameResult {
+        let standings = Standings::new(&state.scores);
+        GameResult {
+            game_id: state.game_id,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
+            final_scores: state.scores.clone(),
+            total_rounds: state.round,
+            duration_ms: 0,
+            emergence_events: vec![],
+            analytics: GameAnalytics::default(),
+        }
+    }
+
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Walk Away")
+            .simple_action("tap", "Stay in")
+            .simple_action("walk", "Leave the game")
+            .build()
+    }
+
+    async fn get_valid_actions(&self, _state: &GameState, _player_id: &str) -> Vec<LegalAction> {
+        vec![LegalAction::simple("tap"), LegalAction::simple("walk")]
+    }
+
+    async fn current_turn(&self, state: &GameState) -> Turn {
+        let mut players: Vec<String> = state.player_status.keys().cloned().collect();
+        players.sort();
+        Turn::simultaneous(players)
+    }
+
+    async fn eliminated_players(&self, _state: &GameState) -> Vec<String> {
+        self.walked.iter().cloned().collect()
+    }
+}
+
+fn act(player: &str, action_type: &str) -> (String, PlayerAction) {
+    (player.to_string(), LegalAction::simple(action_type).to_action(player))
+}
+
+async fn walk_away_engine() -> (GameEngine, uuid::Uuid) {
+    let engine = GameEngine::new(|_: GameType| -> Result<Box<dyn Game>> { Ok(Box::new(WalkAwayGame::default())) });
+    let config = GameConfig {
+        game_type: GameType::Custom("WalkAway".to_string()),
+        rounds: 10,
+        time_limit_ms: 1000,
+        params: HashMap::new(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(1),
+    };
+    let game_id = engine.create_game(config).await.unwrap().game_id;
+    (engine, game_id)
+}
+
+#[tokio::test]
+async fn test_eliminated_players_are_no_longer_asked_to_act() {
+    let (engine, game_id) = walk_away_engine().await;
+
+    engine.process_turn(game_id, [act("p0", "tap"), act("p1", "walk"), act("p2", "tap")].into_iter().collect())
+        .await
+        .unwrap();
+
+    let state = engine.get_game_state(game_id).await.unwrap();
+    assert_eq!(state.status("p1"), PlayerStatus::Eliminated { round: 1 });
+    assert_eq!(state.active_players(), vec!["p0", "p2"]);
+
+    let turn = engine.get_current_turn(game_id).await.unwrap();
+    assert_eq!(turn.players, vec!["p0".to_string(), "p2".to_string()]);
+    assert!(engine.get_valid_actions(game_id, "p1").await.unwrap().is_empty());
+
+    match engine.process_turn(game_id, [act("p0", "tap"), act("p1", "tap")].into_iter().collect()).await {
+        Err(GameError::ActionsRejected { rejections }) => {
+            assert_eq!(rejections.len(), 1);
+            assert!(rejections[0].reason.contains("eliminated in round 1"));
+        }
+        other => panic!("Expected the eliminated player to be rejected, got {:?}", other.map(|r| r.round)),
+    }
+
+    // Elimination sticks to the round the player went out in
+    engine.process_turn(game_id, [act("p0", "tap"), act("p2", "tap")].into_iter().collect()).await.unwrap();
+    let observation = engine.get_observation(game_id, &genius_core::Observer::Spectator).await.unwrap();
+    assert_eq!(observation.player_status["p1"], PlayerStatus::Eliminated { round: 1 });
+}
+
+#[tokio::test]
+async fn test_spectators_watch_without_playing() {
+    let (engine, game_id) = walk_away_engine().await;
+    engine.process_turn(game_id, [act("p0", "tap")].into_iter().collect()).await.unwrap();
+
+    engine.add_spectator(game_id, "watcher").await.expect("Newcomers may spectate");
+    assert!(engine.add_spectator(game_id, "p0").await.is_err(), "Players cannot switch to spectating");
+
+    let state = engine.get_game_state(game_id).await.unwrap();
+    assert_eq!(state.status("watcher"), PlayerStatus::Spectating);
+    assert!(!engine.get_current_turn(game_id).await.unwrap().players.contains(&"watcher".to_string()));
+
+    let rejected = engine.process_turn(game_id, [act("watcher", "tap")].into_iter().collect()).await;
+    assert!(matches!(rejected, Err(GameError::ActionsRejected { .. })));
+}
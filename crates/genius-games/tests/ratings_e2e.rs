@@ -0,0 +1,173 @@
+//! End-to-end tests for skill ratings across games
+
+use genius_core::{
+    GameConfig, GameError, GameResult, GameType, Placement, Player, PlayerAction, PlayerId, PlayerType, TeamResult,
+};
+use genius_engine::{GameEngine, RatingService, RatingSubject, RatingSystem};
+use genius_games::create_game;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+fn ai(id: &str, model: &str) -> Player {
+    Player {
+        id: PlayerId::from_string(id.to_string()),
+        name: id.to_string(),
+        player_type: PlayerType::AI { provider: "mock".to_string(), model: model.to_string() },
+        metadata: serde_json::Value::Null,
+    }
+}
+
+/// One round of the prisoner's dilemma between two seats
+async fn dilemma(engine: &GameEngine, first: (&str, &str), second: (&str, &str)) -> GameResult {
+    let config = GameConfig {
+        game_type: GameType::PrisonersDilemma,
+        rounds: 10,
+        time_limit_ms: 1000,
+        params: [("max_rounds".to_string(), serde_json::json!(1))].into_iter().collect::<HashMap<_, _>>(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(3),
+    };
+    let game_id = engine.open_lobby(config).await.unwrap().game_id;
+    engine.join_game(game_id, first.0).await.unwrap();
+    engine.join_game(game_id, second.0).await.unwrap();
+    engine.start_game(game_id).await.unwrap();
+
+    let actions = [first, second].into_iter()
+        .map(|(player, choice)| (player.to_string(), PlayerAction::new(player.to_string(), choice.to_string(), serde_json::Value::Null)))
+        .collect();
+    engine.process_turn(game_id, actions).await.unwrap();
+    engine.finalize_game(game_id).await.unwrap()
+}
+
+/// A finished game re-ranked as `ranks`, so any number of seats can be rated
+fn finish(template: &GameResult, ranks: &[(&str, u32)]) -> GameResult {
+    GameResult {
+        game_id: Uuid::new_v4(),
+        placements: ranks.iter()
+            .map(|(player, rank)| Placement { player_id: player.to_string(), rank: *rank, score: 0, team: None, eliminated_round: None })
+            .collect(),
+        teams: vec![],
+        ..template.clone()
+    }
+}
+
+#[tokio::test]
+async fn test_two_player_games_move_every_rating_system() {
+    let engine = GameEngine::new(create_game);
+    let ratings = RatingService::new();
+    let players = [ai("hawk", "gpt"), ai("dove", "claude")];
+
+    let result = dilemma(&engine, ("hawk", "defect"), ("dove", "cooperate")).await;
+    let changes = ratings.record(&result, &players).await.unwrap();
+    assert_eq!(changes.len(), 4, "Both players and both models are rated");
+
+    let hawk = ratings.rating(&RatingSubject::player("hawk")).await.unwrap();
+    let dove = ratings.rating(&RatingSubject::player("dove")).await.unwrap();
+    assert!((hawk.elo - 1516.0).abs() < 1e-9);
+    assert!((hawk.elo + dove.elo - 3000.0).abs() < 1e-9, "Elo is zero-sum");
+    assert!(hawk.glicko.rating > 1500.0 && dove.glicko.rating < 1500.0);
+    assert!(hawk.glicko.deviation < 350.0);
+    assert!(hawk.trueskill.mu > 25.0 && dove.trueskill.mu < 25.0);
+    assert!(hawk.trueskill.sigma < 25.0 / 3.0);
+    assert_eq!((hawk.games, dove.games), (1, 1));
+
+    // Models are pooled from their players
+    let gpt = ratings.rating(&RatingSubject::model("mock", "gpt")).await.unwrap();
+    assert_eq!(gpt.elo, hawk.elo);
+
+    // A game is only counted once
+    assert!(matches!(ratings.record(&result, &players).await, Err(GameError::InvalidState { .. })));
+
+    // Mutual defection is a draw, which pulls the two back together
+    let result = dilemma(&engine, ("dove", "defect"), ("hawk", "defect")).await;
+    ratings.record(&result, &players).await.unwrap();
+    let after = ratings.rating(&RatingSubject::player("hawk")).await.unwrap();
+    assert!(after.elo < hawk.elo && after.elo > 1500.0);
+    assert_eq!(after.games, 2);
+
+    let board = ratings.leaderboard(RatingSystem::Glicko2).await;
+    assert_eq!(board.len(), 4);
+    assert!(board.windows(2).all(|w| w[0].1.glicko.rating >= w[1].1.glicko.rating));
+}
+
+#[tokio::test]
+async fn test_a_model_playing_itself_learns_nothing_head_to_head() {
+    let engine = GameEngine::new(create_game);
+    let ratings = RatingService::new();
+    let players = [ai("left", "gpt"), ai("right", "gpt")];
+
+    let result = dilemma(&engine, ("left", "defect"), ("right", "cooperate")).await;
+    ratings.record(&result, &players).await.unwrap();
+
+    let gpt = ratings.rating(&RatingSubject::model("mock", "gpt")).await.unwrap();
+    assert!((gpt.elo - 1500.0).abs() < 1e-9);
+    assert!((gpt.trueskill.mu - 25.0).abs() < 1e-9);
+    assert_eq!(gpt.games, 1);
+    assert!(ratings.rating(&RatingSubject::player("left")).await.unwrap().elo > 1500.0);
+}
+
+#[tokio::test]
+async fn test_free_for_all_and_team_games_use_trueskill() {
+    let engine = GameEngine::new(create_game);
+    let ratings = RatingService::new();
+    let template = dilemma(&engine, ("a", "defect"), ("b", "defect")).await;
+    let players = [ai("a", "gpt"), ai("b", "gpt"), ai("c", "claude"), ai("d", "claude")];
+
+    // A four-way free-for-all with a shared second place
+    ratings.record(&finish(&template, &[("a", 1), ("b", 2), ("c", 2), ("d", 4)]), &players).await.unwrap();
+    let mu = |r: Option<genius_engine::Ratings>| r.unwrap().trueskill.mu;
+    let a = mu(ratings.rating(&RatingSubject::player("a")).await);
+    let b = mu(ratings.rating(&RatingSubject::player("b")).await);
+    let c = mu(ratings.rating(&RatingSubject::player("c")).await);
+    let d = mu(ratings.rating(&RatingSubject::player("d")).await);
+    assert!(a > b && (b - c).abs() < 1e-9 && c > d);
+    assert!((a + b + c + d - 100.0).abs() < 1e-6, "Equal uncertainty makes the update zero-sum");
+    // Elo only rates one player against one other
+    assert_eq!(ratings.rating(&RatingSubject::player("a")).await.unwrap().elo, 1500.0);
+
+    // Two teams of two; the winners' model gains
+    let teams = GameResult {
+        teams: vec![
+            TeamResult { team: "red".to_string(), members: vec!["c".to_string(), "d".to_string()], score: 6, rank: 1 },
+            TeamResult { team: "blue".to_string(), members: vec!["a".to_string(), "b".to_string()], score: 2, rank: 2 },
+        ],
+        ..finish(&template, &[("c", 1), ("d", 1), ("a", 3), ("b", 3)])
+    };
+    let claude_before = ratings.rating(&RatingSubject::model("mock", "claude")).await.unwrap();
+    ratings.record(&teams, &players).await.unwrap();
+    let claude = ratings.rating(&RatingSubject::model("mock", "claude")).await.unwrap();
+    assert!(claude.trueskill.mu > claude_before.trueskill.mu);
+    assert!(mu(ratings.rating(&RatingSubject::player("d")).await) > d);
+    assert_eq!(claude.games, 2);
+}
+
+#[tokio::test]
+async fn test_ratings_persist_with_their_history() {
+    let path = std::env::temp_dir().join(format!("genius-ratings-{}.json", Uuid::new_v4()));
+    let engine = GameEngine::new(create_game);
+    let players = [ai("hawk", "gpt"), ai("dove", "claude")];
+
+    let ratings = RatingService::open(&path).await.unwrap();
+    let start = chrono::Utc::now();
+    for _ in 0..3 {
+        let result = dilemma(&engine, ("hawk", "defect"), ("dove", "cooperate")).await;
+        ratings.record(&result, &players).await.unwrap();
+    }
+    let current = ratings.rating(&RatingSubject::player("hawk")).await.unwrap();
+    drop(ratings);
+
+    let ratings = RatingService::open(&path).await.unwrap();
+    assert_eq!(ratings.rating(&RatingSubject::player("hawk")).await.unwrap(), current);
+
+    let history = ratings.history(&RatingSubject::player("hawk")).await;
+    assert_eq!(history.len(), 3);
+    assert!(history.windows(2).all(|w| w[0].after == w[1].before && w[0].at <= w[1].at));
+    assert!(history.windows(2).all(|w| w[1].after.elo > w[0].after.elo));
+
+    // Looking back in time
+    assert_eq!(ratings.rating_at(&RatingSubject::player("hawk"), start - chrono::Duration::seconds(1)).await, None);
+    assert_eq!(ratings.rating_at(&RatingSubject::player("hawk"), history[0].at).await, Some(history[0].after));
+
+    std::fs::remove_file(&path).unwrap();
+}
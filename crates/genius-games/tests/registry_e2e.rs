@@ -0,0 +1,134 @@
+//! End-to-end tests for registering and running custom games
+
+use async_trait::async_trait;
+use genius_core::{
+    ActionSchema, Game, GameAnalytics, GameCategory, GameConfig, GameResult, GameState, GameType,
+    LegalAction, PlayerAction, Result, RoundOutcome, RoundResult, Standings,
+};
+use genius_engine::GameEngine;
+use genius_games::GameRegistry;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Minimal in-house game: every player who taps scores a point
+#[derive(Default, Serialize, Deserialize)]
+struct TapGame {
+    rounds: u32,
+}
+
+#[async_trait]
+impl Game for TapGame {
+    async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        self.rounds = config.rounds;
+        Ok(GameState::new(config.game_type))
+    }
+
+    async fn process_round(&mut self, state: &GameState, actions: HashMap<String, PlayerAction>) -> Result<RoundResult> {
+        let scores_delta = actions.keys().map(|p| (p.clone(), 1)).collect();
+        Ok(RoundResult {
+            round: state.round + 1,
+            actions,
+            outcome: RoundOutcome {
+                winners: vec![],
+                losers: vec![],
+                special_events: vec![],
+                emergence_detected: false,
+            },
+            scores_delta,
+            events: vec![],
+            emergence: Vec::new(),
+            timestamp: chrono::Utc::now(),
+        })
+    }
+
+    async fn is_game_over(&self, state: &GameState) -> bool {
+        state.round >= self.rounds
+    }
+
+    async fn calculate_final_result(&self, state: &GameState) -> GameResult {
+        let standings = Standings::new(&state.scores);
+        GameResult {
+            game_id: state.game_id,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
+            final_scores: state.scores.clone(),
+            total_rounds: state.round,
+            duration_ms: 0,
+            emergence_events: vec![],
+            analytics: GameAnalytics::default(),
+        }
+    }
+
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Tap").simple_action("tap", "Score a point").build()
+    }
+
+    async fn get_valid_actions(&self, _state: &GameState, _player_id: &str) -> Vec<LegalAction> {
+        vec![LegalAction::simple("tap")]
+    }
+}
+
+fn tap_game() -> GameType {
+    GameType::Custom("Tap".to_string())
+}
+
+fn register_tap(registry: &GameRegistry) -> Result<()> {
+    registry.register(
+        tap_game(),
+        GameCategory::Custom,
+        serde_json::json!({ "description": "Tap to score" }),
+        || Box::new(TapGame::default()),
+    )
+}
+
+#[tokio::test]
+async fn test_engine_runs_game_registered_after_startup() {
+    let registry = GameRegistry::new();
+    let engine = GameEngine::new(registry.clone());
+
+    // The engine shares the registry, so later registrations are visible to it
+    register_tap(&registry).expect("Custom game should register");
+
+    let config = GameConfig {
+        game_type: tap_game(),
+        rounds: 3,
+        time_limit_ms: 1000,
+        params: HashMap::new(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: None,
+    };
+    let state = engine.create_game(config).await.expect("Registered custom game should start");
+
+    let actions: HashMap<String, PlayerAction> = [(
+        "p0".to_string(),
+        LegalAction::simple("tap").to_action("p0"),
+    )].into_iter().collect();
+    let result = engine.process_turn(state.game_id, actions).await.expect("Tap should be legal");
+    assert_eq!(result.scores_delta.get("p0"), Some(&1));
+}
+
+#[tokio::test]
+async fn test_registry_lists_and_resolves_games() {
+    let registry = GameRegistry::new();
+    register_tap(&registry).unwrap();
+
+    assert!(register_tap(&registry).is_err(), "Duplicate registrations should be refused");
+    for name in ["MiniGo", "mini go", "TAP"] {
+        let clash = registry.register(GameType::Custom(name.to_string()), GameCategory::Custom, serde_json::Value::Null, || Box::new(TapGame::default()));
+        assert!(clash.is_err(), "{} should not shadow a registered game", name);
+    }
+    assert_eq!(registry.resolve("tap"), Some(tap_game()));
+    assert_eq!(registry.resolve("Mini Go"), Some(GameType::MiniGo));
+    assert_eq!(registry.resolve("MiniGo"), Some(GameType::MiniGo));
+    assert_eq!(registry.resolve("Chess"), None);
+
+    assert_eq!(registry.games_by_category(GameCategory::Custom), vec![tap_game()]);
+    assert_eq!(registry.info(&tap_game()).unwrap().metadata["description"], "Tap to score");
+    assert_eq!(registry.all_games().len(), 26);
+
+    // Unregistered custom games fail cleanly rather than falling back to a built-in
+    assert!(GameRegistry::empty().create_game(&tap_game()).is_err());
+}
@@ -0,0 +1,73 @@
+//! End-to-end tests for replay recording and verification
+
+use genius_core::{GameConfig, GameType, PlayerAction, Replay};
+use genius_games::{create_game, replay::verify_replay};
+use std::collections::HashMap;
+
+const PLAYERS: [&str; 5] = ["p0", "p1", "p2", "p3", "p4"];
+
+/// Play a seeded minority game and record it the way the engine does
+async fn record_minority_game(rounds: u32) -> Replay {
+    let config = GameConfig {
+        game_type: GameType::MinorityGame,
+        rounds,
+        time_limit_ms: 1000,
+        params: HashMap::new(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(2024),
+    };
+
+    let mut game = create_game(config.game_type.clone()).expect("Game should exist");
+    let mut state = game.initialize(config.clone()).await.expect("Game should initialize");
+    let mut replay = Replay::new(config);
+
+    for round in 0..rounds {
+        let actions: HashMap<String, PlayerAction> = PLAYERS.iter().enumerate()
+            .map(|(i, p)| {
+                let choice = (i as u32 + round) % 2;
+                (p.to_string(), PlayerAction::new(p.to_string(), "choose".to_string(), serde_json::json!(choice)))
+            })
+            .collect();
+
+        let result = game.process_round(&state, actions.clone()).await.expect("Round should process");
+        state.apply_round(&result);
+        replay.record_round(actions, result);
+    }
+
+    replay
+}
+
+#[tokio::test]
+async fn test_replay_reproduces_recorded_game() {
+    let replay = record_minority_game(8).await;
+
+    let json = replay.to_json().expect("Replay should serialize");
+    let loaded = Replay::from_json(&json).expect("Replay should parse");
+
+    let verification = verify_replay(&loaded).await.expect("Replay should run");
+    assert!(verification.is_match(), "Unexpected divergence: {:?}", verification.divergence);
+    assert_eq!(verification.rounds_checked, 8);
+}
+
+#[tokio::test]
+async fn test_replay_reports_first_divergent_round() {
+    let mut replay = record_minority_game(8).await;
+
+    // Tamper with round 5 as if process_round had changed since recording
+    replay.rounds[4].result.scores_delta.insert("p0".to_string(), 1000);
+
+    let verification = verify_replay(&replay).await.expect("Replay should run");
+    let divergence = verification.divergence.expect("Tampered round should diverge");
+    assert_eq!(divergence.round, 5);
+    assert_eq!(verification.rounds_checked, 5);
+}
+
+#[tokio::test]
+async fn test_replay_rejects_unknown_version() {
+    let mut replay = record_minority_game(1).await;
+    replay.version += 1;
+
+    let json = serde_json::to_string(&replay).unwrap();
+    assert!(Replay::from_json(&json).is_err(), "Future replay versions should be rejected");
+}
@@ -0,0 +1,69 @@
+//! End-to-end tests for rewinding and forking games in the engine
+
+use genius_core::{GameConfig, GameType, PlayerAction};
+use genius_engine::GameEngine;
+use genius_games::create_game;
+use std::collections::HashMap;
+
+const PLAYERS: [&str; 5] = ["p0", "p1", "p2", "p3", "p4"];
+
+fn round_actions(round: u32) -> HashMap<String, PlayerAction> {
+    PLAYERS.iter().enumerate()
+        .map(|(i, p)| {
+            let choice = (i as u32 * 3 + round) % 2;
+            (p.to_string(), PlayerAction::new(p.to_string(), "choose".to_string(), serde_json::json!(choice)))
+        })
+        .collect()
+}
+
+async fn engine_with_game(rounds: u32) -> (GameEngine, uuid::Uuid) {
+    let engine = GameEngine::new(create_game);
+    let config = GameConfig {
+        game_type: GameType::MinorityGame,
+        rounds: 20,
+        time_limit_ms: 1000,
+        params: HashMap::new(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: None,
+    };
+
+    let state = engine.create_game(config).await.expect("Game should be created");
+    for round in 0..rounds {
+        engine.process_turn(state.game_id, round_actions(round)).await.expect("Turn should process");
+    }
+    (engine, state.game_id)
+}
+
+#[tokio::test]
+async fn test_fork_branches_from_earlier_round() {
+    let (engine, game_id) = engine_with_game(6).await;
+    let original = engine.get_game_state(game_id).await.unwrap();
+
+    let fork = engine.fork_game(game_id, 3).await.expect("Fork should succeed");
+    assert_ne!(fork.game_id, game_id);
+    assert_eq!(fork.round, 3);
+    for (forked, recorded) in fork.history.iter().zip(&original.history) {
+        assert_eq!(forked.scores_delta, recorded.scores_delta, "Fork should replay the same rounds");
+    }
+
+    // Both branches keep playing independently
+    engine.process_turn(fork.game_id, round_actions(99)).await.expect("Fork should accept turns");
+    assert_eq!(engine.get_game_state(game_id).await.unwrap().round, 6);
+    assert_eq!(engine.get_game_state(fork.game_id).await.unwrap().round, 4);
+}
+
+#[tokio::test]
+async fn test_rewind_in_place() {
+    let (engine, game_id) = engine_with_game(5).await;
+
+    let rewound = engine.rewind_game(game_id, 2).await.expect("Rewind should succeed");
+    assert_eq!(rewound.game_id, game_id);
+    assert_eq!(rewound.round, 2);
+    assert_eq!(engine.get_replay(game_id).await.unwrap().rounds.len(), 2);
+
+    assert!(
+        engine.rewind_game(game_id, 4).await.is_err(),
+        "Cannot rewind past the current round"
+    );
+}
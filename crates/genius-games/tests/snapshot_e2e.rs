@@ -0,0 +1,99 @@
+//! End-to-end tests for snapshotting and restoring game internals
+
+use genius_core::{Game, GameConfig, GameState, GameType, LegalAction, PlayerAction, RoundResult};
+use genius_games::{create_game, restore_game, GameRegistry};
+use std::collections::HashMap;
+
+const PLAYERS: [&str; 4] = ["p0", "p1", "p2", "p3"];
+
+async fn scripted_actions(game: &dyn Game, state: &GameState) -> HashMap<String, PlayerAction> {
+    let mut actions = HashMap::new();
+    for player in PLAYERS {
+        let action = game.get_valid_actions(state, player).await
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| LegalAction::simple("pass"));
+        actions.insert(player.to_string(), action.to_action(player));
+    }
+    actions
+}
+
+async fn play_round(game: &mut dyn Game, state: &mut GameState) -> RoundResult {
+    let actions = scripted_actions(game, state).await;
+    let result = game.process_round(state, actions).await.expect("Round should process");
+    state.round += 1;
+    state.apply_score_deltas(&result.scores_delta);
+    state.history.push(result.clone());
+    result
+}
+
+/// Order-independent summary of a round, for comparing two instances
+fn summarize(result: &RoundResult) -> serde_json::Value {
+    let mut winners = result.outcome.winners.clone();
+    let mut losers = result.outcome.losers.clone();
+    winners.sort();
+    losers.sort();
+    serde_json::json!({
+        "scores_delta": result.scores_delta,
+        "winners": winners,
+        "losers": losers,
+    })
+}
+
+#[tokio::test]
+async fn test_restored_game_continues_identically() {
+    for game_type in GameRegistry::new().all_games().iter() {
+        let mut game = create_game(game_type.clone()).expect("Game should exist");
+        let config = GameConfig {
+            game_type: game_type.clone(),
+            rounds: 20,
+            time_limit_ms: 1000,
+            params: HashMap::new(),
+            initial_players: vec![],
+            teams: vec![],
+            seed: Some(99),
+        };
+
+        let mut state = game.initialize(config).await.expect("Game should initialize");
+        for player in PLAYERS {
+            game.apply_join(&mut state, player).await;
+        }
+
+        for _ in 0..3 {
+            play_round(game.as_mut(), &mut state).await;
+        }
+
+        let snapshot = game.snapshot().expect("Game should snapshot");
+        let json = serde_json::to_string(&snapshot).expect("Snapshot should be valid JSON");
+        let mut restored = restore_game(game_type.clone(), serde_json::from_str(&json).unwrap())
+            .unwrap_or_else(|e| panic!("{:?} should restore: {}", game_type, e));
+        let mut restored_state = state.clone();
+
+        let original_next = play_round(game.as_mut(), &mut state).await;
+        let restored_next = play_round(restored.as_mut(), &mut restored_state).await;
+
+        assert_eq!(
+            summarize(&original_next),
+            summarize(&restored_next),
+            "{:?} diverged after restore",
+            game_type
+        );
+        assert_eq!(
+            game.is_game_over(&state).await,
+            restored.is_game_over(&restored_state).await,
+            "{:?} disagrees on game over after restore",
+            game_type
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_restore_rejects_foreign_snapshot() {
+    let holdem = create_game(GameType::MiniHoldem).expect("Game should exist");
+    let snapshot = holdem.snapshot().expect("Game should snapshot");
+
+    assert!(
+        restore_game(GameType::MiniGo, snapshot).is_err(),
+        "A snapshot from another game type should not restore"
+    );
+}
@@ -0,0 +1,93 @@
+//! End-to-end tests for placements, draws, teams and eliminations in game results
+
+use genius_core::{GameConfig, GameOutcome, GameType, PlayerAction, Standings};
+use genius_engine::GameEngine;
+use genius_games::create_game;
+use std::collections::HashMap;
+
+fn scores(entries: &[(&str, i32)]) -> HashMap<String, i32> {
+    entries.iter().map(|(p, s)| (p.to_string(), *s)).collect()
+}
+
+#[test]
+fn test_tied_players_share_a_rank() {
+    let standings = Standings::new(&scores(&[("a", 10), ("b", 10), ("c", 4)]));
+
+    let ranks: Vec<(String, u32)> = standings.placements().into_iter().map(|p| (p.player_id, p.rank)).collect();
+    assert_eq!(ranks, vec![("a".to_string(), 1), ("b".to_string(), 1), ("c".to_string(), 3)]);
+    assert_eq!(standings.outcome(), GameOutcome::Draw { player_ids: vec!["a".to_string(), "b".to_string()] });
+}
+
+#[test]
+fn test_eliminated_players_place_by_how_long_they_lasted() {
+    // The early leader still places last once knocked out first
+    let standings = Standings::new(&scores(&[("early", 90), ("late", 10), ("survivor", 0)]))
+        .eliminated("early", 2)
+        .eliminated("late", 7);
+
+    let placements = standings.placements();
+    let order: Vec<&str> = placements.iter().map(|p| p.player_id.as_str()).collect();
+    assert_eq!(order, vec!["survivor", "late", "early"]);
+    assert_eq!(placements[2].eliminated_round, Some(2));
+    assert_eq!(placements[0].eliminated_round, None);
+    assert_eq!(standings.outcome(), GameOutcome::Winner { player_id: "survivor".to_string() });
+
+    // Co-operative survival: everyone still standing wins together
+    let shared = Standings::new(&scores(&[("a", 5), ("b", 50), ("c", 99)]))
+        .eliminated("c", 3)
+        .survivors_share_first();
+    assert_eq!(shared.outcome(), GameOutcome::Draw { player_ids: vec!["a".to_string(), "b".to_string()] });
+}
+
+#[test]
+fn test_teams_are_ranked_together() {
+    let standings = Standings::new(&scores(&[("collective_0", 8), ("collective_1", 8), ("sota_0", 12)]))
+        .team("collective", ["collective_0", "collective_1"])
+        .team("sota", ["sota_0"]);
+
+    let teams = standings.teams();
+    assert_eq!(teams[0].team, "collective");
+    assert_eq!(teams[0].score, 16);
+    assert_eq!(teams[1].rank, 2);
+    assert!(matches!(standings.outcome(), GameOutcome::TeamWin { ref team, .. } if team == "collective"));
+
+    // The top individual belongs to the losing team, but placements still record team membership
+    let top = &standings.placements()[0];
+    assert_eq!(top.player_id, "sota_0");
+    assert_eq!(top.team.as_deref(), Some("sota"));
+
+    // A team nobody plays on can't win, even when every real player is in the red
+    let unopposed = Standings::new(&scores(&[("honest_0", -3), ("honest_1", -3)]))
+        .team("honest", ["honest_0", "honest_1"])
+        .team("traitors", Vec::<String>::new());
+    assert_eq!(unopposed.teams().len(), 1);
+    assert!(matches!(unopposed.outcome(), GameOutcome::TeamWin { ref team, .. } if team == "honest"));
+}
+
+#[tokio::test]
+async fn test_engine_reports_a_draw() {
+    let engine = GameEngine::new(create_game);
+    let config = GameConfig {
+        game_type: GameType::PrisonersDilemma,
+        rounds: 3,
+        time_limit_ms: 1000,
+        params: HashMap::new(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(9),
+    };
+    let game_id = engine.create_game(config).await.unwrap().game_id;
+
+    for _ in 0..3 {
+        let actions: HashMap<String, PlayerAction> = ["p0", "p1"].iter()
+            .map(|p| (p.to_string(), PlayerAction::new(p.to_string(), "cooperate".to_string(), serde_json::Value::Null)))
+            .collect();
+        engine.process_turn(game_id, actions).await.expect("Cooperation should be legal");
+    }
+
+    let result = engine.finalize_game(game_id).await.unwrap();
+    assert_eq!(result.outcome, GameOutcome::Draw { player_ids: vec!["p0".to_string(), "p1".to_string()] });
+    assert_eq!(result.placement("p0").unwrap().rank, 1);
+    assert_eq!(result.placement("p1").unwrap().rank, 1);
+    assert!(result.teams.is_empty());
+}
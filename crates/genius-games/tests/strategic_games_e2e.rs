@@ -98,9 +98,9 @@ async fn test_mini_holdem() {
     // Poker should have betting dynamics
     let has_betting_events = result.round_results.iter()
         .any(|r| r.events.iter().any(|e| 
-            e.event_type.contains("bet") || 
-            e.event_type.contains("fold") ||
-            e.event_type.contains("raise")
+            e.event_type().contains("bet") || 
+            e.event_type().contains("fold") ||
+            e.event_type().contains("raise")
         ));
         
     assert!(
@@ -165,7 +165,7 @@ async fn test_strategic_depth() {
         
         let runner = E2ETestRunner::new(config);
         let result = runner.run_game().await
-            .expect(&format!("{:?} should complete", game_type));
+            .unwrap_or_else(|_| panic!("{:?} should complete", game_type));
         
         assert!(
             result.final_result.analytics.strategic_depth >= min_depth,
@@ -0,0 +1,200 @@
+//! End-to-end tests for declared teams and in-game coalitions
+
+use async_trait::async_trait;
+use genius_core::{
+    ActionSchema, Coalition, Game, GameAnalytics, GameConfig, GameError, GameResult, GameState, GameType,
+    PlayerAction, Result, RoundOutcome, RoundResult, Standings, Team,
+};
+use genius_engine::{AnalyticsEngine, GameEngine};
+use genius_games::create_game;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// Players make and break two-way pacts; a pact holds until either side breaks it
+#[derive(Default, Serialize, Deserialize)]
+struct PactGame {
+    pacts: BTreeSet<(String, String)>,
+}
+
+fn pair(a: &str, b: &str) -> (String, String) {
+    if a < b { (a.to_string(), b.to_string()) } else { (b.to_string(), a.to_string()) }
+}
+
+#[async_trait]
+impl Game for PactGame {
+    async fn initialize(&mut self, config: GameConfig) -> Result<GameState> {
+        Ok(GameState::new(config.game_type))
+    }
+
+    async fn process_round(&mut self, state: &GameState, actions: HashMap<String, PlayerAction>) -> Result<RoundResult> {
+        for (player, action) in &actions {
+            let target = action.data.as_str().unwrap_or_default();
+            match action.action_type.as_str() {
+                "pact" => { self.pacts.insert(pair(player, target)); }
+                "break" => { self.pacts.remove(&pair(player, target)); }
+                _ => {}
+            }
+        }
+        Ok(RoundResult {
+            round: state.round + 1,
+            actions,
+            outcome: RoundOutcome {
+                winners: vec![],
+                losers: vec![],
+                special_events: vec![],
+                emergence_detected: false,
+            },
+            scores_delta: HashMap::new(),
+            events: vec![],
+            emergence: Vec::new(),
+            timestamp: chrono::Utc::now(),
+        })
+    }
+
+    async fn is_game_over(&self, _state: &GameState) -> bool {
+        false
+    }
+
+    async fn calculate_final_result(&self, state: &GameState) -> GameResult {
+        let standings = Standings::new(&state.scores);
+        GameResult {
+            game_id: state.game_id,
+            winner: standings.winner(),
+            outcome: standings.outcome(),
+            placements: standings.placements(),
+            teams: standings.teams(),
+            final_scores: state.scores.clone(),
+            total_rounds: state.round,
+            duration_ms: 0,
+            emergence_events: vec![],
+            analytics: GameAnalytics::default(),
+        }
+    }
+
+    fn action_schema(&self) -> serde_json::Value {
+        ActionSchema::new("Pacts")
+            .simple_action("wait", "Do nothing")
+            .build()
+    }
+
+    async fn validate_action(&self, _state: &GameState, _player_id: &str, _action: &PlayerAction) -> std::result::Result<(), String> {
+        Ok(())
+    }
+
+    async fn coalitions(&self, _state: &GameState) -> Vec<Vec<String>> {
+        self.pacts.iter().map(|(a, b)| vec![a.clone(), b.clone()]).collect()
+    }
+}
+
+fn act(player: &str, action_type: &str, target: &str) -> (String, PlayerAction) {
+    (player.to_string(), PlayerAction::new(player.to_string(), action_type.to_string(), serde_json::json!(target)))
+}
+
+fn config(game_type: GameType, teams: Vec<Team>) -> GameConfig {
+    GameConfig {
+        game_type,
+        rounds: 10,
+        time_limit_ms: 1000,
+        params: HashMap::new(),
+        initial_players: vec![],
+        teams,
+        seed: Some(3),
+    }
+}
+
+#[tokio::test]
+async fn test_declared_teams_are_validated_and_recorded() {
+    let engine = GameEngine::new(create_game);
+
+    let overlapping = vec![Team::new("red", ["p0", "p1"]), Team::new("blue", ["p1", "p2"])];
+    match engine.create_game(config(GameType::PrisonersDilemma, overlapping)).await {
+        Err(GameError::ConfigError { reason }) => assert!(reason.contains("p1")),
+        other => panic!("A player on two teams should be rejected, got {:?}", other.map(|s| s.round)),
+    }
+    let duplicated = vec![Team::new("red", ["p0"]), Team::new("red", ["p1"])];
+    assert!(engine.create_game(config(GameType::PrisonersDilemma, duplicated)).await.is_err());
+
+    let teams = vec![Team::new("red", ["p1", "p0"]), Team::new("blue", ["p2", "p3"])];
+    let state = engine.create_game(config(GameType::PrisonersDilemma, teams.clone())).await.unwrap();
+    assert_eq!(state.teams, teams);
+    assert_eq!(state.team_of("p1"), Some("red"));
+    assert_eq!(state.team_of("p9"), None);
+    assert_eq!(state.allies("p0"), vec!["p1"]);
+
+    let observation = engine.get_observation(state.game_id, &genius_core::Observer::Spectator).await.unwrap();
+    assert_eq!(observation.teams, teams);
+    let replay = engine.get_replay(state.game_id).await.unwrap();
+    assert_eq!(replay.config.teams, teams);
+}
+
+#[tokio::test]
+async fn test_coalitions_record_when_they_form_and_break() {
+    let engine = GameEngine::new(|_: GameType| -> Result<Box<dyn Game>> { Ok(Box::new(PactGame::default())) });
+    let game_id = engine.create_game(config(GameType::Custom("Pacts".to_string()), vec![Team::new("red", ["a", "d"])]))
+        .await
+        .unwrap()
+        .game_id;
+
+    engine.process_turn(game_id, [act("a", "pact", "b"), act("c", "wait", "")].into_iter().collect()).await.unwrap();
+    engine.process_turn(game_id, [act("c", "pact", "a")].into_iter().collect()).await.unwrap();
+
+    let state = engine.get_game_state(game_id).await.unwrap();
+    assert_eq!(state.active_coalitions().len(), 2);
+    assert_eq!(state.allies("a"), vec!["b", "c", "d"]);
+
+    engine.process_turn(game_id, [act("b", "break", "a")].into_iter().collect()).await.unwrap();
+
+    let state = engine.get_game_state(game_id).await.unwrap();
+    let ab = state.coalitions.iter().find(|c| c.members == ["a", "b"]).unwrap();
+    assert_eq!((ab.formed_round, ab.dissolved_round), (1, Some(3)));
+    assert_eq!(state.active_coalitions(), vec![&Coalition {
+        members: vec!["a".to_string(), "c".to_string()],
+        formed_round: 2,
+        dissolved_round: None,
+    }]);
+    assert_eq!(state.allies("b"), Vec::<&String>::new());
+
+    // Re-forming a broken pact starts a new coalition
+    engine.process_turn(game_id, [act("a", "pact", "b")].into_iter().collect()).await.unwrap();
+    let state = engine.get_game_state(game_id).await.unwrap();
+    assert_eq!(state.coalitions.iter().filter(|c| c.members == ["a", "b"]).count(), 2);
+}
+
+#[tokio::test]
+async fn test_analytics_group_winners_by_team() {
+    let analytics = AnalyticsEngine::new();
+    let game_id = uuid::Uuid::new_v4();
+    let mut state = GameState::new(GameType::MinorityGame);
+    // Ids that carry no hint of which side the players are on
+    state.teams = vec![Team::new("red", ["alpha", "beta"]), Team::new("blue", ["gamma"]), Team::new("green", ["delta"])];
+
+    let round = |round: u32, winners: &[&str]| RoundResult {
+        round,
+        actions: HashMap::new(),
+        outcome: RoundOutcome {
+            winners: winners.iter().map(|w| w.to_string()).collect(),
+            losers: vec![],
+            special_events: vec![],
+            emergence_detected: false,
+        },
+        scores_delta: HashMap::new(),
+        events: vec![],
+        emergence: Vec::new(),
+        timestamp: chrono::Utc::now(),
+    };
+
+    analytics.process_round(game_id, &state, &round(1, &["alpha", "beta"])).await;
+    analytics.process_round(game_id, &state, &round(2, &["gamma"])).await;
+    analytics.process_round(game_id, &state, &round(3, &["alpha"])).await;
+    // One winner on each side: nobody takes the round
+    analytics.process_round(game_id, &state, &round(4, &["beta", "gamma"])).await;
+
+    let data = analytics.calculate_final_analytics(game_id).await.unwrap();
+    assert_eq!(data.emergence_analysis.collective_advantage_moments, vec![1, 3]);
+    assert_eq!(data.performance_comparison.team_round_wins["red"], 2);
+    assert_eq!(data.performance_comparison.team_round_wins["blue"], 1);
+    assert_eq!(data.performance_comparison.team_round_wins["green"], 0, "Teams that never win a round are still listed");
+    assert_eq!(data.performance_comparison.team_win_rates["green"], 0.0);
+    assert_eq!(data.performance_comparison.collective_win_rate, 0.5);
+    assert_eq!(data.performance_comparison.sota_win_rate, 0.25);
+}
@@ -0,0 +1,134 @@
+//! End-to-end tests for chess-clock time controls
+
+use async_trait::async_trait;
+use genius_ai::{providers::MockProvider, AIDecision, AIProvider};
+use genius_core::{
+    FlagPenalty, GameConfig, GameError, GameType, LegalAction, Observation, Observer, PlayerStatus, Result, TimeControl,
+};
+use genius_engine::{GameEngine, MatchRunner, TurnScheduler};
+use genius_games::create_game;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Thinks for a fixed time, then plays its first legal move
+struct SlowProvider {
+    delay: Duration,
+}
+
+#[async_trait]
+impl AIProvider for SlowProvider {
+    fn name(&self) -> &str {
+        "Slow Provider"
+    }
+
+    async fn make_decision(&self, _observation: &Observation, player_id: &str, valid_actions: Vec<LegalAction>) -> Result<AIDecision> {
+        tokio::time::sleep(self.delay).await;
+        Ok(AIDecision {
+            action: valid_actions[0].to_action(player_id),
+            reasoning: "Thought it over".to_string(),
+            confidence: 1.0,
+        })
+    }
+}
+
+fn config(max_rounds: i64) -> GameConfig {
+    GameConfig {
+        game_type: GameType::PrisonersDilemma,
+        rounds: 20,
+        time_limit_ms: 1000,
+        params: [("max_rounds".to_string(), serde_json::json!(max_rounds))].into_iter().collect::<HashMap<_, _>>(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(4),
+    }
+}
+
+#[test]
+fn test_fischer_banks_time_and_bronstein_does_not() {
+    let fischer = TimeControl::fischer(1000, 100);
+    let mut clock = fischer.start();
+    assert!(clock.charge(300, &fischer));
+    assert_eq!(clock.remaining_ms, 800);
+    assert!(clock.charge(50, &fischer));
+    assert_eq!(clock.remaining_ms, 850, "Quick moves gain time under Fischer");
+
+    let bronstein = TimeControl::bronstein(1000, 100);
+    let mut clock = bronstein.start();
+    assert!(clock.charge(300, &bronstein));
+    assert_eq!(clock.remaining_ms, 800);
+    assert!(clock.charge(50, &bronstein));
+    assert_eq!(clock.remaining_ms, 800, "Bronstein only refunds time actually used");
+
+    assert!(!clock.charge(801, &bronstein));
+    assert!(clock.flagged);
+    assert_eq!(clock.remaining_ms, 0);
+    assert!(!clock.charge(0, &bronstein), "A flagged clock stays flagged");
+}
+
+#[tokio::test]
+async fn test_scheduler_cuts_decisions_off_at_the_bank() {
+    let control = TimeControl::fischer(50, 10);
+    let scheduler = TurnScheduler::new(Duration::from_secs(5)).with_time_control(control);
+
+    let mut clock = control.start();
+    let quick = scheduler.execute_on_clock(&mut clock, "p0".to_string(), async { 7 }).await.unwrap();
+    assert_eq!(quick, 7);
+    assert!(clock.remaining_ms > 50, "The increment should more than cover an instant move");
+
+    let slow = scheduler.execute_on_clock(&mut clock, "p0".to_string(), tokio::time::sleep(Duration::from_secs(1))).await;
+    assert!(matches!(slow, Err(GameError::TurnTimeout { player_id }) if player_id == "p0"));
+    assert!(clock.flagged);
+}
+
+#[tokio::test]
+async fn test_flagged_players_get_the_default_action() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let game_id = engine.create_game(config(4)).await.unwrap().game_id;
+
+    let scheduler = TurnScheduler::new(Duration::from_secs(1)).with_time_control(TimeControl::fischer(250, 0));
+    let report = MatchRunner::new(engine.clone())
+        .seat("quick", Arc::new(MockProvider::deterministic()))
+        .seat("slow", Arc::new(SlowProvider { delay: Duration::from_millis(100) }))
+        .scheduler(scheduler)
+        .run(game_id)
+        .await
+        .expect("Match should run");
+
+    // Two moves fit in the bank; the third runs it dry
+    assert_eq!(report.rounds_played, 4);
+    let rounds: Vec<u32> = report.fallbacks.iter().map(|f| f.round).collect();
+    assert_eq!(rounds, vec![3, 4]);
+    assert!(report.fallbacks.iter().all(|f| f.player_id == "slow"));
+    assert_eq!(report.fallbacks[0].reason, "out of time");
+
+    let observation = engine.get_observation(game_id, &Observer::Player("quick".to_string())).await.unwrap();
+    assert!(observation.clocks["slow"].flagged);
+    assert_eq!(observation.clocks["slow"].remaining_ms, 0);
+    assert!(!observation.clocks["quick"].flagged);
+}
+
+#[tokio::test]
+async fn test_flagged_players_can_be_made_to_forfeit() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let game_id = engine.create_game(config(4)).await.unwrap().game_id;
+
+    let scheduler = TurnScheduler::new(Duration::from_secs(1))
+        .with_time_control(TimeControl::bronstein(250, 0))
+        .with_flag_penalty(FlagPenalty::Forfeit);
+    let report = MatchRunner::new(engine.clone())
+        .seat("quick", Arc::new(MockProvider::deterministic()))
+        .seat("slow", Arc::new(SlowProvider { delay: Duration::from_millis(100) }))
+        .scheduler(scheduler)
+        .run(game_id)
+        .await
+        .expect("Match should run");
+
+    assert_eq!(report.rounds_played, 4);
+    assert_eq!(report.fallbacks.len(), 1);
+    assert_eq!(report.fallbacks[0].reason, "out of time; forfeited");
+
+    let state = engine.get_game_state(game_id).await.unwrap();
+    assert_eq!(state.status("slow"), PlayerStatus::Eliminated { round: 2 });
+    assert!(state.history[2..].iter().all(|r| !r.actions.contains_key("slow")));
+}
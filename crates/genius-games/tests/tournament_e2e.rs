@@ -0,0 +1,186 @@
+//! End-to-end tests for round-robin, Swiss and knockout tournaments
+
+use async_trait::async_trait;
+use genius_ai::{providers::MockProvider, AIDecision, AIProvider};
+use genius_core::{GameConfig, GameType, LegalAction, Observation, Result};
+use genius_engine::{Format, GameEngine, MatchOutcome, MemoryStore, Tournament};
+use genius_games::create_game;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Always plays the same move when it is legal
+struct FixedProvider {
+    action_type: &'static str,
+}
+
+#[async_trait]
+impl AIProvider for FixedProvider {
+    fn name(&self) -> &str {
+        "Fixed Provider"
+    }
+
+    async fn make_decision(&self, _observation: &Observation, player_id: &str, valid_actions: Vec<LegalAction>) -> Result<AIDecision> {
+        let choice = valid_actions.iter().find(|a| a.action_type == self.action_type).unwrap_or(&valid_actions[0]);
+        Ok(AIDecision {
+            action: choice.to_action(player_id),
+            reasoning: "Same as always".to_string(),
+            confidence: 1.0,
+        })
+    }
+}
+
+fn config(game_type: GameType, params: &[(&str, i64)]) -> GameConfig {
+    GameConfig {
+        game_type,
+        rounds: 10,
+        time_limit_ms: 1000,
+        params: params.iter().map(|(k, v)| (k.to_string(), serde_json::json!(v))).collect::<HashMap<_, _>>(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(13),
+    }
+}
+
+fn dilemma(engine: &Arc<GameEngine>, format: Format) -> Tournament {
+    Tournament::new(engine.clone(), config(GameType::PrisonersDilemma, &[("max_rounds", 3)]), format)
+}
+
+/// One defector among cooperators, who draw with each other
+fn hawk_and_doves(tournament: Tournament, doves: usize) -> Tournament {
+    (0..doves).fold(
+        tournament.entrant("hawk", Arc::new(FixedProvider { action_type: "defect" })),
+        |t, i| t.entrant(&format!("dove_{}", i), Arc::new(FixedProvider { action_type: "cooperate" })),
+    )
+}
+
+#[tokio::test]
+async fn test_round_robin_meets_everyone_with_seats_swapped() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let tournament = ["ada", "ben", "cy", "dot"].into_iter().enumerate()
+        .fold(dilemma(&engine, Format::RoundRobin { cycles: 2 }), |t, (i, name)| t.entrant(name, Arc::new(MockProvider::seeded(i as u64))));
+    let report = tournament.run().await.expect("Tournament should run");
+
+    assert_eq!(report.rounds_played, 6);
+    assert_eq!(report.matches.len(), 12);
+    assert!(report.byes.is_empty());
+
+    // Every pair meets once per cycle, and the rematch swaps seats
+    for a in ["ada", "ben", "cy", "dot"] {
+        for b in ["ada", "ben", "cy", "dot"].into_iter().filter(|&b| b != a) {
+            let meetings: Vec<_> = report.matches.iter().filter(|m| m.seats.contains(&a.to_string()) && m.seats.contains(&b.to_string())).collect();
+            assert_eq!(meetings.len(), 2);
+            assert_ne!(meetings[0].seats[0], meetings[1].seats[0]);
+            assert_eq!(report.crosstable[a][b] + report.crosstable[b][a], 2.0);
+        }
+    }
+
+    assert!(report.standings.windows(2).all(|w| w[0].points >= w[1].points));
+    assert!(report.standings.iter().all(|s| s.played == 6 && s.wins + s.draws + s.losses == 6));
+    assert_eq!(report.champion, report.standings[0].entrant);
+}
+
+#[tokio::test]
+async fn test_swiss_gives_each_bye_once_and_avoids_rematches() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let report = hawk_and_doves(dilemma(&engine, Format::Swiss { rounds: 3 }), 4).run().await.expect("Tournament should run");
+
+    assert_eq!(report.matches.len(), 6);
+    assert_eq!(report.byes.len(), 3);
+    let byes: HashSet<&str> = report.byes.iter().map(|b| b.entrant.as_str()).collect();
+    assert_eq!(byes.len(), 3, "Nobody should sit out twice");
+
+    let pairs: HashSet<(String, String)> = report.matches.iter()
+        .map(|m| {
+            let mut pair = m.seats.clone();
+            pair.sort();
+            (pair[0].clone(), pair[1].clone())
+        })
+        .collect();
+    assert_eq!(pairs.len(), 6, "Five entrants have enough opponents for three rounds without rematches");
+
+    let hawk = report.standings.iter().find(|s| s.entrant == "hawk").unwrap();
+    assert_eq!(hawk.points, (hawk.wins + hawk.byes) as f64, "Byes are worth a win in Swiss");
+    assert_eq!(report.champion, "hawk");
+}
+
+#[tokio::test]
+async fn test_single_elimination_knocks_out_everyone_but_the_champion() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let report = hawk_and_doves(dilemma(&engine, Format::SingleElimination), 4).tiebreak_games(1).run().await.expect("Tournament should run");
+
+    assert_eq!(report.rounds_played, 3);
+    assert_eq!(report.champion, "hawk");
+    assert_eq!(report.standings[0].eliminated_in, None);
+    assert!(report.standings[1..].iter().all(|s| s.eliminated_in.is_some()));
+    assert!(report.standings.windows(2).skip(1).all(|w| w[0].eliminated_in >= w[1].eliminated_in));
+
+    // Doves only ever draw each other, so each of their matches is replayed once
+    let dove_games = report.matches.iter().filter(|m| !m.seats.contains(&"hawk".to_string())).count();
+    assert_eq!(dove_games % 2, 0);
+    assert!(report.matches.iter().all(|m| !matches!(&m.outcome, MatchOutcome::Won { loser, .. } if loser == "hawk")));
+}
+
+#[tokio::test]
+async fn test_knockout_seats_entrants_in_turn_based_games() {
+    let engine = Arc::new(GameEngine::new(create_game).with_store(Arc::new(MemoryStore::new())));
+    let report = ["ada", "ben", "cy", "dot"].into_iter().enumerate()
+        .fold(
+            Tournament::new(engine.clone(), config(GameType::LiarsDice, &[("dice_per_player", 2), ("max_rounds", 30)]), Format::SingleElimination),
+            |t, (i, name)| t.entrant(name, Arc::new(MockProvider::seeded(i as u64))),
+        )
+        .tiebreak_games(0)
+        .run()
+        .await
+        .expect("Tournament should run");
+
+    assert_eq!(report.rounds_played, 2);
+    assert_eq!(report.matches.len(), 3);
+    for m in &report.matches {
+        // Both entrants were dealt in, and the first seat opened the bidding
+        let replay = engine.get_replay(m.game_id).await.unwrap();
+        assert_eq!(replay.players, m.seats);
+        assert!(!replay.rounds.is_empty(), "The match should have been played");
+        assert_eq!(replay.rounds[0].actions.keys().collect::<Vec<_>>(), vec![&m.seats[0]]);
+    }
+    assert!(report.standings[1..].iter().all(|s| s.eliminated_in.is_some()));
+}
+
+#[tokio::test]
+async fn test_double_elimination_needs_two_losses() {
+    let engine = Arc::new(GameEngine::new(create_game));
+    let report = hawk_and_doves(dilemma(&engine, Format::DoubleElimination), 3).tiebreak_games(0).run().await.expect("Tournament should run");
+
+    assert_eq!(report.champion, "hawk");
+    assert_eq!(report.standings.iter().filter(|s| s.eliminated_in.is_some()).count(), 3);
+    // The winners-bracket champion meets the losers-bracket champion last
+    let last = report.matches.last().unwrap();
+    assert!(last.seats.contains(&"hawk".to_string()));
+    assert!(report.matches.iter().filter(|m| m.seats.contains(&"hawk".to_string())).count() >= 2);
+}
+
+#[tokio::test]
+async fn test_sides_rotate_in_asymmetric_games() {
+    let engine = Arc::new(GameEngine::new(create_game).with_store(Arc::new(MemoryStore::new())));
+    let report = Tournament::new(engine.clone(), config(GameType::MiniGo, &[("board_size", 5)]), Format::RoundRobin { cycles: 2 })
+        .entrant("sente", Arc::new(FixedProvider { action_type: "pass" }))
+        .entrant("gote", Arc::new(FixedProvider { action_type: "pass" }))
+        .sides("black", "white")
+        .run()
+        .await
+        .expect("Tournament should run");
+
+    assert_eq!(report.matches.len(), 2);
+    assert_eq!(report.matches[0].seats[0], report.matches[1].seats[1]);
+
+    for m in &report.matches {
+        let replay = engine.get_replay(m.game_id).await.unwrap();
+        let black = replay.config.teams.iter().find(|t| t.name == "black").unwrap();
+        assert_eq!(black.members, vec![m.seats[0].clone()]);
+        // Black moves first
+        assert_eq!(replay.rounds[0].actions.keys().collect::<Vec<_>>(), vec![&m.seats[0]]);
+    }
+
+    // With both passing at once, komi decides it for white every time
+    assert!(report.matches.iter().all(|m| m.outcome == MatchOutcome::Won { winner: m.seats[1].clone(), loser: m.seats[0].clone() }));
+    assert_eq!(report.crosstable["sente"]["gote"], 1.0);
+}
@@ -116,7 +116,7 @@ async fn test_trust_evolution() {
         
         let runner = E2ETestRunner::new(config).with_deterministic_ai();
         let result = runner.run_game().await
-            .expect(&format!("{:?} should complete", game_type));
+            .unwrap_or_else(|_| panic!("{:?} should complete", game_type));
         
         // Analyze trust evolution
         let early_scores: i32 = result.round_results.iter()
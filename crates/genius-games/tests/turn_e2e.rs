@@ -0,0 +1,90 @@
+//! End-to-end tests for sequential and simultaneous turn structure
+
+use genius_core::{GameConfig, GameError, GameType, PlayerAction, Turn, TurnResolution};
+use genius_engine::{GameEngine, TurnScheduler};
+use genius_games::create_game;
+use std::collections::HashMap;
+use std::time::Duration;
+
+fn config(game_type: GameType) -> GameConfig {
+    GameConfig {
+        game_type,
+        rounds: 20,
+        time_limit_ms: 1000,
+        params: HashMap::new(),
+        initial_players: vec![],
+        teams: vec![],
+        seed: Some(3),
+    }
+}
+
+fn place(player: &str, row: usize, col: usize) -> (String, PlayerAction) {
+    let data = serde_json::json!({ "row": row, "col": col });
+    (player.to_string(), PlayerAction::new(player.to_string(), "place".to_string(), data))
+}
+
+#[tokio::test]
+async fn test_turn_based_game_names_the_player_to_move() {
+    let engine = GameEngine::new(create_game);
+    let game_id = engine.create_game(config(GameType::MiniGo)).await.unwrap().game_id;
+
+    // Both players take their seats in the opening round
+    let opening = engine.get_current_turn(game_id).await.unwrap();
+    assert!(opening.players.is_empty(), "Go should be open until both colors are assigned");
+    engine.process_turn(game_id, [place("p0", 2, 2), place("p1", 6, 6)].into_iter().collect())
+        .await
+        .expect("Opening round should process");
+
+    let turn = engine.get_current_turn(game_id).await.unwrap();
+    assert_eq!(turn.resolution, TurnResolution::OneOf);
+    assert_eq!(turn.players.len(), 1);
+    let mover = turn.players[0].clone();
+    let waiting = if mover == "p0" { "p1" } else { "p0" };
+
+    // Moving out of turn is rejected without touching the game
+    match engine.process_turn(game_id, [place(waiting, 3, 3)].into_iter().collect()).await {
+        Err(GameError::ActionsRejected { rejections }) => {
+            assert_eq!(rejections[0].player_id, waiting);
+            assert!(rejections[0].reason.contains("turn"));
+        }
+        other => panic!("Expected an out-of-turn rejection, got {:?}", other.map(|r| r.round)),
+    }
+
+    engine.process_turn(game_id, [place(&mover, 3, 3)].into_iter().collect())
+        .await
+        .expect("The player to move should be accepted");
+    assert_eq!(engine.get_current_turn(game_id).await.unwrap(), Turn::sequential(waiting.to_string()));
+}
+
+#[tokio::test]
+async fn test_simultaneous_game_waits_for_everyone() {
+    let mut game = create_game(GameType::PrisonersDilemma).unwrap();
+    let mut state = game.initialize(config(GameType::PrisonersDilemma)).await.unwrap();
+    state.add_player("p0".to_string());
+    state.add_player("p1".to_string());
+
+    let turn = game.current_turn(&state).await;
+    assert_eq!(turn, Turn::simultaneous(vec!["p0".to_string(), "p1".to_string()]));
+
+    let mut actions = HashMap::new();
+    actions.insert("p0".to_string(), PlayerAction::new("p0".to_string(), "cooperate".to_string(), serde_json::Value::Null));
+    assert!(!turn.is_ready(&actions));
+    assert_eq!(turn.waiting_on(&actions), vec!["p1".to_string()]);
+
+    actions.insert("p1".to_string(), PlayerAction::new("p1".to_string(), "defect".to_string(), serde_json::Value::Null));
+    assert!(turn.is_ready(&actions));
+}
+
+#[tokio::test]
+async fn test_scheduler_collects_only_expected_players() {
+    let scheduler = TurnScheduler::new(Duration::from_millis(200));
+    let turn = Turn::sequential("p1".to_string());
+    let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+
+    tx.send(PlayerAction::new("p0".to_string(), "pass".to_string(), serde_json::Value::Null)).await.unwrap();
+    tx.send(PlayerAction::new("p1".to_string(), "pass".to_string(), serde_json::Value::Null)).await.unwrap();
+
+    let actions = scheduler.collect_turn(&turn, &mut rx).await;
+    assert_eq!(actions.len(), 1);
+    assert!(actions.contains_key("p1"));
+}
@@ -1,4 +1,7 @@
+use genius_engine::FileStore;
+use genius_games::GameRegistry;
 use genius_server::SimpleGameServer;
+use std::sync::Arc;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -8,7 +11,14 @@ async fn main() -> anyhow::Result<()> {
         .init();
     
     // Use simple server for now (full server has complex dependencies)
-    let server = SimpleGameServer::new();
+    // Keep games on disk when a data directory is configured
+    let server = match std::env::var("GENIUS_DATA_DIR") {
+        Ok(dir) => {
+            let store = FileStore::open(dir).await?;
+            SimpleGameServer::with_store(GameRegistry::new(), Arc::new(store)).await?
+        }
+        Err(_) => SimpleGameServer::new(),
+    };
     server.run("0.0.0.0:8080").await?;
     
     Ok(())
@@ -1,29 +1,50 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
+    http::StatusCode,
     response::IntoResponse,
     routing::{get, post},
     Json, Router,
 };
-use genius_core::{GameConfig, GameType, GameState, PlayerAction};
-use genius_engine::GameEngine;
-use genius_games::create_game;
+use genius_core::{GameConfig, GameError, GameType, Observer, PlayerAction, Team};
+use genius_core::player::{Player, PlayerId, PlayerType};
+use genius_engine::{GameEngine, GameStore, Matchmaker, QueueRules};
+use genius_games::GameRegistry;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
-use tokio::sync::RwLock;
+use std::{collections::HashMap, sync::Arc, time::Duration};
 use tower_http::cors::CorsLayer;
 use tower_http::services::{ServeDir, ServeFile};
 use uuid::Uuid;
 
 pub struct SimpleGameServer {
-    games: Arc<RwLock<HashMap<Uuid, GameState>>>,
     engine: Arc<GameEngine>,
+    registry: GameRegistry,
+    matchmaker: Arc<Matchmaker>,
 }
 
 impl SimpleGameServer {
     pub fn new() -> Self {
+        Self::with_registry(GameRegistry::new())
+    }
+    
+    /// Serve the games in the given registry, including any custom games registered on it
+    pub fn with_registry(registry: GameRegistry) -> Self {
+        Self::serving(GameEngine::new(registry.clone()), registry)
+    }
+    
+    /// Serve the registry's games, saving them to the store and resuming any it still holds
+    pub async fn with_store(registry: GameRegistry, store: Arc<dyn GameStore>) -> anyhow::Result<Self> {
+        let engine = GameEngine::new(registry.clone()).with_store(store);
+        let recovered = engine.recover().await?;
+        tracing::info!("Recovered {} in-flight games", recovered.len());
+        Ok(Self::serving(engine, registry))
+    }
+    
+    fn serving(engine: GameEngine, registry: GameRegistry) -> Self {
+        let engine = Arc::new(engine);
         Self {
-            games: Arc::new(RwLock::new(HashMap::new())),
-            engine: Arc::new(GameEngine::new()),
+            matchmaker: Arc::new(Matchmaker::new(engine.clone())),
+            engine,
+            registry,
         }
     }
 
@@ -35,11 +56,18 @@ impl SimpleGameServer {
         // Serve demo files
         let demo_dir = ServeDir::new("demo");
         
+        // Apply waiting-time limits even when nobody new joins a queue
+        self.matchmaker.clone().run(Duration::from_secs(1));
+        
         let app = Router::new()
             // API routes
             .route("/api/v1/games", post(create_game_handler))
+            .route("/api/v1/game-types", get(list_game_types_handler))
             .route("/api/v1/games/:id", get(get_game_handler))
-            .route("/api/v1/games/:id/actions", post(submit_action_handler))
+            .route("/api/v1/games/:id/actions", get(get_actions_handler).post(submit_action_handler))
+            .route("/api/v1/games/:id/lifecycle", post(lifecycle_handler))
+            .route("/api/v1/matchmaking", post(enqueue_handler))
+            .route("/api/v1/matchmaking/:player_id", get(ticket_handler).delete(leave_queue_handler))
             .route("/api/v1/stats", get(get_stats_handler))
             // Static files
             .nest_service("/demo", demo_dir)
@@ -64,6 +92,17 @@ struct CreateGameRequest {
     players: Vec<String>,
     #[serde(default)]
     config: Option<serde_json::Value>,
+    #[serde(default)]
+    seed: Option<u64>,
+    /// Game-specific parameters, validated against the game's declared parameter set
+    #[serde(default)]
+    params: HashMap<String, serde_json::Value>,
+    /// Fixed sides, e.g. `[{"name": "red", "members": ["p0", "p2"]}]`
+    #[serde(default)]
+    teams: Vec<Team>,
+    /// Wait in the lobby for players to join instead of starting right away
+    #[serde(default)]
+    lobby: bool,
 }
 
 #[derive(Serialize)]
@@ -76,15 +115,15 @@ async fn create_game_handler(
     State(server): State<Arc<SimpleGameServer>>,
     Json(req): Json<CreateGameRequest>,
 ) -> impl IntoResponse {
-    let game_id = Uuid::new_v4();
-    
-    // Parse game type
-    let game_type = match req.game_type.as_str() {
-        "MiniGo" => GameType::MiniGo,
-        "MiniHoldem" => GameType::MiniHoldem,
-        "PrisonersDilemma" => GameType::PrisonersDilemma,
-        "ConsciousnessPoker" => GameType::ConsciousnessPoker,
-        _ => GameType::MinorityGame, // Default
+    // Resolve the game type through the registry
+    let game_type = match server.registry.resolve(&req.game_type) {
+        Some(game_type) => game_type,
+        None => {
+            return Json(CreateGameResponse {
+                game_id: Uuid::nil(),
+                status: format!("error: unknown game type {}", req.game_type),
+            });
+        }
     };
     
     // Create game config
@@ -92,67 +131,229 @@ async fn create_game_handler(
         game_type,
         rounds: 100,
         time_limit_ms: 5000,
-        special_rules: HashMap::new(),
+        params: req.params,
         initial_players: req.players.into_iter()
-            .map(|name| genius_core::Player {
-                id: genius_core::player::PlayerId::from_string(name.clone()),
+            .map(|name| Player {
+                id: PlayerId::from_string(name.clone()),
                 name,
-                player_type: genius_core::player::PlayerType::Human,
+                player_type: PlayerType::Human,
                 metadata: serde_json::Value::Null,
             })
             .collect(),
+        teams: req.teams,
+        seed: req.seed,
     };
     
-    // Create game instance
-    match create_game(game_type) {
-        Ok(mut game) => {
-            match game.initialize(config).await {
-                Ok(state) => {
-                    server.games.write().await.insert(game_id, state);
-                    Json(CreateGameResponse {
-                        game_id,
-                        status: "created".to_string(),
-                    })
-                }
-                Err(e) => {
-                    tracing::error!("Failed to initialize game: {}", e);
-                    Json(CreateGameResponse {
-                        game_id,
-                        status: format!("error: {}", e),
-                    })
-                }
-            }
-        }
+    // Create and initialize game instance
+    let created = if req.lobby {
+        server.engine.open_lobby(config).await
+    } else {
+        server.engine.create_game(config).await
+    };
+    match created {
+        Ok(state) => Json(CreateGameResponse {
+            game_id: state.game_id,
+            status: if req.lobby { "lobby" } else { "created" }.to_string(),
+        }),
         Err(e) => {
             tracing::error!("Failed to create game: {}", e);
             Json(CreateGameResponse {
-                game_id,
+                game_id: Uuid::nil(),
                 status: format!("error: {}", e),
             })
         }
     }
 }
 
+async fn list_game_types_handler(
+    State(server): State<Arc<SimpleGameServer>>,
+) -> impl IntoResponse {
+    Json(serde_json::json!({
+        "games": server.registry.list(),
+    }))
+}
+
+#[derive(Deserialize)]
+struct GetGameQuery {
+    /// Player to view the game as; omitted means a spectator view
+    #[serde(default)]
+    player_id: Option<String>,
+}
+
 async fn get_game_handler(
     State(server): State<Arc<SimpleGameServer>>,
     Path(game_id): Path<Uuid>,
+    Query(query): Query<GetGameQuery>,
 ) -> impl IntoResponse {
-    let games = server.games.read().await;
-    match games.get(&game_id) {
-        Some(state) => Json(serde_json::json!({
+    let observer = match query.player_id {
+        Some(player_id) => Observer::Player(player_id),
+        None => Observer::Spectator,
+    };
+    
+    match server.engine.get_observation(game_id, &observer).await {
+        Ok(observation) => Json(serde_json::json!({
             "game_id": game_id,
-            "state": state,
+            "phase": server.engine.get_phase(game_id).await.ok(),
+            "state": observation,
         })),
-        None => Json(serde_json::json!({
+        Err(_) => Json(serde_json::json!({
             "error": "Game not found",
         })),
     }
 }
 
+async fn get_actions_handler(
+    State(server): State<Arc<SimpleGameServer>>,
+    Path(game_id): Path<Uuid>,
+    Query(query): Query<GetGameQuery>,
+) -> impl IntoResponse {
+    let schema = match server.engine.get_action_schema(game_id).await {
+        Ok(schema) => schema,
+        Err(_) => {
+            return Json(serde_json::json!({
+                "error": "Game not found",
+            }));
+        }
+    };
+    
+    // Legal moves depend on who is asking; spectators only get the schema
+    let legal_actions = match &query.player_id {
+        Some(player_id) => server.engine.get_valid_actions(game_id, player_id).await.unwrap_or_default(),
+        None => Vec::new(),
+    };
+    let turn = server.engine.get_current_turn(game_id).await.ok();
+    
+    Json(serde_json::json!({
+        "game_id": game_id,
+        "schema": schema,
+        "turn": turn,
+        "legal_actions": legal_actions,
+    }))
+}
+
 #[derive(Deserialize)]
-struct SubmitActionRequest {
+#[serde(tag = "command", rename_all = "snake_case")]
+enum LifecycleRequest {
+    Join { player_id: String },
+    Leave { player_id: String },
+    Start,
+    Pause,
+    Resume,
+    Abort,
+}
+
+async fn lifecycle_handler(
+    State(server): State<Arc<SimpleGameServer>>,
+    Path(game_id): Path<Uuid>,
+    Json(req): Json<LifecycleRequest>,
+) -> impl IntoResponse {
+    let engine = &server.engine;
+    let result = match &req {
+        LifecycleRequest::Join { player_id } => engine.join_game(game_id, player_id).await,
+        LifecycleRequest::Leave { player_id } => engine.leave_game(game_id, player_id).await,
+        LifecycleRequest::Start => engine.start_game(game_id).await,
+        LifecycleRequest::Pause => engine.pause_game(game_id).await,
+        LifecycleRequest::Resume => engine.resume_game(game_id).await,
+        LifecycleRequest::Abort => engine.abort_game(game_id).await,
+    };
+    
+    match result {
+        Ok(()) => Json(serde_json::json!({
+            "game_id": game_id,
+            "phase": engine.get_phase(game_id).await.ok(),
+        })),
+        Err(e) => Json(serde_json::json!({
+            "error": e.to_string(),
+        })),
+    }
+}
+
+#[derive(Deserialize)]
+struct EnqueueRequest {
+    game_type: String,
     player_id: String,
-    action: serde_json::Value,
+    /// e.g. `{"AI": {"provider": "openai", "model": "gpt-4o"}}`; defaults to a human player
+    #[serde(default)]
+    player_type: Option<PlayerType>,
+}
+
+/// Settings for queues the server opens on demand
+fn default_queue(game_type: GameType) -> (GameConfig, QueueRules) {
+    let config = GameConfig {
+        game_type,
+        rounds: 100,
+        time_limit_ms: 5000,
+        params: HashMap::new(),
+        initial_players: Vec::new(),
+        teams: Vec::new(),
+        seed: None,
+    };
+    (config, QueueRules::default().expire_after(Duration::from_secs(300)))
+}
+
+async fn enqueue_handler(
+    State(server): State<Arc<SimpleGameServer>>,
+    Json(req): Json<EnqueueRequest>,
+) -> impl IntoResponse {
+    let Some(game_type) = server.registry.resolve(&req.game_type) else {
+        return Json(serde_json::json!({
+            "error": format!("unknown game type {}", req.game_type),
+        }));
+    };
+    
+    let matchmaker = &server.matchmaker;
+    if !matchmaker.has_queue(&game_type).await {
+        let (config, rules) = default_queue(game_type.clone());
+        if let Err(e) = matchmaker.open_queue(config, rules).await {
+            return Json(serde_json::json!({ "error": e.to_string() }));
+        }
+    }
+    
+    let player = Player {
+        id: PlayerId::from_string(req.player_id.clone()),
+        name: req.player_id,
+        player_type: req.player_type.unwrap_or(PlayerType::Human),
+        metadata: serde_json::Value::Null,
+    };
+    match matchmaker.enqueue(&game_type, player).await {
+        Ok(status) => Json(serde_json::json!(status)),
+        Err(e) => Json(serde_json::json!({ "error": e.to_string() })),
+    }
+}
+
+async fn ticket_handler(
+    State(server): State<Arc<SimpleGameServer>>,
+    Path(player_id): Path<String>,
+) -> impl IntoResponse {
+    match server.matchmaker.status(&player_id).await {
+        Some(status) => Json(serde_json::json!(status)),
+        None => Json(serde_json::json!({
+            "error": "Player is not queued",
+        })),
+    }
+}
+
+async fn leave_queue_handler(
+    State(server): State<Arc<SimpleGameServer>>,
+    Path(player_id): Path<String>,
+) -> impl IntoResponse {
+    match server.matchmaker.leave(&player_id).await {
+        Ok(()) => Json(serde_json::json!({ "player_id": player_id, "status": "left" })),
+        Err(e) => Json(serde_json::json!({ "error": e.to_string() })),
+    }
+}
+
+#[derive(Deserialize)]
+struct SubmitActionRequest {
+    /// The round's moves, keyed by the player making them
+    actions: HashMap<String, SubmittedAction>,
+}
+
+#[derive(Deserialize)]
+struct SubmittedAction {
+    action_type: String,
+    #[serde(default)]
+    data: serde_json::Value,
 }
 
 async fn submit_action_handler(
@@ -160,11 +361,31 @@ async fn submit_action_handler(
     Path(game_id): Path<Uuid>,
     Json(req): Json<SubmitActionRequest>,
 ) -> impl IntoResponse {
-    Json(serde_json::json!({
-        "status": "action_received",
-        "game_id": game_id,
-        "player_id": req.player_id,
-    }))
+    let actions = req.actions.into_iter()
+        .map(|(player_id, action)| {
+            let action = PlayerAction::new(player_id.clone(), action.action_type, action.data);
+            (player_id, action)
+        })
+        .collect();
+    
+    match server.engine.process_turn(game_id, actions).await {
+        Ok(round) => (StatusCode::OK, Json(serde_json::json!({
+            "game_id": game_id,
+            "phase": server.engine.get_phase(game_id).await.ok(),
+            "round": round,
+        }))),
+        // Nothing was applied; tell the client which moves to fix
+        Err(GameError::ActionsRejected { rejections }) => (StatusCode::UNPROCESSABLE_ENTITY, Json(serde_json::json!({
+            "error": "actions_rejected",
+            "rejections": rejections,
+        }))),
+        Err(e @ GameError::GameNotFound { .. }) => (StatusCode::NOT_FOUND, Json(serde_json::json!({
+            "error": e.to_string(),
+        }))),
+        Err(e) => (StatusCode::CONFLICT, Json(serde_json::json!({
+            "error": e.to_string(),
+        }))),
+    }
 }
 
 async fn get_stats_handler() -> impl IntoResponse {
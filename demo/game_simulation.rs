@@ -16,7 +16,9 @@ async fn main() {
         game_type: GameType::MinorityGame,
         rounds: 30,
         time_limit_ms: 1000,
-        special_rules: HashMap::new(),
+        params: HashMap::new(),
+        teams: vec![],
+        seed: None,
     };
     
     let game_id = engine.create_game(config).await.unwrap();
@@ -62,7 +64,7 @@ async fn main() {
             
             actions.insert(id.to_string(), Action {
                 player_id: id.to_string(),
-                action_type: "decision".to_string(),
+                action_type: "choose".to_string(),
                 data: json!(choice),
                 reasoning: Some(reasoning.clone()),
                 confidence: Some(0.7 + (round as f32 * 0.01)),
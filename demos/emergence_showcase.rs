@@ -32,8 +32,10 @@ pub async fn demo_consciousness_cascade() -> anyhow::Result<()> {
         game_type: GameType::ConsciousnessCascade,
         rounds: 20,
         time_limit_ms: 5000,
-        special_rules: HashMap::new(),
+        params: HashMap::new(),
         initial_players: players.clone(),
+        teams: vec![],
+        seed: None,
     };
     
     let mut state = game.initialize(config).await?;
@@ -191,8 +193,10 @@ pub async fn demo_quantum_dreamer() -> anyhow::Result<()> {
         game_type: GameType::QuantumDreamer,
         rounds: 15,
         time_limit_ms: 5000,
-        special_rules: HashMap::new(),
+        params: HashMap::new(),
         initial_players: players.clone(),
+        teams: vec![],
+        seed: None,
     };
     
     let mut state = game.initialize(config).await?;
@@ -338,8 +342,10 @@ pub async fn demo_reality_consensus() -> anyhow::Result<()> {
         game_type: GameType::RealityConsensus,
         rounds: 12,
         time_limit_ms: 5000,
-        special_rules: HashMap::new(),
+        params: HashMap::new(),
         initial_players: players.clone(),
+        teams: vec![],
+        seed: None,
     };
     
     let mut state = game.initialize(config).await?;